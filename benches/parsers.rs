@@ -0,0 +1,121 @@
+//! Criterion benchmarks, run with `cargo bench --features bench,json`
+
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use parsicomb::and::AndExt;
+use parsicomb::ascii::{take_ident, take_whitespace, u64};
+use parsicomb::bench::{expression, json_document, keyword_soup, utf8_text};
+use parsicomb::byte::is_byte;
+use parsicomb::many::many;
+use parsicomb::map::MapExt;
+use parsicomb::or::OrExt;
+use parsicomb::utf8::{char, is_string, keywords};
+use parsicomb::{ByteCursor, Parser, choice, json, separated_list};
+use std::hint::black_box;
+
+const KEYWORDS: &[&str] = &[
+    "break", "const", "continue", "else", "enum", "false", "fn", "for", "if", "impl", "in", "let",
+    "loop", "match", "mod", "return", "struct", "true", "while",
+];
+
+const KEYWORD_TABLE: &[(&str, usize)] = &[
+    ("continue", 2),
+    ("return", 15),
+    ("struct", 16),
+    ("break", 0),
+    ("const", 1),
+    ("false", 5),
+    ("match", 13),
+    ("while", 18),
+    ("else", 3),
+    ("enum", 4),
+    ("impl", 9),
+    ("loop", 12),
+    ("true", 17),
+    ("for", 7),
+    ("let", 11),
+    ("mod", 14),
+    ("fn", 6),
+    ("if", 8),
+    ("in", 10),
+];
+
+fn char_decoding(c: &mut Criterion) {
+    let text = utf8_text(10_000);
+    let mut group = c.benchmark_group("char_decoding");
+    group.throughput(Throughput::Bytes(text.len() as u64));
+    group.bench_function("many_char", |b| {
+        let parser = many(char());
+        b.iter(|| parser.parse(ByteCursor::new(black_box(text.as_bytes()))))
+    });
+    group.finish();
+}
+
+fn keyword_matching(c: &mut Criterion) {
+    let text = keyword_soup(5_000, KEYWORDS);
+    let mut group = c.benchmark_group("keyword_matching");
+    group.throughput(Throughput::Bytes(text.len() as u64));
+
+    group.bench_function("keywords_table", |b| {
+        let word = keywords(KEYWORD_TABLE)
+            .map(Some)
+            .or(take_ident().map(|_| None));
+        let parser = separated_list(word, is_byte(b' '));
+        b.iter(|| parser.parse(ByteCursor::new(black_box(text.as_bytes()))))
+    });
+
+    group.bench_function("choice_of_strings", |b| {
+        let keyword = choice(
+            KEYWORDS
+                .iter()
+                .map(|keyword| is_string(*keyword))
+                .collect::<Vec<_>>(),
+        );
+        let word = keyword.map(|_| ()).or(take_ident().map(|_| ()));
+        let parser = separated_list(word, is_byte(b' '));
+        b.iter(|| parser.parse(ByteCursor::new(black_box(text.as_bytes()))))
+    });
+    group.finish();
+}
+
+fn or_heavy_tokens(c: &mut Criterion) {
+    let text = expression(5_000);
+    let mut group = c.benchmark_group("or_heavy");
+    group.throughput(Throughput::Bytes(text.len() as u64));
+    group.bench_function("token_chain", |b| {
+        let token = u64()
+            .map(|_| ())
+            .or(take_ident().map(|_| ()))
+            .or(is_string("==").map(|_| ()))
+            .or(is_string("!=").map(|_| ()))
+            .or(is_string("<=").map(|_| ()))
+            .or(is_string(">=").map(|_| ()))
+            .or(is_byte(b'+').map(|_| ()))
+            .or(is_byte(b'-').map(|_| ()))
+            .or(is_byte(b'*').map(|_| ()))
+            .or(is_byte(b'/').map(|_| ()))
+            .or(is_byte(b'(').map(|_| ()))
+            .or(is_byte(b')').map(|_| ()));
+        let parser = many(take_whitespace().and(token));
+        b.iter(|| parser.parse(ByteCursor::new(black_box(text.as_bytes()))))
+    });
+    group.finish();
+}
+
+fn json_workload(c: &mut Criterion) {
+    let document = json_document(1_000);
+    let mut group = c.benchmark_group("json");
+    group.throughput(Throughput::Bytes(document.len() as u64));
+    group.bench_function("parse", |b| {
+        b.iter(|| json::parse(black_box(document.as_bytes())))
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    char_decoding,
+    keyword_matching,
+    or_heavy_tokens,
+    json_workload
+);
+criterion_main!(benches);
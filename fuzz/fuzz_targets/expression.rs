@@ -0,0 +1,23 @@
+//! Runs a small arithmetic grammar with an operator table over arbitrary bytes
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use parsicomb::ascii::i64;
+use parsicomb::expression::{Assoc, OperatorTable, expression};
+use parsicomb::fuzz::check_no_panic;
+
+fuzz_target!(|data: &[u8]| {
+    let table = OperatorTable::new()
+        .with_operator("+", 6, Assoc::Left)
+        .with_operator("-", 6, Assoc::Left)
+        .with_operator("*", 7, Assoc::Left)
+        .with_operator("^", 8, Assoc::Right);
+    let parser = expression(i64(), &table, |op, a, b| match op {
+        "+" => a.wrapping_add(b),
+        "-" => a.wrapping_sub(b),
+        "*" => a.wrapping_mul(b),
+        _ => a.wrapping_pow(b as u32),
+    });
+    check_no_panic(&parser, data);
+});
@@ -0,0 +1,14 @@
+//! Runs the JSON grammar over arbitrary bytes
+//!
+//! Template for fuzzing a grammar: build the parser, hand it the fuzzer's bytes and
+//! let `check_no_panic` turn misbehavior into a crash.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use parsicomb::fuzz::check_no_panic;
+use parsicomb::json;
+
+fuzz_target!(|data: &[u8]| {
+    check_no_panic(&json::value(), data);
+});
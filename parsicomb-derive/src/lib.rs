@@ -0,0 +1,83 @@
+//! Derive macros for parsicomb, enabled with its `derive` feature
+
+use proc_macro::TokenStream;
+use quote::{ToTokens, quote};
+use syn::{Data, DeriveInput, Fields, LitStr, Meta, parse_macro_input};
+
+/// Derive a keyword parser for an enum of unit variants
+///
+/// Generates `KEYWORDS`, the keyword table sorted longest first, and `keywords()`,
+/// a `KeywordsParser` that matches any of them at a word boundary and returns the
+/// variant. A variant's keyword is its name in lowercase unless it is set with
+/// `#[keyword = "..."]`. The enum must implement `Clone`.
+#[proc_macro_derive(Keywords, attributes(keyword))]
+pub fn derive_keywords(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand_keywords(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn expand_keywords(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            name,
+            "Keywords can only be derived for enums",
+        ));
+    };
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "Keywords cannot be derived for generic enums",
+        ));
+    }
+
+    let mut table = Vec::new();
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(syn::Error::new_spanned(
+                variant,
+                "Keywords variants cannot have fields",
+            ));
+        }
+
+        let mut keyword = variant.ident.to_string().to_lowercase();
+        for attr in &variant.attrs {
+            if !attr.path().is_ident("keyword") {
+                continue;
+            }
+            let Meta::NameValue(meta) = &attr.meta else {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "expected #[keyword = \"...\"]",
+                ));
+            };
+            let literal: LitStr = syn::parse2(meta.value.to_token_stream())?;
+            keyword = literal.value();
+            if keyword.is_empty() {
+                return Err(syn::Error::new_spanned(literal, "keywords cannot be empty"));
+            }
+        }
+        table.push((keyword, &variant.ident));
+    }
+
+    // Longest first for maximal munch, the sort is stable so ties keep declaration order
+    table.sort_by_key(|(keyword, _)| std::cmp::Reverse(keyword.len()));
+    let entries = table
+        .iter()
+        .map(|(keyword, variant)| quote! { (#keyword, #name::#variant) });
+
+    Ok(quote! {
+        impl #name {
+            /// Keywords of every variant, longest first
+            pub const KEYWORDS: &'static [(&'static str, #name)] = &[#(#entries),*];
+
+            /// Parser matching any keyword of this enum at a word boundary
+            pub fn keywords() -> ::parsicomb::utf8::keyword::KeywordsParser<#name> {
+                ::parsicomb::utf8::keyword::KeywordsParser::new(Self::KEYWORDS)
+            }
+        }
+    })
+}
@@ -1,6 +1,10 @@
 use super::parser::Parser;
+#[cfg(feature = "arena")]
+use crate::arena::ParserArena;
 use crate::atomic::Atomic;
+use crate::boxed::ParserNode;
 use crate::cursor::Cursor;
+use crate::describe::Description;
 use crate::error::{ErrorLeaf, ErrorNode};
 use std::fmt;
 
@@ -95,15 +99,22 @@ impl<'code, T: Atomic + 'code> ErrorNode<'code> for AndError<'code, T> {
             AndError::SecondParser(e2) => e2.as_ref().likely_error(),
         }
     }
+
+    fn children(&self) -> Vec<&dyn ErrorNode<'code, Element = Self::Element>> {
+        match self {
+            AndError::FirstParser(e1) => vec![e1.as_ref()],
+            AndError::SecondParser(e2) => vec![e2.as_ref()],
+        }
+    }
 }
 
 /// Parser combinator that sequences two parsers and returns both results as a tuple
 ///
 /// Note: When chaining multiple `.and()` calls, this produces nested tuples like
 /// `(((a, b), c), d)` rather than flat tuples like `(a, b, c, d)`. This is due
-/// to Rust's lack of variadic generics. While we could use macros to work around
-/// this for specific arities, the nested tuple approach is more general and the
-/// destructuring pattern is explicit about the parsing order.
+/// to Rust's lack of variadic generics. The nested tuple approach is fully general
+/// and the destructuring pattern is explicit about the parsing order; for flat
+/// tuples of up to 8 parsers use [`seq`](crate::seq::seq) instead.
 ///
 /// Example:
 /// ```
@@ -123,8 +134,8 @@ impl<'code, T: Atomic + 'code> ErrorNode<'code> for AndError<'code, T> {
 /// assert_eq!(frac_part, 456);
 /// ```
 pub struct And<'code, C, O1, O2, E1, E2> {
-    parser1: Box<dyn Parser<'code, Cursor = C, Output = O1, Error = E1> + 'code>,
-    parser2: Box<dyn Parser<'code, Cursor = C, Output = O2, Error = E2> + 'code>,
+    parser1: ParserNode<'code, C, O1, E1>,
+    parser2: ParserNode<'code, C, O2, E2>,
 }
 
 impl<'code, C, O1, O2, E1, E2> And<'code, C, O1, O2, E1, E2> {
@@ -134,8 +145,21 @@ impl<'code, C, O1, O2, E1, E2> And<'code, C, O1, O2, E1, E2> {
         P2: Parser<'code, Cursor = C, Output = O2, Error = E2> + 'code,
     {
         And {
-            parser1: Box::new(parser1),
-            parser2: Box::new(parser2),
+            parser1: ParserNode::boxed(parser1),
+            parser2: ParserNode::boxed(parser2),
+        }
+    }
+
+    /// Like `new`, but places both parsers in `arena` instead of boxing them
+    #[cfg(feature = "arena")]
+    pub fn new_in<P1, P2>(arena: &'code ParserArena, parser1: P1, parser2: P2) -> Self
+    where
+        P1: Parser<'code, Cursor = C, Output = O1, Error = E1> + 'code,
+        P2: Parser<'code, Cursor = C, Output = O2, Error = E2> + 'code,
+    {
+        And {
+            parser1: ParserNode::arena(arena, parser1),
+            parser2: ParserNode::arena(arena, parser2),
         }
     }
 }
@@ -162,9 +186,14 @@ where
             .map_err(|e| AndError::SecondParser(Box::new(e)))?;
         Ok(((result1, result2), cursor))
     }
+
+    fn describe(&self) -> Description {
+        Description::sequence(self.parser1.describe(), self.parser2.describe())
+    }
 }
 
 /// Convenience function to create an And parser
+#[allow(clippy::type_complexity)]
 pub fn and<'code, P1, P2>(
     parser1: P1,
     parser2: P2,
@@ -178,6 +207,7 @@ where
 
 /// Extension trait to add .and() method support for parsers
 pub trait AndExt<'code>: Parser<'code> + Sized {
+    #[allow(clippy::type_complexity)]
     fn and<P>(
         self,
         other: P,
@@ -188,11 +218,152 @@ pub trait AndExt<'code>: Parser<'code> + Sized {
     {
         And::new(self, other)
     }
+
+    /// Statically dispatched `.and()`, see [`AndStatic`] for the trade-off
+    fn and_static<P>(self, other: P) -> AndStatic<Self, P>
+    where
+        P: Parser<'code, Cursor = Self::Cursor>,
+    {
+        AndStatic::new(self, other)
+    }
 }
 
 /// Implement AndExt for all parsers
 impl<'code, P> AndExt<'code> for P where P: Parser<'code> {}
 
+// ## Static Dispatch Variant
+//
+// `AndStatic` nests its parsers and errors as plain generics, so a chain like
+// `a.and_static(b).and_static(c)` has the type `AndStatic<AndStatic<A, B>, C>`.
+// Nothing is boxed: parsing allocates nothing, even on failure, and the compiler
+// can inline through the whole chain. The price is the nested types described
+// above, so keep it to small, hot grammars and use `.and()` everywhere else.
+
+/// Error type for AndStatic parser, generic over both error types instead of boxing
+pub enum AndStaticError<E1, E2> {
+    /// Error from the first parser
+    FirstParser(E1),
+    /// Error from the second parser
+    SecondParser(E2),
+}
+
+impl<E1: fmt::Display, E2: fmt::Display> fmt::Debug for AndStaticError<E1, E2> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AndStaticError::FirstParser(e) => {
+                f.debug_tuple("FirstParser").field(&e.to_string()).finish()
+            }
+            AndStaticError::SecondParser(e) => {
+                f.debug_tuple("SecondParser").field(&e.to_string()).finish()
+            }
+        }
+    }
+}
+
+impl<E1: fmt::Display, E2: fmt::Display> fmt::Display for AndStaticError<E1, E2> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AndStaticError::FirstParser(e) => write!(f, "First parser failed: {}", e),
+            AndStaticError::SecondParser(e) => write!(f, "Second parser failed: {}", e),
+        }
+    }
+}
+
+impl<E1: fmt::Display, E2: fmt::Display> std::error::Error for AndStaticError<E1, E2> {}
+
+impl<'code, T, E1, E2> ErrorNode<'code> for AndStaticError<E1, E2>
+where
+    T: Atomic + 'code,
+    E1: ErrorNode<'code, Element = T> + fmt::Display,
+    E2: ErrorNode<'code, Element = T> + fmt::Display,
+{
+    type Element = T;
+
+    fn likely_error(&self) -> &dyn ErrorLeaf<'code, Element = Self::Element> {
+        match self {
+            AndStaticError::FirstParser(e1) => e1.likely_error(),
+            AndStaticError::SecondParser(e2) => e2.likely_error(),
+        }
+    }
+
+    fn children(&self) -> Vec<&dyn ErrorNode<'code, Element = Self::Element>> {
+        match self {
+            AndStaticError::FirstParser(e1) => vec![e1],
+            AndStaticError::SecondParser(e2) => vec![e2],
+        }
+    }
+}
+
+/// Parser combinator that sequences two parsers without boxing them
+///
+/// Behaves exactly like [`And`], but stores both parsers inline. Prefer `.and()`
+/// unless profiling shows the allocation and virtual calls matter.
+///
+/// Example:
+/// ```
+/// use parsicomb::ascii::u64;
+/// use parsicomb::byte::is_byte;
+/// use parsicomb::ByteCursor;
+/// use parsicomb::and::AndExt;
+/// use parsicomb::parser::Parser;
+///
+/// let cursor = ByteCursor::new(b"4:2");
+/// let ((hours, _), minutes) = u64()
+///     .and_static(is_byte(b':'))
+///     .and_static(u64())
+///     .parse(cursor)
+///     .unwrap()
+///     .0;
+/// assert_eq!((hours, minutes), (4, 2));
+/// ```
+pub struct AndStatic<P1, P2> {
+    parser1: P1,
+    parser2: P2,
+}
+
+impl<P1, P2> AndStatic<P1, P2> {
+    pub fn new(parser1: P1, parser2: P2) -> Self {
+        AndStatic { parser1, parser2 }
+    }
+}
+
+impl<'code, P1, P2> Parser<'code> for AndStatic<P1, P2>
+where
+    P1: Parser<'code>,
+    P2: Parser<'code, Cursor = P1::Cursor>,
+    <P1::Cursor as Cursor<'code>>::Element: Atomic + 'code,
+{
+    type Cursor = P1::Cursor;
+    type Output = (P1::Output, P2::Output);
+    type Error = AndStaticError<P1::Error, P2::Error>;
+
+    #[inline]
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (result1, cursor) = self
+            .parser1
+            .parse(cursor)
+            .map_err(AndStaticError::FirstParser)?;
+        let (result2, cursor) = self
+            .parser2
+            .parse(cursor)
+            .map_err(AndStaticError::SecondParser)?;
+        Ok(((result1, result2), cursor))
+    }
+
+    fn describe(&self) -> Description {
+        Description::sequence(self.parser1.describe(), self.parser2.describe())
+    }
+}
+
+/// Convenience function to create an AndStatic parser
+pub fn and_static<'code, P1, P2>(parser1: P1, parser2: P2) -> AndStatic<P1, P2>
+where
+    P1: Parser<'code>,
+    P2: Parser<'code, Cursor = P1::Cursor>,
+{
+    AndStatic::new(parser1, parser2)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -269,4 +440,33 @@ mod tests {
         assert_eq!(y, b'Y');
         assert!(matches!(cursor, ByteCursor::EndOfFile { .. }));
     }
+
+    #[test]
+    fn test_and_static_matches_and() {
+        let data = b"A5B";
+        let parser = is_byte(b'A')
+            .and_static(is_byte(b'5'))
+            .and_static(is_byte(b'B'));
+
+        let (((a, five), b), cursor) = parser.parse(ByteCursor::new(data)).unwrap();
+        assert_eq!((a, five, b), (b'A', b'5', b'B'));
+        assert!(cursor.eos());
+    }
+
+    #[test]
+    fn test_and_static_error_reports_failing_side() {
+        let data = b"A5x";
+        let parser = and_static(is_byte(b'A'), is_byte(b'5')).and_static(is_byte(b'B'));
+
+        let error = parser.parse(ByteCursor::new(data)).unwrap_err();
+        assert!(matches!(error, AndStaticError::SecondParser(_)));
+        assert_eq!(error.likely_error().loc().position(), 2);
+        assert!(error.to_string().starts_with("Second parser failed"));
+
+        let error = parser.parse(ByteCursor::new(b"B")).unwrap_err();
+        assert!(matches!(
+            error,
+            AndStaticError::FirstParser(AndStaticError::FirstParser(_))
+        ));
+    }
 }
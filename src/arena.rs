@@ -0,0 +1,205 @@
+use crate::and::And;
+use crate::atomic::Atomic;
+use crate::between::Between;
+use crate::cursor::Cursor;
+use crate::describe::Description;
+use crate::error::ErrorNode;
+use crate::or::Or;
+use crate::parser::Parser;
+use bumpalo::Bump;
+use bumpalo::boxed::Box;
+use std::fmt;
+
+// # Parser Arena - Bump Allocation for Combinator Nodes
+//
+// `And`, `Or` and `Between` box their children to keep types flat (see and.rs).
+// A grammar built per request therefore costs one heap allocation per combinator,
+// and freeing it walks the whole tree again. `ParserArena` bump-allocates those
+// children instead: construction is a pointer bump and the memory of the whole
+// grammar is released at once when the arena is dropped.
+//
+// Children live in bumpalo boxes owned by the combinator that holds them, so they
+// are dropped together with it, like ordinary boxed children. Parsers owning heap
+// data such as an `Rc` or a `String` release it then, only the arena memory
+// itself waits for the arena.
+//
+// The arena is borrowed for `'code`, so it must be created before the input it
+// is used to parse and outlive every parser built from it.
+
+/// Bump allocator for the children of boxed combinators
+///
+/// Example:
+/// ```
+/// use parsicomb::arena::ParserArena;
+/// use parsicomb::byte::is_byte;
+/// use parsicomb::{ByteCursor, Parser};
+///
+/// let arena = ParserArena::new();
+/// let data = b"[b]";
+/// let letter = arena.or(is_byte(b'a'), is_byte(b'b'));
+/// let parser = arena.between(is_byte(b'['), letter, is_byte(b']'));
+///
+/// let (byte, _) = parser.parse(ByteCursor::new(data)).unwrap();
+/// assert_eq!(byte, b'b');
+/// ```
+pub struct ParserArena {
+    bump: Bump,
+}
+
+impl ParserArena {
+    pub fn new() -> Self {
+        ParserArena { bump: Bump::new() }
+    }
+
+    /// Creates an arena with room for `bytes` of parsers before it grows
+    pub fn with_capacity(bytes: usize) -> Self {
+        ParserArena {
+            bump: Bump::with_capacity(bytes),
+        }
+    }
+
+    /// Moves `parser` into the arena, it is dropped with the returned box and its memory
+    /// is freed with the arena
+    pub fn alloc<P>(&self, parser: P) -> Box<'_, P> {
+        Box::new_in(parser, &self.bump)
+    }
+
+    /// Bytes currently allocated from the system for this arena
+    pub fn allocated_bytes(&self) -> usize {
+        self.bump.allocated_bytes()
+    }
+
+    /// Arena-backed equivalent of [`and`](crate::and::and)
+    #[allow(clippy::type_complexity)]
+    pub fn and<'code, P1, P2>(
+        &'code self,
+        parser1: P1,
+        parser2: P2,
+    ) -> And<'code, P1::Cursor, P1::Output, P2::Output, P1::Error, P2::Error>
+    where
+        P1: Parser<'code> + 'code,
+        P2: Parser<'code, Cursor = P1::Cursor> + 'code,
+    {
+        And::new_in(self, parser1, parser2)
+    }
+
+    /// Arena-backed equivalent of [`or`](crate::or::or)
+    pub fn or<'code, P1, P2>(
+        &'code self,
+        parser1: P1,
+        parser2: P2,
+    ) -> Or<'code, P1::Cursor, P1::Output, P1::Error, P2::Error>
+    where
+        P1: Parser<'code> + 'code,
+        P2: Parser<'code, Output = P1::Output, Cursor = P1::Cursor> + 'code,
+    {
+        Or::new_in(self, parser1, parser2)
+    }
+
+    /// Arena-backed equivalent of [`between`](crate::between::between)
+    pub fn between<'code, P1, P2, P3>(
+        &'code self,
+        open: P1,
+        content: P2,
+        close: P3,
+    ) -> Between<'code, P1, P3, P1::Cursor, P2::Output, P2::Error>
+    where
+        P1: Parser<'code> + 'code,
+        P2: Parser<'code, Cursor = P1::Cursor> + 'code,
+        P3: Parser<'code, Cursor = P1::Cursor> + 'code,
+        P1::Error: ErrorNode<'code, Element = <P1::Cursor as Cursor<'code>>::Element> + 'code,
+        P2::Error: ErrorNode<'code, Element = <P1::Cursor as Cursor<'code>>::Element> + 'code,
+        P3::Error: ErrorNode<'code, Element = <P1::Cursor as Cursor<'code>>::Element> + 'code,
+        <P1::Cursor as Cursor<'code>>::Element: Atomic + 'code,
+    {
+        Between::new_in(self, open, content, close)
+    }
+}
+
+/// Forward parsing through arena boxes, like through `Box`
+impl<'code, P> Parser<'code> for Box<'_, P>
+where
+    P: Parser<'code> + ?Sized,
+{
+    type Cursor = P::Cursor;
+    type Output = P::Output;
+    type Error = P::Error;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        (**self).parse(cursor)
+    }
+
+    fn describe(&self) -> Description {
+        (**self).describe()
+    }
+}
+
+impl Default for ParserArena {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for ParserArena {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ParserArena")
+            .field("allocated_bytes", &self.allocated_bytes())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ByteCursor;
+    use crate::byte::is_byte;
+    use crate::map::MapExt;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_arena_grammar_parses_like_boxed() {
+        let arena = ParserArena::new();
+        let data = b"(ab)";
+        let pair = arena.and(is_byte(b'a'), arena.or(is_byte(b'x'), is_byte(b'b')));
+        let parser = arena.between(is_byte(b'('), pair, is_byte(b')'));
+
+        let ((a, b), cursor) = parser.parse(ByteCursor::new(data)).unwrap();
+        assert_eq!((a, b), (b'a', b'b'));
+        assert!(cursor.eos());
+
+        let error = parser.parse(ByteCursor::new(b"(ay)")).unwrap_err();
+        assert_eq!(error.likely_error().loc().position(), 2);
+    }
+
+    #[test]
+    fn test_arena_drops_children_with_their_combinator() {
+        let shared = Rc::new(());
+        {
+            let arena = ParserArena::new();
+            let captured = Rc::clone(&shared);
+            let parser = arena.or(
+                is_byte(b'a').map(move |byte| {
+                    let _ = &captured;
+                    byte
+                }),
+                is_byte(b'b'),
+            );
+            assert!(parser.parse(ByteCursor::new(b"a")).is_ok());
+            assert_eq!(Rc::strong_count(&shared), 2);
+            drop(parser);
+            // Dropped with the combinator, before the arena goes away
+            assert_eq!(Rc::strong_count(&shared), 1);
+        }
+        assert_eq!(Rc::strong_count(&shared), 1);
+    }
+
+    #[test]
+    fn test_arena_alloc_reference_is_a_parser() {
+        let arena = ParserArena::with_capacity(256);
+        let byte = arena.alloc(is_byte(b'z'));
+
+        let (value, _) = byte.parse(ByteCursor::new(b"z")).unwrap();
+        assert_eq!(value, b'z');
+        assert!(arena.allocated_bytes() >= 256);
+    }
+}
@@ -0,0 +1,261 @@
+use crate::ByteCursor;
+use crate::cursor::Cursor;
+use crate::describe::Description;
+use crate::error::join_alternatives;
+use crate::parser::Parser;
+use crate::{CodeLoc, ParsicombError};
+
+// # ASCII Characters
+//
+// `utf8::is_char` decodes a whole character before comparing, which is wasted work
+// for the ASCII punctuation most grammars are made of. `ascii::is_char` and
+// `ascii::one_of` compare a single byte instead and still return a `char`, so they
+// can replace the UTF-8 parsers wherever the expected characters are ASCII. A
+// multi-byte character in the input never matches, but is decoded for the error.
+
+/// Describe what was found at `position` for error messages
+fn found(data: &[u8], position: usize) -> String {
+    let Some(&byte) = data.get(position) else {
+        return "reached end of input".to_string();
+    };
+    if byte.is_ascii() {
+        return format!("found '{}'", byte.escape_ascii());
+    }
+    let end = (position + 4).min(data.len());
+    let text = match std::str::from_utf8(&data[position..end]) {
+        Ok(text) => text,
+        Err(error) => std::str::from_utf8(&data[position..position + error.valid_up_to()])
+            .expect("prefix is valid UTF-8"),
+    };
+    match text.chars().next() {
+        Some(ch) => format!("found '{}'", ch),
+        None => format!("found byte 0x{:02X}", byte),
+    }
+}
+
+/// Parser that matches one ASCII character without decoding UTF-8
+pub struct AsciiChar {
+    expected: u8,
+}
+
+impl AsciiChar {
+    /// Panics if `expected` is not ASCII
+    pub fn new(expected: char) -> Self {
+        assert!(
+            expected.is_ascii(),
+            "'{}' is not an ASCII character",
+            expected
+        );
+        AsciiChar {
+            expected: expected as u8,
+        }
+    }
+}
+
+impl<'code> Parser<'code> for AsciiChar {
+    type Cursor = ByteCursor<'code>;
+    type Output = char;
+    type Error = ParsicombError<'code>;
+
+    #[inline]
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (data, position) = cursor.inner();
+        if data.get(position) == Some(&self.expected) {
+            return Ok((
+                self.expected as char,
+                ByteCursor::scanned(data, position, position + 1)?,
+            ));
+        }
+        Err(ParsicombError::SyntaxError {
+            message: format!(
+                "expected '{}', {}",
+                self.expected.escape_ascii(),
+                found(data, position)
+            )
+            .into(),
+            loc: CodeLoc::new(data, position),
+        })
+    }
+
+    fn describe(&self) -> Description {
+        Description::terminal(format!("'{}'", self.expected.escape_ascii()))
+    }
+}
+
+/// Parser that matches any of a set of ASCII characters without decoding UTF-8
+pub struct OneOf {
+    /// Bit `b` is set if byte `b` is in the set
+    set: [u64; 2],
+    /// The characters in the order they were given, for error messages
+    chars: Vec<u8>,
+}
+
+impl OneOf {
+    /// Panics if `chars` contains a byte that is not ASCII
+    pub fn new(chars: &[u8]) -> Self {
+        let mut set = [0; 2];
+        let mut unique = Vec::new();
+        for &byte in chars {
+            assert!(byte.is_ascii(), "0x{:02X} is not an ASCII character", byte);
+            if !unique.contains(&byte) {
+                set[usize::from(byte / 64)] |= 1 << (byte % 64);
+                unique.push(byte);
+            }
+        }
+        OneOf { set, chars: unique }
+    }
+
+    #[inline]
+    fn contains(&self, byte: u8) -> bool {
+        byte.is_ascii() && self.set[usize::from(byte / 64)] & (1 << (byte % 64)) != 0
+    }
+}
+
+impl<'code> Parser<'code> for OneOf {
+    type Cursor = ByteCursor<'code>;
+    type Output = char;
+    type Error = ParsicombError<'code>;
+
+    #[inline]
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (data, position) = cursor.inner();
+        if let Some(&byte) = data.get(position)
+            && self.contains(byte)
+        {
+            return Ok((
+                byte as char,
+                ByteCursor::scanned(data, position, position + 1)?,
+            ));
+        }
+
+        let chars: Vec<String> = self
+            .chars
+            .iter()
+            .map(|byte| format!("'{}'", byte.escape_ascii()))
+            .collect();
+        let expected = match chars.len() {
+            0 => "nothing".to_string(),
+            _ => join_alternatives(chars.iter().map(String::as_str)),
+        };
+        Err(ParsicombError::SyntaxError {
+            message: format!("expected {}, {}", expected, found(data, position)).into(),
+            loc: CodeLoc::new(data, position),
+        })
+    }
+
+    fn describe(&self) -> Description {
+        let chars: Vec<String> = self
+            .chars
+            .iter()
+            .map(|byte| format!("'{}'", byte.escape_ascii()))
+            .collect();
+        Description::terminal(format!("({})", chars.join(" | ")))
+    }
+}
+
+/// Creates a parser for the ASCII character `expected`, panicking if it is not ASCII
+///
+/// Example:
+/// ```
+/// use parsicomb::Parser;
+/// use parsicomb::ByteCursor;
+/// use parsicomb::ascii::is_char;
+///
+/// let (ch, _) = is_char('{').parse(ByteCursor::new(b"{}")).unwrap();
+/// assert_eq!(ch, '{');
+/// ```
+pub fn is_char(expected: char) -> AsciiChar {
+    AsciiChar::new(expected)
+}
+
+/// Creates a parser for any of the ASCII characters in `chars`, panicking on non-ASCII bytes
+///
+/// Example:
+/// ```
+/// use parsicomb::Parser;
+/// use parsicomb::ByteCursor;
+/// use parsicomb::ascii::one_of;
+///
+/// let operator = one_of(b"+-*/");
+/// let (ch, _) = operator.parse(ByteCursor::new(b"*2")).unwrap();
+/// assert_eq!(ch, '*');
+/// assert!(operator.parse(ByteCursor::new(b"%2")).is_err());
+/// ```
+pub fn one_of(chars: &[u8]) -> OneOf {
+    OneOf::new(chars)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ErrorLeaf;
+
+    fn parse_error<'code, P>(parser: P, data: &'code [u8]) -> (usize, String)
+    where
+        P: Parser<'code, Cursor = ByteCursor<'code>, Error = ParsicombError<'code>>,
+    {
+        let error = parser.parse(ByteCursor::new(data)).err().unwrap();
+        (error.loc().position(), error.message().into_owned())
+    }
+
+    #[test]
+    fn test_is_char() {
+        let (ch, cursor) = is_char(';').parse(ByteCursor::new(b";x")).unwrap();
+        assert_eq!((ch, cursor.position()), (';', 1));
+
+        assert_eq!(
+            parse_error(is_char(';'), b"x"),
+            (0, "expected ';', found 'x'".into())
+        );
+        assert_eq!(
+            parse_error(is_char('\n'), b""),
+            (0, "expected '\\n', reached end of input".into())
+        );
+    }
+
+    #[test]
+    fn test_is_char_reports_multibyte_input() {
+        assert_eq!(
+            parse_error(is_char('e'), "é".as_bytes()),
+            (0, "expected 'e', found 'é'".into())
+        );
+        assert_eq!(
+            parse_error(is_char('e'), b"\xFF"),
+            (0, "expected 'e', found byte 0xFF".into())
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "not an ASCII character")]
+    fn test_is_char_rejects_non_ascii() {
+        is_char('é');
+    }
+
+    #[test]
+    fn test_one_of() {
+        let operator = one_of(b"+-*/");
+        for (input, expected) in [(b"+", '+'), (b"-", '-'), (b"*", '*'), (b"/", '/')] {
+            let (ch, cursor) = operator.parse(ByteCursor::new(input)).unwrap();
+            assert_eq!((ch, cursor.position()), (expected, 1));
+        }
+
+        assert_eq!(
+            parse_error(one_of(b"+-+"), b"%"),
+            (0, "expected '+' or '-', found '%'".into())
+        );
+        assert_eq!(
+            parse_error(one_of(b"()"), "\u{2212}".as_bytes()),
+            (0, "expected '(' or ')', found '\u{2212}'".into())
+        );
+        assert!(one_of(b"").parse(ByteCursor::new(b"a")).is_err());
+    }
+
+    #[test]
+    fn test_describe() {
+        assert_eq!(is_char('\'').describe(), Description::terminal("'\\''"));
+        assert_eq!(
+            one_of(b"ab").describe(),
+            Description::terminal("('a' | 'b')")
+        );
+    }
+}
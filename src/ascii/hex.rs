@@ -0,0 +1,240 @@
+use crate::ByteCursor;
+use crate::Cursor;
+use crate::parser::Parser;
+use crate::{CodeLoc, ParsicombError};
+
+// # Hex Bytes
+//
+// `hex_u8()` reads one byte written as two hex digits. `hex_bytes()` reads a byte
+// string in either of the two usual spellings:
+//
+// - `0xDEADBEEF`: after a `0x` or `0X` prefix, an even number of digits without
+//   separators.
+// - `DE AD BE EF` or `deadbeef`: pairs of digits, optionally separated by a space
+//   or tab. The sequence ends before a separator that isn't followed by another
+//   pair.
+//
+// Digits may be upper or lower case. Errors point at the offending nibble, so a
+// typo in a long dump is easy to find.
+
+fn nibble(byte: u8) -> Option<u8> {
+    (byte as char).to_digit(16).map(|digit| digit as u8)
+}
+
+fn error(data: &[u8], position: usize, message: impl Into<String>) -> ParsicombError<'_> {
+    ParsicombError::SyntaxError {
+        message: message.into().into(),
+        loc: CodeLoc::new(data, position),
+    }
+}
+
+/// Read the two digits of a byte at `position`
+fn pair(data: &[u8], position: usize) -> Result<u8, ParsicombError<'_>> {
+    let high = data.get(position).copied().and_then(nibble);
+    let Some(high) = high else {
+        return Err(error(data, position, "expected hex digit"));
+    };
+    let low = data.get(position + 1).copied().and_then(nibble);
+    let Some(low) = low else {
+        return Err(error(
+            data,
+            position + 1,
+            "expected second hex digit of byte",
+        ));
+    };
+    Ok((high << 4) | low)
+}
+
+/// Read up to `limit` bytes starting at `start`, returning them and the end position
+fn scan(data: &[u8], start: usize, limit: usize) -> Result<(Vec<u8>, usize), ParsicombError<'_>> {
+    let is_hex = |position: usize| data.get(position).is_some_and(u8::is_ascii_hexdigit);
+    let mut bytes = Vec::new();
+
+    if matches!(data.get(start..start + 2), Some(b"0x" | b"0X")) {
+        let mut position = start + 2;
+        bytes.push(pair(data, position)?);
+        position += 2;
+        while bytes.len() < limit && is_hex(position) {
+            bytes.push(pair(data, position)?);
+            position += 2;
+        }
+        return Ok((bytes, position));
+    }
+
+    bytes.push(pair(data, start)?);
+    let mut position = start + 2;
+    while bytes.len() < limit {
+        let next = match data.get(position) {
+            Some(b' ' | b'\t') if is_hex(position + 1) => position + 1,
+            _ if is_hex(position) => position,
+            _ => break,
+        };
+        bytes.push(pair(data, next)?);
+        position = next + 2;
+    }
+    Ok((bytes, position))
+}
+
+/// Parser for one byte written as two hex digits, such as `7f`
+pub struct HexU8;
+
+impl<'code> Parser<'code> for HexU8 {
+    type Cursor = ByteCursor<'code>;
+    type Output = u8;
+    type Error = ParsicombError<'code>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (data, start) = cursor.inner();
+        let byte = pair(data, start)?;
+        Ok((byte, ByteCursor::scanned(data, start, start + 2)?))
+    }
+}
+
+/// Parser for a non-empty byte string written in hex
+pub struct HexBytes;
+
+impl<'code> Parser<'code> for HexBytes {
+    type Cursor = ByteCursor<'code>;
+    type Output = Vec<u8>;
+    type Error = ParsicombError<'code>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (data, start) = cursor.inner();
+        let (bytes, end) = scan(data, start, usize::MAX)?;
+        Ok((bytes, ByteCursor::scanned(data, start, end)?))
+    }
+}
+
+/// Parser for exactly `N` bytes written in hex
+pub struct HexArray<const N: usize>;
+
+impl<'code, const N: usize> Parser<'code> for HexArray<N> {
+    type Cursor = ByteCursor<'code>;
+    type Output = [u8; N];
+    type Error = ParsicombError<'code>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (data, start) = cursor.inner();
+        let (bytes, end) = scan(data, start, N.max(1))?;
+        let array = <[u8; N]>::try_from(bytes).map_err(|bytes| {
+            error(
+                data,
+                end,
+                format!("expected {} bytes, found {}", N, bytes.len()),
+            )
+        })?;
+        Ok((array, ByteCursor::scanned(data, start, end)?))
+    }
+}
+
+/// Creates a parser for one byte written as two hex digits
+pub fn hex_u8() -> HexU8 {
+    HexU8
+}
+
+/// Creates a parser for bytes written as `DE AD BE EF` or `0xDEADBEEF`
+///
+/// Example:
+/// ```
+/// use parsicomb::Parser;
+/// use parsicomb::ByteCursor;
+/// use parsicomb::ascii::hex_bytes;
+///
+/// let (bytes, _) = hex_bytes().parse(ByteCursor::new(b"DE AD be ef")).unwrap();
+/// assert_eq!(bytes, [0xde, 0xad, 0xbe, 0xef]);
+///
+/// let (bytes, _) = hex_bytes().parse(ByteCursor::new(b"0xCAFE")).unwrap();
+/// assert_eq!(bytes, [0xca, 0xfe]);
+/// ```
+pub fn hex_bytes() -> HexBytes {
+    HexBytes
+}
+
+/// Creates a parser for exactly `N` bytes in either spelling of [`hex_bytes`]
+pub fn hex_array<const N: usize>() -> HexArray<N> {
+    HexArray
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ErrorLeaf;
+
+    fn parse_error<'code, P>(parser: P, data: &'code [u8]) -> (usize, String)
+    where
+        P: Parser<'code, Cursor = ByteCursor<'code>, Error = ParsicombError<'code>>,
+    {
+        let error = parser.parse(ByteCursor::new(data)).err().unwrap();
+        (error.loc().position(), error.message().into_owned())
+    }
+
+    #[test]
+    fn test_hex_u8() {
+        let (byte, cursor) = hex_u8().parse(ByteCursor::new(b"fF0")).unwrap();
+        assert_eq!(byte, 0xff);
+        assert_eq!(cursor.value().unwrap(), b'0');
+
+        assert_eq!(
+            parse_error(hex_u8(), b"g0"),
+            (0, "expected hex digit".into())
+        );
+        assert_eq!(
+            parse_error(hex_u8(), b"a"),
+            (1, "expected second hex digit of byte".into())
+        );
+    }
+
+    #[test]
+    fn test_hex_bytes_spellings() {
+        let cases: [(&[u8], &[u8], usize); 5] = [
+            (b"de ad\tbe ef", &[0xde, 0xad, 0xbe, 0xef], 11),
+            (b"deadbeef!", &[0xde, 0xad, 0xbe, 0xef], 8),
+            (b"0x00ff rest", &[0x00, 0xff], 6),
+            (b"01 02 , 03", &[0x01, 0x02], 5),
+            (b"0A  0B", &[0x0a], 2),
+        ];
+        for (input, expected, end) in cases {
+            let (bytes, cursor) = hex_bytes().parse(ByteCursor::new(input)).unwrap();
+            assert_eq!(bytes, expected);
+            assert_eq!(cursor.position(), end);
+        }
+    }
+
+    #[test]
+    fn test_hex_bytes_errors() {
+        assert_eq!(
+            parse_error(hex_bytes(), b"DE AD B"),
+            (7, "expected second hex digit of byte".into())
+        );
+        assert_eq!(
+            parse_error(hex_bytes(), b"DE AD BX EF"),
+            (7, "expected second hex digit of byte".into())
+        );
+        assert_eq!(
+            parse_error(hex_bytes(), b"0xABC"),
+            (5, "expected second hex digit of byte".into())
+        );
+        assert_eq!(
+            parse_error(hex_bytes(), b"0x"),
+            (2, "expected hex digit".into())
+        );
+        assert_eq!(
+            parse_error(hex_bytes(), b""),
+            (0, "expected hex digit".into())
+        );
+    }
+
+    #[test]
+    fn test_hex_array() {
+        let (mac, cursor) = hex_array::<6>()
+            .parse(ByteCursor::new(b"00 1A 2b 3C 4d 5E 6f"))
+            .unwrap();
+        assert_eq!(mac, [0x00, 0x1a, 0x2b, 0x3c, 0x4d, 0x5e]);
+        assert_eq!(cursor.position(), 17);
+
+        assert_eq!(
+            parse_error(hex_array::<4>(), b"0xABCDEF;"),
+            (8, "expected 4 bytes, found 3".into())
+        );
+    }
+}
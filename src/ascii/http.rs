@@ -0,0 +1,271 @@
+use crate::ByteCursor;
+use crate::cursor::Cursor;
+use crate::parser::Parser;
+use crate::{CodeLoc, ParsicombError};
+
+// # Protocol Tokens - HTTP, SMTP and Friends
+//
+// Line-based text protocols are defined on bytes, not characters: header names are
+// ASCII and compared case-insensitively, lines end in CRLF and the allowed token
+// characters come from RFC 7230. These parsers work directly on the byte slice so
+// headers can be parsed without taking the UTF-8 decoding path.
+
+/// `tchar` from RFC 7230 section 3.2.6
+fn is_tchar(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric()
+        || matches!(
+            byte,
+            b'!' | b'#'
+                | b'$'
+                | b'%'
+                | b'&'
+                | b'\''
+                | b'*'
+                | b'+'
+                | b'-'
+                | b'.'
+                | b'^'
+                | b'_'
+                | b'`'
+                | b'|'
+                | b'~'
+        )
+}
+
+/// Describe the byte at `position` for error messages
+fn found(data: &[u8], position: usize) -> String {
+    match data.get(position) {
+        Some(byte) => format!("found {}", byte.escape_ascii()),
+        None => "reached end of input".to_string(),
+    }
+}
+
+/// Parser that matches a byte string ignoring ASCII case
+///
+/// Returns the input slice as written, e.g. `Content-Length` for the tag
+/// `content-length`.
+pub struct TagNoCase {
+    tag: &'static [u8],
+}
+
+impl TagNoCase {
+    pub fn new(tag: &'static [u8]) -> Self {
+        TagNoCase { tag }
+    }
+}
+
+impl<'code> Parser<'code> for TagNoCase {
+    type Cursor = ByteCursor<'code>;
+    type Output = &'code [u8];
+    type Error = ParsicombError<'code>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (data, start) = cursor.inner();
+        let matched = self
+            .tag
+            .iter()
+            .zip(&data[start..])
+            .take_while(|(expected, found)| expected.eq_ignore_ascii_case(found))
+            .count();
+
+        if matched < self.tag.len() {
+            return Err(ParsicombError::SyntaxError {
+                message: format!(
+                    "expected '{}', {} while matching '{}'",
+                    self.tag[matched].escape_ascii(),
+                    found(data, start + matched),
+                    self.tag.escape_ascii()
+                )
+                .into(),
+                loc: CodeLoc::new(data, start + matched),
+            });
+        }
+
+        let end = start + matched;
+        Ok((&data[start..end], ByteCursor::scanned(data, start, end)?))
+    }
+}
+
+/// Parser that matches a `\r\n` line ending
+pub struct Crlf;
+
+impl<'code> Parser<'code> for Crlf {
+    type Cursor = ByteCursor<'code>;
+    type Output = &'code [u8];
+    type Error = ParsicombError<'code>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (data, start) = cursor.inner();
+        if data[start..].starts_with(b"\r\n") {
+            return Ok((
+                &data[start..start + 2],
+                ByteCursor::scanned(data, start, start + 2)?,
+            ));
+        }
+
+        let position = if data.get(start) == Some(&b'\r') {
+            start + 1
+        } else {
+            start
+        };
+        Err(ParsicombError::SyntaxError {
+            message: format!("expected CRLF, {}", found(data, position)).into(),
+            loc: CodeLoc::new(data, position),
+        })
+    }
+}
+
+/// Parser that matches an RFC 7230 token, one or more `tchar`
+///
+/// Tokens are used for header names, methods and parameter names.
+pub struct HttpToken;
+
+impl<'code> Parser<'code> for HttpToken {
+    type Cursor = ByteCursor<'code>;
+    type Output = &'code str;
+    type Error = ParsicombError<'code>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (data, start) = cursor.inner();
+        let length = data[start..].iter().take_while(|&&b| is_tchar(b)).count();
+        if length == 0 {
+            return Err(ParsicombError::SyntaxError {
+                message: format!("expected token, {}", found(data, start)).into(),
+                loc: CodeLoc::new(data, start),
+            });
+        }
+
+        let end = start + length;
+        // Token characters are ASCII, so the slice is valid UTF-8
+        let text = std::str::from_utf8(&data[start..end]).unwrap_or_default();
+        Ok((text, ByteCursor::scanned(data, start, end)?))
+    }
+}
+
+/// Parser for optional whitespace `OWS`, zero or more spaces and horizontal tabs
+///
+/// Unlike `take_whitespace` it never crosses a line ending, which ends a header.
+pub struct Ows;
+
+impl<'code> Parser<'code> for Ows {
+    type Cursor = ByteCursor<'code>;
+    type Output = &'code [u8];
+    type Error = ParsicombError<'code>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (data, start) = cursor.inner();
+        let end = start
+            + data[start..]
+                .iter()
+                .take_while(|&&b| b == b' ' || b == b'\t')
+                .count();
+        Ok((&data[start..end], ByteCursor::scanned(data, start, end)?))
+    }
+}
+
+/// Creates a parser that matches `tag` ignoring ASCII case
+pub fn tag_no_case(tag: &'static [u8]) -> TagNoCase {
+    TagNoCase::new(tag)
+}
+
+/// Creates a parser that matches `\r\n`
+pub fn crlf() -> Crlf {
+    Crlf
+}
+
+/// Creates a parser that matches an RFC 7230 token
+///
+/// Example:
+/// ```
+/// use parsicomb::and::AndExt;
+/// use parsicomb::ascii::{crlf, ows, token};
+/// use parsicomb::byte::is_byte;
+/// use parsicomb::map::MapExt;
+/// use parsicomb::{ByteCursor, Parser};
+///
+/// let header = token()
+///     .and(is_byte(b':'))
+///     .and(ows())
+///     .and(token())
+///     .and(ows())
+///     .and(crlf())
+///     .map(|(((((name, _), _), value), _), _)| (name, value));
+///
+/// let data = b"Connection: keep-alive \r\n";
+/// let ((name, value), _) = header.parse(ByteCursor::new(data)).unwrap();
+/// assert_eq!((name, value), ("Connection", "keep-alive"));
+/// ```
+pub fn token() -> HttpToken {
+    HttpToken
+}
+
+/// Creates a parser for optional spaces and tabs
+pub fn ows() -> Ows {
+    Ows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorLeaf;
+
+    #[test]
+    fn test_tag_no_case_returns_input_spelling() {
+        let data = b"Content-Length: 5";
+        let parser = tag_no_case(b"content-length");
+
+        let (name, cursor) = parser.parse(ByteCursor::new(data)).unwrap();
+        assert_eq!(name, b"Content-Length");
+        assert_eq!(cursor.value().unwrap(), b':');
+    }
+
+    #[test]
+    fn test_tag_no_case_mismatch() {
+        let data = b"Content-Type";
+        let parser = tag_no_case(b"content-length");
+
+        let error = parser.parse(ByteCursor::new(data)).unwrap_err();
+        assert_eq!(error.loc().position(), 8);
+        assert_eq!(
+            error.message(),
+            "expected 'l', found T while matching 'content-length'"
+        );
+
+        let error = parser.parse(ByteCursor::new(b"content")).unwrap_err();
+        assert!(error.message().contains("reached end of input"));
+    }
+
+    #[test]
+    fn test_crlf() {
+        let (line_end, cursor) = crlf().parse(ByteCursor::new(b"\r\nx")).unwrap();
+        assert_eq!(line_end, b"\r\n");
+        assert_eq!(cursor.position(), 2);
+
+        // A bare CR points at the missing LF
+        let error = crlf().parse(ByteCursor::new(b"\rx")).unwrap_err();
+        assert_eq!(error.loc().position(), 1);
+        assert!(crlf().parse(ByteCursor::new(b"\n")).is_err());
+    }
+
+    #[test]
+    fn test_token_characters() {
+        let data = b"x-custom_header.v1~!#$%&'*+^`|: value";
+        let (name, cursor) = token().parse(ByteCursor::new(data)).unwrap();
+        assert_eq!(name, "x-custom_header.v1~!#$%&'*+^`|");
+        assert_eq!(cursor.value().unwrap(), b':');
+
+        for separator in [&b"(x"[..], b" x", b"\"x", b"@x", b""] {
+            assert!(token().parse(ByteCursor::new(separator)).is_err());
+        }
+    }
+
+    #[test]
+    fn test_ows_stops_at_line_end() {
+        let (spaces, cursor) = ows().parse(ByteCursor::new(b" \t \r\n")).unwrap();
+        assert_eq!(spaces, b" \t ");
+        assert_eq!(cursor.position(), 3);
+
+        let (spaces, _) = ows().parse(ByteCursor::new(b"x")).unwrap();
+        assert!(spaces.is_empty());
+    }
+}
@@ -1,5 +1,16 @@
+pub mod char;
+pub mod hex;
+pub mod http;
 pub mod number;
+pub mod scan;
 pub mod whitespace;
 
-pub use number::{Number, digit, f64, i64, number, u64};
+pub use char::{AsciiChar, OneOf, is_char, one_of};
+pub use hex::{HexArray, HexBytes, HexU8, hex_array, hex_bytes, hex_u8};
+pub use http::{Crlf, HttpToken, Ows, TagNoCase, crlf, ows, tag_no_case, token};
+pub use number::{
+    FloatConfig, Integer, IntegerParser, Number, NumberFormat, NumberFormatParser, NumberLiteral,
+    Signs, digit, f64, f64_with, i32, i64, i128, integer, number, u8, u16, u32, u64, u128,
+};
+pub use scan::{take_ident, take_whitespace};
 pub use whitespace::whitespace;
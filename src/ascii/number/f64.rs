@@ -1,91 +1,165 @@
-use super::i64::i64;
-use super::u64::u64;
 use crate::ByteCursor;
 use crate::Cursor;
-use crate::and::AndExt;
-use crate::byte::is_byte;
 use crate::parser::Parser;
 use crate::{CodeLoc, ParsicombError};
 
-const MAX_FRACTIONAL_DIGITS: usize = 15;
+/// Which parts of the float grammar the f64 parser accepts
+///
+/// The full grammar is `[+-]? digits? ('.' digits?)? ([eE] [+-]? digits)?` with at
+/// least one digit in the mantissa. A float must contain a `.` or an exponent, so
+/// plain integers are left to the integer parsers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FloatConfig {
+    /// Accept a leading `+`, on by default
+    pub plus_sign: bool,
+    /// Accept a missing integer part such as `.5`
+    pub leading_dot: bool,
+    /// Accept a missing fraction such as `5.`
+    pub trailing_dot: bool,
+    /// Accept an exponent such as `1.5e3` or `2E-8`
+    pub exponent: bool,
+}
 
-/// Parser for int.uint format (e.g., 123.456, -42.789)
-fn int_dot_uint<'code>()
--> impl Parser<'code, Cursor = ByteCursor<'code>, Output = f64, Error = ParsicombError<'code>> {
-    IntDotUintParser
+impl FloatConfig {
+    /// Every part of the grammar enabled
+    pub fn full() -> Self {
+        FloatConfig {
+            plus_sign: true,
+            leading_dot: true,
+            trailing_dot: true,
+            exponent: true,
+        }
+    }
+}
+
+impl Default for FloatConfig {
+    fn default() -> Self {
+        FloatConfig {
+            plus_sign: true,
+            leading_dot: false,
+            trailing_dot: false,
+            exponent: false,
+        }
+    }
+}
+
+fn digits_from(data: &[u8], position: usize) -> usize {
+    data[position..]
+        .iter()
+        .take_while(|byte| byte.is_ascii_digit())
+        .count()
 }
 
-struct IntDotUintParser;
+/// Parser that recognizes a float lexeme and converts it with correct rounding
+pub struct F64Parser {
+    config: FloatConfig,
+}
 
-impl<'code> Parser<'code> for IntDotUintParser {
+impl F64Parser {
+    pub fn new(config: FloatConfig) -> Self {
+        F64Parser { config }
+    }
+
+    /// End of the exponent starting at `position`, if there is a complete one
+    fn exponent_end(&self, data: &[u8], position: usize) -> Option<usize> {
+        if !self.config.exponent || !matches!(data.get(position), Some(b'e' | b'E')) {
+            return None;
+        }
+        let mut position = position + 1;
+        if matches!(data.get(position), Some(b'+' | b'-')) {
+            position += 1;
+        }
+        match digits_from(data, position) {
+            0 => None,
+            digits => Some(position + digits),
+        }
+    }
+}
+
+impl<'code> Parser<'code> for F64Parser {
     type Cursor = ByteCursor<'code>;
     type Output = f64;
     type Error = ParsicombError<'code>;
 
     fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
-        let (((int_part, _), frac_part), cursor) = i64()
-            .and(is_byte(b'.'))
-            .and(u64())
-            .parse(cursor)
-            .map_err(ParsicombError::wrap)?;
-
-        let frac_digits = frac_part.to_string().len();
-
-        // Check for too many fractional digits
-        if frac_digits > MAX_FRACTIONAL_DIGITS {
-            let (data, position) = cursor.inner();
-            return Err(ParsicombError::SyntaxError {
-                message: format!(
-                    "too many fractional digits: {} (max {})",
-                    frac_digits, MAX_FRACTIONAL_DIGITS
-                )
-                .into(),
-                loc: CodeLoc::new(data, position),
-            });
+        let (data, start) = cursor.inner();
+        let error = |message: &'static str, position: usize| ParsicombError::SyntaxError {
+            message: message.into(),
+            loc: CodeLoc::new(data, position),
+        };
+
+        let mut position = start;
+        match data.get(position) {
+            Some(b'-') => position += 1,
+            Some(b'+') if self.config.plus_sign => position += 1,
+            _ => {}
         }
 
-        let frac_divisor = 10_f64.powi(frac_digits as i32);
-        let fractional = frac_part as f64 / frac_divisor;
+        let int_digits = digits_from(data, position);
+        position += int_digits;
+
+        let mut has_dot = false;
+        if data.get(position) == Some(&b'.') {
+            has_dot = true;
+            let frac_digits = digits_from(data, position + 1);
+
+            if int_digits == 0 && frac_digits == 0 {
+                return Err(error("expected floating point number", start));
+            }
+            if int_digits == 0 && !self.config.leading_dot {
+                return Err(error("expected digit before '.'", position));
+            }
+            if frac_digits == 0 && !self.config.trailing_dot {
+                return Err(error("expected digit after '.'", position + 1));
+            }
+            position += 1 + frac_digits;
+        } else if int_digits == 0 {
+            return Err(error("expected floating point number", start));
+        }
 
-        // Check for integer part precision loss
-        let int_as_f64 = int_part as f64;
-        if int_as_f64 as i64 != int_part {
-            let (data, position) = cursor.inner();
-            return Err(ParsicombError::SyntaxError {
-                message: format!("integer part too large for f64 precision: {}", int_part).into(),
-                loc: CodeLoc::new(data, position),
+        let exponent = self.exponent_end(data, position);
+        if !has_dot && exponent.is_none() {
+            ByteCursor::charge(data, start, position)?;
+            return Err(if self.config.exponent {
+                error(
+                    "expected '.' or exponent in floating point number",
+                    position,
+                )
+            } else {
+                error("expected '.' in floating point number", position)
             });
         }
+        let end = exponent.unwrap_or(position);
 
-        let result = if int_part >= 0 {
-            int_as_f64 + fractional
-        } else {
-            int_as_f64 - fractional
-        };
+        // The lexeme is ASCII and follows Rust's float syntax, which rounds correctly
+        let lexeme = std::str::from_utf8(&data[start..end]).unwrap_or_default();
+        let value: f64 = lexeme
+            .parse()
+            .map_err(|_| error("invalid floating point number", start))?;
 
-        // Check for overflow/infinity
-        if !result.is_finite() {
-            let (data, position) = cursor.inner();
-            return Err(ParsicombError::SyntaxError {
-                message: "floating point overflow".into(),
-                loc: CodeLoc::new(data, position),
-            });
+        if !value.is_finite() {
+            return Err(error("floating point overflow", start));
         }
 
-        Ok((result, cursor))
+        Ok((value, ByteCursor::scanned(data, start, end)?))
     }
 }
 
-/// Parser that matches ASCII floating point numbers
+/// Parser that matches ASCII floating point numbers such as `123.456` or `-42.5`
 pub fn f64<'code>()
 -> impl Parser<'code, Cursor = ByteCursor<'code>, Output = f64, Error = ParsicombError<'code>> {
-    int_dot_uint()
+    F64Parser::new(FloatConfig::default())
+}
+
+/// Parser that matches ASCII floating point numbers using the given grammar
+pub fn f64_with(config: FloatConfig) -> F64Parser {
+    F64Parser::new(config)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::Cursor;
+    use crate::error::ErrorLeaf;
 
     #[test]
     fn test_int_dot_uint() {
@@ -169,20 +243,19 @@ mod tests {
     }
 
     #[test]
-    fn test_too_many_fractional_digits() {
-        // 20 fractional digits (exceeds MAX_FRACTIONAL_DIGITS = 15)
+    fn test_long_fraction_rounds_correctly() {
         let data = b"1.12345678901234567890";
-        let cursor = ByteCursor::new(data);
-        let parser = f64();
-
-        let result = parser.parse(cursor);
-        assert!(result.is_err());
-        assert!(
-            result
-                .unwrap_err()
-                .to_string()
-                .contains("too many fractional digits")
-        );
+        let (value, cursor) = f64().parse(ByteCursor::new(data)).unwrap();
+        assert_eq!(value, 1.123_456_789_012_345_7);
+        assert!(cursor.eos());
+
+        // Reconstructing from int and fraction parts gets this one wrong
+        let (value, _) = f64().parse(ByteCursor::new(b"0.3")).unwrap();
+        assert_eq!(value, 0.3);
+        let (value, _) = f64()
+            .parse(ByteCursor::new(b"-9007199254740993.0"))
+            .unwrap();
+        assert_eq!(value, -9007199254740992.0);
     }
 
     #[test]
@@ -195,4 +268,61 @@ mod tests {
         let result = parser.parse(cursor);
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_plain_integer_fails() {
+        let error = f64().parse(ByteCursor::new(b"123;")).unwrap_err();
+        assert!(error.to_string().contains("expected '.'"));
+        assert_eq!(error.loc().position(), 3);
+    }
+
+    #[test]
+    fn test_exponent_disabled_by_default() {
+        let (value, cursor) = f64().parse(ByteCursor::new(b"1.5e3")).unwrap();
+        assert_eq!(value, 1.5);
+        assert_eq!(cursor.value().unwrap(), b'e');
+    }
+
+    #[test]
+    fn test_full_grammar() {
+        let parser = f64_with(FloatConfig::full());
+        let cases: [(&[u8], f64, usize); 7] = [
+            (b".5", 0.5, 2),
+            (b"5.", 5.0, 2),
+            (b"+2.5", 2.5, 4),
+            (b"1e3", 1000.0, 3),
+            (b"-2.5E-2", -0.025, 7),
+            (b"1.5em", 1.5, 3),
+            (b"7e+2x", 700.0, 4),
+        ];
+
+        for (data, expected, end) in cases {
+            let (value, cursor) = parser.parse(ByteCursor::new(data)).unwrap();
+            assert_eq!(value, expected);
+            assert_eq!(cursor.position(), end);
+        }
+
+        assert!(parser.parse(ByteCursor::new(b".")).is_err());
+        assert!(parser.parse(ByteCursor::new(b"12e")).is_err());
+    }
+
+    #[test]
+    fn test_config_flags() {
+        let no_plus = FloatConfig {
+            plus_sign: false,
+            ..FloatConfig::full()
+        };
+        assert!(f64_with(no_plus).parse(ByteCursor::new(b"+1.0")).is_err());
+        assert!(f64().parse(ByteCursor::new(b"+1.0")).is_ok());
+
+        let error = f64().parse(ByteCursor::new(b".5")).unwrap_err();
+        assert!(error.to_string().contains("expected digit before '.'"));
+    }
+
+    #[test]
+    fn test_float_overflow() {
+        let parser = f64_with(FloatConfig::full());
+        let error = parser.parse(ByteCursor::new(b"1e400")).unwrap_err();
+        assert!(error.to_string().contains("floating point overflow"));
+    }
 }
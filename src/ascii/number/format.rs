@@ -0,0 +1,445 @@
+use super::f64::FloatConfig;
+use super::{Number, NumberLiteral};
+use crate::ByteCursor;
+use crate::Cursor;
+use crate::parser::Parser;
+use crate::position::Span;
+use crate::{CodeLoc, ParsicombError};
+use std::borrow::Cow;
+
+/// Which signs may precede a number
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Signs {
+    /// No sign at all
+    None,
+    /// Only `-`
+    Minus,
+    /// `+` or `-`
+    PlusMinus,
+}
+
+/// Builder for numeric literal grammars
+///
+/// Describes the lexical rules of a language's numbers and produces a parser with
+/// [`NumberFormat::parser`]. The defaults accept `[+-]? digits ('.' digits)?` and
+/// match the same input as the `number()` parser. Floats follow a [`FloatConfig`],
+/// set with [`NumberFormat::float`], so `FloatConfig::full()` adds exponents and
+/// missing integer or fraction parts.
+///
+/// - A digit separator may only appear between two digits, so `1_000` is one number
+///   while `1_` stops before the `_`
+/// - Without `trailing_dot` the decimal point must be followed by a digit, `5.` stops
+///   before the point
+/// - Radix prefixes introduce integers only, such as `0xff` or `0b1010`
+///
+/// Integers produce `Number::I64`, anything with a fraction or exponent produces
+/// `Number::F64`. The lexeme is kept as written, separators included.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NumberFormat {
+    signs: Signs,
+    digit_separator: Option<u8>,
+    decimal_point: u8,
+    /// Rules for floats, `None` if only integers are accepted
+    float: Option<FloatConfig>,
+    exponent_markers: Cow<'static, [u8]>,
+    radix_prefixes: Vec<(Cow<'static, str>, u32)>,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        NumberFormat {
+            signs: Signs::PlusMinus,
+            digit_separator: None,
+            decimal_point: b'.',
+            float: Some(FloatConfig::default()),
+            exponent_markers: Cow::Borrowed(b"eE"),
+            radix_prefixes: Vec::new(),
+        }
+    }
+}
+
+impl NumberFormat {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set which signs may precede the number
+    pub fn signs(mut self, signs: Signs) -> Self {
+        self.signs = signs;
+        self
+    }
+
+    /// Allow `separator` between digits, such as `_` in `1_000_000`
+    pub fn digit_separator(mut self, separator: u8) -> Self {
+        self.digit_separator = Some(separator);
+        self
+    }
+
+    /// Use `point` between the integer and fraction digits, such as `,` in `3,14`
+    pub fn decimal_point(mut self, point: u8) -> Self {
+        self.decimal_point = point;
+        self
+    }
+
+    /// Shorthand for `decimal_point(b',')`
+    pub fn decimal_comma(self) -> Self {
+        self.decimal_point(b',')
+    }
+
+    /// Accept floats following `config`
+    ///
+    /// Signs are still decided by [`signs`](NumberFormat::signs), the `plus_sign`
+    /// field of `config` is not used.
+    pub fn float(mut self, config: FloatConfig) -> Self {
+        self.float = Some(config);
+        self
+    }
+
+    /// Reject fractions and exponents, so only integers are accepted
+    pub fn integers_only(mut self) -> Self {
+        self.float = None;
+        self
+    }
+
+    /// Bytes that introduce an exponent, enabling exponents unless the slice is empty
+    pub fn exponent_markers(mut self, markers: impl Into<Cow<'static, [u8]>>) -> Self {
+        self.exponent_markers = markers.into();
+        if let Some(float) = &mut self.float {
+            float.exponent = !self.exponent_markers.is_empty();
+        }
+        self
+    }
+
+    /// Accept integers in `radix` after `prefix`, such as `("0x", 16)`
+    ///
+    /// Prefixes are tried in the order they were added and match case-sensitively.
+    pub fn radix_prefix(mut self, prefix: impl Into<Cow<'static, str>>, radix: u32) -> Self {
+        assert!((2..=36).contains(&radix), "radix must be in 2..=36");
+        self.radix_prefixes.push((prefix.into(), radix));
+        self
+    }
+
+    /// Create a parser for this grammar
+    pub fn parser(self) -> NumberFormatParser {
+        NumberFormatParser { format: self }
+    }
+
+    fn is_digit(byte: u8, radix: u32) -> bool {
+        (byte as char).is_digit(radix)
+    }
+
+    /// End of the run of `radix` digits and separators starting at `position`
+    fn digits_end(&self, data: &[u8], mut position: usize, radix: u32) -> usize {
+        let start = position;
+        while let Some(&byte) = data.get(position) {
+            let separator = Some(byte) == self.digit_separator
+                && position > start
+                && data
+                    .get(position + 1)
+                    .is_some_and(|&next| Self::is_digit(next, radix));
+            if !Self::is_digit(byte, radix) && !separator {
+                break;
+            }
+            position += 1;
+        }
+        position
+    }
+
+    /// End of the exponent starting at `position`, if there is a complete one
+    fn exponent_end(&self, data: &[u8], position: usize) -> Option<usize> {
+        let marker = *data.get(position)?;
+        if !self.exponent_markers.contains(&marker) {
+            return None;
+        }
+        let mut position = position + 1;
+        if matches!(data.get(position), Some(b'+' | b'-')) {
+            position += 1;
+        }
+        match self.digits_end(data, position, 10) {
+            end if end == position => None,
+            end => Some(end),
+        }
+    }
+}
+
+/// Parser built from a [`NumberFormat`]
+pub struct NumberFormatParser {
+    format: NumberFormat,
+}
+
+impl NumberFormatParser {
+    /// Accumulate the digits in `text` as an `i64`, `None` on overflow
+    fn integer_value(&self, text: &[u8], radix: u32, negative: bool) -> Option<i64> {
+        text.iter()
+            .filter(|&&byte| Some(byte) != self.format.digit_separator)
+            .try_fold(0i64, |value, &byte| {
+                let digit = (byte as char).to_digit(radix)? as i64;
+                let shifted = value.checked_mul(radix as i64)?;
+                if negative {
+                    shifted.checked_sub(digit)
+                } else {
+                    shifted.checked_add(digit)
+                }
+            })
+    }
+
+    /// Rewrite a decimal float lexeme into Rust's float syntax
+    fn normalized_float(&self, text: &[u8]) -> String {
+        let format = &self.format;
+        text.iter()
+            .filter(|&&byte| Some(byte) != format.digit_separator)
+            .map(|&byte| {
+                if byte == format.decimal_point {
+                    '.'
+                } else if format.exponent_markers.contains(&byte) {
+                    'e'
+                } else {
+                    byte as char
+                }
+            })
+            .collect()
+    }
+}
+
+impl<'code> Parser<'code> for NumberFormatParser {
+    type Cursor = ByteCursor<'code>;
+    type Output = NumberLiteral<'code>;
+    type Error = ParsicombError<'code>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let format = &self.format;
+        let (data, start) = cursor.inner();
+        let error = |message: Cow<'static, str>, position: usize| ParsicombError::SyntaxError {
+            message,
+            loc: CodeLoc::new(data, position),
+        };
+
+        let mut position = start;
+        let mut negative = false;
+        match (data.get(position), format.signs) {
+            (Some(b'-'), Signs::Minus | Signs::PlusMinus) => {
+                negative = true;
+                position += 1;
+            }
+            (Some(b'+'), Signs::PlusMinus) => position += 1,
+            _ => {}
+        }
+        let sign_end = position;
+
+        let literal = |end: usize, value: Number| {
+            let span = Span::new(data, start, end);
+            // Numbers are pure ASCII, so the matched bytes are valid UTF-8
+            let lexeme = std::str::from_utf8(span.slice()).unwrap_or_default();
+            Ok((
+                NumberLiteral {
+                    value,
+                    lexeme,
+                    span,
+                },
+                ByteCursor::scanned(data, start, end)?,
+            ))
+        };
+        let overflow = |end: usize| ParsicombError::IntegerOverflow {
+            span: Span::new(data, start, end),
+            target: "i64",
+        };
+
+        for (prefix, radix) in &format.radix_prefixes {
+            if !data[position..].starts_with(prefix.as_bytes()) {
+                continue;
+            }
+            let digits_start = position + prefix.len();
+            let end = format.digits_end(data, digits_start, *radix);
+            if end == digits_start {
+                return Err(error(
+                    format!("expected base {} digit after '{}'", radix, prefix).into(),
+                    digits_start,
+                ));
+            }
+            let value = self
+                .integer_value(&data[digits_start..end], *radix, negative)
+                .ok_or_else(|| overflow(end))?;
+            return literal(end, Number::I64(value));
+        }
+
+        position = format.digits_end(data, position, 10);
+        let has_integer = position > sign_end;
+
+        let mut is_float = false;
+        if let Some(float) = format.float {
+            if data.get(position) == Some(&format.decimal_point) {
+                let fraction_end = format.digits_end(data, position + 1, 10);
+                let accepted = match fraction_end > position + 1 {
+                    true => has_integer || float.leading_dot,
+                    false => has_integer && float.trailing_dot,
+                };
+                if accepted {
+                    is_float = true;
+                    position = fraction_end;
+                }
+            }
+            if float.exponent
+                && (has_integer || is_float)
+                && let Some(end) = format.exponent_end(data, position)
+            {
+                is_float = true;
+                position = end;
+            }
+        }
+        if !has_integer && !is_float {
+            return Err(error("expected number".into(), start));
+        }
+
+        if !is_float {
+            let value = self
+                .integer_value(&data[sign_end..position], 10, negative)
+                .ok_or_else(|| overflow(position))?;
+            return literal(position, Number::I64(value));
+        }
+
+        let value: f64 = self
+            .normalized_float(&data[start..position])
+            .parse()
+            .map_err(|_| error("invalid floating point number".into(), start))?;
+        if !value.is_finite() {
+            return Err(error("floating point overflow".into(), start));
+        }
+        literal(position, Number::F64(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ascii::number::number;
+
+    fn parse<'a>(format: &NumberFormat, input: &'a str) -> (Number, &'a str, usize) {
+        let cursor = ByteCursor::new(input.as_bytes());
+        let (literal, cursor) = format.clone().parser().parse(cursor).unwrap();
+        (literal.value, literal.lexeme, cursor.position())
+    }
+
+    #[test]
+    fn test_default_format() {
+        let format = NumberFormat::new();
+        assert_eq!(parse(&format, "42;"), (Number::I64(42), "42", 2));
+        assert_eq!(parse(&format, "-2.5e3"), (Number::F64(-2.5), "-2.5", 4));
+        assert_eq!(parse(&format, "+7."), (Number::I64(7), "+7", 2));
+    }
+
+    #[test]
+    fn test_default_format_matches_number() {
+        let inputs = [
+            "42;", "-2.5e3", "+7.", "1e5", "0.25", "-0", "+3.5", "007", ".5", "-", "x",
+        ];
+        for input in inputs {
+            let expected = number()
+                .parse(ByteCursor::new(input.as_bytes()))
+                .map(|(literal, cursor)| (literal.value, literal.lexeme, cursor.position()));
+            let actual = NumberFormat::new()
+                .parser()
+                .parse(ByteCursor::new(input.as_bytes()))
+                .map(|(literal, cursor)| (literal.value, literal.lexeme, cursor.position()));
+            match (expected, actual) {
+                (Ok(expected), Ok(actual)) => assert_eq!(expected, actual, "{}", input),
+                (Err(_), Err(_)) => {}
+                (expected, actual) => {
+                    panic!(
+                        "{}: number() {:?}, NumberFormat {:?}",
+                        input, expected, actual
+                    )
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_float_config() {
+        let format = NumberFormat::new().float(FloatConfig::full());
+        assert_eq!(
+            parse(&format, "-2.5e3"),
+            (Number::F64(-2500.0), "-2.5e3", 6)
+        );
+        assert_eq!(parse(&format, ".5"), (Number::F64(0.5), ".5", 2));
+        assert_eq!(parse(&format, "5.;"), (Number::F64(5.0), "5.", 2));
+        assert_eq!(parse(&format, "1e5"), (Number::F64(100000.0), "1e5", 3));
+        assert!(format.parser().parse(ByteCursor::new(b".")).is_err());
+    }
+
+    #[test]
+    fn test_digit_separators() {
+        let format = NumberFormat::new().digit_separator(b'_');
+        assert_eq!(
+            parse(&format, "1_000_000"),
+            (Number::I64(1_000_000), "1_000_000", 9)
+        );
+        assert_eq!(
+            parse(&format, "3_0.2_5"),
+            (Number::F64(30.25), "3_0.2_5", 7)
+        );
+        // Separators must sit between digits
+        assert_eq!(parse(&format, "1__0"), (Number::I64(1), "1", 1));
+        assert_eq!(parse(&format, "1_"), (Number::I64(1), "1", 1));
+        assert!(format.parser().parse(ByteCursor::new(b"_1")).is_err());
+    }
+
+    #[test]
+    fn test_decimal_comma_and_signs() {
+        let format = NumberFormat::new().decimal_comma().signs(Signs::Minus);
+        assert_eq!(parse(&format, "-3,75"), (Number::F64(-3.75), "-3,75", 5));
+        assert_eq!(parse(&format, "3.75"), (Number::I64(3), "3", 1));
+        assert!(format.parser().parse(ByteCursor::new(b"+1")).is_err());
+
+        let unsigned = NumberFormat::new().signs(Signs::None);
+        assert!(unsigned.parser().parse(ByteCursor::new(b"-1")).is_err());
+    }
+
+    #[test]
+    fn test_exponent_markers() {
+        let format = NumberFormat::new().exponent_markers(&b"dD"[..]);
+        assert_eq!(parse(&format, "1.5D2"), (Number::F64(150.0), "1.5D2", 5));
+        assert_eq!(parse(&format, "1e2"), (Number::I64(1), "1", 1));
+
+        let integers = NumberFormat::new().integers_only();
+        assert_eq!(parse(&integers, "1.5e2"), (Number::I64(1), "1", 1));
+    }
+
+    #[test]
+    fn test_radix_prefixes() {
+        let format = NumberFormat::new()
+            .digit_separator(b'_')
+            .radix_prefix("0x", 16)
+            .radix_prefix("0b", 2);
+        assert_eq!(
+            parse(&format, "0xff_ff"),
+            (Number::I64(0xffff), "0xff_ff", 7)
+        );
+        assert_eq!(parse(&format, "-0b101"), (Number::I64(-5), "-0b101", 6));
+        assert_eq!(parse(&format, "0"), (Number::I64(0), "0", 1));
+
+        let error = format.parser().parse(ByteCursor::new(b"0xg")).unwrap_err();
+        assert_eq!(error.position(), 2);
+        assert!(error.to_string().contains("expected base 16 digit"));
+    }
+
+    #[test]
+    fn test_overflow_spans_literal() {
+        let data = b"99999999999999999999 ";
+        let error = NumberFormat::new()
+            .parser()
+            .parse(ByteCursor::new(data))
+            .unwrap_err();
+        match error {
+            ParsicombError::IntegerOverflow { span, target } => {
+                assert_eq!((span.start, span.end), (0, 20));
+                assert_eq!(target, "i64");
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+        let min = NumberFormat::new()
+            .parser()
+            .parse(ByteCursor::new(b"-9223372036854775808"))
+            .unwrap();
+        assert_eq!(min.0.value, Number::I64(i64::MIN));
+    }
+}
@@ -1,72 +1,18 @@
-use super::u64::u64;
+use super::integer::integer;
 use crate::ByteCursor;
-use crate::Cursor;
+use crate::ParsicombError;
 use crate::parser::Parser;
-use crate::{CodeLoc, ParsicombError};
 
 /// Parser that matches ASCII integer numbers (positive or negative)
 pub fn i64<'code>()
 -> impl Parser<'code, Cursor = ByteCursor<'code>, Output = i64, Error = ParsicombError<'code>> {
-    IntParser
-}
-
-struct IntParser;
-
-impl<'code> Parser<'code> for IntParser {
-    type Cursor = ByteCursor<'code>;
-    type Output = i64;
-    type Error = ParsicombError<'code>;
-
-    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
-        let mut cursor = cursor;
-        let mut is_negative = false;
-
-        // Check for optional sign
-        match cursor.value() {
-            Ok(b'-') => {
-                is_negative = true;
-                cursor = cursor.next();
-            }
-            Ok(b'+') => {
-                // Skip optional plus sign
-                cursor = cursor.next();
-            }
-            _ => {}
-        }
-
-        // Parse the unsigned integer part
-        let (value, cursor) = u64().parse(cursor)?;
-
-        // Convert to signed and apply sign
-        let signed_value = if is_negative {
-            // Check for overflow when negating
-            if value > i64::MAX as u64 + 1 {
-                let (data, position) = cursor.inner();
-                return Err(ParsicombError::SyntaxError {
-                    message: format!("negative number too large: -{}", value).into(),
-                    loc: CodeLoc::new(data, position),
-                });
-            }
-            -(value as i64)
-        } else {
-            // Check for positive overflow
-            if value > i64::MAX as u64 {
-                let (data, position) = cursor.inner();
-                return Err(ParsicombError::SyntaxError {
-                    message: format!("positive number too large: {}", value).into(),
-                    loc: CodeLoc::new(data, position),
-                });
-            }
-            value as i64
-        };
-
-        Ok((signed_value, cursor))
-    }
+    integer::<i64>()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Cursor;
 
     #[test]
     fn test_positive_integer() {
@@ -153,4 +99,15 @@ mod tests {
         assert_eq!(value, 9876543210);
         assert!(matches!(cursor, ByteCursor::EndOfFile { .. }));
     }
+
+    #[test]
+    fn test_min_and_overflow() {
+        let data = b"-9223372036854775808";
+        let (value, _) = i64().parse(ByteCursor::new(data)).unwrap();
+        assert_eq!(value, i64::MIN);
+
+        let data = b"9223372036854775808";
+        let error = i64().parse(ByteCursor::new(data)).unwrap_err();
+        assert!(matches!(error, ParsicombError::IntegerOverflow { .. }));
+    }
 }
@@ -0,0 +1,205 @@
+use super::digit::digit;
+use crate::ByteCursor;
+use crate::Cursor;
+use crate::ParsicombError;
+use crate::fold::fold_many1;
+use crate::parser::Parser;
+use crate::position::Span;
+use std::marker::PhantomData;
+
+/// Primitive integer types the ASCII integer parsers can produce
+pub trait Integer: Copy {
+    /// Type name used in overflow errors
+    const NAME: &'static str;
+    /// Whether a leading `+` or `-` is accepted
+    const SIGNED: bool;
+
+    /// Append a decimal digit, subtracting it for negative numbers
+    ///
+    /// Negative numbers are accumulated below zero so that `MIN` is reachable.
+    fn push_digit(self, digit: u8, negative: bool) -> Option<Self>;
+
+    fn zero() -> Self;
+}
+
+macro_rules! impl_integer {
+    ($($ty:ident: $signed:expr),*) => {
+        $(
+            impl Integer for $ty {
+                const NAME: &'static str = stringify!($ty);
+                const SIGNED: bool = $signed;
+
+                fn push_digit(self, digit: u8, negative: bool) -> Option<Self> {
+                    let shifted = self.checked_mul(10)?;
+                    if negative {
+                        shifted.checked_sub(digit as $ty)
+                    } else {
+                        shifted.checked_add(digit as $ty)
+                    }
+                }
+
+                fn zero() -> Self {
+                    0
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(
+    u8: false, u16: false, u32: false, u64: false, u128: false,
+    i8: true, i16: true, i32: true, i64: true, i128: true
+);
+
+/// Parser that matches an ASCII decimal integer of type `N`
+///
+/// Signed types accept an optional `+` or `-`. A literal that does not fit `N` fails
+/// with `ParsicombError::IntegerOverflow` spanning the sign and all digits.
+pub struct IntegerParser<N> {
+    _integer: PhantomData<N>,
+}
+
+impl<N: Integer> IntegerParser<N> {
+    pub fn new() -> Self {
+        IntegerParser {
+            _integer: PhantomData,
+        }
+    }
+}
+
+impl<N: Integer> Default for IntegerParser<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'code, N: Integer> Parser<'code> for IntegerParser<N> {
+    type Cursor = ByteCursor<'code>;
+    type Output = N;
+    type Error = ParsicombError<'code>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let start = cursor.position();
+        let mut cursor = cursor;
+        let mut negative = false;
+
+        if N::SIGNED {
+            match cursor.value() {
+                Ok(b'-') => {
+                    negative = true;
+                    cursor = cursor.next();
+                }
+                Ok(b'+') => cursor = cursor.next(),
+                _ => {}
+            }
+        }
+
+        let digits = fold_many1(
+            digit(),
+            || Some(N::zero()),
+            |acc, d| acc?.push_digit(d - b'0', negative),
+        );
+        let (value, cursor) = digits.parse(cursor)?;
+
+        match value {
+            Some(value) => Ok((value, cursor)),
+            None => {
+                let (data, end) = cursor.inner();
+                Err(ParsicombError::IntegerOverflow {
+                    span: Span::new(data, start, end),
+                    target: N::NAME,
+                })
+            }
+        }
+    }
+}
+
+/// Creates a parser for a decimal integer of type `N`, e.g. `integer::<i16>()`
+pub fn integer<N: Integer>() -> IntegerParser<N> {
+    IntegerParser::new()
+}
+
+/// Parser that matches one or more ASCII digits and returns them as a u8
+pub fn u8() -> IntegerParser<u8> {
+    integer()
+}
+
+/// Parser that matches one or more ASCII digits and returns them as a u16
+pub fn u16() -> IntegerParser<u16> {
+    integer()
+}
+
+/// Parser that matches one or more ASCII digits and returns them as a u32
+pub fn u32() -> IntegerParser<u32> {
+    integer()
+}
+
+/// Parser that matches one or more ASCII digits and returns them as a u128
+pub fn u128() -> IntegerParser<u128> {
+    integer()
+}
+
+/// Parser that matches a signed ASCII integer and returns it as an i32
+pub fn i32() -> IntegerParser<i32> {
+    integer()
+}
+
+/// Parser that matches a signed ASCII integer and returns it as an i128
+pub fn i128() -> IntegerParser<i128> {
+    integer()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorLeaf;
+
+    #[test]
+    fn test_typed_bounds() {
+        let (value, _) = u8().parse(ByteCursor::new(b"255")).unwrap();
+        assert_eq!(value, 255);
+        let (value, _) = u16().parse(ByteCursor::new(b"65535")).unwrap();
+        assert_eq!(value, u16::MAX);
+        let (value, _) = i32().parse(ByteCursor::new(b"-2147483648")).unwrap();
+        assert_eq!(value, i32::MIN);
+        let data = b"340282366920938463463374607431768211455";
+        let (value, _) = u128().parse(ByteCursor::new(data)).unwrap();
+        assert_eq!(value, u128::MAX);
+        let data = b"-170141183460469231731687303715884105728";
+        let (value, _) = i128().parse(ByteCursor::new(data)).unwrap();
+        assert_eq!(value, i128::MIN);
+    }
+
+    #[test]
+    fn test_overflow_spans_whole_literal() {
+        let data = b"x = -2147483649;";
+        let cursor = ByteCursor::at(data, 4);
+
+        let error = i32().parse(cursor).unwrap_err();
+        match &error {
+            ParsicombError::IntegerOverflow { span, target } => {
+                assert_eq!(span.as_string(), "-2147483649");
+                assert_eq!(*target, "i32");
+            }
+            other => panic!("expected overflow, got {:?}", other),
+        }
+        assert_eq!(error.loc().position(), 4);
+        assert_eq!(error.message(), "number too large for i32: -2147483649");
+    }
+
+    #[test]
+    fn test_unsigned_rejects_sign() {
+        assert!(u32().parse(ByteCursor::new(b"-1")).is_err());
+        assert!(u32().parse(ByteCursor::new(b"+1")).is_err());
+
+        let error = u8().parse(ByteCursor::new(b"256")).unwrap_err();
+        assert!(matches!(error, ParsicombError::IntegerOverflow { .. }));
+    }
+
+    #[test]
+    fn test_generic_integer() {
+        let (value, cursor) = integer::<i16>().parse(ByteCursor::new(b"+300,")).unwrap();
+        assert_eq!(value, 300);
+        assert_eq!(cursor.value().unwrap(), b',');
+    }
+}
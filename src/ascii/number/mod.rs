@@ -2,15 +2,20 @@ use crate::ByteCursor;
 use crate::map::MapExt;
 use crate::or::OrExt;
 use crate::parser::Parser;
+use crate::position::{PositionExt, Span};
 
 pub mod digit;
 pub mod f64;
+pub mod format;
 pub mod i64;
+pub mod integer;
 pub mod u64;
 
 pub use digit::digit;
-pub use f64::f64;
+pub use f64::{FloatConfig, f64, f64_with};
+pub use format::{NumberFormat, NumberFormatParser, Signs};
 pub use i64::i64;
+pub use integer::{Integer, IntegerParser, i32, i128, integer, u8, u16, u32, u128};
 pub use u64::u64;
 
 #[derive(Debug, PartialEq)]
@@ -19,9 +24,29 @@ pub enum Number {
     F64(f64),
 }
 
-/// Parser that matches either an integer or a float and returns a Number enum
-pub fn number<'code>() -> impl Parser<'code, Cursor = ByteCursor<'code>, Output = Number> {
-    f64().map(Number::F64).or(i64().map(Number::I64))
+/// A parsed number together with the exact text it was parsed from
+///
+/// The lexeme keeps the literal as written, e.g. `1.50` or `-0`, which the converted
+/// value cannot reproduce.
+#[derive(Debug, PartialEq)]
+pub struct NumberLiteral<'code> {
+    pub value: Number,
+    pub lexeme: &'code str,
+    pub span: Span<'code>,
+}
+
+/// Parser that matches either an integer or a float and returns it with its lexeme
+pub fn number<'code>()
+-> impl Parser<'code, Cursor = ByteCursor<'code>, Output = NumberLiteral<'code>> {
+    f64()
+        .map(Number::F64)
+        .or(i64().map(Number::I64))
+        .map_with_span(|value, span: Span<'code>| NumberLiteral {
+            value,
+            // Numbers are pure ASCII, so the matched bytes are valid UTF-8
+            lexeme: std::str::from_utf8(span.slice()).unwrap_or_default(),
+            span,
+        })
 }
 
 #[cfg(test)]
@@ -31,13 +56,14 @@ mod tests {
     use crate::Cursor;
 
     #[test]
+    #[allow(clippy::approx_constant)]
     fn test_number_float() {
         let data = b"3.14abc";
         let cursor = ByteCursor::new(data);
         let parser = number();
 
         let (num, cursor) = parser.parse(cursor).unwrap();
-        match num {
+        match num.value {
             Number::F64(f) => assert!((f - 3.14).abs() < f64::EPSILON),
             Number::I64(_) => panic!("Expected float, got int"),
         }
@@ -51,7 +77,7 @@ mod tests {
         let parser = number();
 
         let (num, cursor) = parser.parse(cursor).unwrap();
-        match num {
+        match num.value {
             Number::I64(i) => assert_eq!(i, 123),
             Number::F64(_) => panic!("Expected int, got float"),
         }
@@ -65,7 +91,7 @@ mod tests {
         let parser = number();
 
         let (num, cursor) = parser.parse(cursor).unwrap();
-        match num {
+        match num.value {
             Number::F64(f) => assert!((f - (-2.5)).abs() < f64::EPSILON),
             Number::I64(_) => panic!("Expected float, got int"),
         }
@@ -79,10 +105,25 @@ mod tests {
         let parser = number();
 
         let (num, cursor) = parser.parse(cursor).unwrap();
-        match num {
+        match num.value {
             Number::I64(i) => assert_eq!(i, -456),
             Number::F64(_) => panic!("Expected int, got float"),
         }
         assert_eq!(cursor.value().unwrap(), b'x');
     }
+
+    #[test]
+    fn test_number_keeps_lexeme() {
+        let data = b"x = 1.50;";
+        let cursor = ByteCursor::new(data);
+        let (_, cursor) = crate::utf8::string::is_string("x = ")
+            .parse(cursor)
+            .unwrap();
+
+        let (num, cursor) = number().parse(cursor).unwrap();
+        assert_eq!(num.lexeme, "1.50");
+        assert_eq!(num.span, Span::new(data, 4, 8));
+        assert_eq!(num.value, Number::F64(1.5));
+        assert_eq!(cursor.value().unwrap(), b';');
+    }
 }
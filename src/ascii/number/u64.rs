@@ -1,52 +1,12 @@
-use super::digit::digit;
+use super::integer::integer;
 use crate::ByteCursor;
-use crate::Cursor;
+use crate::ParsicombError;
 use crate::parser::Parser;
-use crate::some::some;
-use crate::{CodeLoc, ParsicombError};
 
 /// Parser that matches one or more ASCII digits and returns them as a u64
 pub fn u64<'code>()
 -> impl Parser<'code, Cursor = ByteCursor<'code>, Output = u64, Error = ParsicombError<'code>> {
-    UIntParser
-}
-
-struct UIntParser;
-
-impl<'code> Parser<'code> for UIntParser {
-    type Cursor = ByteCursor<'code>;
-    type Output = u64;
-    type Error = ParsicombError<'code>;
-
-    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
-        let (digit_bytes, cursor) = some(digit()).parse(cursor)?;
-
-        // Convert digits to string
-        let num_str = match std::str::from_utf8(&digit_bytes) {
-            Ok(s) => s,
-            Err(_) => {
-                let (data, position) = cursor.inner();
-                return Err(ParsicombError::SyntaxError {
-                    message: "invalid UTF-8 in digits".into(),
-                    loc: CodeLoc::new(data, position),
-                });
-            }
-        };
-
-        // Parse the number
-        let value = match num_str.parse::<u64>() {
-            Ok(v) => v,
-            Err(_) => {
-                let (data, position) = cursor.inner();
-                return Err(ParsicombError::SyntaxError {
-                    message: format!("number too large: {}", num_str).into(),
-                    loc: CodeLoc::new(data, position),
-                });
-            }
-        };
-
-        Ok((value, cursor))
-    }
+    integer::<u64>()
 }
 
 #[cfg(test)]
@@ -0,0 +1,219 @@
+use crate::ByteCursor;
+use crate::cursor::Cursor;
+use crate::parser::Parser;
+use crate::{CodeLoc, ParsicombError};
+
+// # Bulk Scanners - Whitespace and Identifiers
+//
+// Matching runs of whitespace or identifier characters one byte at a time through
+// `Parser::parse` pays for a cursor step and a `Result` per byte. These scanners work
+// on the underlying slice instead and classify eight bytes per step using SWAR
+// (SIMD within a register): a chunk is loaded into a `u64` and every byte lane is
+// tested at once, so the high bit of each lane tells whether that byte matches.
+// The first lane that does not match ends the scan.
+
+const ONES: u64 = u64::from_ne_bytes([0x01; 8]);
+const HIGH: u64 = u64::from_ne_bytes([0x80; 8]);
+const LOW: u64 = !HIGH;
+
+/// High bit set in every lane of `chunk` equal to `byte`
+fn lanes_equal(chunk: u64, byte: u8) -> u64 {
+    let x = chunk ^ (ONES * byte as u64);
+    !(((x & LOW) + LOW) | x) & HIGH
+}
+
+/// High bit set in every lane in `lo..=hi`, `chunk` must be pure ASCII
+fn lanes_in_range(chunk: u64, lo: u8, hi: u8) -> u64 {
+    let at_least_lo = chunk + ONES * (0x80 - lo as u64);
+    let above_hi = chunk + ONES * (0x7f - hi as u64);
+    at_least_lo & !above_hi & HIGH
+}
+
+fn whitespace_lanes(chunk: u64) -> u64 {
+    lanes_equal(chunk, b' ')
+        | lanes_equal(chunk, b'\t')
+        | lanes_equal(chunk, b'\n')
+        | lanes_equal(chunk, b'\r')
+}
+
+fn ident_lanes(chunk: u64) -> u64 {
+    let ascii = !chunk & HIGH;
+    let chunk = chunk & LOW;
+    let matches = lanes_in_range(chunk | (ONES * 0x20), b'a', b'z')
+        | lanes_in_range(chunk, b'0', b'9')
+        | lanes_equal(chunk, b'_');
+    matches & ascii
+}
+
+fn is_ident_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_'
+}
+
+fn is_whitespace_byte(byte: u8) -> bool {
+    matches!(byte, b' ' | b'\t' | b'\n' | b'\r')
+}
+
+/// Length of the prefix of `bytes` accepted by a chunk classifier and its scalar twin
+fn scan(bytes: &[u8], lanes: impl Fn(u64) -> u64, accepts: impl Fn(u8) -> bool) -> usize {
+    let mut chunks = bytes.chunks_exact(8);
+    let mut length = 0;
+
+    for chunk in chunks.by_ref() {
+        let chunk = u64::from_le_bytes(chunk.try_into().unwrap());
+        let rejected = !lanes(chunk) & HIGH;
+        if rejected != 0 {
+            // Little-endian load, so the lowest set lane is the first rejected byte
+            return length + rejected.trailing_zeros() as usize / 8;
+        }
+        length += 8;
+    }
+
+    length
+        + chunks
+            .remainder()
+            .iter()
+            .take_while(|&&byte| accepts(byte))
+            .count()
+}
+
+/// Parser that consumes zero or more ASCII whitespace characters
+///
+/// Matches the same characters as `whitespace()` and returns them as one slice.
+pub struct TakeWhitespace;
+
+impl<'code> Parser<'code> for TakeWhitespace {
+    type Cursor = ByteCursor<'code>;
+    type Output = &'code str;
+    type Error = ParsicombError<'code>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (data, start) = cursor.inner();
+        let end = start + scan(&data[start..], whitespace_lanes, is_whitespace_byte);
+
+        // Only ASCII was consumed, so the slice is valid UTF-8
+        let text = std::str::from_utf8(&data[start..end]).unwrap_or_default();
+        Ok((text, ByteCursor::scanned(data, start, end)?))
+    }
+}
+
+/// Parser that matches an ASCII identifier `[A-Za-z_][A-Za-z0-9_]*`
+pub struct TakeIdent;
+
+impl<'code> Parser<'code> for TakeIdent {
+    type Cursor = ByteCursor<'code>;
+    type Output = &'code str;
+    type Error = ParsicombError<'code>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (data, start) = cursor.inner();
+
+        match data.get(start) {
+            Some(&byte) if byte.is_ascii_alphabetic() || byte == b'_' => {}
+            _ => {
+                return Err(ParsicombError::SyntaxError {
+                    message: "expected identifier".into(),
+                    loc: CodeLoc::new(data, start),
+                });
+            }
+        }
+
+        let end = start + 1 + scan(&data[start + 1..], ident_lanes, is_ident_byte);
+        let text = std::str::from_utf8(&data[start..end]).unwrap_or_default();
+        Ok((text, ByteCursor::scanned(data, start, end)?))
+    }
+}
+
+/// Creates a parser that consumes a run of ASCII whitespace, possibly empty
+pub fn take_whitespace() -> TakeWhitespace {
+    TakeWhitespace
+}
+
+/// Creates a parser that matches an ASCII identifier
+pub fn take_ident() -> TakeIdent {
+    TakeIdent
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_take_whitespace() {
+        let data = b" \t\r\n  x";
+        let cursor = ByteCursor::new(data);
+
+        let (ws, cursor) = take_whitespace().parse(cursor).unwrap();
+        assert_eq!(ws, " \t\r\n  ");
+        assert_eq!(cursor.value().unwrap(), b'x');
+    }
+
+    #[test]
+    fn test_take_whitespace_empty_and_eof() {
+        let data = b"x";
+        let (ws, cursor) = take_whitespace().parse(ByteCursor::new(data)).unwrap();
+        assert_eq!(ws, "");
+        assert_eq!(cursor.position(), 0);
+
+        let data = b"                    ";
+        let (ws, cursor) = take_whitespace().parse(ByteCursor::new(data)).unwrap();
+        assert_eq!(ws.len(), 20);
+        assert!(cursor.eos());
+    }
+
+    #[test]
+    fn test_take_whitespace_stops_inside_chunk() {
+        // Stop at every lane of a chunk and in the remainder
+        for stop in 0..20 {
+            let mut data = vec![b' '; 20];
+            data[stop] = b'.';
+            let (ws, cursor) = take_whitespace().parse(ByteCursor::new(&data)).unwrap();
+            assert_eq!(ws.len(), stop);
+            assert_eq!(cursor.value().unwrap(), b'.');
+        }
+    }
+
+    #[test]
+    fn test_take_ident() {
+        let data = b"_snake_Case_123456789 = 1";
+        let cursor = ByteCursor::new(data);
+
+        let (ident, cursor) = take_ident().parse(cursor).unwrap();
+        assert_eq!(ident, "_snake_Case_123456789");
+        assert_eq!(cursor.value().unwrap(), b' ');
+    }
+
+    #[test]
+    fn test_take_ident_rejects_bad_start() {
+        let data = b"1abc";
+        let error = take_ident().parse(ByteCursor::new(data)).unwrap_err();
+        assert!(error.to_string().contains("expected identifier"));
+
+        assert!(take_ident().parse(ByteCursor::new(b"")).is_err());
+    }
+
+    #[test]
+    fn test_ident_lanes_match_scalar() {
+        // Every byte value in every lane position must agree with the scalar check
+        for byte in 0..=255u8 {
+            for lane in 0..8 {
+                let mut chunk = [b'a'; 8];
+                chunk[lane] = byte;
+                let lanes = ident_lanes(u64::from_le_bytes(chunk));
+                assert_eq!(lanes & (0x80 << (lane * 8)) != 0, is_ident_byte(byte));
+
+                let mut chunk = [b' '; 8];
+                chunk[lane] = byte;
+                let lanes = whitespace_lanes(u64::from_le_bytes(chunk));
+                assert_eq!(lanes & (0x80 << (lane * 8)) != 0, is_whitespace_byte(byte));
+            }
+        }
+    }
+
+    #[test]
+    fn test_take_ident_stops_at_non_ascii() {
+        let data = "abcdefghé".as_bytes();
+        let (ident, cursor) = take_ident().parse(ByteCursor::new(data)).unwrap();
+        assert_eq!(ident, "abcdefgh");
+        assert_eq!(cursor.position(), 8);
+    }
+}
@@ -17,6 +17,14 @@ pub trait Atomic: Copy + Clone + PartialEq + Debug + Display {
     fn display_width(&self) -> usize {
         1
     }
+
+    /// Check if this element is whitespace that [`skip_space`](crate::space::skip_space) passes over
+    ///
+    /// Defaults to false, so token types only need to override it if their lexer
+    /// keeps whitespace tokens.
+    fn is_space(&self) -> bool {
+        false
+    }
 }
 
 /// A parser that reads one atomic element from the cursor and advances it
@@ -50,6 +58,12 @@ where
     }
 }
 
+impl<C> Default for AtomicParser<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Convenience function to create an atomic parser for a specific cursor type
 pub fn atomic<C>() -> AtomicParser<C> {
     AtomicParser::new()
@@ -60,11 +74,56 @@ impl Atomic for u8 {
         *self == b'\n'
     }
 
+    fn is_space(&self) -> bool {
+        matches!(self, b' ' | b'\t' | b'\n' | b'\r')
+    }
+
     fn format_slice(slice: &[Self]) -> String {
         String::from_utf8_lossy(slice).to_string()
     }
 }
 
+/// Implement Atomic for integer elements such as machine words or UTF-16 code units
+///
+/// The value 10 (ASCII `\n`) counts as a newline and slices are formatted as
+/// space-separated numbers. No value counts as whitespace, since integers are as
+/// likely to be machine words as code points.
+macro_rules! impl_atomic_for_integers {
+    ($($ty:ty),+) => {
+        $(
+            impl Atomic for $ty {
+                fn is_newline(&self) -> bool {
+                    *self == 10
+                }
+
+                fn format_slice(slice: &[Self]) -> String {
+                    slice
+                        .iter()
+                        .map(|x| x.to_string())
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                }
+            }
+        )+
+    };
+}
+
+impl_atomic_for_integers!(u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl Atomic for char {
+    fn is_newline(&self) -> bool {
+        *self == '\n'
+    }
+
+    fn is_space(&self) -> bool {
+        self.is_whitespace()
+    }
+
+    fn format_slice(slice: &[Self]) -> String {
+        slice.iter().collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -72,24 +131,6 @@ mod tests {
     use crate::many::many;
     use crate::{ByteCursor, CodeLoc, Parser, ParsicombError};
 
-    // Test implementation of Atomic for u32
-    impl Atomic for u32 {
-        fn is_newline(&self) -> bool {
-            *self == 10 // ASCII newline as u32
-        }
-
-        fn format_slice(slice: &[Self]) -> String {
-            slice
-                .iter()
-                .map(|&x| format!("{}", x))
-                .collect::<Vec<_>>()
-                .join(" ")
-        }
-    }
-
-    // Note: We can't implement Display for u32 here due to orphan rules
-    // u32 already implements Display in std, so this is not needed anyway
-
     // Custom U32Cursor for testing
     #[derive(Debug, Copy, Clone)]
     pub enum U32Cursor<'code> {
@@ -115,7 +156,7 @@ mod tests {
             match self {
                 U32Cursor::Valid { data, position } => Ok(data[*position]),
                 U32Cursor::EndOfFile { data } => Err(ParsicombError::CannotReadValueAtEof(
-                    CodeLoc::new(data, data.len()),
+                    CodeLoc::new(data, data.len()).into(),
                 )),
             }
         }
@@ -143,12 +184,12 @@ mod tests {
                     match next {
                         U32Cursor::Valid { .. } => Ok(next),
                         U32Cursor::EndOfFile { data } => Err(ParsicombError::UnexpectedEndOfFile(
-                            CodeLoc::new(data, data.len()),
+                            CodeLoc::new(data, data.len()).into(),
                         )),
                     }
                 }
                 U32Cursor::EndOfFile { data } => Err(ParsicombError::AlreadyAtEndOfFile(
-                    CodeLoc::new(data, data.len()),
+                    CodeLoc::new(data, data.len()).into(),
                 )),
             }
         }
@@ -217,6 +258,34 @@ mod tests {
         assert_eq!(results, vec![5u32, 5u32, 5u32]);
     }
 
+    #[test]
+    fn test_repetitions_with_u32_cursor() {
+        use crate::count::count;
+        use crate::error::ErrorNode;
+        use crate::some::some;
+        use crate::take_until::take_until;
+
+        let data = [5u32, 5u32, 7u32, 8u32];
+        let five = || atomic::<U32Cursor>().filter(|&x| x == 5, "expected 5");
+
+        let (fives, cursor) = some(five()).parse(U32Cursor::new(&data)).unwrap();
+        assert_eq!(fives, vec![5, 5]);
+        let (rest, _) = take_until(atomic::<U32Cursor>(), |x: &u32| *x == 8)
+            .parse(cursor)
+            .unwrap();
+        assert_eq!(rest, vec![7]);
+
+        let error = count::<3, _>(five())
+            .parse(U32Cursor::new(&data))
+            .unwrap_err();
+        assert_eq!(error.index(), 2);
+        assert_eq!(error.likely_error().loc().position(), 2);
+        assert!(error.likely_error().message().contains("expected 5"));
+
+        let error = some(five()).parse(U32Cursor::new(&data[2..])).unwrap_err();
+        assert_eq!(error.index(), 0);
+    }
+
     #[test]
     fn test_filter_with_byte_cursor() {
         let data = b"A";
@@ -265,4 +334,23 @@ mod tests {
         let error_string = error.to_string();
         assert!(error_string.contains("expected value < 50"));
     }
+
+    #[test]
+    fn test_integer_atomics_with_atomic_cursor() {
+        use crate::AtomicCursor;
+
+        // UTF-16 code units of "hi\n!"
+        let data: Vec<u16> = "hi\n!".encode_utf16().collect();
+        let parser: AtomicParser<AtomicCursor<u16>> = atomic();
+        let (units, cursor) = many(parser).parse(AtomicCursor::new(&data)).unwrap();
+        assert_eq!(units, data);
+        assert!(cursor.eos());
+
+        let loc = CodeLoc::new(&data, 3);
+        assert_eq!(loc.readable_position().line, 2);
+
+        assert_eq!(i64::format_slice(&[-1, 0, 7]), "-1 0 7");
+        assert!(10u64.is_newline());
+        assert!(!(-10i8).is_newline());
+    }
 }
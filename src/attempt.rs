@@ -0,0 +1,226 @@
+use crate::atomic::Atomic;
+use crate::cursor::Cursor;
+use crate::describe::Description;
+use crate::error::{CodeLoc, ErrorLeaf, ErrorNode, ParsicombError};
+use crate::parser::Parser;
+use std::cell::OnceCell;
+use std::fmt;
+
+// # Attempt Combinator - Atomic Units for Error Reporting
+//
+// A failing parser reports where it gave up, which for a long construct can be deep
+// inside it: `let x = ;` fails at the `;`, not at the `let`. Sometimes the construct
+// should count as a single unit instead, so an `or` around it compares where the
+// alternatives *started* and a report points at the beginning of the construct.
+// `attempt(parser)` reports every failure at the position where the attempt began.
+// The original error stays available as a child for tooling that wants the details.
+//
+// Failing parsers never hand back a cursor, so the attempt also restores the input
+// position. Cursors that can only move forward will rely on this to know that input
+// consumed by the attempt may still be re-read.
+
+/// Error type for Attempt parser reporting the failure at the start of the attempt
+pub struct AttemptError<'code, E, T: Atomic> {
+    start: CodeLoc<'code, T>,
+    inner: E,
+    rewound: OnceCell<ParsicombError<'code, T>>,
+}
+
+impl<'code, E, T: Atomic> AttemptError<'code, E, T> {
+    /// The position where the attempt started
+    pub fn start(&self) -> CodeLoc<'code, T> {
+        self.start
+    }
+
+    /// The error produced by the wrapped parser
+    pub fn inner(&self) -> &E {
+        &self.inner
+    }
+}
+
+impl<'code, E: fmt::Display, T: Atomic> fmt::Debug for AttemptError<'code, E, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AttemptError")
+            .field("start", &self.start.position())
+            .field("inner", &self.inner.to_string())
+            .finish()
+    }
+}
+
+impl<'code, E, T> fmt::Display for AttemptError<'code, E, T>
+where
+    E: ErrorNode<'code, Element = T>,
+    T: Atomic + 'code,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.likely_error())
+    }
+}
+
+impl<'code, E, T> std::error::Error for AttemptError<'code, E, T>
+where
+    E: ErrorNode<'code, Element = T> + fmt::Display,
+    T: Atomic + 'code,
+{
+}
+
+impl<'code, E, T> ErrorNode<'code> for AttemptError<'code, E, T>
+where
+    E: ErrorNode<'code, Element = T>,
+    T: Atomic + 'code,
+{
+    type Element = T;
+
+    fn likely_error(&self) -> &dyn ErrorLeaf<'code, Element = T> {
+        self.rewound.get_or_init(|| {
+            let leaf = self.inner.likely_error();
+            let pos = leaf.loc().readable_position();
+            ParsicombError::SyntaxError {
+                message: format!(
+                    "{} (at line {}, byte offset {})",
+                    leaf.message(),
+                    pos.line,
+                    pos.byte_offset
+                )
+                .into(),
+                loc: self.start,
+            }
+        })
+    }
+
+    fn furthest_leaves(&self) -> Vec<&dyn ErrorLeaf<'code, Element = T>> {
+        vec![self.likely_error()]
+    }
+
+    fn children(&self) -> Vec<&dyn ErrorNode<'code, Element = T>> {
+        vec![&self.inner]
+    }
+}
+
+/// Parser combinator that treats a parser as one unit for error reporting
+///
+/// On failure the error points at the position where the attempt started, and its
+/// message names the place where the wrapped parser actually gave up. Alternatives
+/// that got further than the start of the attempt win in `or` and `choice`.
+///
+/// Example:
+/// ```
+/// use parsicomb::and::AndExt;
+/// use parsicomb::ascii::i64;
+/// use parsicomb::attempt::AttemptExt;
+/// use parsicomb::utf8::string::is_string;
+/// use parsicomb::{ByteCursor, ErrorNode, Parser};
+///
+/// let assignment = is_string("x = ").and(i64()).attempt();
+///
+/// let error = assignment.parse(ByteCursor::new(b"x = ;")).unwrap_err();
+/// assert_eq!(error.likely_error().loc().position(), 0);
+/// assert_eq!(error.inner().likely_error().loc().position(), 4);
+/// ```
+pub struct Attempt<P> {
+    parser: P,
+}
+
+impl<P> Attempt<P> {
+    pub fn new(parser: P) -> Self {
+        Attempt { parser }
+    }
+}
+
+impl<'code, P> Parser<'code> for Attempt<P>
+where
+    P: Parser<'code>,
+    <P::Cursor as Cursor<'code>>::Element: Atomic + 'code,
+{
+    type Cursor = P::Cursor;
+    type Output = P::Output;
+    type Error = AttemptError<'code, P::Error, <P::Cursor as Cursor<'code>>::Element>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (data, position) = cursor.inner();
+        self.parser.parse(cursor).map_err(|inner| AttemptError {
+            start: CodeLoc::new(data, position),
+            inner,
+            rewound: OnceCell::new(),
+        })
+    }
+
+    fn describe(&self) -> Description {
+        self.parser.describe()
+    }
+}
+
+/// Extension trait to add .attempt() method support for parsers
+pub trait AttemptExt<'code>: Parser<'code> + Sized {
+    fn attempt(self) -> Attempt<Self> {
+        Attempt::new(self)
+    }
+}
+
+/// Implement AttemptExt for all parsers
+impl<'code, P> AttemptExt<'code> for P where P: Parser<'code> {}
+
+/// Convenience function to create an Attempt parser
+pub fn attempt<'code, P>(parser: P) -> Attempt<P>
+where
+    P: Parser<'code>,
+{
+    Attempt::new(parser)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ByteCursor;
+    use crate::and::AndExt;
+    use crate::ascii::i64;
+    use crate::hint::HintExt;
+    use crate::map::MapExt;
+    use crate::or::OrExt;
+    use crate::utf8::string::is_string;
+
+    #[test]
+    fn test_attempt_success_passes_through() {
+        let parser = attempt(is_string("let ").and(i64()));
+
+        let ((_, value), cursor) = parser.parse(ByteCursor::new(b"let 5;")).unwrap();
+        assert_eq!(value, 5);
+        assert_eq!(cursor.position(), 5);
+    }
+
+    #[test]
+    fn test_attempt_reports_start_position() {
+        let data = b"a; let x";
+        let cursor = ByteCursor::new(data).next().next().next();
+        let parser = is_string("let ").and(i64()).attempt();
+
+        let error = parser.parse(cursor).unwrap_err();
+        assert_eq!(error.start().position(), 3);
+        let leaf = error.likely_error();
+        assert_eq!(leaf.loc().position(), 3);
+        assert!(leaf.message().ends_with("(at line 1, byte offset 7)"));
+        assert_eq!(error.furthest_leaves().len(), 1);
+        assert_eq!(error.children().len(), 1);
+    }
+
+    #[test]
+    fn test_attempt_loses_to_branch_that_got_further() {
+        let parser = is_string("ab")
+            .and(is_string("cd"))
+            .map(|_| 1)
+            .attempt()
+            .or(is_string("a").and(is_string("x")).map(|_| 2));
+
+        // Without attempt the first branch would win at position 2
+        let error = parser.parse(ByteCursor::new(b"abzz")).unwrap_err();
+        assert_eq!(error.likely_error().loc().position(), 1);
+    }
+
+    #[test]
+    fn test_attempt_keeps_hints() {
+        let parser = is_string("x").with_hint("try x").attempt();
+
+        let error = parser.parse(ByteCursor::new(b"y")).unwrap_err();
+        assert_eq!(error.hints(), ["try x"]);
+    }
+}
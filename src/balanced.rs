@@ -0,0 +1,214 @@
+use crate::ByteCursor;
+use crate::Cursor;
+use crate::parser::Parser;
+use crate::position::Span;
+use crate::{CodeLoc, ParsicombError};
+
+/// Parser for a region enclosed by a pair of delimiters, such as `{ ... }`
+///
+/// Nested pairs are balanced, so `{ a { b } c }` is consumed up to the last `}`.
+/// The content is not parsed, which makes this useful for skipping a broken block
+/// during error recovery, capturing macro bodies, or deferring the parse of a
+/// region. The output is the slice between the delimiters and the span of the whole
+/// region including them.
+///
+/// With [`skip_strings`](Balanced::skip_strings), delimiters inside string literals
+/// are ignored. Fails with `ParsicombError::UnterminatedConstruct` if the input ends
+/// before the region is closed.
+pub struct Balanced {
+    open: u8,
+    close: u8,
+    quotes: Vec<u8>,
+    escape: Option<u8>,
+}
+
+impl Balanced {
+    pub fn new(open: u8, close: u8) -> Self {
+        Balanced {
+            open,
+            close,
+            quotes: Vec::new(),
+            escape: Some(b'\\'),
+        }
+    }
+
+    /// Skip string literals delimited by any of `quotes`
+    ///
+    /// A string ends at the same quote it started with. Backslash escapes the next
+    /// byte unless changed with [`with_escape`](Balanced::with_escape).
+    pub fn skip_strings(mut self, quotes: &[u8]) -> Self {
+        self.quotes = quotes.to_vec();
+        self
+    }
+
+    /// Use `escape` inside string literals instead of backslash, `None` for none
+    pub fn with_escape(mut self, escape: Option<u8>) -> Self {
+        self.escape = escape;
+        self
+    }
+}
+
+/// Position just past the string literal whose opening quote is at `position`, or
+/// `None` if the input ends first
+///
+/// The string ends at the same quote it started with, `escape` skips the byte after
+/// it. Shared by the scanners that step over strings without decoding them.
+pub(crate) fn skip_string(data: &[u8], position: usize, escape: Option<u8>) -> Option<usize> {
+    let quote = data[position];
+    let mut position = position + 1;
+    while position < data.len() {
+        match data[position] {
+            b if b == quote => return Some(position + 1),
+            b if Some(b) == escape => position += 2,
+            _ => position += 1,
+        }
+    }
+    None
+}
+
+impl<'code> Parser<'code> for Balanced {
+    type Cursor = ByteCursor<'code>;
+    type Output = (&'code [u8], Span<'code>);
+    type Error = ParsicombError<'code>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (data, start) = cursor.inner();
+        if data.get(start) != Some(&self.open) {
+            return Err(ParsicombError::SyntaxError {
+                message: format!("expected '{}'", self.open.escape_ascii()).into(),
+                loc: CodeLoc::new(data, start),
+            });
+        }
+
+        // The whole rest of the input was scanned looking for the end
+        let unterminated = || match ByteCursor::charge(data, start, data.len()) {
+            Err(limit) => limit,
+            Ok(()) => ParsicombError::UnterminatedConstruct {
+                terminator: self.close.escape_ascii().to_string().into(),
+                span: Span::new(data, start, data.len()),
+            },
+        };
+
+        let mut depth = 1usize;
+        let mut position = start + 1;
+        while position < data.len() {
+            let byte = data[position];
+            // Checked before `open` so that identical delimiters do not nest
+            if byte == self.close {
+                depth -= 1;
+                if depth == 0 {
+                    let inner = &data[start + 1..position];
+                    let span = Span::new(data, start, position + 1);
+                    let cursor = ByteCursor::scanned(data, start, position + 1)?;
+                    return Ok(((inner, span), cursor));
+                }
+            } else if byte == self.open {
+                depth += 1;
+            } else if self.quotes.contains(&byte) {
+                position = skip_string(data, position, self.escape).ok_or_else(unterminated)?;
+                continue;
+            }
+            position += 1;
+        }
+
+        Err(unterminated())
+    }
+}
+
+/// Creates a parser for a balanced region between `open` and `close`
+///
+/// Example:
+/// ```
+/// use parsicomb::Parser;
+/// use parsicomb::ByteCursor;
+/// use parsicomb::balanced::balanced;
+///
+/// let data = br#"{ "}" { x } } rest"#;
+/// let ((inner, span), _) = balanced(b'{', b'}')
+///     .skip_strings(b"\"")
+///     .parse(ByteCursor::new(data))
+///     .unwrap();
+/// assert_eq!(inner, br#" "}" { x } "#);
+/// assert_eq!((span.start, span.end), (0, 13));
+/// ```
+pub fn balanced(open: u8, close: u8) -> Balanced {
+    Balanced::new(open, close)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorLeaf;
+
+    #[test]
+    fn test_nesting() {
+        let data = b"(a (b) ((c)) d) e";
+        let ((inner, span), cursor) = balanced(b'(', b')').parse(ByteCursor::new(data)).unwrap();
+        assert_eq!(inner, b"a (b) ((c)) d");
+        assert_eq!(span.as_string(), "(a (b) ((c)) d)");
+        assert_eq!(cursor.value().unwrap(), b' ');
+    }
+
+    #[test]
+    fn test_other_brackets_are_ignored() {
+        let data = b"[ ( ] ]";
+        let ((inner, _), _) = balanced(b'[', b']').parse(ByteCursor::new(data)).unwrap();
+        assert_eq!(inner, b" ( ");
+    }
+
+    #[test]
+    fn test_skip_string() {
+        assert_eq!(skip_string(br#""a\"b" c"#, 0, Some(b'\\')), Some(6));
+        assert_eq!(skip_string(br#"'a\'b' c"#, 0, None), Some(4));
+        assert_eq!(skip_string(br#""a\""#, 0, Some(b'\\')), None);
+    }
+
+    #[test]
+    fn test_strings_only_skipped_when_enabled() {
+        let data = br#"{ '}' "a\"}" }"#;
+        let ((inner, _), _) = balanced(b'{', b'}').parse(ByteCursor::new(data)).unwrap();
+        assert_eq!(inner, b" '");
+
+        let ((inner, _), cursor) = balanced(b'{', b'}')
+            .skip_strings(b"\"'")
+            .parse(ByteCursor::new(data))
+            .unwrap();
+        assert_eq!(inner, br#" '}' "a\"}" "#);
+        assert!(cursor.eos());
+
+        // Without escapes the backslash does not protect the quote
+        let ((inner, _), _) = balanced(b'{', b'}')
+            .skip_strings(b"\"")
+            .with_escape(None)
+            .parse(ByteCursor::new(br#"{"a\"}"}"#))
+            .unwrap();
+        assert_eq!(inner, br#""a\""#);
+    }
+
+    #[test]
+    fn test_identical_delimiters() {
+        let ((inner, _), _) = balanced(b'|', b'|')
+            .parse(ByteCursor::new(b"|x| y|"))
+            .unwrap();
+        assert_eq!(inner, b"x");
+    }
+
+    #[test]
+    fn test_errors() {
+        let parser = balanced(b'{', b'}').skip_strings(b"\"");
+
+        let error = parser.parse(ByteCursor::new(b"x{}")).unwrap_err();
+        assert_eq!(error.message(), "expected '{'");
+
+        let error = parser.parse(ByteCursor::new(b"{{ }")).unwrap_err();
+        assert!(matches!(
+            error,
+            ParsicombError::UnterminatedConstruct { .. }
+        ));
+        assert_eq!(error.message(), "expected '}' before end of input");
+        assert_eq!(error.loc().position(), 0);
+
+        let error = parser.parse(ByteCursor::new(br#"{ "} }"#)).unwrap_err();
+        assert_eq!(error.message(), "expected '}' before end of input");
+    }
+}
@@ -0,0 +1,123 @@
+//! Input generators for benchmarks, enabled with the `bench` feature
+//!
+//! Every generator is deterministic, the same arguments always produce the same
+//! input, so numbers from different runs and configurations can be compared. The
+//! crate's own criterion benchmarks in `benches/` are built from these.
+
+/// Small deterministic pseudo-random generator, a 64-bit LCG
+#[derive(Debug, Clone)]
+pub struct Lcg(u64);
+
+impl Lcg {
+    pub fn new(seed: u64) -> Self {
+        Lcg(seed)
+    }
+
+    /// Next value in `0..bound`, `bound` must not be 0
+    pub fn below(&mut self, bound: usize) -> usize {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        ((self.0 >> 33) % bound as u64) as usize
+    }
+}
+
+/// Text of `chars` characters mixing 1, 2, 3 and 4 byte UTF-8 sequences
+pub fn utf8_text(chars: usize) -> String {
+    const ALPHABET: [char; 8] = ['a', 'z', ' ', 'é', 'ß', '€', '中', '🦀'];
+    let mut rng = Lcg::new(1);
+    (0..chars)
+        .map(|_| ALPHABET[rng.below(ALPHABET.len())])
+        .collect()
+}
+
+/// `words` space separated words, about half of them taken from `keywords`
+pub fn keyword_soup(words: usize, keywords: &[&str]) -> String {
+    let mut rng = Lcg::new(2);
+    let mut text = String::new();
+    for index in 0..words {
+        if index > 0 {
+            text.push(' ');
+        }
+        if !keywords.is_empty() && rng.below(2) == 0 {
+            text.push_str(keywords[rng.below(keywords.len())]);
+        } else {
+            let length = 1 + rng.below(8);
+            text.extend((0..length).map(|_| (b'a' + rng.below(26) as u8) as char));
+        }
+    }
+    text
+}
+
+/// Arithmetic expression of `terms` numbers and names joined by operators, with parentheses
+pub fn expression(terms: usize) -> String {
+    const OPERATORS: [&str; 8] = ["+", "-", "*", "/", "==", "!=", "<=", ">="];
+    let mut rng = Lcg::new(3);
+    let mut text = String::new();
+    let mut open = 0;
+    for index in 0..terms {
+        if index > 0 {
+            text.push_str(&format!(" {} ", OPERATORS[rng.below(OPERATORS.len())]));
+        }
+        if rng.below(4) == 0 {
+            text.push('(');
+            open += 1;
+        }
+        if rng.below(2) == 0 {
+            text.push_str(&rng.below(100_000).to_string());
+        } else {
+            text.push_str(&format!("x{}", rng.below(100)));
+        }
+        if open > 0 && rng.below(3) == 0 {
+            text.push(')');
+            open -= 1;
+        }
+    }
+    text.extend(std::iter::repeat_n(')', open));
+    text
+}
+
+/// JSON document of `values` array elements, each an object with mixed members
+pub fn json_document(values: usize) -> String {
+    let mut rng = Lcg::new(4);
+    let mut text = String::from("[");
+    for index in 0..values {
+        if index > 0 {
+            text.push_str(",\n");
+        }
+        text.push_str(&format!(
+            r#"{{"id": {}, "name": "item {}", "price": {}.{:02}, "tags": ["a", "b\n"], "active": {}, "parent": null}}"#,
+            index,
+            rng.below(1000),
+            rng.below(1000),
+            rng.below(100),
+            rng.below(2) == 0
+        ));
+    }
+    text.push(']');
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generators_are_deterministic() {
+        assert_eq!(utf8_text(100), utf8_text(100));
+        assert_eq!(utf8_text(100).chars().count(), 100);
+        assert_eq!(keyword_soup(50, &["if", "else"]).split(' ').count(), 50);
+
+        let expression = expression(200);
+        let open = expression.matches('(').count();
+        assert_eq!(open, expression.matches(')').count());
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_json_document_parses() {
+        let document = json_document(20);
+        assert!(crate::json::parse(document.as_bytes()).is_ok());
+    }
+}
@@ -1,7 +1,12 @@
+#[cfg(feature = "arena")]
+use crate::arena::ParserArena;
 use crate::atomic::Atomic;
+use crate::boxed::ParserNode;
 use crate::cursor::Cursor;
+use crate::describe::Description;
 use crate::error::{ErrorLeaf, ErrorNode};
 use crate::parser::Parser;
+use crate::skip::skip_padding;
 use std::fmt;
 
 /// Error type for Between parser that can wrap errors from all constituent parsers
@@ -70,6 +75,14 @@ where
             BetweenError::CloseDelimiter(e3) => e3.likely_error(),
         }
     }
+
+    fn children(&self) -> Vec<&dyn ErrorNode<'code, Element = Self::Element>> {
+        match self {
+            BetweenError::OpenDelimiter(e1) => vec![e1],
+            BetweenError::Content(e2) => vec![e2.as_ref()],
+            BetweenError::CloseDelimiter(e3) => vec![e3],
+        }
+    }
 }
 
 /// Parser that matches content between opening and closing delimiters
@@ -77,8 +90,8 @@ where
 /// This is a generic combinator that parses: `open + content + close`
 /// and returns just the `content` value with the delimiters discarded.
 ///
-/// Unlike the UTF-8 whitespace version, this does not handle whitespace automatically.
-/// For whitespace handling, use the specific version in `utf8::whitespace::between`.
+/// This does not skip whitespace or other padding automatically.
+/// For padding inside the delimiters, use [`between_padded`].
 ///
 /// # Examples
 /// - `"[content]"` → `"content"`
@@ -91,7 +104,7 @@ where
     P3: Parser<'code, Cursor = C>,
 {
     open: P1,
-    content: Box<dyn Parser<'code, Cursor = C, Output = O, Error = E2> + 'code>,
+    content: ParserNode<'code, C, O, E2>,
     close: P3,
 }
 
@@ -125,6 +138,13 @@ where
 
         Ok((content_val, cursor))
     }
+
+    fn describe(&self) -> Description {
+        Description::sequence(
+            Description::sequence(self.open.describe(), self.content.describe()),
+            self.close.describe(),
+        )
+    }
 }
 
 impl<'code, P1, P3, C, O, E2> Between<'code, P1, P3, C, O, E2>
@@ -143,7 +163,24 @@ where
     {
         Between {
             open,
-            content: Box::new(content),
+            content: ParserNode::boxed(content),
+            close,
+        }
+    }
+
+    /// Like `new`, but places the content parser in `arena` instead of boxing it
+    #[cfg(feature = "arena")]
+    pub fn new_in<P2>(arena: &'code ParserArena, open: P1, content: P2, close: P3) -> Self
+    where
+        P1::Error: ErrorNode<'code, Element = C::Element> + 'code,
+        P2: Parser<'code, Cursor = C, Output = O, Error = E2> + 'code,
+        P3::Error: ErrorNode<'code, Element = C::Element> + 'code,
+        E2: ErrorNode<'code, Element = C::Element> + 'code,
+        C::Element: Atomic + 'code,
+    {
+        Between {
+            open,
+            content: ParserNode::arena(arena, content),
             close,
         }
     }
@@ -152,7 +189,7 @@ where
 /// Creates a parser that matches content between opening and closing delimiters
 ///
 /// This is a generic combinator that does not handle whitespace automatically.
-/// For automatic whitespace handling, use `utf8::whitespace::between`.
+/// To skip whitespace or comments inside the delimiters, use [`between_padded`].
 pub fn between<'code, P1, P2, P3>(
     open: P1,
     content: P2,
@@ -171,6 +208,113 @@ where
     Between::new(open, content, close)
 }
 
+/// Parser that matches content between delimiters with padding on the inside
+///
+/// Parses: `open + pad* + content + pad* + close`, where `pad*` is zero or more
+/// matches of the padding parser whose outputs are discarded. Padding never fails,
+/// so errors have the same structure as [`Between`].
+pub struct BetweenPadded<'code, P1, PP, P3, C, O, E2>
+where
+    C: Cursor<'code>,
+    P1: Parser<'code, Cursor = C>,
+    PP: Parser<'code, Cursor = C>,
+    P3: Parser<'code, Cursor = C>,
+{
+    open: P1,
+    pad: PP,
+    content: ParserNode<'code, C, O, E2>,
+    close: P3,
+}
+
+impl<'code, P1, PP, P3, C, O, E2> BetweenPadded<'code, P1, PP, P3, C, O, E2>
+where
+    C: Cursor<'code>,
+    P1: Parser<'code, Cursor = C>,
+    PP: Parser<'code, Cursor = C>,
+    P3: Parser<'code, Cursor = C>,
+{
+    pub fn new<P2>(open: P1, pad: PP, content: P2, close: P3) -> Self
+    where
+        P2: Parser<'code, Cursor = C, Output = O, Error = E2> + 'code,
+    {
+        BetweenPadded {
+            open,
+            pad,
+            content: ParserNode::boxed(content),
+            close,
+        }
+    }
+}
+
+impl<'code, P1, PP, P3, C, O, E2> Parser<'code> for BetweenPadded<'code, P1, PP, P3, C, O, E2>
+where
+    P1: Parser<'code, Cursor = C>,
+    P1::Error: ErrorNode<'code, Element = C::Element>,
+    PP: Parser<'code, Cursor = C>,
+    P3: Parser<'code, Cursor = C>,
+    P3::Error: ErrorNode<'code, Element = C::Element>,
+    E2: ErrorNode<'code, Element = C::Element> + 'code,
+    C: Cursor<'code>,
+    C::Element: Atomic + 'code,
+{
+    type Cursor = C;
+    type Output = O;
+    type Error = BetweenError<'code, P1::Error, P3::Error, C::Element>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (_, cursor) = self
+            .open
+            .parse(cursor)
+            .map_err(BetweenError::OpenDelimiter)?;
+        let cursor = skip_padding(&self.pad, cursor).0;
+        let (content_val, cursor) = self
+            .content
+            .parse(cursor)
+            .map_err(|e| BetweenError::Content(Box::new(e)))?;
+        let cursor = skip_padding(&self.pad, cursor).0;
+        let (_, cursor) = self
+            .close
+            .parse(cursor)
+            .map_err(BetweenError::CloseDelimiter)?;
+
+        Ok((content_val, cursor))
+    }
+
+    fn describe(&self) -> Description {
+        let pad = || Description::repeat(0, self.pad.describe());
+        Description::Sequence(vec![
+            self.open.describe(),
+            pad(),
+            self.content.describe(),
+            pad(),
+            self.close.describe(),
+        ])
+    }
+}
+
+/// Creates a parser that matches content between delimiters, skipping any
+/// number of `pad` matches after `open` and before `close`
+///
+/// The padding parser can match whitespace, comments, or any other trivia.
+pub fn between_padded<'code, P1, PP, P2, P3>(
+    open: P1,
+    pad: PP,
+    content: P2,
+    close: P3,
+) -> BetweenPadded<'code, P1, PP, P3, P1::Cursor, P2::Output, P2::Error>
+where
+    P1: Parser<'code>,
+    PP: Parser<'code, Cursor = P1::Cursor>,
+    P2: Parser<'code, Cursor = P1::Cursor> + 'code,
+    P3: Parser<'code, Cursor = P1::Cursor>,
+    P1::Error: ErrorNode<'code, Element = <P1::Cursor as Cursor<'code>>::Element>,
+    P2::Error: ErrorNode<'code, Element = <P1::Cursor as Cursor<'code>>::Element> + 'code,
+    P3::Error: ErrorNode<'code, Element = <P1::Cursor as Cursor<'code>>::Element>,
+    <P1::Cursor as Cursor<'code>>::Element: Atomic + 'code,
+{
+    BetweenPadded::new(open, pad, content, close)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -239,4 +383,75 @@ mod tests {
         assert!((value - 42.0).abs() < f64::EPSILON);
         assert_eq!(cursor.value().unwrap(), b' ');
     }
+
+    #[test]
+    fn test_padded_whitespace() {
+        use crate::utf8::whitespace::unicode_whitespace;
+
+        let data = b"[  42.5 \t]";
+        let cursor = ByteCursor::new(data);
+        let parser = between_padded(is_byte(b'['), unicode_whitespace(), f64(), is_byte(b']'));
+
+        let (value, cursor) = parser.parse(cursor).unwrap();
+        assert!((value - 42.5).abs() < f64::EPSILON);
+        assert!(matches!(cursor, ByteCursor::EndOfFile { .. }));
+    }
+
+    #[test]
+    fn test_padded_without_padding() {
+        let data = b"(hello)";
+        let cursor = ByteCursor::new(data);
+        let parser = between_padded(
+            is_byte(b'('),
+            is_byte(b' '),
+            is_string("hello"),
+            is_byte(b')'),
+        );
+
+        let (value, _) = parser.parse(cursor).unwrap();
+        assert_eq!(value.as_ref(), "hello");
+    }
+
+    #[test]
+    fn test_padded_with_comments() {
+        let data = b"{/**/test/**//**/}";
+        let cursor = ByteCursor::new(data);
+        let parser = between_padded(
+            is_byte(b'{'),
+            is_string("/**/"),
+            is_string("test"),
+            is_byte(b'}'),
+        );
+
+        let (value, _) = parser.parse(cursor).unwrap();
+        assert_eq!(value.as_ref(), "test");
+    }
+
+    #[test]
+    fn test_padded_close_error_after_padding() {
+        let data = b"[ 1.0  x";
+        let cursor = ByteCursor::new(data);
+        let parser = between_padded(is_byte(b'['), is_byte(b' '), f64(), is_byte(b']'));
+
+        let error = parser.parse(cursor).unwrap_err();
+        assert!(matches!(error, BetweenError::CloseDelimiter(_)));
+        assert_eq!(error.likely_error().loc().position(), 7);
+    }
+
+    #[test]
+    fn test_padded_with_empty_matching_padding() {
+        use crate::ascii::take_whitespace;
+
+        let data = b"[ 1.5 ]";
+        let cursor = ByteCursor::new(data);
+        let parser = between_padded(is_byte(b'['), take_whitespace(), f64(), is_byte(b']'));
+
+        let (value, cursor) = parser.parse(cursor).unwrap();
+        assert!((value - 1.5).abs() < f64::EPSILON);
+        assert!(cursor.eos());
+
+        let error = parser.parse(ByteCursor::new(b"[ x ]")).unwrap_err();
+        assert!(matches!(error, BetweenError::Content(_)));
+        assert_eq!(error.likely_error().loc().position(), 2);
+    }
 }
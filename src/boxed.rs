@@ -0,0 +1,155 @@
+use super::parser::Parser;
+#[cfg(feature = "arena")]
+use crate::arena::ParserArena;
+use crate::cursor::Cursor;
+use crate::describe::Description;
+use crate::error::ErrorNode;
+
+/// A type-erased parser
+///
+/// Useful for storing parsers of different concrete types in one collection, or
+/// for cutting deeply nested combinator types short where inference struggles.
+/// `Box<dyn Parser>` implements `Parser` itself, so a `BoxedParser` can be used
+/// anywhere a parser is expected.
+pub type BoxedParser<'code, C, O, E> =
+    Box<dyn Parser<'code, Cursor = C, Output = O, Error = E> + 'code>;
+
+/// Extension trait to add .boxed() method support for parsers
+pub trait BoxedExt<'code>: Parser<'code> + Sized + 'code {
+    fn boxed(self) -> BoxedParser<'code, Self::Cursor, Self::Output, Self::Error> {
+        Box::new(self)
+    }
+}
+
+/// Implement BoxedExt for all parsers
+impl<'code, P> BoxedExt<'code> for P where P: Parser<'code> + 'code {}
+
+/// Type-erased child of a combinator, owned in a box on the heap or in an arena
+pub(crate) enum ParserNode<'code, C, O, E> {
+    Boxed(BoxedParser<'code, C, O, E>),
+    #[cfg(feature = "arena")]
+    Arena(ArenaParser<'code, C, O, E>),
+}
+
+/// A type-erased parser owned by a [`ParserArena`], dropped with the combinator holding it
+#[cfg(feature = "arena")]
+pub(crate) type ArenaParser<'code, C, O, E> =
+    bumpalo::boxed::Box<'code, dyn Parser<'code, Cursor = C, Output = O, Error = E> + 'code>;
+
+impl<'code, C, O, E> ParserNode<'code, C, O, E> {
+    pub(crate) fn boxed<P>(parser: P) -> Self
+    where
+        P: Parser<'code, Cursor = C, Output = O, Error = E> + 'code,
+    {
+        ParserNode::Boxed(Box::new(parser))
+    }
+
+    /// Moves `parser` into `arena`, it is dropped when the node is
+    #[cfg(feature = "arena")]
+    pub(crate) fn arena<P>(arena: &'code ParserArena, parser: P) -> Self
+    where
+        P: Parser<'code, Cursor = C, Output = O, Error = E> + 'code,
+    {
+        let raw: *mut P = bumpalo::boxed::Box::into_raw(arena.alloc(parser));
+        let raw: *mut (dyn Parser<'code, Cursor = C, Output = O, Error = E> + 'code) = raw;
+        // SAFETY: `raw` comes from `into_raw` and is only unsized, which bumpalo's `Box`
+        // can't do by coercion on stable. The new box owns the value like the old one did.
+        ParserNode::Arena(unsafe { bumpalo::boxed::Box::from_raw(raw) })
+    }
+}
+
+impl<'code, C, O, E> Parser<'code> for ParserNode<'code, C, O, E>
+where
+    C: Cursor<'code>,
+    E: std::error::Error + ErrorNode<'code, Element = C::Element>,
+{
+    type Cursor = C;
+    type Output = O;
+    type Error = E;
+
+    #[inline]
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        match self {
+            ParserNode::Boxed(parser) => parser.parse(cursor),
+            #[cfg(feature = "arena")]
+            ParserNode::Arena(parser) => parser.parse(cursor),
+        }
+    }
+
+    fn describe(&self) -> Description {
+        match self {
+            ParserNode::Boxed(parser) => parser.describe(),
+            #[cfg(feature = "arena")]
+            ParserNode::Arena(parser) => parser.describe(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ByteCursor;
+    use crate::Cursor;
+    use crate::ParsicombError;
+    use crate::byte::is_byte;
+    use crate::filter::FilterExt;
+    use crate::map::MapExt;
+    use crate::map_err::{MapErrError, MapErrExt};
+    use crate::or::OrExt;
+    use crate::utf8::string::is_string;
+    use std::rc::Rc;
+
+    #[derive(Debug, PartialEq)]
+    enum Token {
+        Keyword,
+        Digit(u8),
+    }
+
+    #[test]
+    fn test_boxed_parsers_in_collection() {
+        let parsers: Vec<BoxedParser<'_, ByteCursor, Token, MapErrError<ParsicombError>>> = vec![
+            is_string("let")
+                .map(|_| Token::Keyword)
+                .map_err(ParsicombError::wrap)
+                .boxed(),
+            is_byte(b'0')
+                .filter(|_| true, "never fails")
+                .map(|b| Token::Digit(b - b'0'))
+                .map_err(ParsicombError::wrap)
+                .boxed(),
+        ];
+
+        let data = b"0";
+        let cursor = ByteCursor::new(data);
+        let results: Vec<_> = parsers.iter().map(|p| p.parse(cursor).is_ok()).collect();
+        assert_eq!(results, vec![false, true]);
+
+        let (token, _) = parsers[1].parse(cursor).unwrap();
+        assert_eq!(token, Token::Digit(0));
+    }
+
+    #[test]
+    fn test_boxed_parser_composes() {
+        let data = b"b";
+        let cursor = ByteCursor::new(data);
+        let parser = is_byte(b'a').boxed().or(is_byte(b'b').boxed());
+
+        let (byte, cursor) = parser.parse(cursor).unwrap();
+        assert_eq!(byte, b'b');
+        assert!(cursor.eos());
+    }
+
+    #[test]
+    fn test_reference_and_rc_parsers() {
+        let data = b"aa";
+        let cursor = ByteCursor::new(data);
+
+        let shared = Rc::new(is_byte(b'a'));
+        let parser = Rc::clone(&shared).or(&*shared);
+        let (_, cursor) = parser.parse(cursor).unwrap();
+
+        let (byte, cursor) = shared.parse(cursor).unwrap();
+        assert_eq!(byte, b'a');
+        assert!(cursor.eos());
+    }
+}
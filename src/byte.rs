@@ -1,4 +1,5 @@
 use crate::cursor::Cursor;
+use crate::describe::Description;
 use crate::{AtomicParser, ByteCursor, CodeLoc, Parser, ParsicombError, atomic};
 
 /// Type alias for a parser that consumes and returns a single byte
@@ -42,9 +43,13 @@ impl<'code> Parser<'code> for IsByteParser {
                     loc: CodeLoc::new(data, position),
                 })
             }
-            Err(e) => Err(e),
+            Err(e) => Err(e.expecting(format!("'{}'", (self.expected as char).escape_default()))),
         }
     }
+
+    fn describe(&self) -> Description {
+        Description::terminal(format!("'{}'", (self.expected as char).escape_default()))
+    }
 }
 
 /// Parser that matches a byte within a range (inclusive)
@@ -83,7 +88,11 @@ impl<'code> Parser<'code> for BetweenBytesParser {
                     loc: CodeLoc::new(data, position),
                 })
             }
-            Err(e) => Err(e),
+            Err(e) => Err(e.expecting(format!(
+                "a byte in range '{}'-'{}'",
+                (self.start as char).escape_default(),
+                (self.end as char).escape_default()
+            ))),
         }
     }
 }
@@ -103,6 +112,7 @@ mod tests {
     use super::*;
     use crate::cursor::Cursor;
     use crate::cursors::atomic::AtomicCursor;
+    use crate::error::ErrorLeaf;
 
     #[test]
     fn test_byte_parser_success() {
@@ -133,6 +143,20 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_eof_errors_say_what_was_expected() {
+        let error = is_byte(b';').parse(ByteCursor::new(b"")).unwrap_err();
+        assert_eq!(error.message(), "expected ';', reached end of file");
+
+        let error = between_bytes(b'0', b'9')
+            .parse(ByteCursor::new(b""))
+            .unwrap_err();
+        assert_eq!(
+            error.message(),
+            "expected a byte in range '0'-'9', reached end of file"
+        );
+    }
+
     #[test]
     fn test_byte_parser_sequence() {
         let data = b"abc";
@@ -0,0 +1,313 @@
+use crate::atomic::Atomic;
+use crate::cursor::Cursor;
+use crate::error::ParsicombError;
+use crate::lazy::{GrowRule, Recursive};
+use crate::limits;
+use crate::parser::Parser;
+use std::sync::{Arc, Mutex, OnceLock, PoisonError, Weak};
+
+// # Cached Grammars - Build Once, Parse Many Times
+//
+// A recursive grammar written with `lazy(|| expr())` calls the grammar function
+// every time the lazy parser runs, so the whole boxed combinator tree is rebuilt at
+// every nesting level of every parse. `cached` builds the grammar once, on first
+// use, and gives the builder a `Recursive` handle to refer back to the rule instead
+// of calling the grammar function again. The handle is the one `left_recursive`
+// uses, only over a rule that is `Send + Sync`.
+//
+// The grammar sits behind an `Arc` and is built through a `OnceLock`, so a
+// `CachedParser` can be shared between threads, and when several threads parse
+// before the grammar exists it is still built exactly once. The cache lives in the
+// `CachedParser` value rather than in a static: combinator trees are tied to the
+// `'code` lifetime of the input they parse, so a process-wide static could only ever
+// parse `'static` input. Keep the parser around and reuse it across parses, clones
+// share the same grammar.
+
+/// Handle to the grammar being defined, handed to the builder of [`cached`]
+///
+/// Every `CachedRef` counts as one level of nesting for `with_recursion_limit`.
+pub type CachedRef<'code, C, O> =
+    Recursive<'code, C, O, dyn GrowRule<'code, C, O> + Send + Sync + 'code>;
+
+struct CachedGrammar<'code, F, P>
+where
+    P: Parser<'code>,
+{
+    build: Mutex<Option<F>>,
+    parser: OnceLock<P>,
+    this: Weak<dyn GrowRule<'code, P::Cursor, P::Output> + Send + Sync + 'code>,
+}
+
+impl<'code, F, P> CachedGrammar<'code, F, P>
+where
+    F: FnOnce(CachedRef<'code, P::Cursor, P::Output>) -> P,
+    P: Parser<'code>,
+{
+    fn parser(&self) -> &P {
+        self.parser.get_or_init(|| {
+            let build = self
+                .build
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .take()
+                .expect("cached grammar is built at most once");
+            build(Recursive::new(Weak::clone(&self.this)))
+        })
+    }
+}
+
+impl<'code, F, P> GrowRule<'code, P::Cursor, P::Output> for CachedGrammar<'code, F, P>
+where
+    F: FnOnce(CachedRef<'code, P::Cursor, P::Output>) -> P,
+    P: Parser<'code>,
+    P::Error: 'code,
+    <P::Cursor as Cursor<'code>>::Element: Atomic + 'code,
+{
+    fn parse_rule(
+        &self,
+        cursor: P::Cursor,
+    ) -> Result<(P::Output, P::Cursor), ParsicombError<'code, <P::Cursor as Cursor<'code>>::Element>>
+    {
+        let _depth = limits::enter_rule(cursor.position())
+            .map_err(|refused| refused.error(cursor.source()))?;
+        self.parser().parse(cursor).map_err(ParsicombError::wrap)
+    }
+}
+
+/// A grammar that is built on first use and reused by every later parse
+///
+/// Created with [`cached`] or [`cached_parser!`](crate::cached_parser). It is
+/// `Send + Sync` like the grammar it builds, so one instance can serve parses on
+/// several threads.
+pub struct CachedParser<'code, F, P>
+where
+    P: Parser<'code>,
+{
+    grammar: Arc<CachedGrammar<'code, F, P>>,
+}
+
+impl<'code, F, P> CachedParser<'code, F, P>
+where
+    F: FnOnce(CachedRef<'code, P::Cursor, P::Output>) -> P,
+    P: Parser<'code>,
+{
+    /// Whether the grammar has been built yet
+    pub fn is_built(&self) -> bool {
+        self.grammar.parser.get().is_some()
+    }
+
+    /// The built grammar, building it now if no parse has happened yet
+    pub fn get(&self) -> &P {
+        self.grammar.parser()
+    }
+}
+
+impl<'code, F, P> Clone for CachedParser<'code, F, P>
+where
+    P: Parser<'code>,
+{
+    fn clone(&self) -> Self {
+        CachedParser {
+            grammar: Arc::clone(&self.grammar),
+        }
+    }
+}
+
+impl<'code, F, P> Parser<'code> for CachedParser<'code, F, P>
+where
+    F: FnOnce(CachedRef<'code, P::Cursor, P::Output>) -> P,
+    P: Parser<'code>,
+{
+    type Cursor = P::Cursor;
+    type Output = P::Output;
+    type Error = P::Error;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        self.grammar.parser().parse(cursor)
+    }
+}
+
+/// Create a grammar that is built once, on first use, and then reused
+///
+/// The builder receives a [`CachedRef`] that parses the grammar being defined, so
+/// recursive rules refer to the cached tree instead of rebuilding it. The builder and
+/// the grammar must be thread-safe: `.or()`, `.and()` and `between` box their
+/// children without `Send`, so cached grammars use `or_static`, `and_static` and
+/// `seq` instead. See [`cached_parser!`](crate::cached_parser) for a shorthand:
+///
+/// ```
+/// use parsicomb::ascii::i64;
+/// use parsicomb::byte::is_byte;
+/// use parsicomb::cached::cached;
+/// use parsicomb::map::MapExt;
+/// use parsicomb::or::OrExt;
+/// use parsicomb::seq::seq;
+/// use parsicomb::{ByteCursor, Parser};
+///
+/// // value = '(' value ')' | number
+/// let value = cached(|value| {
+///     seq((is_byte(b'('), value, is_byte(b')')))
+///         .map(|(_, value, _)| value)
+///         .or_static(i64())
+/// });
+///
+/// for input in [&b"((7))"[..], b"42", b"(-1)"] {
+///     assert!(value.parse(ByteCursor::new(input)).is_ok());
+/// }
+/// ```
+pub fn cached<'code, F, P>(build: F) -> CachedParser<'code, F, P>
+where
+    F: FnOnce(CachedRef<'code, P::Cursor, P::Output>) -> P + Send + 'code,
+    P: Parser<'code> + Send + Sync + 'code,
+    P::Error: 'code,
+    P::Cursor: 'code,
+    P::Output: 'code,
+    <P::Cursor as Cursor<'code>>::Element: Atomic + 'code,
+{
+    let grammar = Arc::new_cyclic(|weak: &Weak<CachedGrammar<'code, F, P>>| {
+        let this: Weak<dyn GrowRule<'code, P::Cursor, P::Output> + Send + Sync + 'code> =
+            weak.clone();
+        CachedGrammar {
+            build: Mutex::new(Some(build)),
+            parser: OnceLock::new(),
+            this,
+        }
+    });
+    CachedParser { grammar }
+}
+
+/// Define `name` as a [`cached`] grammar whose body refers to itself as `name`
+///
+/// `cached_parser!(name, grammar)` is `let name = cached(|name| grammar);`, so the
+/// grammar reads like the rule it defines.
+///
+/// ```
+/// use parsicomb::ascii::i64;
+/// use parsicomb::byte::is_byte;
+/// use parsicomb::cached_parser;
+/// use parsicomb::map::MapExt;
+/// use parsicomb::or::OrExt;
+/// use parsicomb::seq::seq;
+/// use parsicomb::{ByteCursor, Parser};
+///
+/// cached_parser!(
+///     value,
+///     seq((is_byte(b'('), value, is_byte(b')')))
+///         .map(|(_, value, _)| value)
+///         .or_static(i64())
+/// );
+///
+/// let (number, _) = value.parse(ByteCursor::new(b"((7))")).unwrap();
+/// assert_eq!(number, 7);
+/// ```
+#[macro_export]
+macro_rules! cached_parser {
+    ($name:ident, $grammar:expr $(,)?) => {
+        let $name = $crate::cached::cached(|$name| $grammar);
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ByteCursor;
+    use crate::and::AndExt;
+    use crate::ascii::i64;
+    use crate::byte::is_byte;
+    use crate::limits::with_recursion_limit;
+    use crate::map::MapExt;
+    use crate::or::OrExt;
+    use crate::seq::seq;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_cached_builds_once() {
+        let builds = AtomicUsize::new(0);
+        let value = cached(|value| {
+            builds.fetch_add(1, Ordering::Relaxed);
+            seq((is_byte(b'('), value, is_byte(b')')))
+                .map(|(_, value, _)| value)
+                .or_static(i64())
+        });
+        assert!(!value.is_built());
+
+        for input in [&b"((1))"[..], b"2", b"(((3)))"] {
+            assert!(value.parse(ByteCursor::new(input)).is_ok());
+        }
+        assert!(value.is_built());
+        assert_eq!(builds.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_cached_clones_share_grammar() {
+        let builds = AtomicUsize::new(0);
+        let sum = cached(|sum| {
+            builds.fetch_add(1, Ordering::Relaxed);
+            i64()
+                .and_static(is_byte(b'+'))
+                .and_static(sum)
+                .map(|((lhs, _), rhs)| lhs + rhs)
+                .or_static(i64())
+        });
+        let copy = sum.clone();
+
+        let (value, _) = sum.parse(ByteCursor::new(b"1+2+3")).unwrap();
+        assert_eq!(value, 6);
+        let (value, _) = copy.parse(ByteCursor::new(b"4+5")).unwrap();
+        assert_eq!(value, 9);
+        assert_eq!(builds.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_cached_is_shared_between_threads() {
+        let inputs: Vec<String> = (0..4)
+            .map(|depth| format!("{}{depth}{}", "(".repeat(depth), ")".repeat(depth)))
+            .collect();
+        let builds = AtomicUsize::new(0);
+        let value = cached(|value| {
+            builds.fetch_add(1, Ordering::Relaxed);
+            seq((is_byte(b'('), value, is_byte(b')')))
+                .map(|(_, value, _)| value)
+                .or_static(i64())
+        });
+
+        std::thread::scope(|scope| {
+            for (depth, input) in inputs.iter().enumerate() {
+                let value = &value;
+                scope.spawn(move || {
+                    let (number, _) = value.parse(ByteCursor::new(input.as_bytes())).unwrap();
+                    assert_eq!(number, depth as i64);
+                });
+            }
+        });
+        assert_eq!(builds.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_cached_parser_macro() {
+        cached_parser!(
+            sum,
+            i64()
+                .and_static(is_byte(b'+'))
+                .and_static(sum)
+                .map(|((lhs, _), rhs)| lhs + rhs)
+                .or_static(i64())
+        );
+        let (value, _) = sum.parse(ByteCursor::new(b"1+2+3")).unwrap();
+        assert_eq!(value, 6);
+    }
+
+    #[test]
+    fn test_cached_ref_respects_recursion_limit() {
+        let value = cached(|value| {
+            seq((is_byte(b'('), value, is_byte(b')')))
+                .map(|(_, value, _)| value)
+                .or_static(i64())
+        });
+        let data = b"((((((1))))))";
+
+        let limited = with_recursion_limit(value.clone(), 3);
+        assert!(limited.parse(ByteCursor::new(data)).is_err());
+        assert!(value.parse(ByteCursor::new(data)).is_ok());
+    }
+}
@@ -0,0 +1,388 @@
+use super::parser::Parser;
+use crate::atomic::Atomic;
+use crate::cursor::Cursor;
+use crate::describe::Description;
+use crate::error::{
+    CodeLoc, ErrorLeaf, ErrorNode, MergedError, ParsicombError, furthest_only, merge_furthest,
+};
+use std::cell::OnceCell;
+use std::fmt;
+
+// # Choice Combinator - Flat Alternatives
+//
+// A chain `a.or(b).or(c).or(d)` builds a lopsided binary tree of OrErrors, and when
+// every branch fails only one of them is reported. `choice((a, b, c, d))` tries the
+// alternatives in order and keeps all of their errors side by side in one
+// `ChoiceError`. The alternatives that got furthest into the input are merged into a
+// single message such as `expected 'a', 'b' or 'c', found 'x'`.
+//
+// The merged error is only built when `likely_error()` is first asked for, so
+// failures that are backtracked over cost no more than a failing Or.
+
+/// Error type for Choice parser holding the error of every alternative
+pub struct ChoiceError<'code, T: Atomic> {
+    alternatives: Vec<Box<dyn ErrorNode<'code, Element = T> + 'code>>,
+    merged: OnceCell<MergedError<'code, T>>,
+}
+
+impl<'code, T: Atomic + 'code> ChoiceError<'code, T> {
+    pub(crate) fn new(alternatives: Vec<Box<dyn ErrorNode<'code, Element = T> + 'code>>) -> Self {
+        ChoiceError {
+            alternatives,
+            merged: OnceCell::new(),
+        }
+    }
+
+    /// Errors of all alternatives in the order they were tried
+    pub fn alternatives(&self) -> &[Box<dyn ErrorNode<'code, Element = T> + 'code>] {
+        &self.alternatives
+    }
+}
+
+impl<'code, T: Atomic + 'code> fmt::Debug for ChoiceError<'code, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.alternatives.iter().map(|e| format!("{}", &**e)))
+            .finish()
+    }
+}
+
+impl<'code, T: Atomic + 'code> fmt::Display for ChoiceError<'code, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.likely_error())
+    }
+}
+
+impl<'code, T: Atomic + 'code> std::error::Error for ChoiceError<'code, T> {}
+
+impl<'code, T: Atomic + 'code> ErrorNode<'code> for ChoiceError<'code, T> {
+    type Element = T;
+
+    fn likely_error(&self) -> &dyn ErrorLeaf<'code, Element = Self::Element> {
+        merge_furthest(self.furthest_leaves(), &self.merged)
+    }
+
+    fn furthest_leaves(&self) -> Vec<&dyn ErrorLeaf<'code, Element = Self::Element>> {
+        furthest_only(
+            self.alternatives
+                .iter()
+                .flat_map(|alternative| alternative.furthest_leaves())
+                .collect(),
+        )
+    }
+
+    fn children(&self) -> Vec<&dyn ErrorNode<'code, Element = Self::Element>> {
+        self.alternatives
+            .iter()
+            .map(|error| error.as_ref())
+            .collect()
+    }
+}
+
+/// Collections of alternative parsers that can be tried in order by [`Choice`]
+///
+/// Implemented for tuples of 2 to 8 parsers with the same output type, and for
+/// arrays and vectors of parsers of a single type.
+pub trait ChoiceParsers<'code> {
+    type Element: Atomic + 'code;
+    type Cursor: Cursor<'code, Element = Self::Element>;
+    type Output;
+
+    /// Try every alternative in order and return the first success
+    #[allow(clippy::type_complexity)]
+    fn parse_choice(
+        &self,
+        cursor: Self::Cursor,
+    ) -> Result<(Self::Output, Self::Cursor), ChoiceError<'code, Self::Element>>;
+
+    /// The choice between the descriptions of the alternatives
+    fn describe_choice(&self) -> Description;
+}
+
+macro_rules! impl_choice_parsers {
+    ($first:ident $first_index:tt $(, $parser:ident $index:tt)+) => {
+        impl<'code, C, O, $first $(, $parser)+> ChoiceParsers<'code> for ($first, $($parser,)+)
+        where
+            C: Cursor<'code>,
+            C::Element: Atomic + 'code,
+            $first: Parser<'code, Cursor = C, Output = O>,
+            $first::Error: 'code,
+            $($parser: Parser<'code, Cursor = C, Output = O>, $parser::Error: 'code,)+
+        {
+            type Element = C::Element;
+            type Cursor = C;
+            type Output = O;
+
+            fn parse_choice(
+                &self,
+                cursor: Self::Cursor,
+            ) -> Result<(Self::Output, Self::Cursor), ChoiceError<'code, C::Element>> {
+                let mut errors: Vec<Box<dyn ErrorNode<'code, Element = C::Element> + 'code>> =
+                    Vec::new();
+
+                match self.$first_index.parse(cursor) {
+                    Ok(result) => return Ok(result),
+                    Err(error) => errors.push(Box::new(error)),
+                }
+                $(
+                    match self.$index.parse(cursor) {
+                        Ok(result) => return Ok(result),
+                        Err(error) => errors.push(Box::new(error)),
+                    }
+                )+
+
+                Err(ChoiceError::new(errors))
+            }
+
+            fn describe_choice(&self) -> Description {
+                let description = self.$first_index.describe();
+                $(let description = Description::choice(description, self.$index.describe());)+
+                description
+            }
+        }
+    };
+}
+
+impl_choice_parsers!(P0 0, P1 1);
+impl_choice_parsers!(P0 0, P1 1, P2 2);
+impl_choice_parsers!(P0 0, P1 1, P2 2, P3 3);
+impl_choice_parsers!(P0 0, P1 1, P2 2, P3 3, P4 4);
+impl_choice_parsers!(P0 0, P1 1, P2 2, P3 3, P4 4, P5 5);
+impl_choice_parsers!(P0 0, P1 1, P2 2, P3 3, P4 4, P5 5, P6 6);
+impl_choice_parsers!(P0 0, P1 1, P2 2, P3 3, P4 4, P5 5, P6 6, P7 7);
+
+/// Try each parser of a slice in order, collecting the errors of those that fail
+#[allow(clippy::type_complexity)]
+fn parse_slice<'code, P>(
+    parsers: &[P],
+    cursor: P::Cursor,
+) -> Result<(P::Output, P::Cursor), ChoiceError<'code, <P::Cursor as Cursor<'code>>::Element>>
+where
+    P: Parser<'code>,
+    P::Error: 'code,
+    <P::Cursor as Cursor<'code>>::Element: Atomic + 'code,
+{
+    let mut errors: Vec<Box<dyn ErrorNode<'code, Element = _> + 'code>> = Vec::new();
+
+    if parsers.is_empty() {
+        errors.push(Box::new(ParsicombError::SyntaxError {
+            message: "no alternatives to choose from".into(),
+            loc: CodeLoc::new(cursor.source(), cursor.position()),
+        }));
+    }
+
+    for parser in parsers {
+        match parser.parse(cursor) {
+            Ok(result) => return Ok(result),
+            Err(error) => errors.push(Box::new(error)),
+        }
+    }
+
+    Err(ChoiceError::new(errors))
+}
+
+/// The choice between the descriptions of a slice of parsers
+fn describe_slice<'code, P>(parsers: &[P]) -> Description
+where
+    P: Parser<'code>,
+{
+    parsers
+        .iter()
+        .map(Parser::describe)
+        .reduce(Description::choice)
+        .unwrap_or(Description::Choice(Vec::new()))
+}
+
+impl<'code, P, const N: usize> ChoiceParsers<'code> for [P; N]
+where
+    P: Parser<'code>,
+    P::Error: 'code,
+    <P::Cursor as Cursor<'code>>::Element: Atomic + 'code,
+{
+    type Element = <P::Cursor as Cursor<'code>>::Element;
+    type Cursor = P::Cursor;
+    type Output = P::Output;
+
+    fn parse_choice(
+        &self,
+        cursor: Self::Cursor,
+    ) -> Result<(Self::Output, Self::Cursor), ChoiceError<'code, Self::Element>> {
+        parse_slice(self, cursor)
+    }
+
+    fn describe_choice(&self) -> Description {
+        describe_slice(self)
+    }
+}
+
+impl<'code, P> ChoiceParsers<'code> for Vec<P>
+where
+    P: Parser<'code>,
+    P::Error: 'code,
+    <P::Cursor as Cursor<'code>>::Element: Atomic + 'code,
+{
+    type Element = <P::Cursor as Cursor<'code>>::Element;
+    type Cursor = P::Cursor;
+    type Output = P::Output;
+
+    fn parse_choice(
+        &self,
+        cursor: Self::Cursor,
+    ) -> Result<(Self::Output, Self::Cursor), ChoiceError<'code, Self::Element>> {
+        parse_slice(self, cursor)
+    }
+
+    fn describe_choice(&self) -> Description {
+        describe_slice(self)
+    }
+}
+
+/// Parser combinator that tries several alternatives in order and returns the first success
+///
+/// Example:
+/// ```
+/// use parsicomb::byte::is_byte;
+/// use parsicomb::{ByteCursor, Parser, choice};
+///
+/// let data = b"x";
+/// let cursor = ByteCursor::new(data);
+/// let error = choice((is_byte(b'a'), is_byte(b'b'), is_byte(b'c')))
+///     .parse(cursor)
+///     .unwrap_err();
+/// assert!(error.to_string().contains(
+///     "expected byte 0x61 ('a'), byte 0x62 ('b') or byte 0x63 ('c'), found 0x78 ('x')"
+/// ));
+/// ```
+pub struct Choice<T> {
+    parsers: T,
+}
+
+impl<T> Choice<T> {
+    pub fn new(parsers: T) -> Self {
+        Choice { parsers }
+    }
+}
+
+impl<'code, T> Parser<'code> for Choice<T>
+where
+    T: ChoiceParsers<'code>,
+{
+    type Cursor = T::Cursor;
+    type Output = T::Output;
+    type Error = ChoiceError<'code, T::Element>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        self.parsers.parse_choice(cursor)
+    }
+
+    fn describe(&self) -> Description {
+        self.parsers.describe_choice()
+    }
+}
+
+/// Convenience function to create a Choice parser from a tuple, array or vector of parsers
+pub fn choice<'code, T>(parsers: T) -> Choice<T>
+where
+    T: ChoiceParsers<'code>,
+{
+    Choice::new(parsers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ByteCursor;
+    use crate::and::AndExt;
+    use crate::byte::is_byte;
+    use crate::map::MapExt;
+    use crate::utf8::string::is_string;
+
+    #[test]
+    fn test_choice_first_match_wins() {
+        let data = b"b";
+        let cursor = ByteCursor::new(data);
+        let parser = choice((is_byte(b'a'), is_byte(b'b'), is_byte(b'b')));
+
+        let (byte, cursor) = parser.parse(cursor).unwrap();
+        assert_eq!(byte, b'b');
+        assert!(cursor.eos());
+    }
+
+    #[test]
+    fn test_choice_mixed_parser_types() {
+        let data = b"false";
+        let cursor = ByteCursor::new(data);
+        let parser = choice((
+            is_string("true").map(|_| true),
+            is_string("false").map(|_| false),
+            is_byte(b'1').map(|_| true),
+        ));
+
+        let (value, _) = parser.parse(cursor).unwrap();
+        assert!(!value);
+    }
+
+    #[test]
+    fn test_choice_merges_errors_at_same_position() {
+        let data = b"x";
+        let cursor = ByteCursor::new(data);
+        let parser = choice((is_byte(b'a'), is_byte(b'b'), is_byte(b'c')));
+
+        let error = parser.parse(cursor).unwrap_err();
+        assert_eq!(error.alternatives().len(), 3);
+        assert_eq!(error.likely_error().loc().position(), 0);
+        assert_eq!(
+            error.likely_error().message(),
+            "expected byte 0x61 ('a'), byte 0x62 ('b') or byte 0x63 ('c'), found 0x78 ('x')"
+        );
+    }
+
+    #[test]
+    fn test_choice_reports_furthest_alternative() {
+        let data = b"abx";
+        let cursor = ByteCursor::new(data);
+        let parser = choice((
+            is_byte(b'x').map(|_| ()),
+            is_byte(b'a')
+                .and(is_byte(b'b'))
+                .and(is_byte(b'c'))
+                .map(|_| ()),
+            is_byte(b'a').and(is_byte(b'c')).map(|_| ()),
+        ));
+
+        let error = parser.parse(cursor).unwrap_err();
+        assert_eq!(error.likely_error().loc().position(), 2);
+        assert!(error.to_string().contains("expected byte 0x63 ('c')"));
+    }
+
+    #[test]
+    fn test_choice_array_and_vec() {
+        let data = b"let";
+        let cursor = ByteCursor::new(data);
+
+        let keywords = choice([is_string("fn"), is_string("let"), is_string("if")]);
+        let (keyword, _) = keywords.parse(cursor).unwrap();
+        assert_eq!(keyword, "let");
+
+        let keywords = choice(vec![is_string("fn"), is_string("if")]);
+        let error = keywords.parse(cursor).unwrap_err();
+        assert_eq!(
+            error.likely_error().message(),
+            "expected 'f' or 'i', found 'l'"
+        );
+    }
+
+    #[test]
+    fn test_choice_empty_vec_fails() {
+        let data = b"a";
+        let cursor = ByteCursor::new(data);
+        let parser = choice(Vec::<crate::utf8::string::IsStringParser>::new());
+
+        let error = parser.parse(cursor).unwrap_err();
+        assert_eq!(error.likely_error().loc().position(), 0);
+        assert_eq!(
+            error.likely_error().message(),
+            "no alternatives to choose from"
+        );
+    }
+}
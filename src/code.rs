@@ -0,0 +1,210 @@
+use crate::atomic::Atomic;
+use crate::cursor::Cursor;
+use crate::describe::Description;
+use crate::error::{ErrorLeaf, ErrorNode};
+use crate::parser::Parser;
+use std::borrow::Cow;
+use std::fmt;
+
+/// Error type for WithCode parser tagging an error with a stable code
+pub struct CodedError<E> {
+    inner: E,
+    code: Cow<'static, str>,
+}
+
+impl<E> CodedError<E> {
+    /// The error produced by the wrapped parser
+    pub fn inner(&self) -> &E {
+        &self.inner
+    }
+}
+
+impl<E: fmt::Display> fmt::Debug for CodedError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CodedError")
+            .field("inner", &self.inner.to_string())
+            .field("code", &self.code)
+            .finish()
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for CodedError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.code, self.inner)
+    }
+}
+
+impl<E: fmt::Display> std::error::Error for CodedError<E> {}
+
+impl<'code, E, T> ErrorNode<'code> for CodedError<E>
+where
+    E: ErrorNode<'code, Element = T>,
+    T: Atomic + 'code,
+{
+    type Element = T;
+
+    fn likely_error(&self) -> &dyn ErrorLeaf<'code, Element = T> {
+        self.inner.likely_error()
+    }
+
+    fn furthest_leaves(&self) -> Vec<&dyn ErrorLeaf<'code, Element = T>> {
+        self.inner.furthest_leaves()
+    }
+
+    fn children(&self) -> Vec<&dyn ErrorNode<'code, Element = T>> {
+        vec![&self.inner]
+    }
+
+    fn code(&self) -> Option<Cow<'static, str>> {
+        self.inner.code().or_else(|| Some(self.code.clone()))
+    }
+}
+
+/// Parser combinator that tags the errors of a parser with a code
+///
+/// Codes such as `E0001` let tools match on the kind of an error instead of its
+/// message, which may be reworded. The code is available through
+/// [`ErrorNode::code`] and is carried into [`Diagnostic`](crate::Diagnostic) and
+/// [`Report`](crate::report::Report). If codes are nested, the innermost code that
+/// applies to the likely error wins since it is the most specific.
+///
+/// Any type that converts into `Cow<'static, str>` can be used as a code, for
+/// example an enum of the codes of a language.
+///
+/// Example:
+/// ```
+/// use parsicomb::and::AndExt;
+/// use parsicomb::byte::is_byte;
+/// use parsicomb::code::CodeExt;
+/// use parsicomb::{ByteCursor, Diagnostic, Parser};
+///
+/// let statement = is_byte(b'x').and(is_byte(b';').with_code("E0042"));
+///
+/// let error = statement.parse(ByteCursor::new(b"x")).unwrap_err();
+/// assert_eq!(Diagnostic::new(&error).code.as_deref(), Some("E0042"));
+/// ```
+pub struct WithCode<P> {
+    parser: P,
+    code: Cow<'static, str>,
+}
+
+impl<P> WithCode<P> {
+    pub fn new(parser: P, code: impl Into<Cow<'static, str>>) -> Self {
+        WithCode {
+            parser,
+            code: code.into(),
+        }
+    }
+}
+
+impl<'code, P> Parser<'code> for WithCode<P>
+where
+    P: Parser<'code>,
+    <P::Cursor as Cursor<'code>>::Element: Atomic + 'code,
+{
+    type Cursor = P::Cursor;
+    type Output = P::Output;
+    type Error = CodedError<P::Error>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        self.parser.parse(cursor).map_err(|inner| CodedError {
+            inner,
+            code: self.code.clone(),
+        })
+    }
+
+    fn describe(&self) -> Description {
+        self.parser.describe()
+    }
+}
+
+/// Extension trait to add .with_code() method support for parsers
+pub trait CodeExt<'code>: Parser<'code> + Sized {
+    fn with_code(self, code: impl Into<Cow<'static, str>>) -> WithCode<Self> {
+        WithCode::new(self, code)
+    }
+}
+
+/// Implement CodeExt for all parsers
+impl<'code, P> CodeExt<'code> for P where P: Parser<'code> {}
+
+/// Convenience function to create a WithCode parser
+pub fn with_code<'code, P>(parser: P, code: impl Into<Cow<'static, str>>) -> WithCode<P>
+where
+    P: Parser<'code>,
+{
+    WithCode::new(parser, code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ByteCursor;
+    use crate::and::AndExt;
+    use crate::ascii::i64;
+    use crate::byte::is_byte;
+    use crate::diagnostic::Diagnostic;
+    use crate::map::MapExt;
+    use crate::or::OrExt;
+    use crate::report::report;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Code {
+        MissingSemicolon,
+        ExpectedValue,
+    }
+
+    impl From<Code> for Cow<'static, str> {
+        fn from(code: Code) -> Self {
+            match code {
+                Code::MissingSemicolon => "E0001".into(),
+                Code::ExpectedValue => "E0002".into(),
+            }
+        }
+    }
+
+    #[test]
+    fn test_code_found_through_combinators() {
+        let parser = i64()
+            .with_code(Code::ExpectedValue)
+            .and(is_byte(b';').with_code(Code::MissingSemicolon));
+
+        let error = parser.parse(ByteCursor::new(b"x")).unwrap_err();
+        assert_eq!(error.code().as_deref(), Some("E0002"));
+
+        let error = parser.parse(ByteCursor::new(b"1")).unwrap_err();
+        assert_eq!(error.code().as_deref(), Some("E0001"));
+        assert!(error.to_string().contains("[E0001] "));
+    }
+
+    #[test]
+    fn test_code_not_reported_for_other_branch() {
+        let parser = is_byte(b'a')
+            .with_code("E0100")
+            .or(is_byte(b'1').and(is_byte(b';')).map(|(byte, _)| byte));
+
+        // The second branch gets further, so the code of the first does not apply
+        let error = parser.parse(ByteCursor::new(b"1!")).unwrap_err();
+        assert_eq!(error.code(), None);
+    }
+
+    #[test]
+    fn test_innermost_code_wins() {
+        let parser = is_byte(b'a').with_code("E0001").with_code("E0999");
+        let error = parser.parse(ByteCursor::new(b"b")).unwrap_err();
+        assert_eq!(error.code().as_deref(), Some("E0001"));
+
+        let parser = is_byte(b'a').and(is_byte(b'b')).with_code("E0999");
+        let error = parser.parse(ByteCursor::new(b"ax")).unwrap_err();
+        assert_eq!(error.code().as_deref(), Some("E0999"));
+    }
+
+    #[test]
+    fn test_code_in_diagnostic_and_report() {
+        let parser = is_byte(b';').with_code("E0001");
+        let error = parser.parse(ByteCursor::new(b"x")).unwrap_err();
+
+        assert_eq!(Diagnostic::new(&error).code.as_deref(), Some("E0001"));
+        assert!(report(&error).to_string().starts_with("error[E0001]: "));
+    }
+}
@@ -0,0 +1,138 @@
+use super::parser::Parser;
+use crate::atomic::Atomic;
+use crate::cursor::Cursor;
+use crate::describe::Description;
+use crate::repetition::RepetitionError;
+
+/// Error type for Count parser recording which repetition failed
+pub type CountError<'code, T> = RepetitionError<'code, T>;
+
+/// Parser combinator that matches exactly `N` occurrences and returns them as an array
+///
+/// Example:
+/// ```
+/// use parsicomb::ascii::u64;
+/// use parsicomb::byte::is_byte;
+/// use parsicomb::{ByteCursor, Parser, count};
+/// use parsicomb::and::AndExt;
+/// use parsicomb::map::MapExt;
+///
+/// let data = b"192.168.0.1";
+/// let cursor = ByteCursor::new(data);
+/// let octet = u64().and(is_byte(b'.')).map(|(n, _)| n);
+/// let ((first, last), _) = count::<3, _>(octet).and(u64()).parse(cursor).unwrap();
+/// assert_eq!(first, [192, 168, 0]);
+/// assert_eq!(last, 1);
+/// ```
+pub struct Count<P, const N: usize> {
+    parser: P,
+}
+
+impl<P, const N: usize> Count<P, N> {
+    pub fn new(parser: P) -> Self {
+        Count { parser }
+    }
+}
+
+impl<'code, P, const N: usize> Parser<'code> for Count<P, N>
+where
+    P: Parser<'code>,
+    P::Error: 'code,
+    <P::Cursor as Cursor<'code>>::Element: Atomic + 'code,
+{
+    type Cursor = P::Cursor;
+    type Output = [P::Output; N];
+    type Error = CountError<'code, <P::Cursor as Cursor<'code>>::Element>;
+
+    fn parse(&self, mut cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let mut error = None;
+
+        // from_fn fills the array in order, once a repetition fails the rest are skipped
+        let values: [Option<P::Output>; N] = std::array::from_fn(|index| {
+            if error.is_some() {
+                return None;
+            }
+            match self.parser.parse(cursor) {
+                Ok((value, next_cursor)) => {
+                    cursor = next_cursor;
+                    Some(value)
+                }
+                Err(inner) => {
+                    error = Some(RepetitionError::new(index, inner));
+                    None
+                }
+            }
+        });
+
+        match error {
+            Some(error) => Err(error),
+            None => Ok((
+                values.map(|value| value.expect("every repetition matched")),
+                cursor,
+            )),
+        }
+    }
+
+    fn describe(&self) -> Description {
+        Description::Sequence(vec![self.parser.describe(); N])
+    }
+}
+
+/// Convenience function to create a Count parser, `N` is often inferred from the output
+pub fn count<'code, const N: usize, P>(parser: P) -> Count<P, N>
+where
+    P: Parser<'code>,
+{
+    Count::new(parser)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ByteCursor;
+    use crate::byte::is_byte;
+    use crate::error::ErrorNode;
+    use crate::utf8::string::is_string;
+
+    #[test]
+    fn test_count_exact() {
+        let data = b"aaab";
+        let cursor = ByteCursor::new(data);
+
+        let (values, cursor) = count::<3, _>(is_byte(b'a')).parse(cursor).unwrap();
+        assert_eq!(values, [b'a'; 3]);
+        assert_eq!(cursor.value().unwrap(), b'b');
+    }
+
+    #[test]
+    fn test_count_inferred_from_pattern() {
+        let data = b"abab";
+        let cursor = ByteCursor::new(data);
+
+        let ([first, second], cursor) = count(is_string("ab")).parse(cursor).unwrap();
+        assert_eq!(first, "ab");
+        assert_eq!(second, "ab");
+        assert!(cursor.eos());
+    }
+
+    #[test]
+    fn test_count_reports_failing_repetition() {
+        let data = b"aaxa";
+        let cursor = ByteCursor::new(data);
+
+        let error = count::<4, _>(is_byte(b'a')).parse(cursor).unwrap_err();
+        assert_eq!(error.index(), 2);
+        assert_eq!(error.likely_error().loc().position(), 2);
+        assert!(error.to_string().starts_with("Repetition 2 failed"));
+    }
+
+    #[test]
+    fn test_count_zero() {
+        let data = b"x";
+        let cursor = ByteCursor::new(data);
+
+        let (values, cursor) = count::<0, _>(is_byte(b'a')).parse(cursor).unwrap();
+        assert_eq!(values, [0u8; 0]);
+        assert_eq!(cursor.position(), 0);
+    }
+}
@@ -46,4 +46,31 @@ pub trait Cursor<'code>: Copy + Clone + Sized {
     /// Returns a tuple of (data_slice, current_position) where data_slice
     /// contains all the elements and current_position is the cursor's position
     fn inner(self) -> (&'code [Self::Element], usize);
+
+    /// Save the current position so it can be restored with `rewind`
+    ///
+    /// Slice cursors are `Copy` so a checkpoint is just the cursor itself, but
+    /// backtracking through checkpoints keeps parsers working with cursors that
+    /// have to do more to restore their state.
+    fn checkpoint(&self) -> Checkpoint<Self> {
+        Checkpoint { cursor: *self }
+    }
+
+    /// Return to a position saved with `checkpoint`
+    fn rewind(self, checkpoint: Checkpoint<Self>) -> Self {
+        checkpoint.cursor
+    }
+}
+
+/// A saved cursor position, created by `Cursor::checkpoint`
+#[derive(Debug, Clone, Copy)]
+pub struct Checkpoint<C> {
+    cursor: C,
+}
+
+impl<'code, C: Cursor<'code>> Checkpoint<C> {
+    /// Position the checkpoint was taken at
+    pub fn position(&self) -> usize {
+        self.cursor.position()
+    }
 }
@@ -1,5 +1,6 @@
 use crate::atomic::Atomic;
 use crate::cursor::Cursor;
+use crate::limits;
 use crate::{CodeLoc, ParsicombError};
 
 #[derive(Debug, Copy, Clone)]
@@ -15,6 +16,39 @@ impl<'code, T: Atomic> AtomicCursor<'code, T> {
         }
         AtomicCursor::Valid { data, position: 0 }
     }
+
+    /// Create a cursor `position` elements into `data`, at end of file if past the end
+    pub(crate) fn at(data: &'code [T], position: usize) -> Self {
+        if position >= data.len() {
+            AtomicCursor::EndOfFile { data }
+        } else {
+            AtomicCursor::Valid { data, position }
+        }
+    }
+
+    /// Charge the resource limits for the elements `data[start..end]` a scanner looked at
+    ///
+    /// Scanners working on the slice call this instead of reading each element through
+    /// `value()`, so fuel and cancellation apply to them all the same.
+    pub(crate) fn charge(
+        data: &'code [T],
+        start: usize,
+        end: usize,
+    ) -> Result<(), ParsicombError<'code, T>> {
+        limits::check_scan(start, end.saturating_sub(start))
+            .map_err(|(limit, position)| limit.error(CodeLoc::new(data, position)))
+    }
+
+    /// Create a cursor at `end` for a scanner that consumed `data[start..end]`,
+    /// charging the resource limits for the consumed elements
+    pub(crate) fn scanned(
+        data: &'code [T],
+        start: usize,
+        end: usize,
+    ) -> Result<Self, ParsicombError<'code, T>> {
+        Self::charge(data, start, end)?;
+        Ok(Self::at(data, end))
+    }
 }
 
 impl<'code, T: Atomic> Cursor<'code> for AtomicCursor<'code, T> {
@@ -23,13 +57,22 @@ impl<'code, T: Atomic> Cursor<'code> for AtomicCursor<'code, T> {
 
     fn value(&self) -> Result<Self::Element, Self::Error> {
         match self {
-            AtomicCursor::Valid { data, position } => Ok(data[*position]),
+            AtomicCursor::Valid { data, position } => {
+                limits::check_read(*position)
+                    .map_err(|limit| limit.error(CodeLoc::new(data, *position)))?;
+                Ok(data[*position])
+            }
             AtomicCursor::EndOfFile { data } => Err(ParsicombError::CannotReadValueAtEof(
-                CodeLoc::new(data, data.len()),
+                CodeLoc::new(data, data.len()).into(),
             )),
         }
     }
 
+    /// Unlike `value()` this never counts as a read, so it burns no fuel
+    fn eos(&self) -> bool {
+        matches!(self, AtomicCursor::EndOfFile { .. })
+    }
+
     fn next(self) -> Self {
         match self {
             AtomicCursor::Valid { data, position } => {
@@ -53,12 +96,12 @@ impl<'code, T: Atomic> Cursor<'code> for AtomicCursor<'code, T> {
                 match next {
                     AtomicCursor::Valid { .. } => Ok(next),
                     AtomicCursor::EndOfFile { data } => Err(ParsicombError::UnexpectedEndOfFile(
-                        CodeLoc::new(data, data.len()),
+                        CodeLoc::new(data, data.len()).into(),
                     )),
                 }
             }
             AtomicCursor::EndOfFile { data } => Err(ParsicombError::AlreadyAtEndOfFile(
-                CodeLoc::new(data, data.len()),
+                CodeLoc::new(data, data.len()).into(),
             )),
         }
     }
@@ -174,7 +217,7 @@ mod tests {
         assert_eq!(from_b.value().unwrap(), b'c');
     }
 
-    // Note: Using Atomic implementation for u32 from atomic.rs
+    // Note: Using the library Atomic implementation for u32
 
     #[test]
     fn test_basic_operations_u32() {
@@ -302,4 +345,19 @@ mod tests {
         assert_eq!(source, &[99, 88, 77]);
         assert_eq!(pos, 2);
     }
+
+    #[test]
+    fn test_checkpoint_and_rewind() {
+        let data = b"abc";
+        let cursor: AtomicCursor<u8> = AtomicCursor::new(data).next();
+
+        let checkpoint = cursor.checkpoint();
+        assert_eq!(checkpoint.position(), 1);
+
+        let cursor = cursor.next().next();
+        assert!(cursor.eos());
+
+        let cursor = cursor.rewind(checkpoint);
+        assert_eq!(cursor.value().unwrap(), b'b');
+    }
 }
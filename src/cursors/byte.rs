@@ -1,7 +1,7 @@
 use crate::AtomicCursor;
 
 /// A specialized cursor for byte data (u8)
-/// This is now just a type alias for AtomicCursor<u8>
+/// This is now just a type alias for `AtomicCursor<u8>`
 pub type ByteCursor<'code> = AtomicCursor<'code, u8>;
 
 #[cfg(test)]
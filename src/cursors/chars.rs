@@ -0,0 +1,120 @@
+use crate::AtomicCursor;
+use crate::position::Span;
+
+// # Char Cursors
+//
+// Byte parsers decode UTF-8 every time they look at a character, and grammars that
+// backtrack a lot decode the same characters again and again. Decoding the source
+// once into a `Vec<char>` lets parsers work on whole characters through
+// `CharSliceCursor`, with positions counted in chars. `CharSource` keeps the byte
+// offset of every char so results can be mapped back onto the original `&str`.
+
+/// A cursor over pre-decoded characters
+pub type CharSliceCursor<'code> = AtomicCursor<'code, char>;
+
+/// Characters decoded from a `&str` together with their byte offsets in it
+#[derive(Debug, Clone)]
+pub struct CharSource {
+    chars: Vec<char>,
+    offsets: Vec<usize>,
+}
+
+impl CharSource {
+    /// Decode `text` into characters
+    pub fn new(text: &str) -> Self {
+        let (mut offsets, chars): (Vec<usize>, Vec<char>) = text.char_indices().unzip();
+        offsets.push(text.len());
+        CharSource { chars, offsets }
+    }
+
+    /// The decoded characters
+    pub fn as_slice(&self) -> &[char] {
+        &self.chars
+    }
+
+    /// A cursor at the first character
+    pub fn cursor(&self) -> CharSliceCursor<'_> {
+        CharSliceCursor::new(&self.chars)
+    }
+
+    /// Byte offset in the original text of the char at `position`
+    ///
+    /// Positions past the end map to the length of the original text.
+    pub fn byte_offset(&self, position: usize) -> usize {
+        self.offsets[position.min(self.chars.len())]
+    }
+
+    /// Translate a span over the chars into a span over the original text's bytes
+    pub fn byte_span<'code>(&self, original: &'code str, span: Span<'_, char>) -> Span<'code> {
+        Span::new(
+            original.as_bytes(),
+            self.byte_offset(span.start),
+            self.byte_offset(span.end),
+        )
+    }
+}
+
+impl From<&str> for CharSource {
+    fn from(text: &str) -> Self {
+        CharSource::new(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::atomic::Atomic;
+    use crate::cursor::Cursor;
+    use crate::error::ErrorNode;
+    use crate::filter::FilterExt;
+    use crate::many::many;
+    use crate::parser::Parser;
+    use crate::position::PositionExt;
+    use crate::some::some;
+    use crate::{CodeLoc, atomic};
+
+    #[test]
+    fn test_char_cursor_steps_whole_characters() {
+        let source = CharSource::new("añ€😀");
+        let mut cursor = source.cursor();
+
+        for expected in ['a', 'ñ', '€', '😀'] {
+            assert_eq!(cursor.value().unwrap(), expected);
+            cursor = cursor.next();
+        }
+        assert!(cursor.eos());
+        assert_eq!(cursor.position(), 4);
+    }
+
+    #[test]
+    fn test_byte_offsets_map_back() {
+        let text = "héllo wörld";
+        let source = CharSource::from(text);
+        let letter = atomic::<CharSliceCursor>().filter(|c| c.is_alphabetic(), "expected letter");
+
+        let (word, cursor) = some(letter).with_position().parse(source.cursor()).unwrap();
+        let ((_, span), _) = many(atomic::<CharSliceCursor>())
+            .with_position()
+            .parse(cursor.next())
+            .unwrap();
+
+        assert_eq!(word.0.iter().collect::<String>(), "héllo");
+        assert_eq!(source.byte_span(text, span).as_string(), "wörld");
+        assert_eq!(source.byte_offset(6), 7);
+        assert_eq!(source.byte_offset(100), text.len());
+    }
+
+    #[test]
+    fn test_error_formatting_uses_chars() {
+        let source = CharSource::new("ab\nñx");
+        let error = atomic::<CharSliceCursor>()
+            .filter(|c| c.is_ascii(), "expected ascii")
+            .parse(source.cursor().next().next().next())
+            .unwrap_err();
+
+        assert_eq!(error.likely_error().loc().position(), 3);
+        assert_eq!(char::format_slice(&['ñ', 'x']), "ñx");
+        let display = CodeLoc::new(source.as_slice(), 3).readable_position();
+        assert_eq!((display.line, display.byte_offset), (2, 0));
+    }
+}
@@ -0,0 +1,222 @@
+use crate::atomic::Atomic;
+use crate::cursor::Cursor;
+use crate::cursors::AtomicCursor;
+use crate::error::CodeLoc;
+
+/// Cursors that can report which line they are on
+///
+/// `LocatedCursor` answers in constant time, plain cursors scan back through the
+/// source on every call.
+pub trait Located<'code>: Cursor<'code> {
+    /// 1-based line number of the current position
+    fn line(&self) -> usize;
+
+    /// Position of the first element of the current line
+    fn line_start(&self) -> usize;
+
+    /// Number of elements between the start of the line and the current position
+    fn offset_in_line(&self) -> usize {
+        self.position() - self.line_start()
+    }
+}
+
+/// Cursor wrapper that tracks the line and line start as it advances
+///
+/// Useful for indentation-sensitive grammars and hand-written parsers that need the
+/// line of every token, where recomputing it from the start of the input is O(n).
+/// Since it wraps another cursor it works with parsers that are generic over the
+/// cursor, such as `atomic()` and the combinators.
+#[derive(Debug, Clone, Copy)]
+pub struct LocatedCursor<C> {
+    cursor: C,
+    line: usize,
+    line_start: usize,
+}
+
+impl<'code, C: Cursor<'code>> LocatedCursor<C> {
+    /// Wrap a cursor positioned at the start of its source
+    pub fn new(cursor: C) -> Self
+    where
+        C::Element: Atomic + 'code,
+    {
+        let loc = CodeLoc::new(cursor.source(), cursor.position());
+        LocatedCursor {
+            cursor,
+            line: loc.line(),
+            line_start: cursor.position() - loc.byte_offset_in_line(),
+        }
+    }
+
+    /// The wrapped cursor
+    pub fn cursor(&self) -> C {
+        self.cursor
+    }
+}
+
+impl<'code, T: Atomic> LocatedCursor<AtomicCursor<'code, T>> {
+    /// Create a located cursor over `data`, starting at line 1
+    pub fn from_source(data: &'code [T]) -> Self {
+        LocatedCursor {
+            cursor: AtomicCursor::new(data),
+            line: 1,
+            line_start: 0,
+        }
+    }
+}
+
+impl<'code, C> Cursor<'code> for LocatedCursor<C>
+where
+    C: Cursor<'code>,
+    C::Element: Atomic + 'code,
+{
+    type Element = C::Element;
+    type Error = C::Error;
+
+    fn value(&self) -> Result<Self::Element, Self::Error> {
+        self.cursor.value()
+    }
+
+    fn next(self) -> Self {
+        let (data, position) = self.cursor.inner();
+        let next = self.cursor.next();
+
+        // Look at the source directly so stepping does not count as a read
+        match data.get(position) {
+            Some(element) if element.is_newline() && next.position() > position => LocatedCursor {
+                cursor: next,
+                line: self.line + 1,
+                line_start: next.position(),
+            },
+            _ => LocatedCursor {
+                cursor: next,
+                ..self
+            },
+        }
+    }
+
+    fn try_next(self) -> Result<Self, Self::Error> {
+        self.cursor.try_next()?;
+        Ok(self.next())
+    }
+
+    fn position(&self) -> usize {
+        self.cursor.position()
+    }
+
+    fn eos(&self) -> bool {
+        self.cursor.eos()
+    }
+
+    fn source(&self) -> &'code [Self::Element] {
+        self.cursor.source()
+    }
+
+    fn inner(self) -> (&'code [Self::Element], usize) {
+        self.cursor.inner()
+    }
+}
+
+impl<'code, C> Located<'code> for LocatedCursor<C>
+where
+    C: Cursor<'code>,
+    C::Element: Atomic + 'code,
+{
+    fn line(&self) -> usize {
+        self.line
+    }
+
+    fn line_start(&self) -> usize {
+        self.line_start
+    }
+}
+
+impl<'code, T: Atomic> Located<'code> for AtomicCursor<'code, T> {
+    fn line(&self) -> usize {
+        CodeLoc::new(self.source(), self.position()).line()
+    }
+
+    fn line_start(&self) -> usize {
+        self.position() - CodeLoc::new(self.source(), self.position()).byte_offset_in_line()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ByteCursor;
+    use crate::atomic::atomic;
+    use crate::filter::FilterExt;
+    use crate::many::many;
+    use crate::parser::Parser;
+
+    #[test]
+    fn test_tracks_lines() {
+        let data = b"ab\ncd\n\ne";
+        let mut cursor = LocatedCursor::from_source(data);
+        let mut seen = Vec::new();
+
+        while !cursor.eos() {
+            seen.push((cursor.line(), cursor.offset_in_line()));
+            cursor = cursor.next();
+        }
+        assert_eq!(
+            seen,
+            [
+                (1, 0),
+                (1, 1),
+                (1, 2),
+                (2, 0),
+                (2, 1),
+                (2, 2),
+                (3, 0),
+                (4, 0)
+            ]
+        );
+
+        // Stepping at the end does not add lines
+        let cursor = cursor.next();
+        assert_eq!((cursor.line(), cursor.position()), (4, 8));
+    }
+
+    #[test]
+    fn test_agrees_with_plain_cursor() {
+        let data = b"let x = 1\n  let y = 2\n\n    z";
+        let mut located = LocatedCursor::from_source(data);
+        let mut plain = ByteCursor::new(data);
+
+        loop {
+            assert_eq!(located.line(), plain.line());
+            assert_eq!(located.line_start(), plain.line_start());
+            if plain.eos() {
+                break;
+            }
+            located = located.next();
+            plain = plain.next();
+        }
+    }
+
+    #[test]
+    fn test_wraps_cursor_mid_source() {
+        let data = b"a\nb\nc";
+        let cursor = ByteCursor::new(data).next().next().next();
+        let located = LocatedCursor::new(cursor);
+        assert_eq!(located.line(), 2);
+        assert_eq!(located.offset_in_line(), 1);
+    }
+
+    #[test]
+    fn test_generic_parsers() {
+        let data = b"  x\n    y";
+        let cursor = LocatedCursor::from_source(data);
+
+        let spaces = many(atomic::<LocatedCursor<ByteCursor>>().filter(|b| *b == b' ', "space"));
+        let (_, cursor) = spaces.parse(cursor).unwrap();
+        assert_eq!(cursor.offset_in_line(), 2);
+
+        let (_, cursor) = atomic().parse(cursor).unwrap();
+        let (_, cursor) = atomic().parse(cursor).unwrap();
+        let (indent, cursor) = spaces.parse(cursor).unwrap();
+        assert_eq!(indent.len(), 4);
+        assert_eq!((cursor.line(), cursor.offset_in_line()), (2, 4));
+    }
+}
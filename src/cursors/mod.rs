@@ -1,5 +1,9 @@
 pub mod atomic;
 pub mod byte;
+pub mod chars;
+pub mod located;
 
 pub use atomic::AtomicCursor;
 pub use byte::ByteCursor;
+pub use chars::{CharSliceCursor, CharSource};
+pub use located::{Located, LocatedCursor};
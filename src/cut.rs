@@ -0,0 +1,226 @@
+use crate::atomic::Atomic;
+use crate::cursor::Cursor;
+use crate::describe::Description;
+use crate::error::{ErrorLeaf, ErrorNode};
+use crate::parser::Parser;
+use std::fmt;
+
+// # Cut - Committed Errors
+//
+// Repetitions stop at the first item that fails: `many(statement)` on
+// `a = 1; b = ; c = 3;` returns the first statement and leaves the rest to whatever
+// follows, which then reports a confusing error at `b`. Once an item has matched far
+// enough to be recognized, a failure in the rest of it is a real error.
+//
+// `cut()` marks the errors of a parser as committed, see
+// [`ErrorNode::is_committed`]. `many`, `some`, `skip_many`, `fold_many`,
+// `fold_many1`, `many_limited`, `separated_list` and `separated_list_with` return
+// committed item errors instead of ending the repetition.
+// Typically the part after an item's introducer is cut:
+// `key.and(is_byte(b'=')).and(value.cut())`.
+//
+// Other combinators keep backtracking as before, an `or` still tries its next
+// alternative. Separator errors of `separated_list` can't be returned because its
+// error type is the item's, so cut the item after the separator instead.
+
+/// Error type for Cut parser marking an error as committed
+pub struct CutError<E> {
+    inner: E,
+}
+
+impl<E> CutError<E> {
+    /// Mark `inner` as committed
+    pub(crate) fn new(inner: E) -> Self {
+        CutError { inner }
+    }
+
+    /// The error produced by the wrapped parser
+    pub fn inner(&self) -> &E {
+        &self.inner
+    }
+
+    /// Unwrap the error produced by the wrapped parser
+    pub fn into_inner(self) -> E {
+        self.inner
+    }
+}
+
+impl<E: fmt::Display> fmt::Debug for CutError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CutError")
+            .field("inner", &self.inner.to_string())
+            .finish()
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for CutError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.inner)
+    }
+}
+
+impl<E: fmt::Display> std::error::Error for CutError<E> {}
+
+impl<'code, E, T> ErrorNode<'code> for CutError<E>
+where
+    E: ErrorNode<'code, Element = T>,
+    T: Atomic + 'code,
+{
+    type Element = T;
+
+    fn likely_error(&self) -> &dyn ErrorLeaf<'code, Element = T> {
+        self.inner.likely_error()
+    }
+
+    fn furthest_leaves(&self) -> Vec<&dyn ErrorLeaf<'code, Element = T>> {
+        self.inner.furthest_leaves()
+    }
+
+    fn children(&self) -> Vec<&dyn ErrorNode<'code, Element = T>> {
+        vec![&self.inner]
+    }
+
+    fn is_committed(&self) -> bool {
+        true
+    }
+}
+
+/// Parser combinator that marks the errors of a parser as committed
+///
+/// Example:
+/// ```
+/// use parsicomb::and::AndExt;
+/// use parsicomb::ascii::i64;
+/// use parsicomb::byte::is_byte;
+/// use parsicomb::cut::CutExt;
+/// use parsicomb::many::many;
+/// use parsicomb::{ByteCursor, ErrorLeaf, ErrorNode, Parser};
+///
+/// // Once a '-' is read the number after it is required
+/// let negatives = many(is_byte(b'-').and(i64().cut()));
+///
+/// let (items, _) = negatives.parse(ByteCursor::new(b"-1-2")).unwrap();
+/// assert_eq!(items.len(), 2);
+///
+/// let error = negatives.parse(ByteCursor::new(b"-1-x")).unwrap_err();
+/// assert!(error.is_committed());
+/// assert_eq!(error.likely_error().loc().position(), 3);
+/// ```
+pub struct Cut<P> {
+    parser: P,
+}
+
+impl<P> Cut<P> {
+    pub fn new(parser: P) -> Self {
+        Cut { parser }
+    }
+}
+
+impl<'code, P> Parser<'code> for Cut<P>
+where
+    P: Parser<'code>,
+    <P::Cursor as Cursor<'code>>::Element: Atomic + 'code,
+{
+    type Cursor = P::Cursor;
+    type Output = P::Output;
+    type Error = CutError<P::Error>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        self.parser
+            .parse(cursor)
+            .map_err(|inner| CutError { inner })
+    }
+
+    fn describe(&self) -> Description {
+        self.parser.describe()
+    }
+}
+
+/// Extension trait to add .cut() method support for parsers
+pub trait CutExt<'code>: Parser<'code> + Sized {
+    fn cut(self) -> Cut<Self> {
+        Cut::new(self)
+    }
+}
+
+/// Implement CutExt for all parsers
+impl<'code, P> CutExt<'code> for P where P: Parser<'code> {}
+
+/// Convenience function to create a Cut parser
+pub fn cut<'code, P>(parser: P) -> Cut<P>
+where
+    P: Parser<'code>,
+{
+    Cut::new(parser)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ByteCursor;
+    use crate::and::AndExt;
+    use crate::ascii::i64;
+    use crate::byte::is_byte;
+    use crate::many::{many, many_limited};
+    use crate::map::MapExt;
+    use crate::or::OrExt;
+    use crate::separated_list::{separated_list, separated_list_with};
+    use crate::utf8::string::is_string;
+
+    #[test]
+    fn test_uncut_error_ends_repetition() {
+        let parser = many(is_byte(b'-').and(i64()));
+
+        let (items, cursor) = parser.parse(ByteCursor::new(b"-1-x")).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(cursor.position(), 2);
+    }
+
+    #[test]
+    fn test_cut_error_propagates_from_many() {
+        let parser = many(is_byte(b'-').and(i64().cut()));
+
+        // An item that fails before the cut still ends the list
+        let (items, _) = parser.parse(ByteCursor::new(b"-1+2")).unwrap();
+        assert_eq!(items.len(), 1);
+
+        let error = parser.parse(ByteCursor::new(b"-1-x")).unwrap_err();
+        assert!(error.is_committed());
+        assert_eq!(error.likely_error().loc().position(), 3);
+    }
+
+    #[test]
+    fn test_cut_error_propagates_from_many_limited() {
+        let parser = many_limited(is_byte(b'-').and(i64().cut()), 4);
+
+        let error = parser.parse(ByteCursor::new(b"-1-x")).unwrap_err();
+        assert!(error.is_committed());
+        assert_eq!(error.position(), 3);
+    }
+
+    #[test]
+    fn test_cut_error_propagates_from_separated_lists() {
+        let item = is_string("let ").and(i64().cut()).map(|(_, n)| n);
+        let parser = separated_list(item, is_byte(b','));
+        let error = parser.parse(ByteCursor::new(b"let 1,let x")).unwrap_err();
+        assert!(error.is_committed());
+
+        // A trailing separator is accepted unless the item after it was committed
+        let item = is_string("let ").and(i64().cut()).map(|(_, n)| n);
+        let parser = separated_list_with(item, is_byte(b','));
+        let (list, _) = parser.parse(ByteCursor::new(b"let 1,")).unwrap();
+        assert!(list.has_trailing_separator());
+        let error = parser.parse(ByteCursor::new(b"let 1,let x")).unwrap_err();
+        assert_eq!(error.likely_error().loc().position(), 10);
+    }
+
+    #[test]
+    fn test_committed_survives_wrapping() {
+        let parser = is_byte(b'-').and(i64().cut()).or(is_byte(b'+').and(i64()));
+
+        let error = parser.parse(ByteCursor::new(b"-x")).unwrap_err();
+        assert!(error.is_committed());
+        let error = parser.parse(ByteCursor::new(b"*")).unwrap_err();
+        assert!(!error.is_committed());
+    }
+}
@@ -7,7 +7,7 @@ pub struct DefaultParser<T, C> {
     _phantom_cursor: std::marker::PhantomData<C>,
 }
 
-impl<'code, T, C> DefaultParser<T, C> {
+impl<T, C> DefaultParser<T, C> {
     pub fn new(default: T) -> Self {
         DefaultParser {
             default,
@@ -40,9 +40,53 @@ where
     DefaultParser::new(default)
 }
 
+/// Parser combinator that returns `Default::default()` when the given parser fails
+///
+/// Like `Optional`, a failure does not consume input and no error is returned.
+pub struct OrDefault<P> {
+    parser: P,
+}
+
+impl<P> OrDefault<P> {
+    pub fn new(parser: P) -> Self {
+        OrDefault { parser }
+    }
+}
+
+impl<'code, P> Parser<'code> for OrDefault<P>
+where
+    P: Parser<'code>,
+    P::Output: Default,
+{
+    type Cursor = P::Cursor;
+    type Output = P::Output;
+    type Error = P::Error;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        match self.parser.parse(cursor) {
+            Ok(result) => Ok(result),
+            Err(_) => Ok((P::Output::default(), cursor)),
+        }
+    }
+}
+
+/// Extension trait to add .or_default() method support for parsers
+pub trait OrDefaultExt<'code>: Parser<'code> + Sized {
+    fn or_default(self) -> OrDefault<Self>
+    where
+        Self::Output: Default,
+    {
+        OrDefault::new(self)
+    }
+}
+
+/// Implement OrDefaultExt for all parsers
+impl<'code, P> OrDefaultExt<'code> for P where P: Parser<'code> {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ascii::u64;
     use crate::{ByteCursor, Cursor};
 
     #[test]
@@ -79,4 +123,24 @@ mod tests {
         assert_eq!(result, String::default());
         assert!(matches!(remaining, ByteCursor::EndOfFile { .. }));
     }
+
+    #[test]
+    fn test_or_default_on_failure() {
+        let data = b"abc";
+        let cursor = ByteCursor::new(data);
+
+        let (result, remaining) = u64().or_default().parse(cursor).unwrap();
+        assert_eq!(result, 0);
+        assert_eq!(remaining.value().unwrap(), b'a');
+    }
+
+    #[test]
+    fn test_or_default_on_success() {
+        let data = b"42";
+        let cursor = ByteCursor::new(data);
+
+        let (result, remaining) = u64().or_default().parse(cursor).unwrap();
+        assert_eq!(result, 42);
+        assert!(remaining.eos());
+    }
 }
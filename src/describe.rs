@@ -0,0 +1,503 @@
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+// # Describe - Exporting the Grammar of a Parser
+//
+// Every parser can describe itself with `Parser::describe`, which returns a tree of
+// `Description`s: terminals like `'a'` or `"let"`, sequences, choices, repetitions,
+// lookaheads and named rules. The combinators that shape a grammar report their
+// children, so the tree of a large parser reads like its grammar:
+//
+// ```text
+// list ::= '[' (value (',' value)*)? ']'
+// value ::= number | list
+// ```
+//
+// `to_ebnf()` prints one production per named rule, and `to_dot()` prints a
+// GraphViz digraph of the same tree. Rules are named with `.traced(name)`. Parsers
+// that don't describe themselves appear under their type name, and wrappers that
+// don't change what is matched, such as `map` or `with_hint`, are transparent.
+//
+// Recursive grammars go through `lazy`. A `lazy` that is reached again while its
+// own description is being built refers back to the enclosing rule instead of
+// recursing, so trace the body of recursive rules to get a readable name.
+
+/// Name of recursive rules whose body was not given a name with `.traced()`
+const UNNAMED_RULE: &str = "rule";
+
+/// Description of what a parser matches, see [`Parser::describe`](crate::Parser::describe)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Description {
+    /// Matches literal text or a basic token, rendered as is
+    Terminal(Cow<'static, str>),
+    /// Matches each part in order
+    Sequence(Vec<Description>),
+    /// Matches one of the alternatives, tried in order
+    Choice(Vec<Description>),
+    /// Matches the item at least `min` times
+    Repeat { min: usize, item: Box<Description> },
+    /// Matches the item or nothing
+    Optional(Box<Description>),
+    /// Checks that the item matches, or that it doesn't if `negated`, without consuming input
+    Lookahead {
+        negated: bool,
+        item: Box<Description>,
+    },
+    /// A named rule, `body` is `None` for references back to an enclosing rule
+    Rule {
+        name: Cow<'static, str>,
+        body: Option<Box<Description>>,
+    },
+    /// A parser that does not describe itself, named after its type
+    Opaque(Cow<'static, str>),
+}
+
+thread_local! {
+    /// Factories of the `lazy` parsers whose description is being built, innermost last
+    static DESCRIBING: RefCell<Vec<usize>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Identity of the factory type `F`, the address of code instantiated once per factory
+///
+/// Type names can't tell closures defined in the same function apart, and the address
+/// of a `lazy` parser changes whenever its factory rebuilds the grammar. The call
+/// shim is the same for every instance of one factory type and differs between
+/// factories with different bodies.
+fn factory_key<F, P>() -> usize
+where
+    F: Fn() -> P,
+{
+    let call: fn(&F) -> P = |factory| factory();
+    call as usize
+}
+
+impl Description {
+    pub fn terminal(text: impl Into<Cow<'static, str>>) -> Self {
+        Description::Terminal(text.into())
+    }
+
+    /// The sequence of `first` and `second`, flattening nested sequences
+    pub fn sequence(first: Description, second: Description) -> Self {
+        let mut parts = Vec::new();
+        for part in [first, second] {
+            match part {
+                Description::Sequence(nested) => parts.extend(nested),
+                part => parts.push(part),
+            }
+        }
+        Description::Sequence(parts)
+    }
+
+    /// The choice between `first` and `second`, flattening nested choices
+    pub fn choice(first: Description, second: Description) -> Self {
+        let mut alternatives = Vec::new();
+        for alternative in [first, second] {
+            match alternative {
+                Description::Choice(nested) => alternatives.extend(nested),
+                alternative => alternatives.push(alternative),
+            }
+        }
+        Description::Choice(alternatives)
+    }
+
+    pub fn repeat(min: usize, item: Description) -> Self {
+        Description::Repeat {
+            min,
+            item: Box::new(item),
+        }
+    }
+
+    pub fn optional(item: Description) -> Self {
+        Description::Optional(Box::new(item))
+    }
+
+    pub fn lookahead(negated: bool, item: Description) -> Self {
+        Description::Lookahead {
+            negated,
+            item: Box::new(item),
+        }
+    }
+
+    pub fn rule(name: impl Into<Cow<'static, str>>, body: Description) -> Self {
+        Description::Rule {
+            name: name.into(),
+            body: Some(Box::new(body)),
+        }
+    }
+
+    /// Description of a parser of type `T` that does not describe itself
+    pub fn opaque<T: ?Sized>() -> Self {
+        let name = std::any::type_name::<T>();
+        let name = name.split('<').next().unwrap_or(name);
+        Description::Opaque(name.rsplit("::").next().unwrap_or(name).to_string().into())
+    }
+
+    /// Short name of this node, the text for terminals and the name for rules
+    pub fn name(&self) -> Cow<'_, str> {
+        match self {
+            Description::Terminal(text) => Cow::Borrowed(text),
+            Description::Sequence(_) => "sequence".into(),
+            Description::Choice(_) => "choice".into(),
+            Description::Repeat { min: 0, .. } => "many".into(),
+            Description::Repeat { min: 1, .. } => "some".into(),
+            Description::Repeat { min, .. } => format!("at least {}", min).into(),
+            Description::Optional(_) => "optional".into(),
+            Description::Lookahead { negated: false, .. } => "followed by".into(),
+            Description::Lookahead { negated: true, .. } => "not".into(),
+            Description::Rule { name, .. } => Cow::Borrowed(name),
+            Description::Opaque(name) => Cow::Borrowed(name),
+        }
+    }
+
+    /// The descriptions this node is built from, empty for leaves and references
+    pub fn children(&self) -> Vec<&Description> {
+        match self {
+            Description::Sequence(parts) | Description::Choice(parts) => parts.iter().collect(),
+            Description::Repeat { item, .. }
+            | Description::Optional(item)
+            | Description::Lookahead { item, .. } => vec![item],
+            Description::Rule {
+                body: Some(body), ..
+            } => vec![body],
+            _ => Vec::new(),
+        }
+    }
+
+    /// Describe the parser built by a `lazy` factory of type `F`
+    ///
+    /// Returns a reference to the enclosing rule if the factory is already being
+    /// described, which is how recursive grammars end.
+    pub(crate) fn lazy<F, P>(describe: impl FnOnce() -> Description) -> Self
+    where
+        F: Fn() -> P,
+    {
+        let key = factory_key::<F, P>();
+        // Placeholder name of references back to this rule until its name is known
+        let reference = format!("lazy#{:x}", key);
+        let recursive = DESCRIBING.with(|describing| {
+            let mut describing = describing.borrow_mut();
+            let recursive = describing.contains(&key);
+            if !recursive {
+                describing.push(key);
+            }
+            recursive
+        });
+        if recursive {
+            return Description::Rule {
+                name: reference.into(),
+                body: None,
+            };
+        }
+
+        let mut body = describe();
+        DESCRIBING.with(|describing| describing.borrow_mut().pop());
+
+        if !body.refers_to(&reference) {
+            return body;
+        }
+        let name: Cow<'static, str> = match &body {
+            Description::Rule { name, .. } => name.clone(),
+            _ => UNNAMED_RULE.into(),
+        };
+        body.rename_references(&reference, &name);
+        match body {
+            body @ Description::Rule { .. } => body,
+            body => Description::rule(name, body),
+        }
+    }
+
+    fn refers_to(&self, key: &str) -> bool {
+        match self {
+            Description::Rule { name, body: None } => name == key,
+            _ => self.children().iter().any(|child| child.refers_to(key)),
+        }
+    }
+
+    fn rename_references(&mut self, key: &str, to: &str) {
+        match self {
+            Description::Rule { name, body: None } if name == key => *name = to.to_string().into(),
+            Description::Sequence(parts) | Description::Choice(parts) => {
+                for part in parts {
+                    part.rename_references(key, to);
+                }
+            }
+            Description::Repeat { item, .. }
+            | Description::Optional(item)
+            | Description::Lookahead { item, .. } => item.rename_references(key, to),
+            Description::Rule {
+                body: Some(body), ..
+            } => body.rename_references(key, to),
+            _ => {}
+        }
+    }
+
+    /// The grammar as one EBNF-like production per rule, starting with this one
+    ///
+    /// Uses `::=` for productions, juxtaposition for sequences, `|` for choices, the
+    /// `*`, `+` and `?` suffixes and the `&` and `!` lookahead prefixes. An unnamed
+    /// root is called `grammar`.
+    pub fn to_ebnf(&self) -> String {
+        let root = match self {
+            Description::Rule { .. } => self.clone(),
+            _ => Description::rule("grammar", self.clone()),
+        };
+        let mut rules = Vec::new();
+        collect_rules(&root, &mut rules);
+
+        let mut output = String::new();
+        for (name, body) in rules {
+            writeln!(
+                output,
+                "{} ::= {}",
+                name,
+                expression(body, Precedence::Choice)
+            )
+            .unwrap();
+        }
+        output
+    }
+
+    /// The description tree as a GraphViz digraph
+    ///
+    /// Rules are drawn once, references to a rule point at its node.
+    pub fn to_dot(&self) -> String {
+        let mut output = String::from("digraph grammar {\n    node [shape=box];\n");
+        let mut rules = HashMap::new();
+        let mut next_id = 0;
+        dot_node(self, &mut output, &mut rules, &mut next_id);
+        output.push_str("}\n");
+        output
+    }
+}
+
+/// Named rules with a body in the order they are first reached, each once
+fn collect_rules<'d>(description: &'d Description, rules: &mut Vec<(&'d str, &'d Description)>) {
+    if let Description::Rule {
+        name,
+        body: Some(body),
+    } = description
+    {
+        if rules.iter().any(|(seen, _)| seen == name) {
+            return;
+        }
+        rules.push((name, body));
+        collect_rules(body, rules);
+        return;
+    }
+    for child in description.children() {
+        collect_rules(child, rules);
+    }
+}
+
+/// How tightly an expression binds, to decide where parentheses are needed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Precedence {
+    Choice,
+    Sequence,
+    Suffix,
+}
+
+fn expression(description: &Description, context: Precedence) -> String {
+    let (text, precedence) = match description {
+        Description::Terminal(text) | Description::Opaque(text) => {
+            (text.to_string(), Precedence::Suffix)
+        }
+        Description::Rule { name, .. } => (name.to_string(), Precedence::Suffix),
+        Description::Sequence(parts) => (
+            parts
+                .iter()
+                .map(|part| expression(part, Precedence::Sequence))
+                .collect::<Vec<_>>()
+                .join(" "),
+            Precedence::Sequence,
+        ),
+        Description::Choice(alternatives) => (
+            alternatives
+                .iter()
+                .map(|alternative| expression(alternative, Precedence::Sequence))
+                .collect::<Vec<_>>()
+                .join(" | "),
+            Precedence::Choice,
+        ),
+        Description::Repeat { min, item } => {
+            let item = expression(item, Precedence::Suffix);
+            let suffix = match min {
+                0 => "*".to_string(),
+                1 => "+".to_string(),
+                min => format!("{{{},}}", min),
+            };
+            (format!("{}{}", item, suffix), Precedence::Suffix)
+        }
+        Description::Optional(item) => (
+            format!("{}?", expression(item, Precedence::Suffix)),
+            Precedence::Suffix,
+        ),
+        Description::Lookahead { negated, item } => (
+            format!(
+                "{}{}",
+                if *negated { "!" } else { "&" },
+                expression(item, Precedence::Suffix)
+            ),
+            Precedence::Suffix,
+        ),
+    };
+    if precedence < context {
+        format!("({})", text)
+    } else {
+        text
+    }
+}
+
+/// Write the node for `description` and its children, returning the node id
+fn dot_node(
+    description: &Description,
+    output: &mut String,
+    rules: &mut HashMap<String, usize>,
+    next_id: &mut usize,
+) -> usize {
+    if let Description::Rule { name, .. } = description
+        && let Some(&id) = rules.get(name.as_ref())
+    {
+        return id;
+    }
+
+    let id = *next_id;
+    *next_id += 1;
+    let label = description
+        .name()
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"");
+    let shape = match description {
+        Description::Rule { .. } => ", shape=ellipse",
+        _ => "",
+    };
+    writeln!(output, "    n{} [label=\"{}\"{}];", id, label, shape).unwrap();
+    if let Description::Rule { name, .. } = description {
+        rules.insert(name.to_string(), id);
+    }
+
+    for child in description.children() {
+        let child_id = dot_node(child, output, rules, next_id);
+        writeln!(output, "    n{} -> n{};", id, child_id).unwrap();
+    }
+    id
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Parser;
+    use crate::and::AndExt;
+    use crate::ascii::u64;
+    use crate::byte::is_byte;
+    use crate::lazy::lazy;
+    use crate::many::many;
+    use crate::map::MapExt;
+    use crate::optional::OptionalExt;
+    use crate::or::OrExt;
+    use crate::separated_list::separated_list;
+    use crate::trace::TracedExt;
+    use crate::utf8::string::is_string;
+
+    #[derive(Debug, Clone)]
+    enum Value {
+        Number,
+        List,
+    }
+
+    fn value<'code>() -> impl Parser<'code, Cursor = crate::ByteCursor<'code>, Output = Value> {
+        let number = u64().map(|_| Value::Number).traced("number");
+        let list = is_byte(b'[')
+            .and(separated_list(lazy(value), is_byte(b',')).optional())
+            .and(is_byte(b']'))
+            .map(|_| Value::List)
+            .traced("list");
+        number.or(list).traced("value")
+    }
+
+    #[test]
+    fn test_combinators_describe_children() {
+        let parser = is_string("let")
+            .and(many(is_byte(b' ')))
+            .map(|_| ())
+            .or(is_byte(b'x').map(|_| ()));
+
+        let description = parser.describe();
+        assert!(matches!(description, Description::Choice(_)));
+        assert_eq!(description.name(), "choice");
+        assert_eq!(description.children().len(), 2);
+        assert_eq!(description.to_ebnf(), "grammar ::= \"let\" ' '* | 'x'\n");
+    }
+
+    #[test]
+    fn test_structural_combinators_describe_children() {
+        use crate::between::between;
+        use crate::choice::choice;
+        use crate::count::count;
+        use crate::fold::fold_many1;
+        use crate::not::{NotExt, followed_by};
+        use crate::separated_pair::separated_pair;
+        use crate::seq::seq;
+        use crate::skip::skip_many;
+
+        let parser = seq((
+            between(
+                is_byte(b'('),
+                choice((is_string("a"), is_string("b"))),
+                is_byte(b')'),
+            ),
+            is_byte(b'!').not(),
+            count::<2, _>(is_byte(b'x')),
+            skip_many(is_byte(b' ')),
+            separated_pair(u64(), is_byte(b'='), u64()),
+            fold_many1(followed_by(is_byte(b'y'), is_byte(b';')), || (), |_, _| ()),
+        ));
+        assert_eq!(
+            parser.describe().to_ebnf(),
+            "grammar ::= '(' (\"a\" | \"b\") ')' !'!' 'x' 'x' ' '* IntegerParser '=' \
+             IntegerParser ('y' &';')+\n"
+        );
+    }
+
+    #[test]
+    fn test_recursive_grammar_to_ebnf() {
+        assert_eq!(
+            value().describe().to_ebnf(),
+            "value ::= number | list\n\
+             number ::= IntegerParser\n\
+             list ::= '[' (value (',' value)*)? ']'\n"
+        );
+    }
+
+    #[test]
+    fn test_unnamed_recursion_gets_a_rule() {
+        fn nested<'code>() -> impl Parser<'code, Cursor = crate::ByteCursor<'code>, Output = ()> {
+            is_byte(b'(')
+                .and(lazy(nested).optional())
+                .and(is_byte(b')'))
+                .map(|_| ())
+        }
+
+        let description = lazy(nested).describe();
+        assert_eq!(description.to_ebnf(), "rule ::= '(' rule? ')'\n");
+    }
+
+    #[test]
+    fn test_closures_of_one_function_are_different_rules() {
+        let inner = lazy(|| is_byte(b'x'));
+        let outer = lazy(|| is_byte(b'(').and(&inner).and(is_byte(b')')));
+        assert_eq!(outer.describe().to_ebnf(), "grammar ::= '(' 'x' ')'\n");
+    }
+
+    #[test]
+    fn test_to_dot_draws_rules_once() {
+        let dot = value().describe().to_dot();
+        assert!(dot.starts_with("digraph grammar {\n"));
+        assert!(dot.contains("n0 [label=\"value\", shape=ellipse];"));
+        // The recursive reference to `value` points back at the root
+        assert!(dot.contains(" -> n0;"));
+        assert_eq!(dot.matches("label=\"value\"").count(), 1);
+    }
+}
@@ -0,0 +1,269 @@
+//! # Structured Diagnostics
+//!
+//! [`Diagnostic`] is a plain-data description of a parse error: where it is, what
+//! went wrong and the surrounding source lines. Unlike the `Display` output of an
+//! error it needs no scraping, so tools such as language servers can forward it
+//! directly. With the `serde` feature enabled it implements `serde::Serialize`.
+//!
+//! Parsers can also report non-fatal diagnostics, such as warnings about deprecated
+//! syntax, into a [`DiagnosticSink`] while the parse continues. See `emit` for the
+//! combinators that do so.
+
+use crate::atomic::Atomic;
+use crate::error::{CodeLoc, ErrorNode};
+use crate::report::line_ranges;
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// How serious a diagnostic is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Severity {
+    Error,
+    Warning,
+    Note,
+}
+
+/// One line of source shown around a diagnostic
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ContextFrame {
+    /// 1-based line number
+    pub line: usize,
+    /// The text of the line without its line terminator
+    pub text: String,
+}
+
+/// A parse error flattened into plain data
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Diagnostic {
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based column, counted in display widths like the error messages
+    pub column: usize,
+    /// Offset of the error in elements from the start of the source
+    pub offset: usize,
+    /// Number of elements the diagnostic covers
+    pub length: usize,
+    /// Error message without source context
+    pub message: String,
+    /// Code of the error, see [`with_code`](crate::code::CodeExt::with_code)
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub code: Option<String>,
+    pub severity: Severity,
+    /// Source lines around the error line, including the error line itself
+    pub context: Vec<ContextFrame>,
+}
+
+/// Number of lines shown before and after the error line, matching error Display
+const CONTEXT_LINES: usize = 2;
+
+impl Diagnostic {
+    /// Create a diagnostic for the likely error of an error tree
+    pub fn new<'code, E>(error: &E) -> Self
+    where
+        E: ErrorNode<'code> + ?Sized,
+        E::Element: 'code,
+    {
+        let leaf = error.likely_error();
+        let diagnostic = Self::at(leaf.loc(), leaf.message());
+        Diagnostic {
+            code: error.code().map(Cow::into_owned),
+            ..diagnostic
+        }
+    }
+
+    /// Create a diagnostic with a custom message at a location
+    pub fn at<T: Atomic>(loc: CodeLoc<'_, T>, message: impl Into<String>) -> Self {
+        let position = loc.readable_position();
+        let lines = line_ranges(loc.source());
+        let error_line = position.line - 1;
+
+        let first = error_line.saturating_sub(CONTEXT_LINES);
+        let last = (error_line + CONTEXT_LINES).min(lines.len() - 1);
+        let context = lines[first..=last]
+            .iter()
+            .zip(first + 1..)
+            .map(|(&(start, end), line)| ContextFrame {
+                line,
+                text: T::format_slice(&loc.source()[start..end])
+                    .trim_end_matches('\r')
+                    .to_string(),
+            })
+            .collect();
+
+        Diagnostic {
+            line: position.line,
+            column: position.byte_offset + 1,
+            offset: loc.position(),
+            length: 0,
+            message: message.into(),
+            code: None,
+            severity: Severity::Error,
+            context,
+        }
+    }
+
+    /// Set the number of elements the diagnostic covers
+    pub fn with_length(mut self, length: usize) -> Self {
+        self.length = length;
+        self
+    }
+
+    /// Set the code of the diagnostic
+    pub fn with_code(mut self, code: impl Into<String>) -> Self {
+        self.code = Some(code.into());
+        self
+    }
+
+    /// Set the severity of the diagnostic
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+}
+
+/// Shared collection of diagnostics reported while parsing
+///
+/// Clones share the same collection, so a sink can be handed to several parsers
+/// and read once the parse is done.
+#[derive(Debug, Clone, Default)]
+pub struct DiagnosticSink {
+    diagnostics: Rc<RefCell<Vec<Diagnostic>>>,
+}
+
+impl DiagnosticSink {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&self, diagnostic: Diagnostic) {
+        self.diagnostics.borrow_mut().push(diagnostic);
+    }
+
+    /// Remove and return everything reported so far, in source order
+    pub fn take(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = self.diagnostics.take();
+        diagnostics.sort_by_key(|diagnostic| diagnostic.offset);
+        diagnostics
+    }
+
+    pub fn len(&self) -> usize {
+        self.diagnostics.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove and return the diagnostics reported after the first `len`, in source order
+    pub(crate) fn split_off(&self, len: usize) -> Vec<Diagnostic> {
+        let mut diagnostics = self.diagnostics.borrow_mut().split_off(len);
+        diagnostics.sort_by_key(|diagnostic| diagnostic.offset);
+        diagnostics
+    }
+
+    /// Drop diagnostics reported after the first `len`
+    pub(crate) fn truncate(&self, len: usize) {
+        self.diagnostics.borrow_mut().truncate(len);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::byte::is_byte;
+    use crate::error::ParsicombError;
+    use crate::{ByteCursor, Cursor, Parser};
+
+    #[test]
+    fn test_diagnostic_from_error() {
+        let data = b"one\ntwo\nthree\nfour\nfive\nsix";
+        let cursor = ByteCursor::new(data);
+        let cursor = (0..14).fold(cursor, |cursor, _| cursor.next());
+        let error = is_byte(b'x').parse(cursor).unwrap_err();
+
+        let diagnostic = Diagnostic::new(&error);
+        assert_eq!(diagnostic.line, 4);
+        assert_eq!(diagnostic.column, 1);
+        assert_eq!(diagnostic.offset, 14);
+        assert_eq!(diagnostic.length, 0);
+        assert_eq!(
+            diagnostic.message,
+            "expected byte 0x78 ('x'), found 0x66 ('f')"
+        );
+        assert_eq!(diagnostic.code, None);
+        assert_eq!(diagnostic.severity, Severity::Error);
+
+        let lines: Vec<_> = diagnostic.context.iter().map(|f| f.line).collect();
+        assert_eq!(lines, vec![2, 3, 4, 5, 6]);
+        assert_eq!(diagnostic.context[2].text, "four");
+    }
+
+    #[test]
+    fn test_diagnostic_context_at_start_and_end() {
+        let data = b"a\r\nb";
+        let error = ParsicombError::SyntaxError {
+            message: "oops".into(),
+            loc: CodeLoc::new(data, 1),
+        };
+
+        let diagnostic = Diagnostic::new(&error).with_length(2);
+        assert_eq!((diagnostic.line, diagnostic.column), (1, 2));
+        assert_eq!(diagnostic.length, 2);
+        assert_eq!(
+            diagnostic.context,
+            vec![
+                ContextFrame {
+                    line: 1,
+                    text: "a".to_string()
+                },
+                ContextFrame {
+                    line: 2,
+                    text: "b".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diagnostic_uses_wrapped_message() {
+        let data = b"abc";
+        let error = ParsicombError::wrap(ParsicombError::SyntaxError {
+            message: "inner".into(),
+            loc: CodeLoc::new(data, 3),
+        });
+
+        let diagnostic = Diagnostic::new(&error).with_severity(Severity::Warning);
+        assert_eq!(diagnostic.message, "inner");
+        assert_eq!(diagnostic.column, 4);
+        assert_eq!(diagnostic.severity, Severity::Warning);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_diagnostic_is_serializable() {
+        fn assert_serialize<T: serde::Serialize>() {}
+        assert_serialize::<Diagnostic>();
+    }
+
+    #[test]
+    fn test_sink_sorts_by_offset() {
+        let data = b"ab\ncd";
+        let sink = DiagnosticSink::new();
+        let shared = sink.clone();
+
+        shared.push(Diagnostic::at(CodeLoc::new(data, 4), "later").with_severity(Severity::Note));
+        shared.push(Diagnostic::at(CodeLoc::new(data, 1), "earlier"));
+        assert_eq!(sink.len(), 2);
+
+        let diagnostics = sink.take();
+        assert_eq!(diagnostics[0].message, "earlier");
+        assert_eq!(diagnostics[1].severity, Severity::Note);
+        assert_eq!(diagnostics[1].line, 2);
+        assert!(sink.is_empty());
+    }
+}
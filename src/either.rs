@@ -0,0 +1,180 @@
+use super::parser::Parser;
+use crate::atomic::Atomic;
+use crate::cursor::Cursor;
+use crate::error::ErrorNode;
+use crate::or::OrError;
+
+// # Either Combinator
+//
+// `or` needs both branches to produce the same output, so heterogeneous alternatives
+// have to be mapped into a common enum first. `either(a, b)` keeps both output types
+// and tells which branch matched through `Either::Left` or `Either::Right`.
+//
+// Like Or, the branches are boxed to keep long chains cheap to compile, and a double
+// failure is reported as an `OrError`.
+
+/// Output of [`EitherParser`], the value of whichever branch matched
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Either<L, R> {
+    Left(L),
+    Right(R),
+}
+
+impl<L, R> Either<L, R> {
+    pub fn is_left(&self) -> bool {
+        matches!(self, Either::Left(_))
+    }
+
+    pub fn is_right(&self) -> bool {
+        matches!(self, Either::Right(_))
+    }
+
+    /// The left value, if the first branch matched
+    pub fn left(self) -> Option<L> {
+        match self {
+            Either::Left(left) => Some(left),
+            Either::Right(_) => None,
+        }
+    }
+
+    /// The right value, if the second branch matched
+    pub fn right(self) -> Option<R> {
+        match self {
+            Either::Left(_) => None,
+            Either::Right(right) => Some(right),
+        }
+    }
+
+    /// Convert both sides into a common type
+    pub fn either<T>(self, left: impl FnOnce(L) -> T, right: impl FnOnce(R) -> T) -> T {
+        match self {
+            Either::Left(value) => left(value),
+            Either::Right(value) => right(value),
+        }
+    }
+}
+
+impl<T> Either<T, T> {
+    /// The value of whichever branch matched, when both have the same type
+    pub fn into_inner(self) -> T {
+        match self {
+            Either::Left(value) | Either::Right(value) => value,
+        }
+    }
+}
+
+/// Parser combinator that tries the first parser, then the second, keeping both output types
+pub struct EitherParser<'code, C, O1, O2, E1, E2> {
+    parser1: Box<dyn Parser<'code, Cursor = C, Output = O1, Error = E1> + 'code>,
+    parser2: Box<dyn Parser<'code, Cursor = C, Output = O2, Error = E2> + 'code>,
+}
+
+impl<'code, C, O1, O2, E1, E2> EitherParser<'code, C, O1, O2, E1, E2> {
+    pub fn new<P1, P2>(parser1: P1, parser2: P2) -> Self
+    where
+        P1: Parser<'code, Cursor = C, Output = O1, Error = E1> + 'code,
+        P2: Parser<'code, Cursor = C, Output = O2, Error = E2> + 'code,
+    {
+        EitherParser {
+            parser1: Box::new(parser1),
+            parser2: Box::new(parser2),
+        }
+    }
+}
+
+impl<'code, C, O1, O2, E1, E2> Parser<'code> for EitherParser<'code, C, O1, O2, E1, E2>
+where
+    C: Cursor<'code>,
+    C::Element: Atomic + 'code,
+    E1: std::error::Error + ErrorNode<'code, Element = C::Element> + 'code,
+    E2: std::error::Error + ErrorNode<'code, Element = C::Element> + 'code,
+{
+    type Cursor = C;
+    type Output = Either<O1, O2>;
+    type Error = OrError<'code, C::Element>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        match self.parser1.parse(cursor) {
+            Ok((value, cursor)) => Ok((Either::Left(value), cursor)),
+            Err(first_error) => match self.parser2.parse(cursor) {
+                Ok((value, cursor)) => Ok((Either::Right(value), cursor)),
+                Err(second_error) => Err(OrError::both_failed(first_error, second_error)),
+            },
+        }
+    }
+}
+
+/// Extension trait to add .either() method support for parsers
+pub trait EitherExt<'code>: Parser<'code> + Sized {
+    #[allow(clippy::type_complexity)]
+    fn either<P>(
+        self,
+        other: P,
+    ) -> EitherParser<'code, Self::Cursor, Self::Output, P::Output, Self::Error, P::Error>
+    where
+        P: Parser<'code, Cursor = Self::Cursor> + 'code,
+        Self: 'code,
+    {
+        EitherParser::new(self, other)
+    }
+}
+
+/// Implement EitherExt for all parsers
+impl<'code, P> EitherExt<'code> for P where P: Parser<'code> {}
+
+/// Convenience function to create an Either parser
+#[allow(clippy::type_complexity)]
+pub fn either<'code, P1, P2>(
+    parser1: P1,
+    parser2: P2,
+) -> EitherParser<'code, P1::Cursor, P1::Output, P2::Output, P1::Error, P2::Error>
+where
+    P1: Parser<'code> + 'code,
+    P2: Parser<'code, Cursor = P1::Cursor> + 'code,
+{
+    EitherParser::new(parser1, parser2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ByteCursor;
+    use crate::ascii::u64;
+    use crate::utf8::identifier::identifier;
+
+    #[test]
+    fn test_either_keeps_branch_types() {
+        let parser = either(u64(), identifier());
+
+        let (value, _) = parser.parse(ByteCursor::new(b"42")).unwrap();
+        assert_eq!(value, Either::Left(42));
+
+        let (value, cursor) = parser.parse(ByteCursor::new(b"name")).unwrap();
+        let (name, _) = value.right().unwrap();
+        assert_eq!(name, "name");
+        assert!(cursor.eos());
+    }
+
+    #[test]
+    fn test_either_first_branch_wins() {
+        let parser = u64().either(u64());
+        let (value, _) = parser.parse(ByteCursor::new(b"7")).unwrap();
+        assert!(value.is_left());
+        assert_eq!(value.into_inner(), 7);
+    }
+
+    #[test]
+    fn test_either_both_fail() {
+        let error = either(u64(), identifier())
+            .parse(ByteCursor::new(b"+"))
+            .unwrap_err();
+        assert_eq!(error.likely_error().loc().position(), 0);
+        assert_eq!(error.children().len(), 2);
+    }
+
+    #[test]
+    fn test_either_into_common_type() {
+        let value: Either<u64, &str> = Either::Right("x");
+        assert_eq!(value.either(|n| n.to_string(), str::to_string), "x");
+    }
+}
@@ -0,0 +1,211 @@
+use super::parser::Parser;
+use crate::atomic::Atomic;
+use crate::cursor::Cursor;
+use crate::diagnostic::{Diagnostic, DiagnosticSink, Severity};
+use crate::error::CodeLoc;
+use std::borrow::Cow;
+
+// # Non-fatal Diagnostics
+//
+// `emit` reports a diagnostic about input a parser matched without failing the
+// parse, e.g. a warning for deprecated syntax. Diagnostics go to a `DiagnosticSink`
+// and cover the span the parser consumed.
+//
+// A diagnostic is reported as soon as its parser succeeds, even if an enclosing
+// alternative fails later. `with_diagnostics` drops diagnostics from failed attempts
+// of its parser and returns the ones it kept next to the output.
+
+/// Parser combinator that reports a diagnostic when the check returns a message
+pub struct Emit<P, F> {
+    parser: P,
+    sink: DiagnosticSink,
+    severity: Severity,
+    check: F,
+}
+
+impl<P, F> Emit<P, F> {
+    pub fn new(parser: P, sink: DiagnosticSink, severity: Severity, check: F) -> Self {
+        Emit {
+            parser,
+            sink,
+            severity,
+            check,
+        }
+    }
+}
+
+impl<'code, P, F> Parser<'code> for Emit<P, F>
+where
+    P: Parser<'code>,
+    <P::Cursor as Cursor<'code>>::Element: Atomic + 'code,
+    F: Fn(&P::Output) -> Option<Cow<'static, str>>,
+{
+    type Cursor = P::Cursor;
+    type Output = P::Output;
+    type Error = P::Error;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let start = cursor.position();
+        let (value, cursor) = self.parser.parse(cursor)?;
+
+        if let Some(message) = (self.check)(&value) {
+            let (data, end) = cursor.inner();
+            let diagnostic = Diagnostic::at(CodeLoc::new(data, start), message)
+                .with_length(end - start)
+                .with_severity(self.severity);
+            self.sink.push(diagnostic);
+        }
+
+        Ok((value, cursor))
+    }
+}
+
+/// Parser combinator that returns the diagnostics reported while its parser ran
+pub struct WithDiagnostics<P> {
+    parser: P,
+    sink: DiagnosticSink,
+}
+
+impl<P> WithDiagnostics<P> {
+    pub fn new(parser: P, sink: DiagnosticSink) -> Self {
+        WithDiagnostics { parser, sink }
+    }
+}
+
+impl<'code, P> Parser<'code> for WithDiagnostics<P>
+where
+    P: Parser<'code>,
+{
+    type Cursor = P::Cursor;
+    type Output = (P::Output, Vec<Diagnostic>);
+    type Error = P::Error;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let before = self.sink.len();
+        match self.parser.parse(cursor) {
+            Ok((value, cursor)) => Ok(((value, self.sink.split_off(before)), cursor)),
+            Err(error) => {
+                self.sink.truncate(before);
+                Err(error)
+            }
+        }
+    }
+}
+
+/// Extension trait to add diagnostic reporting methods to parsers
+pub trait EmitExt<'code>: Parser<'code> + Sized {
+    /// Report `message` with `severity` when `check` returns one for the output
+    fn emit<F>(self, sink: &DiagnosticSink, severity: Severity, check: F) -> Emit<Self, F>
+    where
+        F: Fn(&Self::Output) -> Option<Cow<'static, str>>,
+    {
+        Emit::new(self, sink.clone(), severity, check)
+    }
+
+    /// Report a warning whenever this parser matches
+    #[allow(clippy::type_complexity)]
+    fn warn(
+        self,
+        sink: &DiagnosticSink,
+        message: impl Into<Cow<'static, str>>,
+    ) -> Emit<Self, Box<dyn Fn(&Self::Output) -> Option<Cow<'static, str>>>> {
+        let message = message.into();
+        Emit::new(
+            self,
+            sink.clone(),
+            Severity::Warning,
+            Box::new(move |_: &Self::Output| Some(message.clone())),
+        )
+    }
+
+    /// Return the output with the diagnostics reported to `sink` by this parser
+    fn with_diagnostics(self, sink: &DiagnosticSink) -> WithDiagnostics<Self> {
+        WithDiagnostics::new(self, sink.clone())
+    }
+}
+
+/// Implement EmitExt for all parsers
+impl<'code, P> EmitExt<'code> for P where P: Parser<'code> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ByteCursor;
+    use crate::and::AndExt;
+    use crate::ascii::u64;
+    use crate::many::many;
+    use crate::map::MapExt;
+    use crate::or::OrExt;
+    use crate::utf8::string::is_string;
+
+    #[test]
+    fn test_warn_continues_parse() {
+        let sink = DiagnosticSink::new();
+        let operator = is_string("<>")
+            .warn(&sink, "'<>' is deprecated, use '!='")
+            .or(is_string("!="));
+
+        let data = b"!=<>!=";
+        let ((ops, diagnostics), _) = many(operator)
+            .with_diagnostics(&sink)
+            .parse(ByteCursor::new(data))
+            .unwrap();
+
+        assert_eq!(ops.len(), 3);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].offset, 2);
+        assert_eq!(diagnostics[0].length, 2);
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn test_emit_checks_output() {
+        let sink = DiagnosticSink::new();
+        let byte = || {
+            u64().emit(&sink, Severity::Note, |n| {
+                (*n > 255).then(|| format!("{} does not fit in a byte", n).into())
+            })
+        };
+
+        let data = b"1,300";
+        let parser = byte()
+            .and(is_string(","))
+            .and(byte())
+            .map(|((a, _), b)| (a, b));
+        let (values, _) = parser.parse(ByteCursor::new(data)).unwrap();
+        assert_eq!(values, (1, 300));
+
+        let diagnostics = sink.take();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message, "300 does not fit in a byte");
+        assert_eq!(diagnostics[0].column, 3);
+    }
+
+    #[test]
+    fn test_with_diagnostics_drops_failed_attempts() {
+        let sink = DiagnosticSink::new();
+        let parser = is_string("a")
+            .warn(&sink, "a")
+            .and(is_string("b"))
+            .with_diagnostics(&sink);
+
+        assert!(parser.parse(ByteCursor::new(b"ac")).is_err());
+        assert!(sink.is_empty());
+    }
+
+    #[test]
+    fn test_with_diagnostics_leaves_earlier_reports() {
+        let sink = DiagnosticSink::new();
+        let first = is_string("a").warn(&sink, "first");
+        let second = is_string("b").warn(&sink, "second").with_diagnostics(&sink);
+
+        let (_, cursor) = first.parse(ByteCursor::new(b"ab")).unwrap();
+        let ((_, diagnostics), _) = second.parse(cursor).unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message, "second");
+
+        let earlier = sink.take();
+        assert_eq!(earlier.len(), 1);
+        assert_eq!(earlier[0].message, "first");
+    }
+}
@@ -0,0 +1,391 @@
+use super::parser::Parser;
+use crate::atomic::Atomic;
+use crate::cursor::Cursor;
+use crate::error::{CodeLoc, ParsicombError};
+use crate::position::Span;
+use crate::skip::skip_padding;
+use std::marker::PhantomData;
+
+/// Error for input left over where the end of input was expected
+pub(crate) fn expected_eof<'code, C>(cursor: C) -> ParsicombError<'code, C::Element>
+where
+    C: Cursor<'code>,
+    C::Element: Atomic + 'code,
+{
+    let (data, position) = cursor.inner();
+    ParsicombError::SyntaxError {
+        message: format!(
+            "expected end of input, found '{}'",
+            C::Element::format_slice(&data[position..position + 1])
+        )
+        .into(),
+        loc: CodeLoc::new(data, position),
+    }
+}
+
+/// Whether `cursor` has no input left
+pub(crate) fn at_eof<'code, C>(cursor: C) -> bool
+where
+    C: Cursor<'code>,
+    C::Element: 'code,
+{
+    let (data, position) = cursor.inner();
+    position >= data.len()
+}
+
+/// Parser that succeeds only at the end of input, without consuming anything
+pub struct Eof<C> {
+    _phantom: PhantomData<C>,
+}
+
+impl<C> Eof<C> {
+    pub fn new() -> Self {
+        Eof {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<C> Default for Eof<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'code, C> Parser<'code> for Eof<C>
+where
+    C: Cursor<'code>,
+    C::Element: Atomic + 'code,
+{
+    type Cursor = C;
+    type Output = ();
+    type Error = ParsicombError<'code, C::Element>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        if at_eof(cursor) {
+            Ok(((), cursor))
+        } else {
+            Err(expected_eof(cursor))
+        }
+    }
+}
+
+/// Parser that consumes any single element, failing only at the end of input
+pub struct Any<C> {
+    _phantom: PhantomData<C>,
+}
+
+impl<C> Any<C> {
+    pub fn new() -> Self {
+        Any {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<C> Default for Any<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'code, C> Parser<'code> for Any<C>
+where
+    C: Cursor<'code>,
+    C::Element: Atomic + 'code,
+{
+    type Cursor = C;
+    type Output = C::Element;
+    type Error = ParsicombError<'code, C::Element>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        match cursor.value() {
+            Ok(value) => Ok((value, cursor.next())),
+            Err(_) => {
+                let (data, position) = cursor.inner();
+                Err(ParsicombError::SyntaxError {
+                    message: "expected any input, but reached end of input".into(),
+                    loc: CodeLoc::new(data, position),
+                })
+            }
+        }
+    }
+}
+
+/// Creates a parser that succeeds only at the end of input
+pub fn eof<'code, C>() -> Eof<C>
+where
+    C: Cursor<'code>,
+{
+    Eof::new()
+}
+
+/// Creates a parser that consumes any single element
+pub fn any<'code, C>() -> Any<C>
+where
+    C: Cursor<'code>,
+{
+    Any::new()
+}
+
+/// Parser combinator that requires the given parser to consume all remaining input
+pub struct ThenEof<P> {
+    parser: P,
+}
+
+impl<P> ThenEof<P> {
+    pub fn new(parser: P) -> Self {
+        ThenEof { parser }
+    }
+}
+
+impl<'code, P> Parser<'code> for ThenEof<P>
+where
+    P: Parser<'code>,
+    P::Error: 'code,
+    <P::Cursor as Cursor<'code>>::Element: Atomic + 'code,
+{
+    type Cursor = P::Cursor;
+    type Output = P::Output;
+    type Error = ParsicombError<'code, <P::Cursor as Cursor<'code>>::Element>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (value, cursor) = self.parser.parse(cursor).map_err(ParsicombError::wrap)?;
+        if at_eof(cursor) {
+            Ok((value, cursor))
+        } else {
+            Err(expected_eof(cursor))
+        }
+    }
+}
+
+/// Extension trait to add .then_eof() method support for parsers
+pub trait ThenEofExt<'code>: Parser<'code> + Sized {
+    fn then_eof(self) -> ThenEof<Self> {
+        ThenEof::new(self)
+    }
+}
+
+/// Implement ThenEofExt for all parsers
+impl<'code, P> ThenEofExt<'code> for P where P: Parser<'code> {}
+
+/// Parser that checks that only trivia is left, reporting the rest as trailing characters
+///
+/// Unlike [`eof`], the error for leftover input is an `InvalidValue` reading
+/// "unexpected trailing characters" that spans from the first leftover element to
+/// the end of its line, so a report underlines the text that should not be there.
+/// Matches of the trivia parser, such as whitespace or comments, are skipped first
+/// and consumed on success. A committed trivia error, such as an unterminated
+/// comment, is returned instead.
+///
+/// Example:
+/// ```
+/// use parsicomb::and::AndExt;
+/// use parsicomb::ascii::u64;
+/// use parsicomb::eof::end_of_input_after;
+/// use parsicomb::utf8::whitespace::unicode_whitespace;
+/// use parsicomb::{ByteCursor, ErrorLeaf, ErrorNode, Parser};
+///
+/// let parser = u64().and(end_of_input_after(unicode_whitespace()));
+/// assert!(parser.parse(ByteCursor::new(b"42 \n")).is_ok());
+///
+/// let error = parser.parse(ByteCursor::new(b"42 x y\nz")).unwrap_err();
+/// assert_eq!(error.likely_error().message(), "unexpected trailing characters");
+/// ```
+pub struct EndOfInput<C, PT = Eof<C>> {
+    trivia: Option<PT>,
+    _phantom: PhantomData<C>,
+}
+
+impl<C> EndOfInput<C> {
+    pub fn new() -> Self {
+        EndOfInput {
+            trivia: None,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<C> Default for EndOfInput<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C, PT> EndOfInput<C, PT> {
+    pub fn after(trivia: PT) -> Self {
+        EndOfInput {
+            trivia: Some(trivia),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<'code, C, PT> Parser<'code> for EndOfInput<C, PT>
+where
+    C: Cursor<'code>,
+    C::Element: Atomic + 'code,
+    PT: Parser<'code, Cursor = C>,
+    PT::Error: 'code,
+{
+    type Cursor = C;
+    type Output = ();
+    type Error = ParsicombError<'code, C::Element>;
+
+    fn parse(&self, mut cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        if let Some(trivia) = &self.trivia {
+            cursor = match skip_padding(trivia, cursor) {
+                (_, Some(error)) => return Err(ParsicombError::wrap(error)),
+                (cursor, None) => cursor,
+            };
+        }
+        if at_eof(cursor) {
+            return Ok(((), cursor));
+        }
+
+        let (data, start) = cursor.inner();
+        let end = if data[start].is_newline() {
+            start + 1
+        } else {
+            data[start..]
+                .iter()
+                .position(|element| element.is_newline())
+                .map_or(data.len(), |length| start + length)
+        };
+        Err(ParsicombError::InvalidValue {
+            message: "unexpected trailing characters".into(),
+            span: Span::new(data, start, end),
+        })
+    }
+}
+
+/// Creates a parser that reports any input left as trailing characters
+pub fn end_of_input<'code, C>() -> EndOfInput<C>
+where
+    C: Cursor<'code>,
+{
+    EndOfInput::new()
+}
+
+/// Creates a parser that skips `trivia` and reports any input left as trailing characters
+pub fn end_of_input_after<'code, PT>(trivia: PT) -> EndOfInput<PT::Cursor, PT>
+where
+    PT: Parser<'code>,
+{
+    EndOfInput::after(trivia)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ByteCursor;
+    use crate::and::AndExt;
+    use crate::ascii::u64;
+    use crate::byte::is_byte;
+    use crate::error::ErrorLeaf;
+    use crate::map::MapExt;
+    use crate::or::OrExt;
+    use crate::utf8::comment::line_comment;
+    use crate::utf8::whitespace::unicode_whitespace;
+
+    #[test]
+    fn test_eof() {
+        let data = b"a";
+        let cursor = ByteCursor::new(data);
+
+        let error = eof().parse(cursor).unwrap_err();
+        assert_eq!(error.message(), "expected end of input, found 'a'");
+
+        let ((_, ()), cursor) = is_byte(b'a').and(eof()).parse(cursor).unwrap();
+        assert!(cursor.eos());
+        assert!(eof().parse(ByteCursor::new(b"")).is_ok());
+    }
+
+    #[test]
+    fn test_any() {
+        let data = b"xy";
+        let cursor = ByteCursor::new(data);
+
+        let (first, cursor) = any().parse(cursor).unwrap();
+        let (second, cursor) = any().parse(cursor).unwrap();
+        assert_eq!((first, second), (b'x', b'y'));
+
+        let error = any().parse(cursor).unwrap_err();
+        assert_eq!(error.position(), 2);
+        assert!(error.message().contains("reached end of input"));
+    }
+
+    #[test]
+    fn test_then_eof() {
+        let (value, _) = u64().then_eof().parse(ByteCursor::new(b"42")).unwrap();
+        assert_eq!(value, 42);
+
+        let error = u64().then_eof().parse(ByteCursor::new(b"42x")).unwrap_err();
+        assert_eq!(error.position(), 2);
+        assert_eq!(error.message(), "expected end of input, found 'x'");
+
+        assert!(u64().then_eof().parse(ByteCursor::new(b"x")).is_err());
+    }
+
+    #[test]
+    fn test_end_of_input_spans_rest_of_line() {
+        let parser = u64().and(end_of_input());
+        assert!(parser.parse(ByteCursor::new(b"42")).is_ok());
+
+        let data = b"42 x;\ny";
+        let error = end_of_input().parse(ByteCursor::at(data, 2)).unwrap_err();
+        assert_eq!(error.message(), "unexpected trailing characters");
+        assert!(matches!(
+            error,
+            ParsicombError::InvalidValue { span, .. } if span == Span::new(data, 2, 5)
+        ));
+
+        // A leftover line break is reported on its own
+        let error = end_of_input().parse(ByteCursor::at(data, 5)).unwrap_err();
+        assert!(matches!(
+            error,
+            ParsicombError::InvalidValue { span, .. } if span == Span::new(data, 5, 6)
+        ));
+    }
+
+    #[test]
+    fn test_end_of_input_skips_trivia() {
+        let data = b"7 // done\n  8 9\n";
+        let end = || {
+            let trivia = unicode_whitespace()
+                .map(|_| ())
+                .or(line_comment("//").map(|_| ()));
+            end_of_input_after(trivia)
+        };
+        let parser = u64().and(end());
+
+        let ((value, ()), cursor) = parser.parse(ByteCursor::new(b"7 // done\n\n")).unwrap();
+        assert_eq!(value, 7);
+        assert!(cursor.eos());
+
+        assert!(parser.parse(ByteCursor::new(data)).is_err());
+        let error = end().parse(ByteCursor::at(data, 1)).unwrap_err();
+        assert!(matches!(
+            error,
+            ParsicombError::InvalidValue { span, .. } if span.as_string() == "8 9"
+        ));
+    }
+
+    #[test]
+    fn test_end_of_input_returns_committed_trivia_errors() {
+        use crate::cut::CutExt;
+        use crate::error::ErrorNode;
+        use crate::utf8::string::is_string;
+        use crate::utf8::take_until_string::take_until_string;
+
+        let trivia = unicode_whitespace().map(|_| ()).or(is_string("/*")
+            .and(take_until_string("*/").cut())
+            .map(|_| ()));
+        let parser = end_of_input_after(trivia);
+
+        let error = parser.parse(ByteCursor::new(b" /* open")).unwrap_err();
+        assert!(error.is_committed());
+        assert!(error.to_string().contains("*/"));
+        assert_eq!(error.likely_error().loc().position(), 3);
+    }
+}
@@ -1,5 +1,8 @@
 use crate::atomic::Atomic;
+use crate::cut::CutError;
+use crate::position::Span;
 use std::borrow::Cow;
+use std::cell::OnceCell;
 use std::error::Error;
 use std::fmt;
 
@@ -11,6 +14,14 @@ pub trait ErrorLeaf<'code>: Error {
 
     /// Returns the location where this error occurred
     fn loc(&self) -> CodeLoc<'code, Self::Element>;
+
+    /// Returns the error message without any source context
+    ///
+    /// Defaults to the `Display` output, leaves that render source snippets
+    /// should override this so reports can do their own rendering.
+    fn message(&self) -> Cow<'_, str> {
+        self.to_string().into()
+    }
 }
 
 /// Generic trait for error types that can be flattened to find the furthest error
@@ -66,15 +77,202 @@ pub trait ErrorNode<'code>: Error {
 
     /// Flatten nested error structures and return the likely error that made it furthest
     fn likely_error(&self) -> &dyn ErrorLeaf<'code, Element = Self::Element>;
+
+    /// All leaves tied for the furthest position, used to merge alternatives
+    ///
+    /// Defaults to the likely error alone. Errors that hold alternatives override
+    /// this so that nested alternatives are merged into one flat list.
+    fn furthest_leaves(&self) -> Vec<&dyn ErrorLeaf<'code, Element = Self::Element>> {
+        vec![self.likely_error()]
+    }
+
+    /// The errors this node was built from, empty for leaves
+    ///
+    /// Combinators that wrap or collect other errors override this so that tooling
+    /// can see every branch that failed, not only the likely error.
+    fn children(&self) -> Vec<&dyn ErrorNode<'code, Element = Self::Element>> {
+        Vec::new()
+    }
+
+    /// Whether this error must end the enclosing repetition instead of being backtracked
+    ///
+    /// Repetitions like `many` and `separated_list` treat a failing item as the end
+    /// of the list. Errors marked with [`cut`](crate::cut::CutExt::cut) are committed
+    /// and propagate out of them instead. Defaults to whether any child is committed,
+    /// so the mark survives combinators that report their children.
+    fn is_committed(&self) -> bool {
+        self.children().iter().any(|child| child.is_committed())
+    }
+
+    /// Hints attached with [`with_hint`](crate::hint::HintExt::with_hint) that apply to the likely error
+    ///
+    /// Defaults to the hints of the child the likely error comes from, or of every
+    /// child if the likely error is merged from several of them, so hints are found
+    /// through every combinator that reports its children.
+    fn hints(&self) -> Vec<Cow<'static, str>> {
+        let mut hints = Vec::new();
+        for child in likely_children(self) {
+            hints.extend(child.hints());
+        }
+        hints
+    }
+
+    /// Code attached with [`with_code`](crate::code::CodeExt::with_code) to the likely error
+    ///
+    /// Defaults to the code of the first child the likely error comes from, chosen
+    /// like for [`hints`](ErrorNode::hints), so codes are found through every
+    /// combinator that reports its children.
+    fn code(&self) -> Option<Cow<'static, str>> {
+        likely_children(self)
+            .into_iter()
+            .find_map(|child| child.code())
+    }
+
+    /// Short name of this node shown by [`ErrorTreeExt::debug_tree`]
+    ///
+    /// Defaults to the type name without its module path and generic arguments.
+    fn kind(&self) -> &'static str {
+        let name = std::any::type_name::<Self>();
+        let name = name.split('<').next().unwrap_or(name);
+        name.rsplit("::").next().unwrap_or(name)
+    }
+}
+
+/// Extension trait to inspect the whole tree of an error
+pub trait ErrorTreeExt<'code>: ErrorNode<'code> {
+    /// Call `visit` on every leaf of the tree, in the order the branches were tried
+    fn walk<F>(&self, visit: &mut F)
+    where
+        F: FnMut(&dyn ErrorLeaf<'code, Element = Self::Element>),
+    {
+        walk_node(self, visit)
+    }
+
+    /// Formatter that prints the tree with the kind of every node and the position of every leaf
+    fn debug_tree(&self) -> DebugTree<'_, Self> {
+        DebugTree { error: self }
+    }
+}
+
+/// Implement ErrorTreeExt for all errors
+impl<'code, E> ErrorTreeExt<'code> for E where E: ErrorNode<'code> + ?Sized {}
+
+/// Children of `node` the likely error comes from, or every child if none of them does
+///
+/// Shared by the default [`ErrorNode::hints`] and [`ErrorNode::code`] so both pick
+/// the same branch of the tree.
+fn likely_children<'a, 'code, N>(node: &'a N) -> Vec<&'a dyn ErrorNode<'code, Element = N::Element>>
+where
+    N: ErrorNode<'code> + ?Sized,
+{
+    let likely = node.likely_error();
+    let (from_likely, others): (Vec<_>, Vec<_>) = node
+        .children()
+        .into_iter()
+        .partition(|child| std::ptr::addr_eq(child.likely_error(), likely));
+    if from_likely.is_empty() {
+        others
+    } else {
+        from_likely
+    }
+}
+
+fn walk_node<'code, N, F>(node: &N, visit: &mut F)
+where
+    N: ErrorNode<'code> + ?Sized,
+    F: FnMut(&dyn ErrorLeaf<'code, Element = N::Element>),
+{
+    let children = node.children();
+    if children.is_empty() {
+        visit(node.likely_error());
+    }
+    for child in children {
+        walk_node(child, visit);
+    }
+}
+
+/// Display adapter returned by [`ErrorTreeExt::debug_tree`]
+///
+/// ```text
+/// OrError
+/// ├── AndError
+/// │   └── expected byte 0x62 ('b'), found 0x78 ('x') at line 1, byte offset 1
+/// └── expected byte 0x63 ('c'), found 0x61 ('a') at line 1, byte offset 0
+/// ```
+pub struct DebugTree<'a, E: ?Sized> {
+    error: &'a E,
+}
+
+impl<'code, E> fmt::Display for DebugTree<'_, E>
+where
+    E: ErrorNode<'code> + ?Sized,
+    E::Element: 'code,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_node(f, self.error, "", "")
+    }
+}
+
+fn write_node<'code, N>(
+    f: &mut fmt::Formatter<'_>,
+    node: &N,
+    prefix: &str,
+    child_prefix: &str,
+) -> fmt::Result
+where
+    N: ErrorNode<'code> + ?Sized,
+    N::Element: 'code,
+{
+    let children = node.children();
+    if children.is_empty() {
+        let leaf = node.likely_error();
+        let pos = leaf.loc().readable_position();
+        return writeln!(
+            f,
+            "{}{} at line {}, byte offset {}",
+            prefix,
+            leaf.message(),
+            pos.line,
+            pos.byte_offset
+        );
+    }
+
+    writeln!(f, "{}{}", prefix, node.kind())?;
+    let last = children.len() - 1;
+    for (index, child) in children.into_iter().enumerate() {
+        let (branch, indent) = if index == last {
+            ("└── ", "    ")
+        } else {
+            ("├── ", "│   ")
+        };
+        write_node(
+            f,
+            child,
+            &format!("{}{}", child_prefix, branch),
+            &format!("{}{}", child_prefix, indent),
+        )?;
+    }
+    Ok(())
 }
 
+/// Line and column of a location as shown in error messages
 #[derive(Debug)]
 pub struct ReadablePosition {
+    /// 1-based line number
     pub line: usize,
     /// Character offset within the line using display widths
     pub byte_offset: usize,
 }
 
+/// Zero-based line and UTF-16 code unit offset, as used by LSP and JavaScript
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Utf16Position {
+    /// 0-based line number
+    pub line: usize,
+    /// UTF-16 code units between the start of the line and the location
+    pub character: usize,
+}
+
 #[derive(Debug, Copy, Clone)]
 pub struct CodeLoc<'code, T: Atomic = u8> {
     code: &'code [T],
@@ -90,6 +288,63 @@ impl<'code, T: Atomic> CodeLoc<'code, T> {
     pub fn position(&self) -> usize {
         self.loc
     }
+
+    /// The source code this location points into
+    pub(crate) fn source(&self) -> &'code [T] {
+        self.code
+    }
+
+    /// 1-based line number of this location
+    pub fn line(&self) -> usize {
+        self.readable_position().line
+    }
+
+    /// Number of elements between the start of the line and this location
+    pub fn byte_offset_in_line(&self) -> usize {
+        self.loc - self.line_start()
+    }
+
+    /// Position of the first element of the line containing this location
+    fn line_start(&self) -> usize {
+        let end = self.loc.min(self.code.len());
+        self.code[..end]
+            .iter()
+            .rposition(|element| element.is_newline())
+            .map_or(0, |newline| newline + 1)
+    }
+}
+
+impl<'code> CodeLoc<'code, u8> {
+    /// 1-based column of this location counted in UTF-8 characters
+    ///
+    /// Unlike the columns in error messages this ignores display widths, so a tab
+    /// or a wide character counts as one column. Invalid UTF-8 sequences count as
+    /// one character each.
+    pub fn column_utf8(&self) -> usize {
+        let end = self.loc.min(self.code.len());
+        String::from_utf8_lossy(&self.code[self.line_start()..end])
+            .chars()
+            .count()
+            + 1
+    }
+
+    /// Line and column of this location in UTF-16 code units
+    ///
+    /// This is the position format of the Language Server Protocol and of
+    /// JavaScript strings: both numbers are 0-based and characters outside the
+    /// Basic Multilingual Plane count as two units. Invalid UTF-8 sequences count as
+    /// one unit each.
+    pub fn utf16_position(&self) -> Utf16Position {
+        let end = self.loc.min(self.code.len());
+        let character = String::from_utf8_lossy(&self.code[self.line_start()..end])
+            .chars()
+            .map(char::len_utf16)
+            .sum();
+        Utf16Position {
+            line: self.line() - 1,
+            character,
+        }
+    }
 }
 
 impl<'code, T: Atomic> CodeLoc<'code, T> {
@@ -98,7 +353,7 @@ impl<'code, T: Atomic> CodeLoc<'code, T> {
     /// Uses display_width() from the Atomic trait to calculate character position
     /// based on how characters would appear when rendered, accounting for things
     /// like tab width, unicode character width, etc.
-    fn readable_position(&self) -> ReadablePosition {
+    pub fn readable_position(&self) -> ReadablePosition {
         let mut line = 1;
         let mut line_start_element = 0;
 
@@ -118,7 +373,10 @@ impl<'code, T: Atomic> CodeLoc<'code, T> {
             .map(|element| element.display_width())
             .sum::<usize>();
 
-        ReadablePosition { line, byte_offset: char_offset }
+        ReadablePosition {
+            line,
+            byte_offset: char_offset,
+        }
     }
 
     /// Get lines of context around the error position
@@ -130,7 +388,7 @@ impl<'code, T: Atomic> CodeLoc<'code, T> {
         let mut line_start = 0;
 
         // Convert to string for easier line handling
-        let text = T::format_slice(&self.code);
+        let text = T::format_slice(self.code);
 
         for (i, ch) in text.char_indices() {
             if ch == '\n' {
@@ -181,15 +439,103 @@ impl<'code, T: Atomic> CodeLoc<'code, T> {
     }
 }
 
+/// Data of the end of file errors: where input ran out, and what was expected there
+///
+/// Cursors only know that there is no more input, so they leave `expected` empty.
+/// Parsers that know what they were looking for fill it in with
+/// [`ParsicombError::expecting`], which turns "cannot read value at end of file"
+/// into "expected ';', reached end of file".
+#[derive(Debug, Clone)]
+pub struct EofError<'code, T: Atomic = u8> {
+    pub loc: CodeLoc<'code, T>,
+    /// The construct that needed more input, such as `';'` or `a digit`
+    pub expected: Option<Cow<'static, str>>,
+}
+
+impl<'code, T: Atomic> EofError<'code, T> {
+    pub fn new(loc: CodeLoc<'code, T>) -> Self {
+        EofError {
+            loc,
+            expected: None,
+        }
+    }
+
+    /// Record what was expected where input ran out
+    pub fn expecting(mut self, expected: impl Into<Cow<'static, str>>) -> Self {
+        self.expected = Some(expected.into());
+        self
+    }
+
+    /// Display the error, `what` being the capitalized kind of end of file error
+    fn fmt_with(&self, f: &mut fmt::Formatter<'_>, what: &str) -> fmt::Result {
+        let pos = self.loc.readable_position();
+        write!(
+            f,
+            "{} at line {}, byte offset {} (absolute position: {})",
+            what, pos.line, pos.byte_offset, self.loc.loc
+        )?;
+        match &self.expected {
+            Some(expected) => writeln!(f, ", expected {}", expected)?,
+            None => writeln!(f)?,
+        }
+        writeln!(f)?;
+        for line in self.loc.context_lines() {
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+
+    /// The message of the error, `what` being the kind of end of file error
+    fn message(&self, what: &'static str) -> Cow<'static, str> {
+        match &self.expected {
+            Some(expected) => format!("expected {}, reached end of file", expected).into(),
+            None => what.into(),
+        }
+    }
+}
+
+impl<'code, T: Atomic> From<CodeLoc<'code, T>> for EofError<'code, T> {
+    fn from(loc: CodeLoc<'code, T>) -> Self {
+        EofError::new(loc)
+    }
+}
+
 #[derive(Debug)]
 pub enum ParsicombError<'code, T: Atomic = u8> {
-    UnexpectedEndOfFile(CodeLoc<'code, T>),
-    AlreadyAtEndOfFile(CodeLoc<'code, T>),
-    CannotReadValueAtEof(CodeLoc<'code, T>),
+    /// Input ended while moving past the last element
+    UnexpectedEndOfFile(EofError<'code, T>),
+    /// The cursor was already past the last element when asked to move
+    AlreadyAtEndOfFile(EofError<'code, T>),
+    /// The cursor was past the last element when asked for a value
+    CannotReadValueAtEof(EofError<'code, T>),
+    /// The step budget installed with `with_fuel` ran out
+    FuelExhausted(CodeLoc<'code, T>),
+    /// `lazy_limited` rules nested deeper than the limit installed with `with_recursion_limit`
+    ///
+    /// Spans the deepest rule that ran, from its start to where it tried to nest one
+    /// rule deeper. The location of the error is the end of the span.
+    RecursionLimitExceeded(Span<'code, T>),
+    /// The token installed with `with_cancellation` was cancelled or its deadline passed
+    Cancelled(CodeLoc<'code, T>),
+    /// An integer literal that does not fit its target type, spanning the whole literal
+    IntegerOverflow {
+        span: Span<'code, T>,
+        target: &'static str,
+    },
+    /// Input ended before the terminator of a construct that starts at `span.start`
+    UnterminatedConstruct {
+        terminator: Cow<'static, str>,
+        span: Span<'code, T>,
+    },
     SyntaxError {
         message: Cow<'static, str>,
         loc: CodeLoc<'code, T>,
     },
+    /// Input that parsed but was rejected while converting it, spanning the rejected input
+    InvalidValue {
+        message: Cow<'static, str>,
+        span: Span<'code, T>,
+    },
     /// Wrapped error from another parser combinator
     WrappedError {
         inner: Box<dyn ErrorNode<'code, Element = T> + 'code>,
@@ -199,11 +545,16 @@ pub enum ParsicombError<'code, T: Atomic = u8> {
 impl<'code, T: Atomic> fmt::Display for ParsicombError<'code, T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            ParsicombError::UnexpectedEndOfFile(code_loc) => {
+            ParsicombError::UnexpectedEndOfFile(eof) => eof.fmt_with(f, "Unexpected end of file"),
+            ParsicombError::AlreadyAtEndOfFile(eof) => eof.fmt_with(f, "Already at end of file"),
+            ParsicombError::CannotReadValueAtEof(eof) => {
+                eof.fmt_with(f, "Cannot read value at EOF")
+            }
+            ParsicombError::FuelExhausted(code_loc) => {
                 let pos = code_loc.readable_position();
                 writeln!(
                     f,
-                    "Unexpected end of file at line {}, byte offset {} (absolute position: {})",
+                    "Parser ran out of fuel at line {}, byte offset {} (absolute position: {})",
                     pos.line, pos.byte_offset, code_loc.loc
                 )?;
                 writeln!(f)?;
@@ -212,11 +563,12 @@ impl<'code, T: Atomic> fmt::Display for ParsicombError<'code, T> {
                 }
                 Ok(())
             }
-            ParsicombError::AlreadyAtEndOfFile(code_loc) => {
+            ParsicombError::RecursionLimitExceeded(span) => {
+                let code_loc = span.end_loc();
                 let pos = code_loc.readable_position();
                 writeln!(
                     f,
-                    "Already at end of file at line {}, byte offset {} (absolute position: {})",
+                    "Recursion limit exceeded at line {}, byte offset {} (absolute position: {})",
                     pos.line, pos.byte_offset, code_loc.loc
                 )?;
                 writeln!(f)?;
@@ -225,11 +577,11 @@ impl<'code, T: Atomic> fmt::Display for ParsicombError<'code, T> {
                 }
                 Ok(())
             }
-            ParsicombError::CannotReadValueAtEof(code_loc) => {
+            ParsicombError::Cancelled(code_loc) => {
                 let pos = code_loc.readable_position();
                 writeln!(
                     f,
-                    "Cannot read value at EOF at line {}, byte offset {} (absolute position: {})",
+                    "Parsing cancelled at line {}, byte offset {} (absolute position: {})",
                     pos.line, pos.byte_offset, code_loc.loc
                 )?;
                 writeln!(f)?;
@@ -238,6 +590,37 @@ impl<'code, T: Atomic> fmt::Display for ParsicombError<'code, T> {
                 }
                 Ok(())
             }
+            ParsicombError::IntegerOverflow { span, target } => {
+                let loc = span.start_loc();
+                let pos = loc.readable_position();
+                writeln!(
+                    f,
+                    "Integer overflow at line {}, byte offset {}: number too large for {}: {}",
+                    pos.line,
+                    pos.byte_offset,
+                    target,
+                    span.as_string()
+                )?;
+                writeln!(f)?;
+                for line in loc.context_lines() {
+                    writeln!(f, "{}", line)?;
+                }
+                Ok(())
+            }
+            ParsicombError::UnterminatedConstruct { terminator, span } => {
+                let loc = span.start_loc();
+                let pos = loc.readable_position();
+                writeln!(
+                    f,
+                    "Unterminated construct at line {}, byte offset {}: expected '{}' before end of input",
+                    pos.line, pos.byte_offset, terminator
+                )?;
+                writeln!(f)?;
+                for line in loc.context_lines() {
+                    writeln!(f, "{}", line)?;
+                }
+                Ok(())
+            }
             ParsicombError::SyntaxError { message, loc } => {
                 let pos = loc.readable_position();
                 writeln!(
@@ -251,6 +634,20 @@ impl<'code, T: Atomic> fmt::Display for ParsicombError<'code, T> {
                 }
                 Ok(())
             }
+            ParsicombError::InvalidValue { message, span } => {
+                let loc = span.start_loc();
+                let pos = loc.readable_position();
+                writeln!(
+                    f,
+                    "Invalid value at line {}, byte offset {}: {}",
+                    pos.line, pos.byte_offset, message
+                )?;
+                writeln!(f)?;
+                for line in loc.context_lines() {
+                    writeln!(f, "{}", line)?;
+                }
+                Ok(())
+            }
             ParsicombError::WrappedError { inner } => {
                 // Delegate to the inner error's likely_error for display
                 let likely = inner.likely_error();
@@ -270,13 +667,56 @@ impl<'code, T: Atomic> ParsicombError<'code, T> {
         }
     }
 
+    /// Reduce an error tree to its likely error, keeping only its message and location
+    ///
+    /// A committed error stays committed, its flattened form is wrapped in a [`CutError`].
+    pub fn flatten<E>(error: &E) -> Self
+    where
+        E: ErrorNode<'code, Element = T> + ?Sized,
+    {
+        let leaf = error.likely_error();
+        let flat = ParsicombError::SyntaxError {
+            message: leaf.message().into_owned().into(),
+            loc: leaf.loc(),
+        };
+        if error.is_committed() {
+            ParsicombError::wrap(CutError::new(flat))
+        } else {
+            flat
+        }
+    }
+
+    /// Record what was expected if this is an end of file error that doesn't say yet
+    ///
+    /// Other errors are returned unchanged, as is context set by a more specific parser.
+    pub fn expecting(self, expected: impl Into<Cow<'static, str>>) -> Self {
+        match self {
+            ParsicombError::UnexpectedEndOfFile(eof) if eof.expected.is_none() => {
+                ParsicombError::UnexpectedEndOfFile(eof.expecting(expected))
+            }
+            ParsicombError::AlreadyAtEndOfFile(eof) if eof.expected.is_none() => {
+                ParsicombError::AlreadyAtEndOfFile(eof.expecting(expected))
+            }
+            ParsicombError::CannotReadValueAtEof(eof) if eof.expected.is_none() => {
+                ParsicombError::CannotReadValueAtEof(eof.expecting(expected))
+            }
+            other => other,
+        }
+    }
+
     /// Returns the position where this error occurred
     pub fn position(&self) -> usize {
         match self {
-            ParsicombError::UnexpectedEndOfFile(code_loc) => code_loc.position(),
-            ParsicombError::AlreadyAtEndOfFile(code_loc) => code_loc.position(),
-            ParsicombError::CannotReadValueAtEof(code_loc) => code_loc.position(),
+            ParsicombError::UnexpectedEndOfFile(eof) => eof.loc.position(),
+            ParsicombError::AlreadyAtEndOfFile(eof) => eof.loc.position(),
+            ParsicombError::CannotReadValueAtEof(eof) => eof.loc.position(),
+            ParsicombError::FuelExhausted(code_loc) => code_loc.position(),
+            ParsicombError::RecursionLimitExceeded(span) => span.end,
+            ParsicombError::Cancelled(code_loc) => code_loc.position(),
+            ParsicombError::IntegerOverflow { span, .. } => span.start,
+            ParsicombError::UnterminatedConstruct { span, .. } => span.start,
             ParsicombError::SyntaxError { loc, .. } => loc.position(),
+            ParsicombError::InvalidValue { span, .. } => span.start,
             ParsicombError::WrappedError { inner } => {
                 // Delegate to the wrapped error's likely_error
                 inner.likely_error().loc().position()
@@ -290,16 +730,44 @@ impl<'code, T: Atomic> ErrorLeaf<'code> for ParsicombError<'code, T> {
 
     fn loc(&self) -> CodeLoc<'code, Self::Element> {
         match self {
-            ParsicombError::UnexpectedEndOfFile(code_loc) => *code_loc,
-            ParsicombError::AlreadyAtEndOfFile(code_loc) => *code_loc,
-            ParsicombError::CannotReadValueAtEof(code_loc) => *code_loc,
+            ParsicombError::UnexpectedEndOfFile(eof) => eof.loc,
+            ParsicombError::AlreadyAtEndOfFile(eof) => eof.loc,
+            ParsicombError::CannotReadValueAtEof(eof) => eof.loc,
+            ParsicombError::FuelExhausted(code_loc) => *code_loc,
+            ParsicombError::RecursionLimitExceeded(span) => span.end_loc(),
+            ParsicombError::Cancelled(code_loc) => *code_loc,
+            ParsicombError::IntegerOverflow { span, .. } => span.start_loc(),
+            ParsicombError::UnterminatedConstruct { span, .. } => span.start_loc(),
             ParsicombError::SyntaxError { loc, .. } => *loc,
+            ParsicombError::InvalidValue { span, .. } => span.start_loc(),
             ParsicombError::WrappedError { inner } => {
                 // Get the likely error and call loc on it
                 inner.likely_error().loc()
             }
         }
     }
+
+    fn message(&self) -> Cow<'_, str> {
+        match self {
+            ParsicombError::UnexpectedEndOfFile(eof) => eof.message("unexpected end of file"),
+            ParsicombError::AlreadyAtEndOfFile(eof) => eof.message("already at end of file"),
+            ParsicombError::CannotReadValueAtEof(eof) => {
+                eof.message("cannot read value at end of file")
+            }
+            ParsicombError::FuelExhausted(_) => "parser ran out of fuel".into(),
+            ParsicombError::RecursionLimitExceeded(_) => "recursion limit exceeded".into(),
+            ParsicombError::Cancelled(_) => "parsing was cancelled".into(),
+            ParsicombError::IntegerOverflow { span, target } => {
+                format!("number too large for {}: {}", target, span.as_string()).into()
+            }
+            ParsicombError::UnterminatedConstruct { terminator, .. } => {
+                format!("expected '{}' before end of input", terminator).into()
+            }
+            ParsicombError::SyntaxError { message, .. } => Cow::Borrowed(message),
+            ParsicombError::InvalidValue { message, .. } => Cow::Borrowed(message),
+            ParsicombError::WrappedError { inner } => inner.likely_error().message(),
+        }
+    }
 }
 
 // ParsicombError implements ErrorNode (converts to itself since it's a terminal type)
@@ -312,6 +780,167 @@ where
     fn likely_error(&self) -> &dyn ErrorLeaf<'code, Element = Self::Element> {
         self // Already the base type
     }
+
+    fn children(&self) -> Vec<&dyn ErrorNode<'code, Element = Self::Element>> {
+        match self {
+            ParsicombError::WrappedError { inner } => vec![inner.as_ref()],
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Error leaf for several alternatives that failed at the same position
+///
+/// Built by `Or` and `Choice` when more than one alternative got furthest, its
+/// message lists everything that was expected there.
+#[derive(Debug)]
+pub struct MergedError<'code, T: Atomic = u8> {
+    message: String,
+    alternatives: Vec<String>,
+    loc: CodeLoc<'code, T>,
+}
+
+impl<'code, T: Atomic + 'code> MergedError<'code, T> {
+    /// Merge leaves that all failed at the same location
+    pub fn new(leaves: &[&dyn ErrorLeaf<'code, Element = T>]) -> Self {
+        let messages: Vec<_> = leaves.iter().map(|leaf| leaf.message()).collect();
+        MergedError {
+            message: merge_expected(&messages),
+            alternatives: messages.iter().map(|m| m.to_string()).collect(),
+            loc: leaves
+                .first()
+                .map_or(CodeLoc::new(&[], 0), |leaf| leaf.loc()),
+        }
+    }
+
+    /// Messages of the merged alternatives in the order they were tried
+    pub fn alternatives(&self) -> &[String] {
+        &self.alternatives
+    }
+}
+
+impl<'code, T: Atomic> fmt::Display for MergedError<'code, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pos = self.loc.readable_position();
+        writeln!(
+            f,
+            "Syntax error at line {}, byte offset {}: {}",
+            pos.line, pos.byte_offset, self.message
+        )?;
+        writeln!(f)?;
+        for line in self.loc.context_lines() {
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'code, T: Atomic> Error for MergedError<'code, T> {}
+
+impl<'code, T: Atomic> ErrorLeaf<'code> for MergedError<'code, T> {
+    type Element = T;
+
+    fn loc(&self) -> CodeLoc<'code, Self::Element> {
+        self.loc
+    }
+
+    fn message(&self) -> Cow<'_, str> {
+        Cow::Borrowed(&self.message)
+    }
+}
+
+impl<'code, T: Atomic + 'code> ErrorNode<'code> for MergedError<'code, T> {
+    type Element = T;
+
+    fn likely_error(&self) -> &dyn ErrorLeaf<'code, Element = Self::Element> {
+        self
+    }
+}
+
+/// Keep only the leaves at the furthest position, with duplicate messages removed
+pub(crate) fn furthest_only<'a, 'code, T: Atomic + 'code>(
+    leaves: Vec<&'a dyn ErrorLeaf<'code, Element = T>>,
+) -> Vec<&'a dyn ErrorLeaf<'code, Element = T>> {
+    let furthest = leaves
+        .iter()
+        .map(|leaf| leaf.loc().position())
+        .max()
+        .unwrap_or(0);
+
+    let mut kept: Vec<&'a dyn ErrorLeaf<'code, Element = T>> = Vec::new();
+    for leaf in leaves {
+        if leaf.loc().position() == furthest
+            && !kept.iter().any(|other| other.message() == leaf.message())
+        {
+            kept.push(leaf);
+        }
+    }
+    kept
+}
+
+/// The single furthest leaf, or a merged error built once in `cell` if several tie
+pub(crate) fn merge_furthest<'a, 'code, T: Atomic + 'code>(
+    leaves: Vec<&'a dyn ErrorLeaf<'code, Element = T>>,
+    cell: &'a OnceCell<MergedError<'code, T>>,
+) -> &'a dyn ErrorLeaf<'code, Element = T> {
+    let leaves = furthest_only(leaves);
+    match leaves.as_slice() {
+        [leaf] => *leaf,
+        _ => cell.get_or_init(|| MergedError::new(&leaves)),
+    }
+}
+
+/// Combine the messages of alternatives that failed at the same position
+///
+/// Messages of the form `expected X, found Y` are merged into
+/// `expected X1, X2 or X3, found Y`. If any message has a different shape,
+/// the messages are joined with `or` unchanged.
+pub(crate) fn merge_expected(messages: &[Cow<'_, str>]) -> String {
+    let mut expected: Vec<&str> = Vec::new();
+    let mut suffix = None;
+
+    for message in messages {
+        let Some(rest) = message.strip_prefix("expected ") else {
+            return join_alternatives(messages.iter().map(|m| m.as_ref()));
+        };
+        let split = [", found ", ", but "]
+            .iter()
+            .filter_map(|separator| rest.find(separator))
+            .min();
+        let what = match split {
+            Some(index) => {
+                suffix.get_or_insert(&rest[index..]);
+                &rest[..index]
+            }
+            None => rest,
+        };
+        if !expected.contains(&what) {
+            expected.push(what);
+        }
+    }
+
+    // Context such as "while matching 'fn'" only applies to one of the alternatives
+    let suffix = suffix.unwrap_or("");
+    let suffix = match suffix.find(" while ") {
+        Some(index) if expected.len() > 1 => &suffix[..index],
+        _ => suffix,
+    };
+
+    format!(
+        "expected {}{}",
+        join_alternatives(expected.into_iter()),
+        suffix
+    )
+}
+
+/// Format `a`, `a or b`, `a, b or c`
+pub(crate) fn join_alternatives<'a>(items: impl Iterator<Item = &'a str>) -> String {
+    let items: Vec<&str> = items.collect();
+    match items.split_last() {
+        None => String::new(),
+        Some((last, [])) => last.to_string(),
+        Some((last, rest)) => format!("{} or {}", rest.join(", "), last),
+    }
 }
 
 #[cfg(test)]
@@ -322,7 +951,7 @@ mod tests {
     fn test_codeloc_eos_empty_data() {
         let empty_data = b"";
         let loc = CodeLoc::new(empty_data, 0);
-        let error = ParsicombError::AlreadyAtEndOfFile(loc);
+        let error = ParsicombError::AlreadyAtEndOfFile(loc.into());
 
         // Should not panic when displaying
         let display_str = format!("{}", error);
@@ -336,7 +965,7 @@ mod tests {
     fn test_codeloc_eos_single_byte() {
         let data = b"a";
         let loc = CodeLoc::new(data, 1); // Position 1 = past end
-        let error = ParsicombError::CannotReadValueAtEof(loc);
+        let error = ParsicombError::CannotReadValueAtEof(loc.into());
 
         // Should not panic when displaying
         let display_str = format!("{}", error);
@@ -350,7 +979,7 @@ mod tests {
     fn test_codeloc_eos_multiline() {
         let data = b"hello\nworld";
         let loc = CodeLoc::new(data, 11); // Position 11 = past end
-        let error = ParsicombError::UnexpectedEndOfFile(loc);
+        let error = ParsicombError::UnexpectedEndOfFile(loc.into());
 
         // Should not panic when displaying
         let display_str = format!("{}", error);
@@ -388,6 +1017,55 @@ mod tests {
         assert_eq!(pos.byte_offset, 5);
     }
 
+    #[test]
+    fn test_codeloc_line_and_columns() {
+        let data = "ab\n\tgrüße x".as_bytes();
+        let loc = CodeLoc::new(data, data.len() - 1);
+
+        assert_eq!(loc.line(), 2);
+        assert_eq!(loc.byte_offset_in_line(), 9);
+        assert_eq!(loc.column_utf8(), 8);
+        assert_eq!(CodeLoc::new(data, 0).column_utf8(), 1);
+        assert_eq!(CodeLoc::new(data, 3).byte_offset_in_line(), 0);
+        assert_eq!(CodeLoc::new(data, data.len()).line(), 2);
+    }
+
+    #[test]
+    fn test_codeloc_utf16_position() {
+        let data = "é\n𝔸b = \"😀\"".as_bytes();
+        let position = |loc| CodeLoc::new(data, loc).utf16_position();
+
+        assert_eq!(
+            position(0),
+            Utf16Position {
+                line: 0,
+                character: 0
+            }
+        );
+        assert_eq!(
+            position(2),
+            Utf16Position {
+                line: 0,
+                character: 1
+            }
+        );
+        // 𝔸 is four bytes in UTF-8 and a surrogate pair in UTF-16
+        assert_eq!(
+            position(7),
+            Utf16Position {
+                line: 1,
+                character: 2
+            }
+        );
+        assert_eq!(
+            position(data.len()),
+            Utf16Position {
+                line: 1,
+                character: 10
+            }
+        );
+    }
+
     #[test]
     fn test_codeloc_context_lines_eos() {
         let data = b"line1\nline2";
@@ -417,7 +1095,7 @@ mod tests {
         // Test that EOS errors display correctly without bounds issues
         let data = b"hello\nworld";
         let loc = CodeLoc::new(data, 11); // Position 11 = past end
-        let error = ParsicombError::UnexpectedEndOfFile(loc);
+        let error = ParsicombError::UnexpectedEndOfFile(loc.into());
 
         let display_str = format!("{}", error);
         println!("EOS Error Display:\n{}", display_str);
@@ -433,7 +1111,7 @@ mod tests {
         // Test EOS position right after a newline
         let data = b"hello\n";
         let loc = CodeLoc::new(data, 6); // Position 6 = past end (after newline)
-        let error = ParsicombError::CannotReadValueAtEof(loc);
+        let error = ParsicombError::CannotReadValueAtEof(loc.into());
 
         let display_str = format!("{}", error);
         println!("EOS After Newline:\n{}", display_str);
@@ -442,4 +1120,124 @@ mod tests {
         assert!(display_str.contains("line 2"));
         assert!(display_str.contains("byte offset 0"));
     }
+
+    #[test]
+    fn test_eof_error_expecting() {
+        let data = b"let x = 1";
+        let error = ParsicombError::CannotReadValueAtEof(CodeLoc::new(data, 9).into());
+        assert_eq!(error.message(), "cannot read value at end of file");
+
+        let error = error.expecting("';'");
+        assert_eq!(error.message(), "expected ';', reached end of file");
+        assert!(error.to_string().contains("line 1, byte offset 9"));
+        assert!(error.to_string().contains("expected ';'"));
+
+        // The most specific context is kept
+        let error = error.expecting("a statement");
+        assert_eq!(error.message(), "expected ';', reached end of file");
+
+        let error = ParsicombError::SyntaxError {
+            message: "expected ';'".into(),
+            loc: CodeLoc::new(data, 0),
+        };
+        assert_eq!(error.expecting("x").message(), "expected ';'");
+    }
+
+    #[test]
+    fn test_merge_expected_messages() {
+        let messages: Vec<Cow<str>> = vec![
+            "expected 'a', found 'x'".into(),
+            "expected 'b', found 'x'".into(),
+            "expected 'a', found 'x'".into(),
+            "expected 'c', but reached end of input".into(),
+        ];
+        assert_eq!(
+            merge_expected(&messages),
+            "expected 'a', 'b' or 'c', found 'x'"
+        );
+
+        let messages: Vec<Cow<str>> = vec!["expected identifier".into(), "expected number".into()];
+        assert_eq!(merge_expected(&messages), "expected identifier or number");
+
+        let messages: Vec<Cow<str>> = vec!["expected digit".into(), "number too large".into()];
+        assert_eq!(
+            merge_expected(&messages),
+            "expected digit or number too large"
+        );
+    }
+
+    #[test]
+    fn test_walk_visits_every_branch() {
+        use crate::Parser;
+        use crate::and::AndExt;
+        use crate::byte::is_byte;
+        use crate::cursors::ByteCursor;
+        use crate::map::MapExt;
+        use crate::or::OrExt;
+
+        let data = b"ax";
+        let parser = is_byte(b'a')
+            .and(is_byte(b'b'))
+            .map(|_| ())
+            .or(is_byte(b'c').map(|_| ()));
+        let error = parser.parse(ByteCursor::new(data)).unwrap_err();
+
+        // likely_error only shows the furthest branch, walk reaches both
+        assert_eq!(error.likely_error().loc().position(), 1);
+        let mut leaves = Vec::new();
+        error.walk(&mut |leaf| leaves.push((leaf.loc().position(), leaf.message().into_owned())));
+        assert_eq!(
+            leaves,
+            [
+                (1, "expected byte 0x62 ('b'), found 0x78 ('x')".to_string()),
+                (0, "expected byte 0x63 ('c'), found 0x61 ('a')".to_string()),
+            ]
+        );
+
+        assert_eq!(
+            error.debug_tree().to_string(),
+            "OrError\n\
+             ├── AndError\n\
+             │   └── expected byte 0x62 ('b'), found 0x78 ('x') at line 1, byte offset 1\n\
+             └── expected byte 0x63 ('c'), found 0x61 ('a') at line 1, byte offset 0\n"
+        );
+    }
+
+    #[test]
+    fn test_walk_leaf_and_wrapped() {
+        let data = b"abc";
+        let leaf = ParsicombError::SyntaxError {
+            message: "expected digit".into(),
+            loc: CodeLoc::new(data, 2),
+        };
+        assert_eq!(leaf.kind(), "ParsicombError");
+        assert!(leaf.children().is_empty());
+
+        let wrapped = ParsicombError::wrap(leaf);
+        let mut count = 0;
+        wrapped.walk(&mut |leaf| {
+            count += 1;
+            assert_eq!(leaf.message(), "expected digit");
+        });
+        assert_eq!(count, 1);
+        assert_eq!(
+            wrapped.debug_tree().to_string(),
+            "ParsicombError\n└── expected digit at line 1, byte offset 2\n"
+        );
+    }
+
+    #[test]
+    fn test_flatten_keeps_committed() {
+        let data = b"abc";
+        let leaf = ParsicombError::SyntaxError {
+            message: "expected digit".into(),
+            loc: CodeLoc::new(data, 2),
+        };
+        assert!(!ParsicombError::flatten(&leaf).is_committed());
+
+        let flat = ParsicombError::flatten(&CutError::new(leaf));
+        assert!(flat.is_committed());
+        assert_eq!(flat.message(), "expected digit");
+        assert_eq!(flat.position(), 2);
+    }
 }
@@ -0,0 +1,336 @@
+use crate::ByteCursor;
+use crate::cursor::Cursor;
+use crate::parser::Parser;
+use std::cell::RefCell;
+
+// # Expression Parsing - Operator Tables
+//
+// Binary expressions are parsed by precedence climbing over an `OperatorTable`
+// instead of one hand-written rule per precedence level. The table is an ordinary
+// value built at runtime, so languages with user-defined operators (Haskell-style
+// `infixl 6 <+>` declarations) can add operators between parses. Like `Interner`
+// the table is shared by reference and changed through `&self`, which lets the
+// grammar keep borrowing it while declarations are processed.
+//
+// Operators are matched on the raw bytes, preferring the longest symbol so that
+// `**` wins over `*`. ASCII whitespace around an operator is skipped, whitespace
+// after the last operand is left for the caller.
+
+/// Associativity of an infix operator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Assoc {
+    /// `a - b - c` parses as `(a - b) - c`
+    Left,
+    /// `a ^ b ^ c` parses as `a ^ (b ^ c)`
+    Right,
+}
+
+/// An infix operator entry of an [`OperatorTable`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Operator {
+    pub symbol: String,
+    /// Operators with a higher precedence bind tighter
+    pub precedence: u32,
+    pub assoc: Assoc,
+}
+
+/// Infix operators known to an [`Expression`] parser
+///
+/// Example:
+/// ```
+/// use parsicomb::expression::{Assoc, OperatorTable};
+///
+/// let table = OperatorTable::from(vec![("+", 6, Assoc::Left), ("*", 7, Assoc::Left)]);
+/// table.add("<+>", 5, Assoc::Right);
+///
+/// assert_eq!(table.len(), 3);
+/// assert_eq!(table.get("<+>").unwrap().precedence, 5);
+/// ```
+#[derive(Debug, Default)]
+pub struct OperatorTable {
+    operators: RefCell<Vec<Operator>>,
+}
+
+impl OperatorTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder method adding an operator to a new table
+    pub fn with_operator(self, symbol: impl Into<String>, precedence: u32, assoc: Assoc) -> Self {
+        self.add(symbol, precedence, assoc);
+        self
+    }
+
+    /// Add an operator, replacing an existing operator with the same symbol
+    ///
+    /// Panics if the symbol is empty.
+    pub fn add(&self, symbol: impl Into<String>, precedence: u32, assoc: Assoc) {
+        let symbol = symbol.into();
+        assert!(!symbol.is_empty(), "operator symbols must not be empty");
+
+        let mut operators = self.operators.borrow_mut();
+        let operator = Operator {
+            symbol,
+            precedence,
+            assoc,
+        };
+        match operators.iter_mut().find(|op| op.symbol == operator.symbol) {
+            Some(existing) => *existing = operator,
+            None => operators.push(operator),
+        }
+    }
+
+    /// Remove an operator, returning whether it was present
+    pub fn remove(&self, symbol: &str) -> bool {
+        let mut operators = self.operators.borrow_mut();
+        let before = operators.len();
+        operators.retain(|op| op.symbol != symbol);
+        operators.len() != before
+    }
+
+    /// The operator with the given symbol, if any
+    pub fn get(&self, symbol: &str) -> Option<Operator> {
+        self.operators
+            .borrow()
+            .iter()
+            .find(|op| op.symbol == symbol)
+            .cloned()
+    }
+
+    /// Number of operators in the table
+    pub fn len(&self) -> usize {
+        self.operators.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Longest operator whose symbol starts `input`
+    fn longest_match(&self, input: &[u8]) -> Option<Operator> {
+        self.operators
+            .borrow()
+            .iter()
+            .filter(|op| input.starts_with(op.symbol.as_bytes()))
+            .max_by_key(|op| op.symbol.len())
+            .cloned()
+    }
+}
+
+impl<S: Into<String>> FromIterator<(S, u32, Assoc)> for OperatorTable {
+    fn from_iter<I: IntoIterator<Item = (S, u32, Assoc)>>(iter: I) -> Self {
+        let table = OperatorTable::new();
+        for (symbol, precedence, assoc) in iter {
+            table.add(symbol, precedence, assoc);
+        }
+        table
+    }
+}
+
+impl<S: Into<String>> From<Vec<(S, u32, Assoc)>> for OperatorTable {
+    fn from(operators: Vec<(S, u32, Assoc)>) -> Self {
+        operators.into_iter().collect()
+    }
+}
+
+/// Parser for binary expressions over the operators of an [`OperatorTable`]
+///
+/// `build` combines the operator symbol with the left and right operand. The table
+/// is read on every parse, so operators added in between take effect immediately.
+///
+/// Example:
+/// ```
+/// use parsicomb::ascii::i64;
+/// use parsicomb::expression::{Assoc, OperatorTable, expression};
+/// use parsicomb::{ByteCursor, Parser};
+///
+/// let table = OperatorTable::new()
+///     .with_operator("+", 6, Assoc::Left)
+///     .with_operator("-", 6, Assoc::Left)
+///     .with_operator("*", 7, Assoc::Left);
+///
+/// let calc = expression(i64(), &table, |op, a, b| match op {
+///     "+" => a + b,
+///     "-" => a - b,
+///     "*" => a * b,
+///     _ => a.pow(b as u32),
+/// });
+/// let (value, _) = calc.parse(ByteCursor::new(b"10 - 2 * 3 - 1")).unwrap();
+/// assert_eq!(value, 3);
+///
+/// // Declare a new operator between parses
+/// table.add("**", 8, Assoc::Right);
+/// let (value, _) = calc.parse(ByteCursor::new(b"2 ** 3 ** 2")).unwrap();
+/// assert_eq!(value, 512);
+/// ```
+pub struct Expression<'t, P, F> {
+    operand: P,
+    table: &'t OperatorTable,
+    build: F,
+}
+
+impl<'t, P, F> Expression<'t, P, F> {
+    pub fn new(operand: P, table: &'t OperatorTable, build: F) -> Self {
+        Expression {
+            operand,
+            table,
+            build,
+        }
+    }
+}
+
+impl<'t, P, F> Expression<'t, P, F> {
+    /// Precedence climbing: parse an operand followed by operators binding at
+    /// least as tight as `min_precedence`
+    fn climb<'code>(
+        &self,
+        cursor: ByteCursor<'code>,
+        min_precedence: u32,
+    ) -> Result<(P::Output, ByteCursor<'code>), P::Error>
+    where
+        P: Parser<'code, Cursor = ByteCursor<'code>>,
+        F: Fn(&str, P::Output, P::Output) -> P::Output,
+    {
+        let (mut lhs, mut cursor) = self.operand.parse(cursor)?;
+
+        loop {
+            let (data, position) = cursor.inner();
+            let start = skip_whitespace(data, position);
+            let operator = match self.table.longest_match(&data[start..]) {
+                Some(operator) if operator.precedence >= min_precedence => operator,
+                _ => return Ok((lhs, cursor)),
+            };
+
+            let next_min = match operator.assoc {
+                Assoc::Left => operator.precedence.saturating_add(1),
+                Assoc::Right => operator.precedence,
+            };
+            let after = skip_whitespace(data, start + operator.symbol.len());
+            let (rhs, next_cursor) = self.climb(ByteCursor::at(data, after), next_min)?;
+            lhs = (self.build)(&operator.symbol, lhs, rhs);
+            cursor = next_cursor;
+        }
+    }
+}
+
+/// Position of the first non-whitespace byte at or after `position`
+fn skip_whitespace(data: &[u8], position: usize) -> usize {
+    position
+        + data[position..]
+            .iter()
+            .take_while(|b| b.is_ascii_whitespace())
+            .count()
+}
+
+impl<'code, 't, P, F> Parser<'code> for Expression<'t, P, F>
+where
+    P: Parser<'code, Cursor = ByteCursor<'code>>,
+    F: Fn(&str, P::Output, P::Output) -> P::Output,
+{
+    type Cursor = ByteCursor<'code>;
+    type Output = P::Output;
+    type Error = P::Error;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        self.climb(cursor, 0)
+    }
+}
+
+/// Creates a parser for `operand (operator operand)*` using the operators of `table`
+pub fn expression<'code, 't, P, F>(
+    operand: P,
+    table: &'t OperatorTable,
+    build: F,
+) -> Expression<'t, P, F>
+where
+    P: Parser<'code, Cursor = ByteCursor<'code>>,
+    F: Fn(&str, P::Output, P::Output) -> P::Output,
+{
+    Expression::new(operand, table, build)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ascii::{i64, take_ident};
+    use crate::error::ErrorNode;
+    use crate::map::MapExt;
+
+    fn show(op: &str, a: String, b: String) -> String {
+        format!("({} {} {})", a, op, b)
+    }
+
+    fn name<'code>() -> impl Parser<'code, Cursor = ByteCursor<'code>, Output = String> {
+        take_ident().map(|s| s.to_string())
+    }
+
+    #[test]
+    fn test_precedence_and_associativity() {
+        let table = OperatorTable::from(vec![
+            ("+", 6, Assoc::Left),
+            ("*", 7, Assoc::Left),
+            ("^", 8, Assoc::Right),
+        ]);
+        let parser = expression(name(), &table, show);
+
+        let (tree, _) = parser.parse(ByteCursor::new(b"a + b * c + d")).unwrap();
+        assert_eq!(tree, "((a + (b * c)) + d)");
+
+        let (tree, _) = parser.parse(ByteCursor::new(b"a^b^c*d")).unwrap();
+        assert_eq!(tree, "((a ^ (b ^ c)) * d)");
+    }
+
+    #[test]
+    fn test_longest_operator_wins() {
+        let table: OperatorTable = [("<", 4, Assoc::Left), ("<=", 4, Assoc::Left)]
+            .into_iter()
+            .collect();
+        let parser = expression(name(), &table, show);
+
+        let (tree, _) = parser.parse(ByteCursor::new(b"a <= b < c")).unwrap();
+        assert_eq!(tree, "((a <= b) < c)");
+    }
+
+    #[test]
+    fn test_table_changes_between_parses() {
+        let table = OperatorTable::new().with_operator("+", 6, Assoc::Left);
+        let parser = expression(name(), &table, show);
+
+        let (tree, cursor) = parser.parse(ByteCursor::new(b"a <+> b")).unwrap();
+        assert_eq!(tree, "a");
+        assert_eq!(cursor.position(), 1);
+
+        table.add("<+>", 5, Assoc::Right);
+        let (tree, _) = parser.parse(ByteCursor::new(b"a <+> b + c <+> d")).unwrap();
+        assert_eq!(tree, "(a <+> ((b + c) <+> d))");
+
+        // Re-declaring an operator replaces its precedence
+        table.add("<+>", 7, Assoc::Left);
+        let (tree, _) = parser.parse(ByteCursor::new(b"a <+> b + c")).unwrap();
+        assert_eq!(tree, "((a <+> b) + c)");
+
+        assert!(table.remove("<+>"));
+        assert!(!table.remove("<+>"));
+        assert_eq!(table.len(), 1);
+    }
+
+    #[test]
+    fn test_trailing_whitespace_not_consumed() {
+        let table = OperatorTable::new().with_operator("+", 6, Assoc::Left);
+        let parser = expression(i64(), &table, |_, a, b| a + b);
+
+        let (value, cursor) = parser.parse(ByteCursor::new(b"1 + 2 ;")).unwrap();
+        assert_eq!(value, 3);
+        assert_eq!(cursor.position(), 5);
+    }
+
+    #[test]
+    fn test_missing_right_operand() {
+        let table = OperatorTable::new().with_operator("+", 6, Assoc::Left);
+        let parser = expression(i64(), &table, |_, a, b| a + b);
+
+        let error = parser.parse(ByteCursor::new(b"1 + ;")).unwrap_err();
+        assert_eq!(error.likely_error().loc().position(), 4);
+    }
+}
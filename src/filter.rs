@@ -1,4 +1,5 @@
 use crate::atomic::Atomic;
+use crate::describe::Description;
 use crate::error::{ErrorLeaf, ErrorNode};
 use crate::parser::Parser;
 use crate::{CodeLoc, Cursor, ParsicombError};
@@ -39,6 +40,13 @@ where
             FilterError::FilterFailed(parsicomb_error) => parsicomb_error.likely_error(),
         }
     }
+
+    fn children(&self) -> Vec<&dyn ErrorNode<'code, Element = Self::Element>> {
+        match self {
+            FilterError::ParserError(e) => vec![e],
+            FilterError::FilterFailed(parsicomb_error) => vec![parsicomb_error],
+        }
+    }
 }
 
 /// Parser that applies a predicate function to filter the output of another parser
@@ -86,6 +94,10 @@ where
             }))
         }
     }
+
+    fn describe(&self) -> Description {
+        self.parser.describe()
+    }
 }
 
 /// Extension trait to add filter method to all parsers
@@ -118,10 +130,82 @@ where
     FilterParser::new(parser, predicate, error_message.into())
 }
 
+/// Parser that checks the output of another parser with a closure that explains failures
+///
+/// Like [`FilterParser`], but the check returns the error message itself, so it can
+/// describe the offending value. The error is anchored at the start of the
+/// consumed input.
+pub struct VerifyParser<P, F> {
+    parser: P,
+    check: F,
+}
+
+impl<P, F> VerifyParser<P, F> {
+    pub fn new(parser: P, check: F) -> Self {
+        Self { parser, check }
+    }
+}
+
+impl<'code, P, F> Parser<'code> for VerifyParser<P, F>
+where
+    P: Parser<'code>,
+    <P::Cursor as Cursor<'code>>::Element: Atomic + 'code,
+    P::Error: ErrorNode<'code, Element = <P::Cursor as Cursor<'code>>::Element>,
+    F: Fn(&P::Output) -> Result<(), Cow<'static, str>>,
+{
+    type Cursor = P::Cursor;
+    type Output = P::Output;
+    type Error = FilterError<'code, P::Error, <P::Cursor as Cursor<'code>>::Element>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (value, new_cursor) = self
+            .parser
+            .parse(cursor)
+            .map_err(FilterError::ParserError)?;
+
+        match (self.check)(&value) {
+            Ok(()) => Ok((value, new_cursor)),
+            Err(message) => {
+                let (data, start) = cursor.inner();
+                Err(FilterError::FilterFailed(ParsicombError::SyntaxError {
+                    message,
+                    loc: CodeLoc::new(data, start),
+                }))
+            }
+        }
+    }
+
+    fn describe(&self) -> Description {
+        self.parser.describe()
+    }
+}
+
+/// Extension trait to add verify method to all parsers
+pub trait VerifyExt<'code>: Parser<'code> + Sized {
+    fn verify<F>(self, check: F) -> VerifyParser<Self, F>
+    where
+        F: Fn(&Self::Output) -> Result<(), Cow<'static, str>>,
+    {
+        VerifyParser::new(self, check)
+    }
+}
+
+impl<'code, P: Parser<'code>> VerifyExt<'code> for P {}
+
+/// Convenience function to create a verified parser
+pub fn verify<'code, P, F>(parser: P, check: F) -> VerifyParser<P, F>
+where
+    P: Parser<'code>,
+    F: Fn(&P::Output) -> Result<(), Cow<'static, str>>,
+{
+    VerifyParser::new(parser, check)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::ByteCursor;
+    use crate::ascii::u64;
     use crate::utf8::char::char;
 
     #[test]
@@ -300,4 +384,49 @@ mod tests {
                 .contains("expected uppercase")
         );
     }
+
+    #[test]
+    fn test_verify_success() {
+        let data = b"200";
+        let cursor = ByteCursor::new(data);
+        let parser = u64().verify(|&n| {
+            if n <= 255 {
+                Ok(())
+            } else {
+                Err(format!("integer {} out of range 0..255", n).into())
+            }
+        });
+
+        let (value, cursor) = parser.parse(cursor).unwrap();
+        assert_eq!(value, 200);
+        assert!(cursor.eos());
+    }
+
+    #[test]
+    fn test_verify_failure_message_and_position() {
+        let data = b"[999]";
+        let cursor = ByteCursor::new(data).next();
+        let parser = verify(u64(), |&n| {
+            if n <= 255 {
+                Ok(())
+            } else {
+                Err(format!("integer {} out of range 0..255", n).into())
+            }
+        });
+
+        let error = parser.parse(cursor).unwrap_err();
+        let leaf = error.likely_error();
+        assert_eq!(leaf.loc().position(), 1);
+        assert_eq!(leaf.message(), "integer 999 out of range 0..255");
+    }
+
+    #[test]
+    fn test_verify_propagates_parser_error() {
+        let data = b"x";
+        let cursor = ByteCursor::new(data);
+        let parser = u64().verify(|_| Ok(()));
+
+        let error = parser.parse(cursor).unwrap_err();
+        assert!(matches!(error, FilterError::ParserError(_)));
+    }
 }
@@ -0,0 +1,197 @@
+use super::parser::Parser;
+use crate::describe::Description;
+use crate::error::ErrorNode;
+
+/// Parser combinator that reduces zero or more matches into an accumulator
+///
+/// Works like `many` followed by `Iterator::fold`, but never allocates a `Vec`.
+/// `init` is called on every parse to create a fresh accumulator.
+pub struct FoldMany<P, I, F> {
+    parser: P,
+    init: I,
+    fold: F,
+}
+
+impl<P, I, F> FoldMany<P, I, F> {
+    pub fn new(parser: P, init: I, fold: F) -> Self {
+        FoldMany { parser, init, fold }
+    }
+}
+
+impl<'code, P, I, F, A> Parser<'code> for FoldMany<P, I, F>
+where
+    P: Parser<'code>,
+    I: Fn() -> A,
+    F: Fn(A, P::Output) -> A,
+{
+    type Cursor = P::Cursor;
+    type Output = A;
+    type Error = P::Error;
+
+    fn parse(&self, mut cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let mut acc = (self.init)();
+
+        // Errors end the repetition unless they are committed, like Many
+        loop {
+            match self.parser.parse(cursor) {
+                Ok((value, next_cursor)) => {
+                    acc = (self.fold)(acc, value);
+                    cursor = next_cursor;
+                }
+                Err(error) if error.is_committed() => return Err(error),
+                Err(_) => break,
+            }
+        }
+
+        Ok((acc, cursor))
+    }
+
+    fn describe(&self) -> Description {
+        Description::repeat(0, self.parser.describe())
+    }
+}
+
+/// Parser combinator that reduces one or more matches into an accumulator
+///
+/// Works like `some` followed by `Iterator::fold`, failing with the parser's
+/// error if the first match fails.
+pub struct FoldMany1<P, I, F> {
+    parser: P,
+    init: I,
+    fold: F,
+}
+
+impl<P, I, F> FoldMany1<P, I, F> {
+    pub fn new(parser: P, init: I, fold: F) -> Self {
+        FoldMany1 { parser, init, fold }
+    }
+}
+
+impl<'code, P, I, F, A> Parser<'code> for FoldMany1<P, I, F>
+where
+    P: Parser<'code>,
+    I: Fn() -> A,
+    F: Fn(A, P::Output) -> A,
+{
+    type Cursor = P::Cursor;
+    type Output = A;
+    type Error = P::Error;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        // First parse must succeed
+        let (first_value, mut cursor) = self.parser.parse(cursor)?;
+        let mut acc = (self.fold)((self.init)(), first_value);
+
+        loop {
+            match self.parser.parse(cursor) {
+                Ok((value, next_cursor)) => {
+                    acc = (self.fold)(acc, value);
+                    cursor = next_cursor;
+                }
+                Err(error) if error.is_committed() => return Err(error),
+                Err(_) => break,
+            }
+        }
+
+        Ok((acc, cursor))
+    }
+
+    fn describe(&self) -> Description {
+        Description::repeat(1, self.parser.describe())
+    }
+}
+
+/// Convenience function to create a FoldMany parser
+pub fn fold_many<'code, P, I, F, A>(parser: P, init: I, fold: F) -> FoldMany<P, I, F>
+where
+    P: Parser<'code>,
+    I: Fn() -> A,
+    F: Fn(A, P::Output) -> A,
+{
+    FoldMany::new(parser, init, fold)
+}
+
+/// Convenience function to create a FoldMany1 parser
+pub fn fold_many1<'code, P, I, F, A>(parser: P, init: I, fold: F) -> FoldMany1<P, I, F>
+where
+    P: Parser<'code>,
+    I: Fn() -> A,
+    F: Fn(A, P::Output) -> A,
+{
+    FoldMany1::new(parser, init, fold)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ByteCursor;
+    use crate::Cursor;
+    use crate::ascii::digit;
+    use crate::byte::is_byte;
+
+    #[test]
+    fn test_fold_many_sums_digits() {
+        let data = b"12345x";
+        let cursor = ByteCursor::new(data);
+        let parser = fold_many(digit(), || 0i64, |acc, d| acc * 10 + (d - b'0') as i64);
+
+        let (value, cursor) = parser.parse(cursor).unwrap();
+        assert_eq!(value, 12345);
+        assert_eq!(cursor.value().unwrap(), b'x');
+    }
+
+    #[test]
+    fn test_fold_many_zero_matches() {
+        let data = b"x";
+        let cursor = ByteCursor::new(data);
+        let parser = fold_many(is_byte(b'a'), String::new, |mut acc, b| {
+            acc.push(b as char);
+            acc
+        });
+
+        let (value, cursor) = parser.parse(cursor).unwrap();
+        assert_eq!(value, "");
+        assert_eq!(cursor.position(), 0);
+
+        // A fresh accumulator is created for every parse
+        let (value, _) = parser.parse(ByteCursor::new(b"aa")).unwrap();
+        assert_eq!(value, "aa");
+        let (value, _) = parser.parse(ByteCursor::new(b"a")).unwrap();
+        assert_eq!(value, "a");
+    }
+
+    #[test]
+    fn test_fold_many1() {
+        let data = b"aaab";
+        let cursor = ByteCursor::new(data);
+        let parser = fold_many1(is_byte(b'a'), || 0, |count, _| count + 1);
+
+        let (count, cursor) = parser.parse(cursor).unwrap();
+        assert_eq!(count, 3);
+        assert_eq!(cursor.value().unwrap(), b'b');
+
+        assert!(parser.parse(ByteCursor::new(b"b")).is_err());
+    }
+
+    #[test]
+    fn test_fold_returns_committed_errors() {
+        use crate::and::AndExt;
+        use crate::cut::CutExt;
+        use crate::error::ErrorNode;
+
+        let item = || is_byte(b'+').and(digit().cut());
+        let count = |acc: usize, _| acc + 1;
+
+        let error = fold_many(item(), || 0, count)
+            .parse(ByteCursor::new(b"+1+x"))
+            .unwrap_err();
+        assert!(error.is_committed());
+        assert_eq!(error.likely_error().loc().position(), 3);
+
+        let error = fold_many1(item(), || 0, count)
+            .parse(ByteCursor::new(b"+1+2+"))
+            .unwrap_err();
+        assert!(error.is_committed());
+        assert_eq!(error.likely_error().loc().position(), 5);
+    }
+}
@@ -0,0 +1,321 @@
+use crate::byte::is_byte;
+use crate::position::{Span, Spanned};
+use crate::separated_list::separated_list;
+use crate::{ByteCursor, CodeLoc, Cursor, Parser, ParsicombError};
+use std::borrow::Cow;
+
+// # CSV and TSV
+//
+// Records are fields separated by a delimiter, and a document is records separated
+// by `\n` or `\r\n`. A field starting with the quote character is quoted: it ends at
+// the next lone quote, may contain delimiters and line breaks, and a doubled quote
+// stands for one quote character. Unquoted fields are taken verbatim.
+//
+// Fields borrow from the input unless they contain doubled quotes.
+
+/// A field with the span it was parsed from, including quotes
+pub type Field<'code> = Spanned<'code, Cow<'code, str>>;
+
+/// Characters that structure a CSV-like document
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dialect {
+    pub delimiter: u8,
+    pub quote: u8,
+}
+
+impl Dialect {
+    /// Comma separated values with `"` quotes
+    pub fn csv() -> Self {
+        Dialect {
+            delimiter: b',',
+            quote: b'"',
+        }
+    }
+
+    /// Tab separated values with `"` quotes
+    pub fn tsv() -> Self {
+        Dialect {
+            delimiter: b'\t',
+            quote: b'"',
+        }
+    }
+
+    /// Use a different field delimiter
+    pub fn with_delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Use a different quote character
+    pub fn with_quote(mut self, quote: u8) -> Self {
+        self.quote = quote;
+        self
+    }
+}
+
+impl Default for Dialect {
+    fn default() -> Self {
+        Self::csv()
+    }
+}
+
+fn field_text(data: &[u8], start: usize, end: usize) -> Result<&str, ParsicombError<'_>> {
+    std::str::from_utf8(&data[start..end]).map_err(|e| ParsicombError::SyntaxError {
+        message: "invalid UTF-8 in field".into(),
+        loc: CodeLoc::new(data, start + e.valid_up_to()),
+    })
+}
+
+/// Parser for a single field, possibly empty
+struct FieldParser {
+    dialect: Dialect,
+}
+
+impl FieldParser {
+    fn quoted<'code>(
+        &self,
+        data: &'code [u8],
+        start: usize,
+    ) -> Result<(Field<'code>, ByteCursor<'code>), ParsicombError<'code>> {
+        let quote = self.dialect.quote;
+        let mut position = start + 1;
+        let mut escaped = false;
+
+        let close = loop {
+            match data.get(position) {
+                None => {
+                    ByteCursor::charge(data, start, data.len())?;
+                    return Err(ParsicombError::SyntaxError {
+                        message: "unterminated quoted field".into(),
+                        loc: CodeLoc::new(data, data.len()),
+                    });
+                }
+                Some(&b) if b == quote && data.get(position + 1) == Some(&quote) => {
+                    escaped = true;
+                    position += 2;
+                }
+                Some(&b) if b == quote => break position,
+                Some(_) => position += 1,
+            }
+        };
+
+        let end = close + 1;
+        match data.get(end) {
+            None | Some(b'\n' | b'\r') => {}
+            Some(&b) if b == self.dialect.delimiter => {}
+            Some(_) => {
+                return Err(ParsicombError::SyntaxError {
+                    message: "expected delimiter or end of record after closing quote".into(),
+                    loc: CodeLoc::new(data, end),
+                });
+            }
+        }
+
+        let text = field_text(data, start + 1, close)?;
+        let text = if escaped {
+            let doubled = String::from_utf8_lossy(&[quote, quote]).into_owned();
+            let single = String::from_utf8_lossy(&[quote]).into_owned();
+            Cow::Owned(text.replace(&doubled, &single))
+        } else {
+            Cow::Borrowed(text)
+        };
+
+        let field = Spanned::new(text, Span::new(data, start, end));
+        Ok((field, ByteCursor::scanned(data, start, end)?))
+    }
+}
+
+impl<'code> Parser<'code> for FieldParser {
+    type Cursor = ByteCursor<'code>;
+    type Output = Field<'code>;
+    type Error = ParsicombError<'code>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (data, start) = cursor.inner();
+        if data.get(start) == Some(&self.dialect.quote) {
+            return self.quoted(data, start);
+        }
+
+        let end = data[start..]
+            .iter()
+            .position(|&b| b == self.dialect.delimiter || b == b'\n' || b == b'\r')
+            .map_or(data.len(), |offset| start + offset);
+        let text = field_text(data, start, end)?;
+
+        let field = Spanned::new(Cow::Borrowed(text), Span::new(data, start, end));
+        Ok((field, ByteCursor::scanned(data, start, end)?))
+    }
+}
+
+/// Parser for one record, stopping before the line terminator
+pub struct RecordParser {
+    dialect: Dialect,
+}
+
+impl RecordParser {
+    pub fn new(dialect: Dialect) -> Self {
+        RecordParser { dialect }
+    }
+}
+
+impl<'code> Parser<'code> for RecordParser {
+    type Cursor = ByteCursor<'code>;
+    type Output = Vec<Field<'code>>;
+    type Error = ParsicombError<'code>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let field = FieldParser {
+            dialect: self.dialect,
+        };
+        separated_list(field, is_byte(self.dialect.delimiter)).parse(cursor)
+    }
+}
+
+/// Parser for a whole document, consuming all input
+///
+/// A line terminator after the last record is optional and empty input has no
+/// records.
+pub struct DocumentParser {
+    dialect: Dialect,
+}
+
+impl DocumentParser {
+    pub fn new(dialect: Dialect) -> Self {
+        DocumentParser { dialect }
+    }
+}
+
+impl<'code> Parser<'code> for DocumentParser {
+    type Cursor = ByteCursor<'code>;
+    type Output = Vec<Vec<Field<'code>>>;
+    type Error = ParsicombError<'code>;
+
+    fn parse(&self, mut cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let record = RecordParser::new(self.dialect);
+        let mut records = Vec::new();
+
+        while !cursor.eos() {
+            let (fields, next_cursor) = record.parse(cursor)?;
+            records.push(fields);
+
+            let (data, position) = next_cursor.inner();
+            cursor = match &data[position..] {
+                [] => next_cursor,
+                [b'\n', ..] => ByteCursor::at(data, position + 1),
+                [b'\r', b'\n', ..] => ByteCursor::at(data, position + 2),
+                _ => {
+                    return Err(ParsicombError::SyntaxError {
+                        message: "expected end of record".into(),
+                        loc: CodeLoc::new(data, position),
+                    });
+                }
+            };
+        }
+
+        Ok((records, cursor))
+    }
+}
+
+/// Creates a parser for one record of the given dialect
+pub fn record(dialect: Dialect) -> RecordParser {
+    RecordParser::new(dialect)
+}
+
+/// Creates a parser for a whole document of the given dialect
+pub fn document(dialect: Dialect) -> DocumentParser {
+    DocumentParser::new(dialect)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorLeaf;
+
+    fn values<'a>(records: &[Vec<Field<'a>>]) -> Vec<Vec<String>> {
+        records
+            .iter()
+            .map(|fields| fields.iter().map(|f| f.value.to_string()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_record() {
+        let data = b"a,,c\nnext";
+        let cursor = ByteCursor::new(data);
+
+        let (fields, cursor) = record(Dialect::csv()).parse(cursor).unwrap();
+        assert_eq!(
+            values(std::slice::from_ref(&fields)),
+            vec![vec!["a", "", "c"]]
+        );
+        assert_eq!(fields[2].span, Span::new(data, 3, 4));
+        assert_eq!(cursor.value().unwrap(), b'\n');
+    }
+
+    #[test]
+    fn test_quoted_fields() {
+        let data = b"\"x, y\",\"say \"\"hi\"\"\",\"line\nbreak\"";
+        let (fields, cursor) = record(Dialect::csv()).parse(ByteCursor::new(data)).unwrap();
+
+        assert_eq!(
+            values(std::slice::from_ref(&fields)),
+            vec![vec!["x, y", "say \"hi\"", "line\nbreak"]]
+        );
+        assert!(matches!(fields[0].value, Cow::Borrowed(_)));
+        assert!(matches!(fields[1].value, Cow::Owned(_)));
+        assert_eq!(fields[0].span.as_string(), "\"x, y\"");
+        assert!(cursor.eos());
+    }
+
+    #[test]
+    fn test_document() {
+        let data = b"name,age\r\nada,36\n\nbob,\"7\"\n";
+        let (records, cursor) = document(Dialect::csv())
+            .parse(ByteCursor::new(data))
+            .unwrap();
+
+        assert_eq!(
+            values(&records),
+            vec![
+                vec!["name", "age"],
+                vec!["ada", "36"],
+                vec![""],
+                vec!["bob", "7"],
+            ]
+        );
+        assert!(cursor.eos());
+
+        let (records, _) = document(Dialect::csv())
+            .parse(ByteCursor::new(b""))
+            .unwrap();
+        assert!(records.is_empty());
+    }
+
+    #[test]
+    fn test_custom_dialects() {
+        let data = b"a\tb,c\n";
+        let (records, _) = document(Dialect::tsv())
+            .parse(ByteCursor::new(data))
+            .unwrap();
+        assert_eq!(values(&records), vec![vec!["a", "b,c"]]);
+
+        let dialect = Dialect::csv().with_delimiter(b';').with_quote(b'\'');
+        let data = b"'a;b';'it''s'";
+        let (records, _) = document(dialect).parse(ByteCursor::new(data)).unwrap();
+        assert_eq!(values(&records), vec![vec!["a;b", "it's"]]);
+    }
+
+    #[test]
+    fn test_quote_errors() {
+        let error = document(Dialect::csv())
+            .parse(ByteCursor::new(b"a,\"open"))
+            .unwrap_err();
+        assert_eq!(error.message(), "unterminated quoted field");
+        assert_eq!(error.loc().position(), 7);
+
+        let error = document(Dialect::csv())
+            .parse(ByteCursor::new(b"\"a\"b,c"))
+            .unwrap_err();
+        assert_eq!(error.loc().position(), 3);
+    }
+}
@@ -0,0 +1,359 @@
+use crate::{ByteCursor, CodeLoc, Cursor, Parser, ParsicombError};
+
+// # RFC 3339 and ISO 8601 Dates
+//
+// Timestamps like `2024-02-29T13:45:00.250+01:00` are fixed-width fields separated by
+// punctuation, so the parsers read the bytes directly. Every field is range checked
+// as soon as it is read, and errors point at the first digit of the offending field,
+// e.g. the `13` of `2024-13-01`.
+//
+// `T` may also be written as `t` or a space and `Z` as `z`, as RFC 3339 allows.
+// Fractional seconds keep up to nanosecond precision, further digits are accepted
+// and dropped.
+
+/// A calendar date
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+}
+
+/// A time of day, `second` is 60 for a leap second
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Time {
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+    pub nanosecond: u32,
+}
+
+/// Offset from UTC of a timestamp
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Offset {
+    /// Written as `Z`
+    Utc,
+    /// Written as `+hh:mm` or `-hh:mm`, in minutes east of UTC
+    Minutes(i16),
+}
+
+impl Offset {
+    /// Minutes east of UTC, 0 for `Z`
+    pub fn minutes(&self) -> i16 {
+        match self {
+            Offset::Utc => 0,
+            Offset::Minutes(minutes) => *minutes,
+        }
+    }
+}
+
+/// A timestamp with date, time and offset as defined by RFC 3339
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DateTime {
+    pub date: Date,
+    pub time: Time,
+    pub offset: Offset,
+}
+
+fn is_leap_year(year: u16) -> bool {
+    year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400))
+}
+
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Reads fields from the input, keeping the position of the next byte
+struct Fields<'code> {
+    data: &'code [u8],
+    position: usize,
+}
+
+impl<'code> Fields<'code> {
+    fn error(&self, position: usize, message: String) -> ParsicombError<'code> {
+        ParsicombError::SyntaxError {
+            message: message.into(),
+            loc: CodeLoc::new(self.data, position),
+        }
+    }
+
+    /// Read exactly `width` digits and check that their value lies in `range`
+    fn number(
+        &mut self,
+        width: usize,
+        name: &str,
+        range: std::ops::RangeInclusive<u32>,
+    ) -> Result<u32, ParsicombError<'code>> {
+        let start = self.position;
+        let mut value = 0;
+        for offset in 0..width {
+            match self.data.get(start + offset) {
+                Some(byte) if byte.is_ascii_digit() => value = value * 10 + (byte - b'0') as u32,
+                _ => {
+                    return Err(
+                        self.error(start + offset, format!("expected {}-digit {}", width, name))
+                    );
+                }
+            }
+        }
+        if !range.contains(&value) {
+            return Err(self.error(
+                start,
+                format!(
+                    "{} out of range: {:0width$}, expected {} to {}",
+                    name,
+                    value,
+                    range.start(),
+                    range.end(),
+                    width = width
+                ),
+            ));
+        }
+        self.position += width;
+        Ok(value)
+    }
+
+    /// Consume one of `accepted`, describing it as `expected` in the error
+    fn separator(&mut self, accepted: &[u8], expected: &str) -> Result<u8, ParsicombError<'code>> {
+        match self.data.get(self.position) {
+            Some(byte) if accepted.contains(byte) => {
+                self.position += 1;
+                Ok(*byte)
+            }
+            _ => Err(self.error(self.position, format!("expected {}", expected))),
+        }
+    }
+
+    fn date(&mut self) -> Result<Date, ParsicombError<'code>> {
+        let year = self.number(4, "year", 0..=9999)? as u16;
+        self.separator(b"-", "'-' after year")?;
+        let month = self.number(2, "month", 1..=12)? as u8;
+        self.separator(b"-", "'-' after month")?;
+
+        let start = self.position;
+        let day = self.number(2, "day", 1..=31)? as u8;
+        let last = days_in_month(year, month);
+        if day > last {
+            return Err(self.error(
+                start,
+                format!(
+                    "day out of range: {:02}, {:04}-{:02} has {} days",
+                    day, year, month, last
+                ),
+            ));
+        }
+        Ok(Date { year, month, day })
+    }
+
+    fn time(&mut self) -> Result<Time, ParsicombError<'code>> {
+        let hour = self.number(2, "hour", 0..=23)? as u8;
+        self.separator(b":", "':' after hour")?;
+        let minute = self.number(2, "minute", 0..=59)? as u8;
+        self.separator(b":", "':' after minute")?;
+        let second = self.number(2, "second", 0..=60)? as u8;
+
+        let mut nanosecond = 0;
+        if self.data.get(self.position) == Some(&b'.') {
+            self.position += 1;
+            let digits = self.data[self.position..]
+                .iter()
+                .take_while(|byte| byte.is_ascii_digit())
+                .count();
+            if digits == 0 {
+                return Err(self.error(self.position, "expected fractional seconds".into()));
+            }
+            for (index, byte) in self.data[self.position..self.position + digits]
+                .iter()
+                .take(9)
+                .enumerate()
+            {
+                nanosecond += (byte - b'0') as u32 * 10u32.pow(8 - index as u32);
+            }
+            self.position += digits;
+        }
+
+        Ok(Time {
+            hour,
+            minute,
+            second,
+            nanosecond,
+        })
+    }
+
+    fn offset(&mut self) -> Result<Offset, ParsicombError<'code>> {
+        let sign = self.separator(b"Zz+-", "'Z' or a '+hh:mm' offset")?;
+        if sign == b'Z' || sign == b'z' {
+            return Ok(Offset::Utc);
+        }
+
+        let hours = self.number(2, "offset hour", 0..=23)? as i16;
+        self.separator(b":", "':' in offset")?;
+        let minutes = self.number(2, "offset minute", 0..=59)? as i16;
+        let total = hours * 60 + minutes;
+        Ok(Offset::Minutes(if sign == b'-' { -total } else { total }))
+    }
+}
+
+/// Parser for an RFC 3339 timestamp such as `1985-04-12T23:20:50.52Z`
+pub struct Rfc3339Parser;
+
+impl<'code> Parser<'code> for Rfc3339Parser {
+    type Cursor = ByteCursor<'code>;
+    type Output = DateTime;
+    type Error = ParsicombError<'code>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (data, position) = cursor.inner();
+        let mut fields = Fields { data, position };
+
+        let date = fields.date()?;
+        fields.separator(b"Tt ", "'T' between date and time")?;
+        let time = fields.time()?;
+        let offset = fields.offset()?;
+
+        Ok((
+            DateTime { date, time, offset },
+            ByteCursor::scanned(data, position, fields.position)?,
+        ))
+    }
+}
+
+/// Parser for an ISO 8601 calendar date in extended format, `YYYY-MM-DD`
+pub struct Iso8601DateParser;
+
+impl<'code> Parser<'code> for Iso8601DateParser {
+    type Cursor = ByteCursor<'code>;
+    type Output = Date;
+    type Error = ParsicombError<'code>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (data, position) = cursor.inner();
+        let mut fields = Fields { data, position };
+        let date = fields.date()?;
+        Ok((date, ByteCursor::scanned(data, position, fields.position)?))
+    }
+}
+
+/// Creates a parser for RFC 3339 timestamps
+pub fn rfc3339() -> Rfc3339Parser {
+    Rfc3339Parser
+}
+
+/// Creates a parser for `YYYY-MM-DD` dates
+pub fn iso8601_date() -> Iso8601DateParser {
+    Iso8601DateParser
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ErrorLeaf;
+
+    fn parse_error(input: &str) -> (usize, String) {
+        let error = rfc3339()
+            .parse(ByteCursor::new(input.as_bytes()))
+            .unwrap_err();
+        (error.loc().position(), error.message().into_owned())
+    }
+
+    #[test]
+    fn test_rfc3339_full() {
+        let data = b"2024-02-29T13:45:07.250+01:30 rest";
+        let (timestamp, cursor) = rfc3339().parse(ByteCursor::new(data)).unwrap();
+
+        assert_eq!(
+            timestamp.date,
+            Date {
+                year: 2024,
+                month: 2,
+                day: 29
+            }
+        );
+        assert_eq!(
+            timestamp.time,
+            Time {
+                hour: 13,
+                minute: 45,
+                second: 7,
+                nanosecond: 250_000_000
+            }
+        );
+        assert_eq!(timestamp.offset, Offset::Minutes(90));
+        assert_eq!(cursor.position(), 29);
+    }
+
+    #[test]
+    fn test_rfc3339_variants() {
+        let (timestamp, cursor) = rfc3339()
+            .parse(ByteCursor::new(b"1990-12-31t23:59:60z"))
+            .unwrap();
+        assert_eq!(timestamp.offset, Offset::Utc);
+        assert_eq!(timestamp.time.second, 60);
+        assert!(cursor.eos());
+
+        let (timestamp, _) = rfc3339()
+            .parse(ByteCursor::new(b"1996-12-19 16:39:57.1234567891-08:00"))
+            .unwrap();
+        assert_eq!(timestamp.time.nanosecond, 123_456_789);
+        assert_eq!(timestamp.offset.minutes(), -480);
+    }
+
+    #[test]
+    fn test_errors_point_at_offending_field() {
+        assert_eq!(
+            parse_error("2024-13-01T00:00:00Z"),
+            (5, "month out of range: 13, expected 1 to 12".to_string())
+        );
+        assert_eq!(
+            parse_error("2023-02-29T00:00:00Z"),
+            (8, "day out of range: 29, 2023-02 has 28 days".to_string())
+        );
+        assert_eq!(
+            parse_error("2024-01-01T24:00:00Z"),
+            (11, "hour out of range: 24, expected 0 to 23".to_string())
+        );
+        assert_eq!(
+            parse_error("2024-01-01T00:0x:00Z"),
+            (15, "expected 2-digit minute".to_string())
+        );
+        assert_eq!(
+            parse_error("2024-01-01T00:00:00"),
+            (19, "expected 'Z' or a '+hh:mm' offset".to_string())
+        );
+        assert_eq!(
+            parse_error("2024-01-01T00:00:00.Z"),
+            (20, "expected fractional seconds".to_string())
+        );
+    }
+
+    #[test]
+    fn test_iso8601_date() {
+        let (date, cursor) = iso8601_date()
+            .parse(ByteCursor::new(b"2000-02-29T"))
+            .unwrap();
+        assert_eq!(
+            date,
+            Date {
+                year: 2000,
+                month: 2,
+                day: 29
+            }
+        );
+        assert_eq!(cursor.position(), 10);
+
+        let error = iso8601_date()
+            .parse(ByteCursor::new(b"1900-02-29"))
+            .unwrap_err();
+        assert_eq!(error.loc().position(), 8);
+        assert!(
+            iso8601_date()
+                .parse(ByteCursor::new(b"2024/01/01"))
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,436 @@
+use crate::ascii::one_of;
+use crate::between::between_padded;
+use crate::byte::{byte, is_byte};
+use crate::choice::choice;
+use crate::eof::eof;
+use crate::error::ErrorNode;
+use crate::filter::FilterExt;
+use crate::map::MapExt;
+use crate::map_err::{MapErrError, MapErrExt};
+use crate::not::followed_by;
+use crate::optional::optional;
+use crate::or::OrExt;
+use crate::position::{PositionExt, Spanned};
+use crate::recognize::RecognizeExt;
+use crate::separated_list::separated_list;
+use crate::seq::seq;
+use crate::skip::skip_many;
+use crate::some::some;
+use crate::utf8::comment::line_comment;
+use crate::utf8::quoted::{EscapePolicy, quoted};
+use crate::utf8::string::is_string;
+use crate::{ByteCursor, CodeLoc, Cursor, Parser, ParsicombError};
+use std::borrow::Cow;
+use std::collections::HashSet;
+
+// # Key-Value Configuration
+//
+// An INI-like format with a little TOML syntax:
+//
+//     # comment
+//     name = parsicomb
+//
+//     [server.http]
+//     host = "localhost"   ; quoted values may contain escapes like \n
+//     root = 'C:\srv'      # single quotes are raw
+//     motd = hello world
+//
+// A document is a sequence of lines. Blank lines and lines starting with `#` or `;`
+// are ignored. `[name]` starts a section and `key = value` adds an entry to the
+// current section, entries before the first header belong to an unnamed root
+// section. Keys and section names are made of ASCII letters, digits, `_`, `-` and
+// `.`.
+//
+// Unquoted values run to the end of the line or to a comment that is preceded by
+// whitespace, surrounding whitespace is trimmed. Double quoted values use the
+// escapes of `utf8::quoted`, single quoted values are taken verbatim.
+//
+// A key may appear only once per section and a section name only once per
+// document, errors point at the repeated occurrence.
+
+/// A value with the span it was parsed from, including quotes
+pub type Value<'code> = Spanned<'code, Cow<'code, str>>;
+
+/// A `key = value` line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry<'code> {
+    pub key: Spanned<'code, &'code str>,
+    pub value: Value<'code>,
+}
+
+/// Entries under a `[name]` header, or before the first header if `name` is `None`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section<'code> {
+    pub name: Option<Spanned<'code, &'code str>>,
+    pub entries: Vec<Entry<'code>>,
+}
+
+impl<'code> Section<'code> {
+    /// The entry for `key`
+    pub fn entry(&self, key: &str) -> Option<&Entry<'code>> {
+        self.entries.iter().find(|entry| entry.key.value == key)
+    }
+
+    /// The value of `key`
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entry(key).map(|entry| entry.value.value.as_ref())
+    }
+}
+
+/// A parsed configuration document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config<'code> {
+    /// All sections in document order, starting with the root section
+    pub sections: Vec<Section<'code>>,
+}
+
+impl<'code> Config<'code> {
+    /// Entries that come before the first section header
+    pub fn root(&self) -> &Section<'code> {
+        &self.sections[0]
+    }
+
+    /// The section with the given name
+    pub fn section(&self, name: &str) -> Option<&Section<'code>> {
+        self.sections
+            .iter()
+            .find(|section| section.name.is_some_and(|n| n.value == name))
+    }
+
+    /// The value of `key` in `section`, use `""` for the root section
+    pub fn get(&self, section: &str, key: &str) -> Option<&str> {
+        if section.is_empty() {
+            return self.root().get(key);
+        }
+        self.section(section)?.get(key)
+    }
+}
+
+fn error(data: &[u8], position: usize, message: String) -> ParsicombError<'_> {
+    ParsicombError::SyntaxError {
+        message: message.into(),
+        loc: CodeLoc::new(data, position),
+    }
+}
+
+/// `parser`, reporting `message` where it failed instead of its own error
+fn expect<'code, P>(
+    parser: P,
+    message: &'static str,
+) -> impl Parser<
+    'code,
+    Cursor = ByteCursor<'code>,
+    Output = P::Output,
+    Error = MapErrError<ParsicombError<'code>>,
+>
+where
+    P: Parser<'code, Cursor = ByteCursor<'code>>,
+    P::Error: ErrorNode<'code, Element = u8>,
+{
+    parser.map_err(move |error| ParsicombError::SyntaxError {
+        message: message.into(),
+        loc: error.likely_error().loc(),
+    })
+}
+
+fn is_blank(byte: u8) -> bool {
+    byte == b' ' || byte == b'\t'
+}
+
+/// Spaces and tabs, never line breaks
+fn blanks<'code>()
+-> impl Parser<'code, Cursor = ByteCursor<'code>, Output = (), Error = ParsicombError<'code>> {
+    skip_many(one_of(b" \t"))
+}
+
+/// A key or section name, which is never empty
+fn name<'code>() -> impl Parser<
+    'code,
+    Cursor = ByteCursor<'code>,
+    Output = Spanned<'code, &'code str>,
+    Error = ParsicombError<'code>,
+> {
+    let name_byte = byte().filter(
+        |&byte| byte.is_ascii_alphanumeric() || matches!(byte, b'_' | b'-' | b'.'),
+        "expected name",
+    );
+    some(name_byte).recognize_str().map_with_span(Spanned::new)
+}
+
+/// `[name]`, returning the name
+fn header<'code>() -> impl Parser<
+    'code,
+    Cursor = ByteCursor<'code>,
+    Output = Spanned<'code, &'code str>,
+    Error = MapErrError<ParsicombError<'code>>,
+> {
+    between_padded(
+        is_byte(b'['),
+        one_of(b" \t"),
+        expect(name(), "expected section name"),
+        expect(is_byte(b']'), "expected ']' after section name"),
+    )
+    .map_err(ParsicombError::wrap)
+}
+
+/// A value that is not quoted, running to the end of the line or to a comment
+///
+/// Only a `#` or `;` after a blank starts a comment. Words are separated by blanks, so
+/// trailing blanks are left for the end of the line and not part of the value.
+fn unquoted<'code>()
+-> impl Parser<'code, Cursor = ByteCursor<'code>, Output = Value<'code>, Error = ParsicombError<'code>>
+{
+    let word = some(byte().filter(
+        |&byte| !is_blank(byte) && byte != b'\n' && byte != b'\r',
+        "expected value",
+    ));
+    let gap = followed_by(
+        some(one_of(b" \t")),
+        byte().filter(
+            |&byte| !is_blank(byte) && !matches!(byte, b'\n' | b'\r' | b'#' | b';'),
+            "expected value",
+        ),
+    );
+
+    optional(separated_list(word, gap))
+        .recognize_str()
+        .map_with_span(|text, span| Spanned::new(Cow::Borrowed(text), span))
+}
+
+/// Parser for a value, quoted or running to the end of the line
+///
+/// The first byte decides the form, so an unterminated quoted value is reported as
+/// such instead of being read as an unquoted one.
+struct ValueParser;
+
+impl<'code> Parser<'code> for ValueParser {
+    type Cursor = ByteCursor<'code>;
+    type Output = Value<'code>;
+    type Error = ParsicombError<'code>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (data, position) = cursor.inner();
+        match data.get(position) {
+            Some(b'"') => quoted("\"", "\"", EscapePolicy::Escape('\\')).parse(cursor),
+            Some(b'\'') => quoted("'", "'", EscapePolicy::Raw).parse(cursor),
+            _ => unquoted().parse(cursor),
+        }
+    }
+}
+
+/// `key = value`
+fn entry<'code>() -> impl Parser<
+    'code,
+    Cursor = ByteCursor<'code>,
+    Output = Entry<'code>,
+    Error = MapErrError<ParsicombError<'code>>,
+> {
+    seq((
+        expect(name(), "expected key or section header"),
+        blanks(),
+        expect(is_byte(b'='), "expected '=' after key"),
+        blanks(),
+        ValueParser,
+    ))
+    .map(|(key, _, _, _, value)| Entry { key, value })
+    .map_err(ParsicombError::wrap)
+}
+
+/// Blanks, an optional comment and the line break or the end of input
+fn end_of_line<'code>() -> impl Parser<
+    'code,
+    Cursor = ByteCursor<'code>,
+    Output = (),
+    Error = MapErrError<ParsicombError<'code>>,
+> {
+    let comment = line_comment("#").or(line_comment(";"));
+    let line_break = choice((
+        eof(),
+        is_byte(b'\n').map(|_| ()),
+        is_string("\r\n").map(|_| ()),
+    ));
+
+    seq((
+        blanks(),
+        optional(comment),
+        expect(line_break, "expected end of line"),
+    ))
+    .map(|_| ())
+    .map_err(ParsicombError::wrap)
+}
+
+/// Parser for a whole key-value configuration document, consuming all input
+pub struct ConfigParser;
+
+impl<'code> Parser<'code> for ConfigParser {
+    type Cursor = ByteCursor<'code>;
+    type Output = Config<'code>;
+    type Error = ParsicombError<'code>;
+
+    fn parse(&self, mut cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (blanks, header, entry, end_of_line) = (blanks(), header(), entry(), end_of_line());
+        let mut sections = vec![Section {
+            name: None,
+            entries: Vec::new(),
+        }];
+        let mut section_names = HashSet::new();
+        let mut keys = HashSet::new();
+
+        while !cursor.eos() {
+            cursor = blanks.parse(cursor)?.1;
+            let (data, position) = cursor.inner();
+            match data.get(position) {
+                None | Some(b'\n' | b'\r' | b'#' | b';') => {}
+                Some(b'[') => {
+                    let (name, next_cursor) = header.parse(cursor).map_err(ParsicombError::wrap)?;
+                    if !section_names.insert(name.value) {
+                        return Err(error(
+                            data,
+                            name.span.start,
+                            format!("duplicate section '{}'", name.value),
+                        ));
+                    }
+                    keys.clear();
+                    sections.push(Section {
+                        name: Some(name),
+                        entries: Vec::new(),
+                    });
+                    cursor = next_cursor;
+                }
+                Some(_) => {
+                    let (entry, next_cursor) = entry.parse(cursor).map_err(ParsicombError::wrap)?;
+                    if !keys.insert(entry.key.value) {
+                        return Err(error(
+                            data,
+                            entry.key.span.start,
+                            format!("duplicate key '{}'", entry.key.value),
+                        ));
+                    }
+                    let section = sections.last_mut().expect("root section always exists");
+                    section.entries.push(entry);
+                    cursor = next_cursor;
+                }
+            }
+            cursor = end_of_line.parse(cursor).map_err(ParsicombError::wrap)?.1;
+        }
+
+        Ok((Config { sections }, cursor))
+    }
+}
+
+/// Creates a parser for key-value configuration documents
+///
+/// Example:
+/// ```
+/// use parsicomb::Parser;
+/// use parsicomb::ByteCursor;
+/// use parsicomb::formats::kv::config;
+///
+/// let data = b"debug = true\n[db]\nurl = \"postgres://localhost\"\n";
+/// let (config, _) = config().parse(ByteCursor::new(data)).unwrap();
+/// assert_eq!(config.get("", "debug"), Some("true"));
+/// assert_eq!(config.get("db", "url"), Some("postgres://localhost"));
+/// ```
+pub fn config() -> ConfigParser {
+    ConfigParser
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ErrorLeaf;
+    use crate::position::Span;
+
+    fn parse(text: &str) -> Config<'_> {
+        let (config, cursor) = config().parse(ByteCursor::new(text.as_bytes())).unwrap();
+        assert!(cursor.eos());
+        config
+    }
+
+    fn parse_error(text: &str) -> (usize, String) {
+        let error = config()
+            .parse(ByteCursor::new(text.as_bytes()))
+            .unwrap_err();
+        (error.loc().position(), error.message().into_owned())
+    }
+
+    #[test]
+    fn test_sections_and_comments() {
+        let config = parse(
+            "# top\nname = demo\n\n; comment\n[server.http]\n  port=8080  \r\n[empty]\n[paths] # trailing\nlog = /var/log # here\n",
+        );
+
+        assert_eq!(config.sections.len(), 4);
+        assert_eq!(config.get("", "name"), Some("demo"));
+        assert_eq!(config.get("server.http", "port"), Some("8080"));
+        assert_eq!(config.get("paths", "log"), Some("/var/log"));
+        assert!(config.section("empty").unwrap().entries.is_empty());
+        assert_eq!(config.get("server.http", "name"), None);
+        assert_eq!(config.get("missing", "name"), None);
+    }
+
+    #[test]
+    fn test_value_forms() {
+        let text = "a = \"x # y\\n\" ; c\nb = 'C:\\dir'\nc = one;two\nd =\ne = \"\"";
+        let config = parse(text);
+        let root = config.root();
+
+        assert_eq!(root.get("a"), Some("x # y\n"));
+        assert_eq!(root.get("b"), Some("C:\\dir"));
+        assert_eq!(root.get("c"), Some("one;two"));
+        assert_eq!(root.get("d"), Some(""));
+        assert_eq!(root.get("e"), Some(""));
+        assert!(matches!(
+            root.entry("c").unwrap().value.value,
+            Cow::Borrowed(_)
+        ));
+    }
+
+    #[test]
+    fn test_spans() {
+        let text = "[s]\nkey = \"v\"";
+        let config = parse(text);
+        let section = config.section("s").unwrap();
+        let entry = section.entry("key").unwrap();
+
+        assert_eq!(section.name.unwrap().span, Span::new(text.as_bytes(), 1, 2));
+        assert_eq!(entry.key.span.start_loc().line(), 2);
+        assert_eq!(entry.value.span.as_string(), "\"v\"");
+    }
+
+    #[test]
+    fn test_keys_are_unique_per_section() {
+        let config = parse("a = 1\n[s]\na = 2\n[t]\na = 3");
+        assert_eq!(config.get("", "a"), Some("1"));
+        assert_eq!(config.get("s", "a"), Some("2"));
+        assert_eq!(config.get("t", "a"), Some("3"));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(parse_error("a b"), (2, "expected '=' after key".into()));
+        assert_eq!(
+            parse_error("= 1"),
+            (0, "expected key or section header".into())
+        );
+        assert_eq!(
+            parse_error("[x"),
+            (2, "expected ']' after section name".into())
+        );
+        assert_eq!(parse_error("[]"), (1, "expected section name".into()));
+        assert_eq!(parse_error("[x] y"), (4, "expected end of line".into()));
+        assert_eq!(
+            parse_error("a = \"1\" 2"),
+            (8, "expected end of line".into())
+        );
+        assert_eq!(parse_error("a = 1\na = 2"), (6, "duplicate key 'a'".into()));
+        assert_eq!(
+            parse_error("[s]\n[t]\n[s]"),
+            (9, "duplicate section 's'".into())
+        );
+
+        let (position, message) = parse_error("a = \"open");
+        assert_eq!(position, 4);
+        assert!(message.contains("before end of input"), "{}", message);
+    }
+}
@@ -0,0 +1,8 @@
+//! Parsers for common data formats built on the crate's combinators
+
+pub mod csv;
+pub mod datetime;
+pub mod kv;
+pub mod net;
+pub mod semver;
+pub mod uri;
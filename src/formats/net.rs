@@ -0,0 +1,413 @@
+use crate::{ByteCursor, CodeLoc, Cursor, Parser, ParsicombError};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+// # IP Addresses and Networks
+//
+// `ipv4()` reads dotted decimal with range checks per octet, `ipv6()` the RFC 4291
+// text forms including `::` compression and an embedded IPv4 tail, `socket_addr()`
+// an address with a port (IPv6 in brackets) and `cidr()` an address with a prefix
+// length. Results are `std::net` types.
+//
+// Like other scanners these read the bytes directly. Errors point at the offending
+// octet, group, port or prefix.
+
+/// An IP network, an address with a prefix length
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cidr {
+    pub address: IpAddr,
+    pub prefix: u8,
+}
+
+fn error(data: &[u8], position: usize, message: String) -> ParsicombError<'_> {
+    ParsicombError::SyntaxError {
+        message: message.into(),
+        loc: CodeLoc::new(data, position),
+    }
+}
+
+/// Read a decimal number of at most `max_digits` digits, `None` if there is no digit
+fn decimal(data: &[u8], start: usize, max_digits: usize) -> Option<(u32, usize)> {
+    let digits = data[start.min(data.len())..]
+        .iter()
+        .take(max_digits)
+        .take_while(|byte| byte.is_ascii_digit())
+        .count();
+    if digits == 0 {
+        return None;
+    }
+    let value = data[start..start + digits]
+        .iter()
+        .fold(0, |value, byte| value * 10 + (byte - b'0') as u32);
+    Some((value, start + digits))
+}
+
+fn parse_ipv4(data: &[u8], start: usize) -> Result<(Ipv4Addr, usize), ParsicombError<'_>> {
+    let mut octets = [0u8; 4];
+    let mut position = start;
+
+    for (index, octet) in octets.iter_mut().enumerate() {
+        if index > 0 {
+            if data.get(position) != Some(&b'.') {
+                return Err(error(data, position, "expected '.' between octets".into()));
+            }
+            position += 1;
+        }
+        let Some((value, end)) = decimal(data, position, 4) else {
+            return Err(error(
+                data,
+                position,
+                format!("expected octet {}", index + 1),
+            ));
+        };
+        if end - position > 1 && data[position] == b'0' {
+            return Err(error(data, position, "octet has a leading zero".into()));
+        }
+        if value > 255 {
+            return Err(error(
+                data,
+                position,
+                format!("octet out of range: {}, expected 0 to 255", value),
+            ));
+        }
+        *octet = value as u8;
+        position = end;
+    }
+
+    Ok((Ipv4Addr::from(octets), position))
+}
+
+/// Read one group of up to four hex digits, `None` if there is no hex digit
+fn hex_group(data: &[u8], start: usize) -> Option<(u16, usize)> {
+    let digits = data[start.min(data.len())..]
+        .iter()
+        .take(5)
+        .take_while(|byte| byte.is_ascii_hexdigit())
+        .count();
+    if digits == 0 || digits > 4 {
+        return None;
+    }
+    let text = std::str::from_utf8(&data[start..start + digits]).ok()?;
+    let value = u16::from_str_radix(text, 16).ok()?;
+    Some((value, start + digits))
+}
+
+/// Whether `rest` starts with an IPv4 address rather than a hex group
+fn ipv4_ahead(rest: &[u8]) -> bool {
+    let digits = rest
+        .iter()
+        .take_while(|byte| byte.is_ascii_hexdigit())
+        .count();
+    digits < 5 && rest.get(digits) == Some(&b'.')
+}
+
+fn parse_ipv6(data: &[u8], start: usize) -> Result<(Ipv6Addr, usize), ParsicombError<'_>> {
+    let rest = |position: usize| &data[position.min(data.len())..];
+    let mut head: Vec<u16> = Vec::new();
+    let mut tail: Vec<u16> = Vec::new();
+    let mut compressed = false;
+    let mut position = start;
+
+    if rest(position).starts_with(b"::") {
+        compressed = true;
+        position += 2;
+    }
+
+    loop {
+        let count = head.len() + tail.len();
+        if count == 8 {
+            break;
+        }
+        let groups = if compressed { &mut tail } else { &mut head };
+
+        // An IPv4 tail takes the place of the last two groups
+        if count <= 6 && ipv4_ahead(rest(position)) {
+            let (ipv4, end) = parse_ipv4(data, position)?;
+            let [a, b, c, d] = ipv4.octets();
+            groups.push(u16::from_be_bytes([a, b]));
+            groups.push(u16::from_be_bytes([c, d]));
+            position = end;
+            break;
+        }
+
+        match hex_group(data, position) {
+            Some((group, end)) => {
+                groups.push(group);
+                position = end;
+            }
+            // The address ends right after `::`
+            None if compressed && groups.is_empty() => break,
+            None => return Err(error(data, position, "expected 1 to 4 hex digits".into())),
+        }
+
+        if rest(position).starts_with(b"::") {
+            if compressed {
+                return Err(error(data, position, "'::' can only appear once".into()));
+            }
+            compressed = true;
+            position += 2;
+        } else if data.get(position) == Some(&b':') && head.len() + tail.len() < 8 {
+            position += 1;
+        } else {
+            break;
+        }
+    }
+
+    let count = head.len() + tail.len();
+    if (compressed && count > 7) || (!compressed && count != 8) {
+        return Err(error(
+            data,
+            start,
+            format!("expected 8 groups in IPv6 address, found {}", count),
+        ));
+    }
+
+    let mut segments = [0u16; 8];
+    segments[..head.len()].copy_from_slice(&head);
+    segments[8 - tail.len()..].copy_from_slice(&tail);
+    Ok((Ipv6Addr::from(segments), position))
+}
+
+fn parse_port(data: &[u8], start: usize) -> Result<(u16, usize), ParsicombError<'_>> {
+    match decimal(data, start, 6) {
+        Some((value, end)) if value <= u16::MAX as u32 => Ok((value as u16, end)),
+        Some((value, _)) => Err(error(
+            data,
+            start,
+            format!("port out of range: {}, expected 0 to 65535", value),
+        )),
+        None => Err(error(data, start, "expected port".into())),
+    }
+}
+
+/// Parser for an IPv4 address in dotted decimal, `192.168.0.1`
+pub struct Ipv4Parser;
+
+impl<'code> Parser<'code> for Ipv4Parser {
+    type Cursor = ByteCursor<'code>;
+    type Output = Ipv4Addr;
+    type Error = ParsicombError<'code>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (data, position) = cursor.inner();
+        let (address, end) = parse_ipv4(data, position)?;
+        Ok((address, ByteCursor::scanned(data, position, end)?))
+    }
+}
+
+/// Parser for an IPv6 address, `2001:db8::1` or `::ffff:10.0.0.1`
+pub struct Ipv6Parser;
+
+impl<'code> Parser<'code> for Ipv6Parser {
+    type Cursor = ByteCursor<'code>;
+    type Output = Ipv6Addr;
+    type Error = ParsicombError<'code>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (data, position) = cursor.inner();
+        let (address, end) = parse_ipv6(data, position)?;
+        Ok((address, ByteCursor::scanned(data, position, end)?))
+    }
+}
+
+/// Parser for an address and port, `10.0.0.1:80` or `[::1]:8080`
+pub struct SocketAddrParser;
+
+impl<'code> Parser<'code> for SocketAddrParser {
+    type Cursor = ByteCursor<'code>;
+    type Output = SocketAddr;
+    type Error = ParsicombError<'code>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (data, position) = cursor.inner();
+
+        let (ip, end) = if data.get(position) == Some(&b'[') {
+            let (address, end) = parse_ipv6(data, position + 1)?;
+            if data.get(end) != Some(&b']') {
+                return Err(error(data, end, "expected ']' after IPv6 address".into()));
+            }
+            (IpAddr::V6(address), end + 1)
+        } else {
+            let (address, end) = parse_ipv4(data, position)?;
+            (IpAddr::V4(address), end)
+        };
+
+        if data.get(end) != Some(&b':') {
+            return Err(error(data, end, "expected ':' before port".into()));
+        }
+        let (port, end) = parse_port(data, end + 1)?;
+
+        Ok((
+            SocketAddr::new(ip, port),
+            ByteCursor::scanned(data, position, end)?,
+        ))
+    }
+}
+
+/// Parser for a network in CIDR notation, `10.0.0.0/8` or `2001:db8::/32`
+pub struct CidrParser;
+
+impl<'code> Parser<'code> for CidrParser {
+    type Cursor = ByteCursor<'code>;
+    type Output = Cidr;
+    type Error = ParsicombError<'code>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (data, position) = cursor.inner();
+
+        // Try IPv4 first, an IPv6 address can't start with a full dotted quad
+        let (address, end) = match parse_ipv4(data, position) {
+            Ok((v4, end)) if data.get(end) != Some(&b':') => (IpAddr::V4(v4), end),
+            _ => {
+                let (v6, end) = parse_ipv6(data, position)?;
+                (IpAddr::V6(v6), end)
+            }
+        };
+
+        if data.get(end) != Some(&b'/') {
+            return Err(error(data, end, "expected '/' before prefix length".into()));
+        }
+        let max = if address.is_ipv4() { 32 } else { 128 };
+        let prefix = match decimal(data, end + 1, 4) {
+            Some((value, prefix_end)) if value <= max => (value as u8, prefix_end),
+            Some((value, _)) => {
+                return Err(error(
+                    data,
+                    end + 1,
+                    format!(
+                        "prefix length out of range: {}, expected 0 to {}",
+                        value, max
+                    ),
+                ));
+            }
+            None => return Err(error(data, end + 1, "expected prefix length".into())),
+        };
+
+        Ok((
+            Cidr {
+                address,
+                prefix: prefix.0,
+            },
+            ByteCursor::scanned(data, position, prefix.1)?,
+        ))
+    }
+}
+
+/// Creates a parser for IPv4 addresses
+pub fn ipv4() -> Ipv4Parser {
+    Ipv4Parser
+}
+
+/// Creates a parser for IPv6 addresses
+pub fn ipv6() -> Ipv6Parser {
+    Ipv6Parser
+}
+
+/// Creates a parser for socket addresses
+pub fn socket_addr() -> SocketAddrParser {
+    SocketAddrParser
+}
+
+/// Creates a parser for networks in CIDR notation
+pub fn cidr() -> CidrParser {
+    CidrParser
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ErrorLeaf;
+
+    fn v6(text: &str) -> Ipv6Addr {
+        let (address, cursor) = ipv6().parse(ByteCursor::new(text.as_bytes())).unwrap();
+        assert!(cursor.eos(), "{} not fully consumed", text);
+        address
+    }
+
+    #[test]
+    fn test_ipv4() {
+        let (address, cursor) = ipv4().parse(ByteCursor::new(b"192.168.0.1 x")).unwrap();
+        assert_eq!(address, Ipv4Addr::new(192, 168, 0, 1));
+        assert_eq!(cursor.position(), 11);
+    }
+
+    #[test]
+    fn test_ipv4_octet_errors() {
+        let error = ipv4().parse(ByteCursor::new(b"10.0.256.1")).unwrap_err();
+        assert_eq!(error.loc().position(), 5);
+        assert_eq!(
+            error.message(),
+            "octet out of range: 256, expected 0 to 255"
+        );
+
+        let error = ipv4().parse(ByteCursor::new(b"10.01.0.1")).unwrap_err();
+        assert_eq!(error.loc().position(), 3);
+
+        let error = ipv4().parse(ByteCursor::new(b"10.0.0")).unwrap_err();
+        assert_eq!(error.loc().position(), 6);
+    }
+
+    #[test]
+    fn test_ipv6_forms() {
+        for text in [
+            "2001:db8:85a3:0:0:8a2e:370:7334",
+            "2001:db8::1",
+            "::1",
+            "::",
+            "fe80::",
+            "::ffff:10.0.0.1",
+            "64:ff9b::192.0.2.33",
+        ] {
+            assert_eq!(v6(text), text.parse::<Ipv6Addr>().unwrap(), "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_ipv6_errors() {
+        assert!(ipv6().parse(ByteCursor::new(b"1::2::3")).is_err());
+        assert!(ipv6().parse(ByteCursor::new(b"1:2:3")).is_err());
+        let (_, cursor) = ipv6().parse(ByteCursor::new(b"1:2:3:4:5:6:7:8:9")).unwrap();
+        assert_eq!(cursor.position(), 15);
+        let error = ipv6().parse(ByteCursor::new(b"12345::")).unwrap_err();
+        assert_eq!(error.loc().position(), 0);
+    }
+
+    #[test]
+    fn test_socket_addr() {
+        let (address, _) = socket_addr()
+            .parse(ByteCursor::new(b"127.0.0.1:8080"))
+            .unwrap();
+        assert_eq!(address, "127.0.0.1:8080".parse().unwrap());
+
+        let (address, _) = socket_addr().parse(ByteCursor::new(b"[::1]:443")).unwrap();
+        assert_eq!(address, "[::1]:443".parse().unwrap());
+
+        let error = socket_addr()
+            .parse(ByteCursor::new(b"127.0.0.1:70000"))
+            .unwrap_err();
+        assert_eq!(error.loc().position(), 10);
+        assert!(error.message().contains("port out of range"));
+    }
+
+    #[test]
+    fn test_cidr() {
+        let (network, _) = cidr().parse(ByteCursor::new(b"10.0.0.0/8")).unwrap();
+        assert_eq!(
+            network,
+            Cidr {
+                address: IpAddr::V4(Ipv4Addr::new(10, 0, 0, 0)),
+                prefix: 8
+            }
+        );
+
+        let (network, _) = cidr().parse(ByteCursor::new(b"2001:db8::/32")).unwrap();
+        assert_eq!(network.address, "2001:db8::".parse::<IpAddr>().unwrap());
+        assert_eq!(network.prefix, 32);
+
+        let error = cidr().parse(ByteCursor::new(b"10.0.0.0/33")).unwrap_err();
+        assert_eq!(error.loc().position(), 9);
+        assert_eq!(
+            error.message(),
+            "prefix length out of range: 33, expected 0 to 32"
+        );
+    }
+}
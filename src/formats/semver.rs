@@ -0,0 +1,721 @@
+use crate::position::Span;
+use crate::{ByteCursor, CodeLoc, Cursor, Parser, ParsicombError};
+use std::cmp::Ordering;
+
+// # Semantic Versions
+//
+// `semver()` reads a version as defined by Semantic Versioning 2.0.0,
+// `MAJOR.MINOR.PATCH[-PRE][+BUILD]`, and `version_req()` a comma separated list of
+// comparators in the syntax Cargo uses, such as `>=1.2, <2.0` or `~0.3`:
+//
+// - `=`, `>`, `>=`, `<`, `<=`, `~` and `^` followed by a version whose minor and
+//   patch components may be left out. A version without an operator means `^`.
+// - `1.*` and `1.2.*` match any version with the given prefix, `*` alone any
+//   version at all.
+//
+// Numeric components must not have leading zeros and must fit in a `u64`. Errors
+// point at the first byte of the offending component.
+
+/// A dot separated identifier of a pre-release
+///
+/// Numeric identifiers sort before alphanumeric ones and compare by value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Identifier<'code> {
+    Numeric(u64),
+    AlphaNumeric(&'code str),
+}
+
+/// A semantic version
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Version<'code> {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    /// Pre-release identifiers, empty for a release
+    pub pre: Vec<Identifier<'code>>,
+    /// Build metadata identifiers, ignored for precedence
+    pub build: Vec<&'code str>,
+    pub span: Span<'code>,
+}
+
+/// Compare pre-releases by precedence, where a release sorts after all pre-releases
+fn compare_pre(a: &[Identifier<'_>], b: &[Identifier<'_>]) -> Ordering {
+    match (a.is_empty(), b.is_empty()) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        (false, false) => a.cmp(b),
+    }
+}
+
+impl<'code> Version<'code> {
+    /// Order two versions by semver precedence, ignoring build metadata
+    pub fn cmp_precedence(&self, other: &Version<'_>) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| compare_pre(&self.pre, &other.pre))
+    }
+}
+
+/// Operator of a [`Comparator`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Op {
+    /// `=1.2.3`
+    Exact,
+    /// `>1.2.3`
+    Greater,
+    /// `>=1.2.3`
+    GreaterEq,
+    /// `<1.2.3`
+    Less,
+    /// `<=1.2.3`
+    LessEq,
+    /// `~1.2.3`, updates to the patch version
+    Tilde,
+    /// `^1.2.3` or `1.2.3`, updates that don't change the leftmost non-zero component
+    Caret,
+    /// `1.*` or `1.2.*`
+    Wildcard,
+}
+
+/// One condition of a [`VersionReq`], missing components are `None`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comparator<'code> {
+    pub op: Op,
+    pub major: u64,
+    pub minor: Option<u64>,
+    pub patch: Option<u64>,
+    pub pre: Vec<Identifier<'code>>,
+    pub span: Span<'code>,
+}
+
+impl<'code> Comparator<'code> {
+    /// Whether `version` satisfies this comparator
+    pub fn matches(&self, version: &Version<'_>) -> bool {
+        match self.op {
+            Op::Exact | Op::Wildcard => self.matches_exact(version),
+            Op::Greater => self.matches_greater(version),
+            Op::GreaterEq => self.matches_exact(version) || self.matches_greater(version),
+            Op::Less => self.matches_less(version),
+            Op::LessEq => self.matches_exact(version) || self.matches_less(version),
+            Op::Tilde => self.matches_tilde(version),
+            Op::Caret => self.matches_caret(version),
+        }
+    }
+
+    fn matches_exact(&self, version: &Version<'_>) -> bool {
+        version.major == self.major
+            && self.minor.is_none_or(|minor| version.minor == minor)
+            && self.patch.is_none_or(|patch| version.patch == patch)
+            && (self.patch.is_none() || version.pre == self.pre)
+    }
+
+    /// Compare the components that are present, `None` if they are all equal
+    fn compare_prefix(&self, version: &Version<'_>) -> Option<Ordering> {
+        let components = [
+            (Some(self.major), version.major),
+            (self.minor, version.minor),
+            (self.patch, version.patch),
+        ];
+        for (bound, value) in components {
+            match bound {
+                Some(bound) if value != bound => return Some(value.cmp(&bound)),
+                Some(_) => {}
+                None => return None,
+            }
+        }
+        Some(compare_pre(&version.pre, &self.pre)).filter(|order| order.is_ne())
+    }
+
+    fn matches_greater(&self, version: &Version<'_>) -> bool {
+        self.compare_prefix(version) == Some(Ordering::Greater)
+    }
+
+    fn matches_less(&self, version: &Version<'_>) -> bool {
+        self.compare_prefix(version) == Some(Ordering::Less)
+    }
+
+    fn matches_tilde(&self, version: &Version<'_>) -> bool {
+        if version.major != self.major {
+            return false;
+        }
+        if let Some(minor) = self.minor
+            && version.minor != minor
+        {
+            return false;
+        }
+        if let Some(patch) = self.patch
+            && version.patch != patch
+        {
+            return version.patch > patch;
+        }
+        compare_pre(&version.pre, &self.pre).is_ge()
+    }
+
+    fn matches_caret(&self, version: &Version<'_>) -> bool {
+        if version.major != self.major {
+            return false;
+        }
+        let Some(minor) = self.minor else {
+            return true;
+        };
+        let Some(patch) = self.patch else {
+            return if self.major > 0 {
+                version.minor >= minor
+            } else {
+                version.minor == minor
+            };
+        };
+
+        if self.major > 0 {
+            if version.minor != minor {
+                return version.minor > minor;
+            }
+            if version.patch != patch {
+                return version.patch > patch;
+            }
+        } else if minor > 0 {
+            if version.minor != minor {
+                return false;
+            }
+            if version.patch != patch {
+                return version.patch > patch;
+            }
+        } else if version.minor != minor || version.patch != patch {
+            return false;
+        }
+        compare_pre(&version.pre, &self.pre).is_ge()
+    }
+
+    /// Whether this comparator names the release that a pre-release belongs to
+    fn allows_pre_of(&self, version: &Version<'_>) -> bool {
+        self.major == version.major
+            && self.minor == Some(version.minor)
+            && self.patch == Some(version.patch)
+            && !self.pre.is_empty()
+    }
+}
+
+/// A set of comparators that must all match, empty for `*`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionReq<'code> {
+    pub comparators: Vec<Comparator<'code>>,
+    pub span: Span<'code>,
+}
+
+impl<'code> VersionReq<'code> {
+    /// Whether `version` satisfies every comparator
+    ///
+    /// As in Cargo, a pre-release only matches if some comparator has a pre-release
+    /// of the same `MAJOR.MINOR.PATCH`, so `>=1.0` does not select `2.0.0-alpha`.
+    pub fn matches(&self, version: &Version<'_>) -> bool {
+        self.comparators
+            .iter()
+            .all(|comparator| comparator.matches(version))
+            && (version.pre.is_empty()
+                || self
+                    .comparators
+                    .iter()
+                    .any(|comparator| comparator.allows_pre_of(version)))
+    }
+}
+
+/// Reads components from the input, keeping the position of the next byte
+struct Components<'code> {
+    data: &'code [u8],
+    position: usize,
+}
+
+impl<'code> Components<'code> {
+    fn error(&self, position: usize, message: String) -> ParsicombError<'code> {
+        ParsicombError::SyntaxError {
+            message: message.into(),
+            loc: CodeLoc::new(self.data, position),
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.data.get(self.position).copied()
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        let found = self.peek() == Some(byte);
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, byte: u8, after: &str) -> Result<(), ParsicombError<'code>> {
+        if self.eat(byte) {
+            return Ok(());
+        }
+        Err(self.error(
+            self.position,
+            format!("expected '{}' after {}", byte as char, after),
+        ))
+    }
+
+    fn skip_spaces(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t')) {
+            self.position += 1;
+        }
+    }
+
+    /// Read a numeric component without leading zeros
+    fn number(&mut self, name: &str) -> Result<u64, ParsicombError<'code>> {
+        let start = self.position;
+        let digits = self.data[start..]
+            .iter()
+            .take_while(|byte| byte.is_ascii_digit())
+            .count();
+        if digits == 0 {
+            return Err(self.error(start, format!("expected {}", name)));
+        }
+        if digits > 1 && self.data[start] == b'0' {
+            return Err(self.error(start, format!("{} has a leading zero", name)));
+        }
+        let text = std::str::from_utf8(&self.data[start..start + digits]).expect("digits");
+        let value = text
+            .parse()
+            .map_err(|_| self.error(start, format!("{} is too large", name)))?;
+        self.position += digits;
+        Ok(value)
+    }
+
+    /// Read a dot separated list of non-empty identifiers made of `[0-9A-Za-z-]`
+    fn identifiers(&mut self, name: &str) -> Result<Vec<&'code str>, ParsicombError<'code>> {
+        let mut identifiers = Vec::new();
+        loop {
+            let start = self.position;
+            let length = self.data[start..]
+                .iter()
+                .take_while(|byte| byte.is_ascii_alphanumeric() || **byte == b'-')
+                .count();
+            if length == 0 {
+                return Err(self.error(start, format!("expected {} identifier", name)));
+            }
+            self.position += length;
+            identifiers.push(std::str::from_utf8(&self.data[start..self.position]).expect("ASCII"));
+            if !self.eat(b'.') {
+                return Ok(identifiers);
+            }
+        }
+    }
+
+    fn pre(&mut self) -> Result<Vec<Identifier<'code>>, ParsicombError<'code>> {
+        let mut start = self.position;
+        let mut pre = Vec::new();
+        for text in self.identifiers("pre-release")? {
+            let identifier = if text.bytes().all(|byte| byte.is_ascii_digit()) {
+                if text.len() > 1 && text.starts_with('0') {
+                    return Err(self.error(
+                        start,
+                        "numeric pre-release identifier has a leading zero".into(),
+                    ));
+                }
+                let value = text.parse().map_err(|_| {
+                    self.error(start, "numeric pre-release identifier is too large".into())
+                })?;
+                Identifier::Numeric(value)
+            } else {
+                Identifier::AlphaNumeric(text)
+            };
+            pre.push(identifier);
+            start += text.len() + 1;
+        }
+        Ok(pre)
+    }
+
+    fn version(&mut self) -> Result<Version<'code>, ParsicombError<'code>> {
+        let start = self.position;
+        let major = self.number("major version")?;
+        self.expect(b'.', "major version")?;
+        let minor = self.number("minor version")?;
+        self.expect(b'.', "minor version")?;
+        let patch = self.number("patch version")?;
+
+        let pre = if self.eat(b'-') {
+            self.pre()?
+        } else {
+            Vec::new()
+        };
+        let build = if self.eat(b'+') {
+            self.identifiers("build")?
+        } else {
+            Vec::new()
+        };
+
+        Ok(Version {
+            major,
+            minor,
+            patch,
+            pre,
+            build,
+            span: Span::new(self.data, start, self.position),
+        })
+    }
+
+    fn op(&mut self) -> Option<Op> {
+        let rest = &self.data[self.position..];
+        let (op, length) = match rest {
+            [b'>', b'=', ..] => (Op::GreaterEq, 2),
+            [b'<', b'=', ..] => (Op::LessEq, 2),
+            [b'>', ..] => (Op::Greater, 1),
+            [b'<', ..] => (Op::Less, 1),
+            [b'=', ..] => (Op::Exact, 1),
+            [b'~', ..] => (Op::Tilde, 1),
+            [b'^', ..] => (Op::Caret, 1),
+            _ => return None,
+        };
+        self.position += length;
+        Some(op)
+    }
+
+    fn wildcard(&mut self) -> bool {
+        self.eat(b'*') || self.eat(b'x') || self.eat(b'X')
+    }
+
+    /// Read a comparator, `None` for a lone `*`
+    fn comparator(&mut self) -> Result<Option<Comparator<'code>>, ParsicombError<'code>> {
+        let start = self.position;
+        if self.wildcard() {
+            return Ok(None);
+        }
+
+        let op = self.op();
+        self.skip_spaces();
+        let major = self.number("major version")?;
+        let mut minor = None;
+        let mut patch = None;
+        let mut wildcard = None;
+
+        if self.eat(b'.') {
+            let position = self.position;
+            if self.wildcard() {
+                wildcard = Some(position);
+                // `1.*.*` is the same as `1.*`
+                if self.eat(b'.') && !self.wildcard() {
+                    return Err(self.error(self.position, "expected '*' after wildcard".into()));
+                }
+            } else {
+                minor = Some(self.number("minor version")?);
+                if self.eat(b'.') {
+                    let position = self.position;
+                    if self.wildcard() {
+                        wildcard = Some(position);
+                    } else {
+                        patch = Some(self.number("patch version")?);
+                    }
+                }
+            }
+        }
+
+        let op = match (op, wildcard) {
+            (None, Some(_)) => Op::Wildcard,
+            (Some(_), Some(position)) => {
+                return Err(self.error(position, "wildcard not allowed after an operator".into()));
+            }
+            (op, None) => op.unwrap_or(Op::Caret),
+        };
+
+        let pre = if self.peek() == Some(b'-') {
+            if patch.is_none() {
+                return Err(self.error(self.position, "pre-release requires a full version".into()));
+            }
+            self.position += 1;
+            self.pre()?
+        } else {
+            Vec::new()
+        };
+
+        Ok(Some(Comparator {
+            op,
+            major,
+            minor,
+            patch,
+            pre,
+            span: Span::new(self.data, start, self.position),
+        }))
+    }
+
+    fn version_req(&mut self) -> Result<VersionReq<'code>, ParsicombError<'code>> {
+        let start = self.position;
+        let mut comparators = Vec::new();
+        loop {
+            if let Some(comparator) = self.comparator()? {
+                comparators.push(comparator);
+            }
+
+            let end = self.position;
+            self.skip_spaces();
+            if !self.eat(b',') {
+                self.position = end;
+                break;
+            }
+            self.skip_spaces();
+        }
+
+        Ok(VersionReq {
+            comparators,
+            span: Span::new(self.data, start, self.position),
+        })
+    }
+}
+
+/// Parser for a semantic version such as `1.0.0-rc.1+build.5`
+pub struct SemverParser;
+
+impl<'code> Parser<'code> for SemverParser {
+    type Cursor = ByteCursor<'code>;
+    type Output = Version<'code>;
+    type Error = ParsicombError<'code>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (data, position) = cursor.inner();
+        let mut components = Components { data, position };
+        let version = components.version()?;
+        Ok((
+            version,
+            ByteCursor::scanned(data, position, components.position)?,
+        ))
+    }
+}
+
+/// Parser for a version requirement such as `>=1.2, <2.0`
+pub struct VersionReqParser;
+
+impl<'code> Parser<'code> for VersionReqParser {
+    type Cursor = ByteCursor<'code>;
+    type Output = VersionReq<'code>;
+    type Error = ParsicombError<'code>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (data, position) = cursor.inner();
+        let mut components = Components { data, position };
+        let req = components.version_req()?;
+        Ok((
+            req,
+            ByteCursor::scanned(data, position, components.position)?,
+        ))
+    }
+}
+
+/// Creates a parser for semantic versions
+///
+/// Example:
+/// ```
+/// use parsicomb::Parser;
+/// use parsicomb::ByteCursor;
+/// use parsicomb::formats::semver::{semver, version_req};
+///
+/// let (version, _) = semver().parse(ByteCursor::new(b"1.4.0-beta.2")).unwrap();
+/// assert_eq!((version.major, version.minor, version.patch), (1, 4, 0));
+///
+/// let (req, _) = version_req().parse(ByteCursor::new(b">=1.2, <2.0")).unwrap();
+/// assert!(!req.matches(&version));
+///
+/// let (release, _) = semver().parse(ByteCursor::new(b"1.4.0")).unwrap();
+/// assert!(req.matches(&release));
+/// ```
+pub fn semver() -> SemverParser {
+    SemverParser
+}
+
+/// Creates a parser for version requirements
+pub fn version_req() -> VersionReqParser {
+    VersionReqParser
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ErrorLeaf;
+
+    fn version(text: &str) -> Version<'_> {
+        let (version, cursor) = semver().parse(ByteCursor::new(text.as_bytes())).unwrap();
+        assert!(cursor.eos(), "{} not fully consumed", text);
+        version
+    }
+
+    fn req(text: &str) -> VersionReq<'_> {
+        let (req, cursor) = version_req()
+            .parse(ByteCursor::new(text.as_bytes()))
+            .unwrap();
+        assert!(cursor.eos(), "{} not fully consumed", text);
+        req
+    }
+
+    fn matches(requirement: &str, candidate: &str) -> bool {
+        req(requirement).matches(&version(candidate))
+    }
+
+    fn semver_error(text: &str) -> (usize, String) {
+        let error = semver()
+            .parse(ByteCursor::new(text.as_bytes()))
+            .unwrap_err();
+        (error.loc().position(), error.message().into_owned())
+    }
+
+    fn req_error(text: &str) -> (usize, String) {
+        let error = version_req()
+            .parse(ByteCursor::new(text.as_bytes()))
+            .unwrap_err();
+        (error.loc().position(), error.message().into_owned())
+    }
+
+    #[test]
+    fn test_semver_components() {
+        let parsed = version("1.20.300-rc.1.x-y+build.007");
+        assert_eq!((parsed.major, parsed.minor, parsed.patch), (1, 20, 300));
+        assert_eq!(
+            parsed.pre,
+            [
+                Identifier::AlphaNumeric("rc"),
+                Identifier::Numeric(1),
+                Identifier::AlphaNumeric("x-y"),
+            ]
+        );
+        assert_eq!(parsed.build, ["build", "007"]);
+
+        let (parsed, cursor) = semver().parse(ByteCursor::new(b"0.1.0 next")).unwrap();
+        assert_eq!(parsed.span.as_string(), "0.1.0");
+        assert_eq!(cursor.position(), 5);
+    }
+
+    #[test]
+    fn test_semver_precedence() {
+        let ordered = [
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-alpha.beta",
+            "1.0.0-beta",
+            "1.0.0-beta.2",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "1.0.0",
+            "1.0.1",
+            "1.10.0",
+        ];
+        for pair in ordered.windows(2) {
+            assert_eq!(
+                version(pair[0]).cmp_precedence(&version(pair[1])),
+                Ordering::Less,
+                "{} < {}",
+                pair[0],
+                pair[1]
+            );
+        }
+        assert_eq!(
+            version("1.0.0+a").cmp_precedence(&version("1.0.0+b")),
+            Ordering::Equal
+        );
+    }
+
+    #[test]
+    fn test_semver_errors() {
+        assert_eq!(
+            semver_error("1.2"),
+            (3, "expected '.' after minor version".into())
+        );
+        assert_eq!(semver_error("1..2"), (2, "expected minor version".into()));
+        assert_eq!(
+            semver_error("01.2.3"),
+            (0, "major version has a leading zero".into())
+        );
+        assert_eq!(
+            semver_error("1.2.99999999999999999999"),
+            (4, "patch version is too large".into())
+        );
+        assert_eq!(
+            semver_error("1.2.3-a..b"),
+            (8, "expected pre-release identifier".into())
+        );
+        assert_eq!(
+            semver_error("1.2.3-a.01"),
+            (
+                8,
+                "numeric pre-release identifier has a leading zero".into()
+            )
+        );
+        assert_eq!(
+            semver_error("1.2.3+"),
+            (6, "expected build identifier".into())
+        );
+    }
+
+    #[test]
+    fn test_version_req_structure() {
+        let parsed = req(">= 1.2, <2.0.0-rc.1 ,1.*");
+        let ops: Vec<_> = parsed.comparators.iter().map(|c| c.op).collect();
+        assert_eq!(ops, [Op::GreaterEq, Op::Less, Op::Wildcard]);
+        assert_eq!(parsed.comparators[0].minor, Some(2));
+        assert_eq!(parsed.comparators[0].patch, None);
+        assert_eq!(
+            parsed.comparators[1].pre,
+            [Identifier::AlphaNumeric("rc"), Identifier::Numeric(1)]
+        );
+        assert_eq!(parsed.comparators[2].span.as_string(), "1.*");
+
+        assert!(req("*").comparators.is_empty());
+
+        let (parsed, cursor) = version_req().parse(ByteCursor::new(b"^1 ;")).unwrap();
+        assert_eq!(parsed.comparators[0].op, Op::Caret);
+        assert_eq!(cursor.position(), 2);
+    }
+
+    #[test]
+    fn test_version_req_matching() {
+        assert!(matches("1.2.3", "1.9.0"));
+        assert!(!matches("1.2.3", "2.0.0"));
+        assert!(!matches("1.2.3", "1.2.2"));
+        assert!(matches("^0.2.3", "0.2.9"));
+        assert!(!matches("^0.2.3", "0.3.0"));
+        assert!(matches("^0.0.3", "0.0.3"));
+        assert!(!matches("^0.0.3", "0.0.4"));
+        assert!(matches("~1.2", "1.2.7"));
+        assert!(!matches("~1.2", "1.3.0"));
+        assert!(matches("~1", "1.9.0"));
+        assert!(matches(">=1.2, <2.0", "1.5.0"));
+        assert!(!matches(">=1.2, <2.0", "2.0.0"));
+        assert!(matches("<=1.2", "1.2.9"));
+        assert!(!matches(">1.2", "1.2.9"));
+        assert!(matches(">1.2", "1.3.0"));
+        assert!(matches("=1.2", "1.2.5"));
+        assert!(matches("1.2.*", "1.2.0"));
+        assert!(!matches("1.*", "2.0.0"));
+        assert!(matches("*", "3.1.4"));
+    }
+
+    #[test]
+    fn test_version_req_pre_releases() {
+        assert!(!matches(">=1.0", "2.0.0-alpha"));
+        assert!(!matches("*", "1.0.0-rc.1"));
+        assert!(matches(">=1.0.0-alpha", "1.0.0-beta"));
+        assert!(!matches(">=1.0.0-beta", "1.0.0-alpha"));
+        assert!(!matches(">=1.0.0-alpha", "1.0.1-beta"));
+        assert!(matches("^1.0.0-rc.1", "1.0.0"));
+    }
+
+    #[test]
+    fn test_version_req_errors() {
+        assert_eq!(req_error(""), (0, "expected major version".into()));
+        assert_eq!(req_error(">=1.2,"), (6, "expected major version".into()));
+        assert_eq!(
+            req_error(">=1.*"),
+            (4, "wildcard not allowed after an operator".into())
+        );
+        assert_eq!(
+            req_error("~1.2-beta"),
+            (4, "pre-release requires a full version".into())
+        );
+        assert_eq!(
+            req_error("1.*.3"),
+            (4, "expected '*' after wildcard".into())
+        );
+        assert_eq!(
+            req_error("<1.02"),
+            (3, "minor version has a leading zero".into())
+        );
+    }
+}
@@ -0,0 +1,381 @@
+use super::net::ipv6;
+use crate::position::Span;
+use crate::{ByteCursor, CodeLoc, Cursor, Parser, ParsicombError};
+
+// # URIs
+//
+// `uri()` reads the generic syntax of RFC 3986:
+//
+//     scheme ":" [ "//" [ userinfo "@" ] host [ ":" port ] ] path [ "?" query ] [ "#" fragment ]
+//
+// Every component is returned as a slice of the input, still percent-encoded, so
+// the parser never allocates. Scheme specific rules, such as which schemes need an
+// authority, are left to the caller.
+//
+// A component ends at the first byte it may not contain, so the URI in
+// `<https://example.com/a>` ends before the `>`. Malformed percent-encodings and
+// unterminated IP literals are errors pointing at their first byte.
+
+/// The authority of a URI, the part after `//`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Authority<'code> {
+    pub userinfo: Option<&'code str>,
+    /// Registered name, IPv4 address, or IP literal including its brackets
+    pub host: &'code str,
+    /// The digits after `:`, possibly empty
+    pub port: Option<&'code str>,
+}
+
+/// A URI split into its components
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Uri<'code> {
+    pub scheme: &'code str,
+    pub authority: Option<Authority<'code>>,
+    /// Possibly empty, starts with `/` when there is an authority
+    pub path: &'code str,
+    pub query: Option<&'code str>,
+    pub fragment: Option<&'code str>,
+    pub span: Span<'code>,
+}
+
+fn is_unreserved(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~')
+}
+
+fn is_sub_delim(byte: u8) -> bool {
+    matches!(
+        byte,
+        b'!' | b'$' | b'&' | b'\'' | b'(' | b')' | b'*' | b'+' | b',' | b';' | b'='
+    )
+}
+
+fn is_pchar(byte: u8) -> bool {
+    is_unreserved(byte) || is_sub_delim(byte) || byte == b':' || byte == b'@'
+}
+
+/// Reads components from the input, keeping the position of the next byte
+struct Components<'code> {
+    data: &'code [u8],
+    position: usize,
+}
+
+impl<'code> Components<'code> {
+    fn error(&self, position: usize, message: &'static str) -> ParsicombError<'code> {
+        ParsicombError::SyntaxError {
+            message: message.into(),
+            loc: CodeLoc::new(self.data, position),
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.data.get(self.position).copied()
+    }
+
+    fn text(&self, start: usize) -> &'code str {
+        std::str::from_utf8(&self.data[start..self.position]).expect("URIs are ASCII")
+    }
+
+    /// Consume bytes accepted by `allowed` and percent-encoded bytes
+    fn scan(&mut self, allowed: impl Fn(u8) -> bool) -> Result<&'code str, ParsicombError<'code>> {
+        let start = self.position;
+        while let Some(byte) = self.peek() {
+            if byte == b'%' {
+                let digits = self.data.get(self.position + 1..self.position + 3);
+                if !digits.is_some_and(|digits| digits.iter().all(u8::is_ascii_hexdigit)) {
+                    return Err(self.error(self.position, "invalid percent-encoding"));
+                }
+                self.position += 3;
+            } else if allowed(byte) {
+                self.position += 1;
+            } else {
+                break;
+            }
+        }
+        Ok(self.text(start))
+    }
+
+    fn scheme(&mut self) -> Result<&'code str, ParsicombError<'code>> {
+        let start = self.position;
+        if !self.peek().is_some_and(|byte| byte.is_ascii_alphabetic()) {
+            return Err(self.error(start, "expected scheme"));
+        }
+        while self
+            .peek()
+            .is_some_and(|byte| byte.is_ascii_alphanumeric() || matches!(byte, b'+' | b'-' | b'.'))
+        {
+            self.position += 1;
+        }
+        let scheme = self.text(start);
+        if self.peek() != Some(b':') {
+            return Err(self.error(self.position, "expected ':' after scheme"));
+        }
+        self.position += 1;
+        Ok(scheme)
+    }
+
+    fn ip_literal(&mut self) -> Result<&'code str, ParsicombError<'code>> {
+        let start = self.position;
+        self.position += 1;
+
+        if matches!(self.peek(), Some(b'v' | b'V')) {
+            // IPvFuture: "v" 1*HEXDIG "." 1*( unreserved / sub-delims / ":" )
+            self.position += 1;
+            let digits = self.data[self.position..]
+                .iter()
+                .take_while(|byte| byte.is_ascii_hexdigit())
+                .count();
+            if digits == 0 {
+                return Err(self.error(self.position, "expected IP literal version"));
+            }
+            self.position += digits;
+            if self.peek() != Some(b'.') {
+                return Err(self.error(self.position, "expected '.' after IP literal version"));
+            }
+            self.position += 1;
+            let address = self.data[self.position..]
+                .iter()
+                .take_while(|&&byte| is_unreserved(byte) || is_sub_delim(byte) || byte == b':')
+                .count();
+            if address == 0 {
+                return Err(self.error(self.position, "expected IP literal address"));
+            }
+            self.position += address;
+        } else {
+            let (_, cursor) = ipv6().parse(ByteCursor::at(self.data, self.position))?;
+            self.position = cursor.position();
+        }
+
+        if self.peek() != Some(b']') {
+            return Err(self.error(self.position, "expected ']' after IP literal"));
+        }
+        self.position += 1;
+        Ok(self.text(start))
+    }
+
+    fn authority(&mut self) -> Result<Authority<'code>, ParsicombError<'code>> {
+        // userinfo can't contain '@', so the first '@' of the authority ends it
+        let length = self.data[self.position..]
+            .iter()
+            .take_while(|&&byte| is_pchar(byte) || matches!(byte, b'%' | b'[' | b']'))
+            .count();
+        let userinfo = if self.data[self.position..self.position + length].contains(&b'@') {
+            let userinfo =
+                self.scan(|byte| is_unreserved(byte) || is_sub_delim(byte) || byte == b':')?;
+            if self.peek() != Some(b'@') {
+                return Err(self.error(self.position, "invalid character in userinfo"));
+            }
+            self.position += 1;
+            Some(userinfo)
+        } else {
+            None
+        };
+
+        let host = if self.peek() == Some(b'[') {
+            self.ip_literal()?
+        } else {
+            self.scan(|byte| is_unreserved(byte) || is_sub_delim(byte))?
+        };
+
+        let port = if self.peek() == Some(b':') {
+            self.position += 1;
+            let start = self.position;
+            while self.peek().is_some_and(|byte| byte.is_ascii_digit()) {
+                self.position += 1;
+            }
+            Some(self.text(start))
+        } else {
+            None
+        };
+
+        Ok(Authority {
+            userinfo,
+            host,
+            port,
+        })
+    }
+
+    fn uri(&mut self) -> Result<Uri<'code>, ParsicombError<'code>> {
+        let start = self.position;
+        let scheme = self.scheme()?;
+
+        let authority = if self.data[self.position..].starts_with(b"//") {
+            self.position += 2;
+            Some(self.authority()?)
+        } else {
+            None
+        };
+
+        let path = self.scan(|byte| is_pchar(byte) || byte == b'/')?;
+        let mut optional = |marker: u8| -> Result<Option<&'code str>, ParsicombError<'code>> {
+            if self.peek() != Some(marker) {
+                return Ok(None);
+            }
+            self.position += 1;
+            self.scan(|byte| is_pchar(byte) || byte == b'/' || byte == b'?')
+                .map(Some)
+        };
+        let query = optional(b'?')?;
+        let fragment = optional(b'#')?;
+
+        Ok(Uri {
+            scheme,
+            authority,
+            path,
+            query,
+            fragment,
+            span: Span::new(self.data, start, self.position),
+        })
+    }
+}
+
+/// Parser for an absolute URI such as `https://user@example.com:8080/a/b?q=1#top`
+pub struct UriParser;
+
+impl<'code> Parser<'code> for UriParser {
+    type Cursor = ByteCursor<'code>;
+    type Output = Uri<'code>;
+    type Error = ParsicombError<'code>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (data, position) = cursor.inner();
+        let mut components = Components { data, position };
+        let uri = components.uri()?;
+        Ok((
+            uri,
+            ByteCursor::scanned(data, position, components.position)?,
+        ))
+    }
+}
+
+/// Creates a parser for URIs
+///
+/// Example:
+/// ```
+/// use parsicomb::Parser;
+/// use parsicomb::ByteCursor;
+/// use parsicomb::formats::uri::uri;
+///
+/// let (parsed, _) = uri()
+///     .parse(ByteCursor::new(b"https://example.com:8443/docs?page=2"))
+///     .unwrap();
+/// let authority = parsed.authority.unwrap();
+/// assert_eq!(parsed.scheme, "https");
+/// assert_eq!((authority.host, authority.port), ("example.com", Some("8443")));
+/// assert_eq!(parsed.path, "/docs");
+/// assert_eq!(parsed.query, Some("page=2"));
+/// ```
+pub fn uri() -> UriParser {
+    UriParser
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ErrorLeaf;
+
+    fn parse(text: &str) -> Uri<'_> {
+        let (parsed, cursor) = uri().parse(ByteCursor::new(text.as_bytes())).unwrap();
+        assert!(cursor.eos(), "{} not fully consumed", text);
+        parsed
+    }
+
+    fn parse_error(text: &str) -> (usize, String) {
+        let error = uri().parse(ByteCursor::new(text.as_bytes())).unwrap_err();
+        (error.loc().position(), error.message().into_owned())
+    }
+
+    #[test]
+    fn test_all_components() {
+        let parsed =
+            parse("foo+bar://us%20er:pw@example.com:8042/over/there;x=1?name=ferret&q=?/#nose/");
+        assert_eq!(parsed.scheme, "foo+bar");
+        assert_eq!(
+            parsed.authority,
+            Some(Authority {
+                userinfo: Some("us%20er:pw"),
+                host: "example.com",
+                port: Some("8042"),
+            })
+        );
+        assert_eq!(parsed.path, "/over/there;x=1");
+        assert_eq!(parsed.query, Some("name=ferret&q=?/"));
+        assert_eq!(parsed.fragment, Some("nose/"));
+    }
+
+    #[test]
+    fn test_without_authority() {
+        let parsed = parse("mailto:John.Doe@example.com");
+        assert_eq!(parsed.authority, None);
+        assert_eq!(parsed.path, "John.Doe@example.com");
+
+        let parsed = parse("urn:oasis:names:specification:docbook:dtd:xml:4.1.2");
+        assert_eq!(
+            parsed.path,
+            "oasis:names:specification:docbook:dtd:xml:4.1.2"
+        );
+
+        let parsed = parse("news:");
+        assert_eq!(
+            (parsed.path, parsed.query, parsed.fragment),
+            ("", None, None)
+        );
+    }
+
+    #[test]
+    fn test_hosts() {
+        let host = |text| parse(text).authority.unwrap().host;
+        assert_eq!(
+            host("ldap://[2001:db8::7]/c=GB?objectClass?one"),
+            "[2001:db8::7]"
+        );
+        assert_eq!(host("telnet://192.0.2.16:80/"), "192.0.2.16");
+        assert_eq!(host("x://[v1.fe80::a+en1]"), "[v1.fe80::a+en1]");
+        assert_eq!(host("file:///etc/hosts"), "");
+
+        let parsed = parse("http://a:");
+        assert_eq!(parsed.authority.unwrap().port, Some(""));
+    }
+
+    #[test]
+    fn test_stops_at_invalid_byte() {
+        let data = b"<https://example.com/a b>";
+        let (parsed, cursor) = uri().parse(ByteCursor::at(data, 1)).unwrap();
+        assert_eq!(parsed.span.as_string(), "https://example.com/a");
+        assert_eq!(cursor.value().unwrap(), b' ');
+
+        // An '@' after the URI is not taken for the end of userinfo
+        let (parsed, _) = uri()
+            .parse(ByteCursor::new(b"http://example.com mail@example.com"))
+            .unwrap();
+        assert_eq!(parsed.authority.unwrap().userinfo, None);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(parse_error("//host"), (0, "expected scheme".into()));
+        assert_eq!(
+            parse_error("http//"),
+            (4, "expected ':' after scheme".into())
+        );
+        assert_eq!(
+            parse_error("http://a/%2x"),
+            (9, "invalid percent-encoding".into())
+        );
+        assert_eq!(
+            parse_error("http://a?%"),
+            (9, "invalid percent-encoding".into())
+        );
+        assert_eq!(
+            parse_error("http://[::1/"),
+            (11, "expected ']' after IP literal".into())
+        );
+        assert_eq!(
+            parse_error("http://[v.x]"),
+            (9, "expected IP literal version".into())
+        );
+        assert_eq!(
+            parse_error("http://a[b@c"),
+            (8, "invalid character in userinfo".into())
+        );
+    }
+}
@@ -0,0 +1,232 @@
+//! # Fuzzing Entry Points
+//!
+//! Helpers for `cargo fuzz` targets. [`check_no_panic`] runs a parser over
+//! arbitrary bytes and panics, so that the fuzzer records a crash, when the parser
+//! misbehaves instead of merely rejecting the input:
+//!
+//! - the parser or the rendering of its error panics,
+//! - the parse runs out of fuel, which a budget proportional to the input length
+//!   only allows for runaway backtracking or loops that keep reading,
+//! - the parser claims to have consumed more input than it was given, or reports
+//!   an error outside of the input.
+//!
+//! Hitting the recursion limit counts as an ordinary rejection, since deeply nested
+//! input is expected to fail that way.
+//!
+//! Harness templates for `cargo fuzz` live in the `fuzz/` directory of the
+//! repository, and [`write_corpus`] seeds a corpus directory with known inputs.
+
+use crate::ByteCursor;
+use crate::cursor::Cursor;
+use crate::error::ErrorNode;
+use crate::limits::{Limit, run_limited};
+use crate::parser::Parser;
+use crate::report::report;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Limits applied by [`check_no_panic_with`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FuzzConfig {
+    /// Fuel allowed per input byte
+    pub fuel_per_byte: usize,
+    /// Fuel allowed on top of the per-byte budget
+    pub base_fuel: usize,
+    /// Maximum nesting of `lazy_limited` rules
+    pub recursion_limit: usize,
+}
+
+impl Default for FuzzConfig {
+    fn default() -> Self {
+        FuzzConfig {
+            fuel_per_byte: 64,
+            base_fuel: 4096,
+            recursion_limit: 256,
+        }
+    }
+}
+
+impl FuzzConfig {
+    /// Total fuel for an input of `length` bytes
+    pub fn fuel_for(&self, length: usize) -> usize {
+        length
+            .saturating_mul(self.fuel_per_byte)
+            .saturating_add(self.base_fuel)
+    }
+}
+
+/// How a parser handled a fuzz input that did not reveal a bug
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FuzzOutcome {
+    /// The parser succeeded after consuming this many bytes
+    Parsed { consumed: usize },
+    /// The parser rejected the input with an error at this position
+    Rejected { position: usize },
+    /// The input nested deeper than the recursion limit
+    TooDeep { position: usize },
+}
+
+/// Run `parser` over `data` with the default [`FuzzConfig`], panicking on bugs
+///
+/// Example, as the body of a `fuzz_target!`:
+/// ```
+/// use parsicomb::ascii::i64;
+/// use parsicomb::fuzz::{FuzzOutcome, check_no_panic};
+///
+/// let data: &[u8] = b"-12x";
+/// assert_eq!(check_no_panic(&i64(), data), FuzzOutcome::Parsed { consumed: 3 });
+/// ```
+#[track_caller]
+pub fn check_no_panic<'code, P>(parser: &P, data: &'code [u8]) -> FuzzOutcome
+where
+    P: Parser<'code, Cursor = ByteCursor<'code>>,
+{
+    check_no_panic_with(parser, data, &FuzzConfig::default())
+}
+
+/// Run `parser` over `data` with the given limits, panicking on bugs
+#[track_caller]
+pub fn check_no_panic_with<'code, P>(
+    parser: &P,
+    data: &'code [u8],
+    config: &FuzzConfig,
+) -> FuzzOutcome
+where
+    P: Parser<'code, Cursor = ByteCursor<'code>>,
+{
+    let (result, aborted) =
+        run_limited(config.fuel_for(data.len()), config.recursion_limit, || {
+            parser.parse(ByteCursor::new(data))
+        });
+
+    match aborted {
+        Some((Limit::Fuel, position)) => panic!(
+            "parser ran out of fuel at byte {} of {}, it may loop or backtrack exponentially: {:?}",
+            position,
+            data.len(),
+            String::from_utf8_lossy(data)
+        ),
+        Some((_, position)) => return FuzzOutcome::TooDeep { position },
+        None => {}
+    }
+
+    match result {
+        Ok((_, cursor)) => {
+            let consumed = cursor.position();
+            assert!(
+                consumed <= data.len(),
+                "parser consumed {} bytes of a {} byte input",
+                consumed,
+                data.len()
+            );
+            FuzzOutcome::Parsed { consumed }
+        }
+        Err(error) => {
+            let position = error.likely_error().loc().position();
+            assert!(
+                position <= data.len(),
+                "error at byte {} of a {} byte input",
+                position,
+                data.len()
+            );
+
+            // Rendering must not panic either, whatever the input looks like
+            let _ = error.to_string();
+            let _ = report(&error).to_string();
+            FuzzOutcome::Rejected { position }
+        }
+    }
+}
+
+/// Write each seed input to its own file in `dir`, creating the directory
+///
+/// Files are named after the index of the seed, so rerunning with the same seeds
+/// overwrites instead of duplicating them.
+pub fn write_corpus<I, S>(dir: impl AsRef<Path>, seeds: I) -> io::Result<usize>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<[u8]>,
+{
+    let dir = dir.as_ref();
+    fs::create_dir_all(dir)?;
+    let mut count = 0;
+    for (index, seed) in seeds.into_iter().enumerate() {
+        fs::write(dir.join(format!("seed-{:04}", index)), seed.as_ref())?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::and::AndExt;
+    use crate::ascii::i64;
+    use crate::byte::is_byte;
+    use crate::lazy::lazy_limited;
+    use crate::map::MapExt;
+    use crate::or::OrExt;
+
+    fn nested<'code>() -> impl Parser<'code, Cursor = ByteCursor<'code>, Output = usize> {
+        lazy_limited(|| {
+            is_byte(b'[')
+                .and(nested())
+                .map(|(_, depth)| depth + 1)
+                .or(is_byte(b'x').map(|_| 0))
+        })
+    }
+
+    // Every level tries the same rule twice, so parsing takes 2^n steps
+    fn blowup<'code>() -> impl Parser<'code, Cursor = ByteCursor<'code>, Output = usize> {
+        lazy_limited(|| {
+            is_byte(b'a')
+                .and(blowup())
+                .and(is_byte(b'!'))
+                .map(|((_, n), _)| n + 1)
+                .or(is_byte(b'a').and(blowup()).map(|(_, n)| n + 1))
+                .or(is_byte(b'b').map(|_| 0))
+        })
+    }
+
+    #[test]
+    fn test_outcomes() {
+        assert_eq!(
+            check_no_panic(&i64(), b"42;"),
+            FuzzOutcome::Parsed { consumed: 2 }
+        );
+        assert_eq!(
+            check_no_panic(&i64(), b"\xff\x00"),
+            FuzzOutcome::Rejected { position: 0 }
+        );
+    }
+
+    #[test]
+    fn test_deep_nesting_is_not_a_bug() {
+        let data = vec![b'['; 1000];
+        assert!(matches!(
+            check_no_panic(&nested(), &data),
+            FuzzOutcome::TooDeep { .. }
+        ));
+        assert_eq!(
+            check_no_panic(&nested(), b"[[x"),
+            FuzzOutcome::Parsed { consumed: 3 }
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ran out of fuel")]
+    fn test_runaway_parser_is_reported() {
+        let data = [b'a'; 40];
+        check_no_panic(&blowup(), &data);
+    }
+
+    #[test]
+    fn test_write_corpus() {
+        let dir = std::env::temp_dir().join(format!("parsicomb-corpus-{}", std::process::id()));
+        let written = write_corpus(&dir, [&b"1"[..], b"[[x]]", b""]).unwrap();
+        assert_eq!(written, 3);
+        assert_eq!(fs::read(dir.join("seed-0001")).unwrap(), b"[[x]]");
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
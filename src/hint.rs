@@ -0,0 +1,206 @@
+use crate::atomic::Atomic;
+use crate::cursor::Cursor;
+use crate::describe::Description;
+use crate::error::{ErrorLeaf, ErrorNode};
+use crate::parser::Parser;
+use std::borrow::Cow;
+use std::fmt;
+
+/// Error type for Hinted parser pairing an error with a suggestion for fixing it
+pub struct HintError<E> {
+    inner: E,
+    hint: Cow<'static, str>,
+}
+
+impl<E> HintError<E> {
+    /// The error produced by the wrapped parser
+    pub fn inner(&self) -> &E {
+        &self.inner
+    }
+
+    /// The hint attached to the error
+    pub fn hint(&self) -> &str {
+        &self.hint
+    }
+}
+
+impl<E: fmt::Display> fmt::Debug for HintError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HintError")
+            .field("inner", &self.inner.to_string())
+            .field("hint", &self.hint)
+            .finish()
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for HintError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let inner = self.inner.to_string();
+        write!(f, "{}", inner)?;
+        // Errors with source context end with a newline, put the hint below it
+        if !inner.ends_with('\n') {
+            writeln!(f)?;
+        }
+        write!(f, "help: {}", self.hint)
+    }
+}
+
+impl<E: fmt::Display> std::error::Error for HintError<E> {}
+
+impl<'code, E, T> ErrorNode<'code> for HintError<E>
+where
+    E: ErrorNode<'code, Element = T>,
+    T: Atomic + 'code,
+{
+    type Element = T;
+
+    fn likely_error(&self) -> &dyn ErrorLeaf<'code, Element = T> {
+        self.inner.likely_error()
+    }
+
+    fn furthest_leaves(&self) -> Vec<&dyn ErrorLeaf<'code, Element = T>> {
+        self.inner.furthest_leaves()
+    }
+
+    fn children(&self) -> Vec<&dyn ErrorNode<'code, Element = T>> {
+        vec![&self.inner]
+    }
+
+    fn hints(&self) -> Vec<Cow<'static, str>> {
+        let mut hints = self.inner.hints();
+        hints.push(self.hint.clone());
+        hints
+    }
+}
+
+/// Parser combinator that attaches a hint to the errors of a parser
+///
+/// The hint is shown as a `help:` line by the error's `Display` and by
+/// [`Report`](crate::report::Report). Combinators wrapping the error keep the hint
+/// reachable through [`ErrorNode::hints`] as long as the hinted error is the one
+/// that got furthest.
+///
+/// Example:
+/// ```
+/// use parsicomb::and::AndExt;
+/// use parsicomb::ascii::i64;
+/// use parsicomb::hint::HintExt;
+/// use parsicomb::report::report;
+/// use parsicomb::utf8::string::is_string;
+/// use parsicomb::{ByteCursor, ErrorNode, Parser};
+///
+/// let statement = i64().and(is_string(";").with_hint("did you forget a semicolon?"));
+///
+/// let error = statement.parse(ByteCursor::new(b"42")).unwrap_err();
+/// assert_eq!(error.hints(), ["did you forget a semicolon?"]);
+/// assert!(report(&error).to_string().ends_with("= help: did you forget a semicolon?\n"));
+/// ```
+pub struct Hinted<P> {
+    parser: P,
+    hint: Cow<'static, str>,
+}
+
+impl<P> Hinted<P> {
+    pub fn new(parser: P, hint: impl Into<Cow<'static, str>>) -> Self {
+        Hinted {
+            parser,
+            hint: hint.into(),
+        }
+    }
+}
+
+impl<'code, P> Parser<'code> for Hinted<P>
+where
+    P: Parser<'code>,
+    <P::Cursor as Cursor<'code>>::Element: Atomic + 'code,
+{
+    type Cursor = P::Cursor;
+    type Output = P::Output;
+    type Error = HintError<P::Error>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        self.parser.parse(cursor).map_err(|inner| HintError {
+            inner,
+            hint: self.hint.clone(),
+        })
+    }
+
+    fn describe(&self) -> Description {
+        self.parser.describe()
+    }
+}
+
+/// Extension trait to add .with_hint() method support for parsers
+pub trait HintExt<'code>: Parser<'code> + Sized {
+    fn with_hint(self, hint: impl Into<Cow<'static, str>>) -> Hinted<Self> {
+        Hinted::new(self, hint)
+    }
+}
+
+/// Implement HintExt for all parsers
+impl<'code, P> HintExt<'code> for P where P: Parser<'code> {}
+
+/// Convenience function to create a Hinted parser
+pub fn with_hint<'code, P>(parser: P, hint: impl Into<Cow<'static, str>>) -> Hinted<P>
+where
+    P: Parser<'code>,
+{
+    Hinted::new(parser, hint)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ByteCursor;
+    use crate::and::AndExt;
+    use crate::ascii::i64;
+    use crate::byte::is_byte;
+    use crate::map::MapExt;
+    use crate::or::OrExt;
+    use crate::utf8::string::is_string;
+
+    #[test]
+    fn test_hint_display_adds_help_line() {
+        let parser = is_byte(b';').with_hint("statements end with ';'");
+
+        let error = parser.parse(ByteCursor::new(b"x")).unwrap_err();
+        assert_eq!(error.hint(), "statements end with ';'");
+        assert!(
+            error
+                .to_string()
+                .ends_with("^--- here\nhelp: statements end with ';'")
+        );
+    }
+
+    #[test]
+    fn test_hint_found_through_combinators() {
+        let parser = i64()
+            .and(is_string("=").with_hint("use '=' to assign"))
+            .and(i64().with_hint("expected a value"));
+
+        let error = parser.parse(ByteCursor::new(b"1 2")).unwrap_err();
+        assert_eq!(error.hints(), ["use '=' to assign"]);
+
+        let error = parser.parse(ByteCursor::new(b"1=x")).unwrap_err();
+        assert_eq!(error.hints(), ["expected a value"]);
+    }
+
+    #[test]
+    fn test_hint_not_reported_for_other_branch() {
+        let parser = is_string("let")
+            .with_hint("declarations start with 'let'")
+            .or(is_string("12").and(is_string(";")).map(|(n, _)| n));
+
+        // The second branch gets further, so the hint of the first does not apply
+        let error = parser.parse(ByteCursor::new(b"12!")).unwrap_err();
+        assert!(error.hints().is_empty());
+    }
+
+    #[test]
+    fn test_nested_hints_outermost_last() {
+        let parser = is_byte(b'a').with_hint("inner").with_hint("outer");
+
+        let error = parser.parse(ByteCursor::new(b"b")).unwrap_err();
+        assert_eq!(error.hints(), ["inner", "outer"]);
+    }
+}
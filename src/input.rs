@@ -0,0 +1,206 @@
+use crate::cursors::ByteCursor;
+use crate::source_map::{SourceId, SourceMap};
+use std::io;
+use std::path::Path;
+
+// # Input Loading
+//
+// Files written by real editors don't always start with the text: Windows tools like
+// to prepend a byte order mark, and some write UTF-16. The parsers of this crate read
+// UTF-8 bytes and would see the UTF-8 BOM as a zero-width no-break space, and UTF-16
+// as garbage. `input::load(path)` reads a file and normalizes it:
+//
+// - A UTF-8 BOM (`EF BB BF`) is stripped.
+// - Text with a UTF-16 BOM (`FF FE` or `FE FF`) is transcoded to UTF-8 without the
+//   BOM. Unpaired surrogates are an `io::ErrorKind::InvalidData` error.
+// - Anything else is kept as is, invalid UTF-8 is left for the parsers to report.
+//
+// Positions in errors are offsets into the normalized buffer, so for transcoded files
+// they are UTF-8 offsets, not offsets into the file. Lines and columns, which is what
+// `SourceMap` and `Report` show, are the same either way.
+
+/// The encoding an [`InputBuffer`] was detected as
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    /// UTF-8 without a byte order mark, or bytes in no particular encoding
+    Utf8,
+    /// UTF-8 with a byte order mark, which was stripped
+    Utf8Bom,
+    /// UTF-16 little endian, transcoded to UTF-8
+    Utf16Le,
+    /// UTF-16 big endian, transcoded to UTF-8
+    Utf16Be,
+}
+
+impl Encoding {
+    /// Detect the encoding from the byte order mark, returning it and the length of the mark
+    pub fn detect(bytes: &[u8]) -> (Encoding, usize) {
+        match bytes {
+            [0xEF, 0xBB, 0xBF, ..] => (Encoding::Utf8Bom, 3),
+            [0xFF, 0xFE, ..] => (Encoding::Utf16Le, 2),
+            [0xFE, 0xFF, ..] => (Encoding::Utf16Be, 2),
+            _ => (Encoding::Utf8, 0),
+        }
+    }
+}
+
+/// A named source normalized to UTF-8 bytes, ready to be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputBuffer {
+    name: String,
+    data: Vec<u8>,
+    encoding: Encoding,
+}
+
+impl InputBuffer {
+    /// Normalize the contents of a source, see the module documentation
+    pub fn from_bytes(name: impl Into<String>, mut bytes: Vec<u8>) -> io::Result<Self> {
+        let (encoding, bom) = Encoding::detect(&bytes);
+        let data = match encoding {
+            Encoding::Utf8 => bytes,
+            Encoding::Utf8Bom => {
+                bytes.drain(..bom);
+                bytes
+            }
+            Encoding::Utf16Le => transcode_utf16(&bytes[bom..], u16::from_le_bytes)?,
+            Encoding::Utf16Be => transcode_utf16(&bytes[bom..], u16::from_be_bytes)?,
+        };
+        Ok(InputBuffer {
+            name: name.into(),
+            data,
+            encoding,
+        })
+    }
+
+    /// The name of the source, the path for loaded files
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The normalized UTF-8 bytes
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// The encoding detected when the source was loaded
+    pub fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    /// A cursor at the start of the normalized bytes
+    pub fn cursor(&self) -> ByteCursor<'_> {
+        ByteCursor::new(&self.data)
+    }
+
+    /// Register the buffer in `sources` under its name
+    pub fn register<'code>(&'code self, sources: &mut SourceMap<'code>) -> SourceId {
+        sources.add(self.name.clone(), &self.data)
+    }
+}
+
+/// Decode UTF-16 code units read with `unit` into UTF-8
+fn transcode_utf16(bytes: &[u8], unit: fn([u8; 2]) -> u16) -> io::Result<Vec<u8>> {
+    if !bytes.len().is_multiple_of(2) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "UTF-16 input has an odd number of bytes",
+        ));
+    }
+    let units = bytes.chunks_exact(2).map(|pair| unit([pair[0], pair[1]]));
+    let text = char::decode_utf16(units)
+        .collect::<Result<String, _>>()
+        .map_err(|error| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid UTF-16 input: {}", error),
+            )
+        })?;
+    Ok(text.into_bytes())
+}
+
+/// Read a file and normalize it to UTF-8, named after its path
+///
+/// Example:
+/// ```no_run
+/// use parsicomb::input;
+/// use parsicomb::source_map::SourceMap;
+///
+/// let input = input::load("config.ini")?;
+/// let mut sources = SourceMap::new();
+/// let id = input.register(&mut sources);
+/// let cursor = input.cursor();
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn load(path: impl AsRef<Path>) -> io::Result<InputBuffer> {
+    let path = path.as_ref();
+    InputBuffer::from_bytes(path.display().to_string(), std::fs::read(path)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::byte::is_byte;
+    use crate::{Cursor, Parser};
+
+    fn utf16(text: &str, bom: [u8; 2], unit: fn(u16) -> [u8; 2]) -> Vec<u8> {
+        let mut bytes = bom.to_vec();
+        bytes.extend(text.encode_utf16().flat_map(unit));
+        bytes
+    }
+
+    #[test]
+    fn test_detects_and_strips_bom() {
+        let cases: [(Vec<u8>, Encoding); 4] = [
+            (b"a = 1\n".to_vec(), Encoding::Utf8),
+            (b"\xEF\xBB\xBFa = 1\n".to_vec(), Encoding::Utf8Bom),
+            (
+                utf16("a = 1\n", [0xFF, 0xFE], u16::to_le_bytes),
+                Encoding::Utf16Le,
+            ),
+            (
+                utf16("a = 1\n", [0xFE, 0xFF], u16::to_be_bytes),
+                Encoding::Utf16Be,
+            ),
+        ];
+        for (bytes, encoding) in cases {
+            let input = InputBuffer::from_bytes("test", bytes).unwrap();
+            assert_eq!(input.encoding(), encoding);
+            assert_eq!(input.data(), b"a = 1\n");
+        }
+    }
+
+    #[test]
+    fn test_transcodes_non_ascii() {
+        let bytes = utf16("π → 𝄞", [0xFF, 0xFE], u16::to_le_bytes);
+        let input = InputBuffer::from_bytes("test", bytes).unwrap();
+        assert_eq!(input.data(), "π → 𝄞".as_bytes());
+    }
+
+    #[test]
+    fn test_invalid_utf16() {
+        let error = InputBuffer::from_bytes("test", vec![0xFF, 0xFE, b'a']).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+        // An unpaired high surrogate
+        let error = InputBuffer::from_bytes("test", vec![0xFF, 0xFE, 0x00, 0xD8]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_load_registers_and_parses() {
+        let path = std::env::temp_dir().join(format!("parsicomb-input-{}.txt", std::process::id()));
+        std::fs::write(&path, b"\xEF\xBB\xBF;").unwrap();
+        let input = load(&path);
+        std::fs::remove_file(&path).unwrap();
+        let input = input.unwrap();
+
+        let mut sources = SourceMap::new();
+        let id = input.register(&mut sources);
+        assert_eq!(sources.name(id), Some(path.display().to_string().as_str()));
+
+        let (byte, cursor) = is_byte(b';').parse(input.cursor()).unwrap();
+        assert_eq!((byte, cursor.eos()), (b';', true));
+
+        assert!(load(path.with_extension("missing")).is_err());
+    }
+}
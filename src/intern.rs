@@ -0,0 +1,167 @@
+use super::parser::Parser;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Identifier for a string stored in an `Interner`
+///
+/// Symbols are small `Copy` values, so comparing and hashing them is much cheaper
+/// than comparing the strings they stand for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Symbol(u32);
+
+impl Symbol {
+    /// Position of the symbol in interning order, starting at 0
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Minimal string interner mapping each distinct string to one `Symbol`
+///
+/// Every distinct string is allocated once. Interning goes through a shared
+/// reference so that parsers can intern while they run.
+#[derive(Debug, Default)]
+pub struct Interner {
+    symbols: RefCell<HashMap<Rc<str>, Symbol>>,
+    strings: RefCell<Vec<Rc<str>>>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the symbol for `text`, adding it if it has not been seen before
+    pub fn intern(&self, text: &str) -> Symbol {
+        if let Some(&symbol) = self.symbols.borrow().get(text) {
+            return symbol;
+        }
+
+        let mut strings = self.strings.borrow_mut();
+        let symbol = Symbol(u32::try_from(strings.len()).expect("more than u32::MAX symbols"));
+        let text: Rc<str> = text.into();
+        strings.push(Rc::clone(&text));
+        self.symbols.borrow_mut().insert(text, symbol);
+        symbol
+    }
+
+    /// Symbol for `text` if it has been interned
+    pub fn get(&self, text: &str) -> Option<Symbol> {
+        self.symbols.borrow().get(text).copied()
+    }
+
+    /// The string a symbol stands for, if the symbol came from this interner
+    pub fn resolve(&self, symbol: Symbol) -> Option<Rc<str>> {
+        self.strings.borrow().get(symbol.index()).cloned()
+    }
+
+    /// Number of distinct strings interned
+    pub fn len(&self) -> usize {
+        self.strings.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Parser combinator that interns the matched text and returns its `Symbol`
+pub struct Intern<'i, P> {
+    parser: P,
+    interner: &'i Interner,
+}
+
+impl<'i, P> Intern<'i, P> {
+    pub fn new(parser: P, interner: &'i Interner) -> Self {
+        Intern { parser, interner }
+    }
+}
+
+impl<'code, 'i, P> Parser<'code> for Intern<'i, P>
+where
+    P: Parser<'code>,
+    P::Output: AsRef<str>,
+{
+    type Cursor = P::Cursor;
+    type Output = Symbol;
+    type Error = P::Error;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (text, cursor) = self.parser.parse(cursor)?;
+        Ok((self.interner.intern(text.as_ref()), cursor))
+    }
+}
+
+/// Convenience function to create an Intern parser
+pub fn intern<'code, P>(parser: P, interner: &Interner) -> Intern<'_, P>
+where
+    P: Parser<'code>,
+    P::Output: AsRef<str>,
+{
+    Intern::new(parser, interner)
+}
+
+/// Extension trait to add .intern() method support for parsers
+pub trait InternExt<'code>: Parser<'code> + Sized {
+    fn intern(self, interner: &Interner) -> Intern<'_, Self>
+    where
+        Self::Output: AsRef<str>,
+    {
+        Intern::new(self, interner)
+    }
+}
+
+/// Implement InternExt for all parsers
+impl<'code, P> InternExt<'code> for P where P: Parser<'code> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ByteCursor;
+    use crate::and::AndExt;
+    use crate::ascii::{take_ident, take_whitespace};
+    use crate::many::many;
+    use crate::map::MapExt;
+
+    #[test]
+    fn test_interner() {
+        let interner = Interner::new();
+        let a = interner.intern("alpha");
+        let b = interner.intern("beta");
+
+        assert_eq!(interner.intern("alpha"), a);
+        assert_ne!(a, b);
+        assert_eq!(b.index(), 1);
+        assert_eq!(interner.get("beta"), Some(b));
+        assert_eq!(interner.get("gamma"), None);
+        assert_eq!(interner.resolve(a).as_deref(), Some("alpha"));
+        assert_eq!(interner.len(), 2);
+    }
+
+    #[test]
+    fn test_intern_identifiers() {
+        let interner = Interner::new();
+        let word = take_ident()
+            .intern(&interner)
+            .and(take_whitespace())
+            .map(|(s, _)| s);
+
+        let data = b"x y x z y";
+        let (symbols, _) = many(word).parse(ByteCursor::new(data)).unwrap();
+
+        assert_eq!(symbols[0], symbols[2]);
+        assert_eq!(symbols[1], symbols[4]);
+        assert_eq!(interner.len(), 3);
+        assert_eq!(interner.resolve(symbols[3]).as_deref(), Some("z"));
+    }
+
+    #[test]
+    fn test_intern_cow_output() {
+        let interner = Interner::new();
+        let keyword = intern(crate::utf8::string::is_string("fn"), &interner);
+
+        let (symbol, _) = keyword.parse(ByteCursor::new(b"fn")).unwrap();
+        assert_eq!(interner.get("fn"), Some(symbol));
+    }
+}
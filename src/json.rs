@@ -0,0 +1,559 @@
+//! # JSON
+//!
+//! A complete JSON parser built from the crate's own combinators, available with the
+//! `json` feature. Every value carries the [`Span`] it was parsed from, and strings
+//! without escapes borrow from the input.
+//!
+//! [`parse`] rejects the whole document on the first error. [`parse_recovering`]
+//! keeps going instead: a broken array element or object value is replaced by
+//! [`JsonValue::Invalid`] covering the skipped input, and all errors are returned.
+//! Wrap [`value`] in `with_recursion_limit` when parsing untrusted, deeply nested input.
+//!
+//! ```
+//! use parsicomb::json::{self, JsonValue};
+//!
+//! let document = json::parse(br#"{"name": "parsicomb", "tags": ["parser", "combinator"]}"#)
+//!     .unwrap();
+//! let JsonValue::Object(members) = &document.value else { panic!() };
+//! assert_eq!(members[0].0.value, "name");
+//! assert_eq!(members[1].1.span.as_string(), r#"["parser", "combinator"]"#);
+//! ```
+
+use crate::and::AndExt;
+use crate::ascii::{digit, take_whitespace};
+use crate::balanced::skip_string;
+use crate::byte::{between_bytes, is_byte};
+use crate::choice::choice;
+use crate::eof::ThenEofExt;
+use crate::lazy::lazy_limited;
+use crate::many::many;
+use crate::map::MapExt;
+use crate::map_err::{MapErrError, MapErrExt};
+use crate::optional::optional;
+use crate::or::OrExt;
+use crate::position::{PositionExt, Span, Spanned};
+use crate::recognize::RecognizeExt;
+use crate::separated_list::separated_list;
+use crate::seq::seq;
+use crate::some::some;
+use crate::utf8::string::is_string;
+use crate::{ByteCursor, CodeLoc, Cursor, Parser, ParsicombError};
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// A JSON value, nested values carry their spans
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonValue<'code> {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(Cow<'code, str>),
+    Array(Vec<Spanned<'code, JsonValue<'code>>>),
+    /// Members in source order, duplicate keys are kept
+    Object(Vec<Member<'code>>),
+    /// Input skipped by [`parse_recovering`] after an error
+    Invalid,
+}
+
+/// A key and value of a JSON object
+pub type Member<'code> = (
+    Spanned<'code, Cow<'code, str>>,
+    Spanned<'code, JsonValue<'code>>,
+);
+
+/// Errors collected while recovering, `None` when parsing without recovery
+type Sink<'code> = Option<Rc<RefCell<Vec<ParsicombError<'code>>>>>;
+
+/// Parser for a JSON value surrounded by optional whitespace
+pub fn value<'code>() -> impl Parser<
+    'code,
+    Cursor = ByteCursor<'code>,
+    Output = Spanned<'code, JsonValue<'code>>,
+    Error = ParsicombError<'code>,
+> {
+    value_with(None)
+}
+
+/// Parse a complete JSON document, failing on the first error
+pub fn parse(source: &[u8]) -> Result<Spanned<'_, JsonValue<'_>>, ParsicombError<'_>> {
+    let (document, _) = value().then_eof().parse(ByteCursor::new(source))?;
+    Ok(document)
+}
+
+/// Parse a JSON document, recovering from errors in array elements and object values
+///
+/// Returns the document if its outer structure could be parsed, together with all
+/// errors in source order.
+pub fn parse_recovering(
+    source: &[u8],
+) -> (Option<Spanned<'_, JsonValue<'_>>>, Vec<ParsicombError<'_>>) {
+    let errors = Rc::new(RefCell::new(Vec::new()));
+    let result = value_with(Some(errors.clone()))
+        .then_eof()
+        .parse(ByteCursor::new(source));
+
+    let document = match result {
+        Ok((document, _)) => Some(document),
+        Err(error) => {
+            errors.borrow_mut().push(error);
+            None
+        }
+    };
+
+    let mut errors = errors.take();
+    errors.sort_by_key(|error| error.position());
+    (document, errors)
+}
+
+fn value_with<'code>(
+    sink: Sink<'code>,
+) -> impl Parser<
+    'code,
+    Cursor = ByteCursor<'code>,
+    Output = Spanned<'code, JsonValue<'code>>,
+    Error = ParsicombError<'code>,
+> {
+    lazy_limited(move || {
+        let value = choice((
+            keyword("null", JsonValue::Null),
+            keyword("true", JsonValue::Bool(true)),
+            keyword("false", JsonValue::Bool(false)),
+            number().map(JsonValue::Number),
+            JsonString.map(JsonValue::String),
+            array(sink.clone()).map(JsonValue::Array),
+            object(sink.clone()).map(JsonValue::Object),
+        ))
+        .map_with_span(Spanned::new);
+
+        take_whitespace()
+            .and(value)
+            .and(take_whitespace())
+            .map(|((_, value), _)| value)
+    })
+}
+
+fn keyword<'code>(
+    text: &'static str,
+    value: JsonValue<'static>,
+) -> impl Parser<
+    'code,
+    Cursor = ByteCursor<'code>,
+    Output = JsonValue<'code>,
+    Error = ParsicombError<'code>,
+> {
+    is_string(text).map(move |_| value.clone())
+}
+
+/// `-?(0|[1-9][0-9]*)(\.[0-9]+)?([eE][+-]?[0-9]+)?`
+fn number<'code>()
+-> impl Parser<'code, Cursor = ByteCursor<'code>, Output = f64, Error = ParsicombError<'code>> {
+    let integer = is_byte(b'0')
+        .map(|_| ())
+        .or(between_bytes(b'1', b'9').and(many(digit())).map(|_| ()));
+    let fraction = is_byte(b'.').and(some(digit()));
+    let exponent = is_byte(b'e')
+        .or(is_byte(b'E'))
+        .and(optional(is_byte(b'+').or(is_byte(b'-'))))
+        .and(some(digit()));
+
+    seq((
+        optional(is_byte(b'-')),
+        integer,
+        optional(fraction),
+        optional(exponent),
+    ))
+    .recognize_str()
+    // The grammar only admits valid float syntax, huge exponents become infinity
+    .map(|text| text.parse().unwrap_or(f64::NAN))
+}
+
+fn array<'code>(
+    sink: Sink<'code>,
+) -> impl Parser<
+    'code,
+    Cursor = ByteCursor<'code>,
+    Output = Vec<Spanned<'code, JsonValue<'code>>>,
+    Error = MapErrError<ParsicombError<'code>>,
+> {
+    let empty = take_whitespace().and(is_string("]")).map(|_| Vec::new());
+    let elements = separated_list(Recover::new(value_with(sink.clone()), sink), is_string(","))
+        .and(is_string("]"))
+        .map(|(elements, _)| elements);
+
+    is_string("[")
+        .and(empty.or(elements))
+        .map(|(_, elements)| elements)
+        .map_err(ParsicombError::wrap)
+}
+
+fn object<'code>(
+    sink: Sink<'code>,
+) -> impl Parser<
+    'code,
+    Cursor = ByteCursor<'code>,
+    Output = Vec<Member<'code>>,
+    Error = MapErrError<ParsicombError<'code>>,
+> {
+    let key = take_whitespace()
+        .and(JsonString.map_with_span(Spanned::new))
+        .and(take_whitespace())
+        .map(|((_, key), _)| key);
+    let member = key
+        .and(is_string(":"))
+        .and(Recover::new(value_with(sink.clone()), sink))
+        .map(|((key, _), value)| (key, value));
+
+    let empty = take_whitespace().and(is_string("}")).map(|_| Vec::new());
+    let members = separated_list(member, is_string(","))
+        .and(is_string("}"))
+        .map(|(members, _)| members);
+
+    is_string("{")
+        .and(empty.or(members))
+        .map(|(_, members)| members)
+        .map_err(ParsicombError::wrap)
+}
+
+/// Parser for a JSON string literal, decoding escapes
+struct JsonString;
+
+impl JsonString {
+    fn error<'code>(
+        data: &'code [u8],
+        position: usize,
+        message: &'static str,
+    ) -> ParsicombError<'code> {
+        ParsicombError::SyntaxError {
+            message: message.into(),
+            loc: CodeLoc::new(data, position),
+        }
+    }
+
+    /// Decode the unescaped input between `start` and `end`, failing at the first bad byte
+    fn text(data: &[u8], start: usize, end: usize) -> Result<&str, ParsicombError<'_>> {
+        std::str::from_utf8(&data[start..end])
+            .map_err(|e| Self::error(data, start + e.valid_up_to(), "invalid UTF-8 in string"))
+    }
+
+    /// Decode the 4 hex digits of a `\u` escape starting at `position`
+    fn hex4(data: &[u8], position: usize) -> Option<u32> {
+        let digits = std::str::from_utf8(data.get(position..position + 4)?).ok()?;
+        if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+        u32::from_str_radix(digits, 16).ok()
+    }
+
+    /// Decode the escape at `position`, returning the character and the escape length
+    fn escape(data: &[u8], position: usize) -> Result<(char, usize), ParsicombError<'_>> {
+        let simple = match data.get(position + 1) {
+            Some(b'"') => '"',
+            Some(b'\\') => '\\',
+            Some(b'/') => '/',
+            Some(b'b') => '\u{8}',
+            Some(b'f') => '\u{c}',
+            Some(b'n') => '\n',
+            Some(b'r') => '\r',
+            Some(b't') => '\t',
+            Some(b'u') => return Self::unicode_escape(data, position),
+            _ => return Err(Self::error(data, position, "invalid escape sequence")),
+        };
+        Ok((simple, 2))
+    }
+
+    fn unicode_escape(data: &[u8], position: usize) -> Result<(char, usize), ParsicombError<'_>> {
+        let invalid = || Self::error(data, position, "invalid unicode escape");
+        let high = Self::hex4(data, position + 2).ok_or_else(invalid)?;
+
+        if !(0xD800..0xDC00).contains(&high) {
+            return char::from_u32(high).map(|c| (c, 6)).ok_or_else(invalid);
+        }
+
+        // A high surrogate must be followed by an escaped low surrogate
+        if data.get(position + 6..position + 8) != Some(b"\\u") {
+            return Err(invalid());
+        }
+        let low = Self::hex4(data, position + 8).ok_or_else(invalid)?;
+        if !(0xDC00..0xE000).contains(&low) {
+            return Err(invalid());
+        }
+        let combined = 0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00);
+        char::from_u32(combined)
+            .map(|c| (c, 12))
+            .ok_or_else(invalid)
+    }
+}
+
+impl<'code> Parser<'code> for JsonString {
+    type Cursor = ByteCursor<'code>;
+    type Output = Cow<'code, str>;
+    type Error = ParsicombError<'code>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (data, start) = cursor.inner();
+        if data.get(start) != Some(&b'"') {
+            return Err(Self::error(data, start, "expected string"));
+        }
+
+        let body_start = start + 1;
+        let mut position = body_start;
+        let mut decoded: Option<String> = None;
+        // Start of the input not yet copied into `decoded`
+        let mut pending = body_start;
+
+        loop {
+            match data.get(position) {
+                None => return Err(Self::error(data, data.len(), "unterminated string")),
+                Some(b'"') => break,
+                Some(b'\\') => {
+                    let chunk = Self::text(data, pending, position)?;
+                    let (c, length) = Self::escape(data, position)?;
+                    let text = decoded.get_or_insert_with(String::new);
+                    text.push_str(chunk);
+                    text.push(c);
+                    position += length;
+                    pending = position;
+                }
+                Some(&b) if b < 0x20 => {
+                    return Err(Self::error(
+                        data,
+                        position,
+                        "control character in string must be escaped",
+                    ));
+                }
+                Some(_) => position += 1,
+            }
+        }
+
+        let rest = Self::text(data, pending, position)?;
+        let text = match decoded {
+            Some(mut text) => {
+                text.push_str(rest);
+                Cow::Owned(text)
+            }
+            None => Cow::Borrowed(rest),
+        };
+
+        Ok((text, ByteCursor::scanned(data, start, position + 1)?))
+    }
+}
+
+/// Replaces a failed array element or object value by `JsonValue::Invalid`
+///
+/// Without a sink the error is returned unchanged. With a sink the error is
+/// collected and the input is skipped up to the next `,` or closing bracket of the
+/// enclosing array or object.
+struct Recover<'code, P> {
+    parser: P,
+    sink: Sink<'code>,
+}
+
+impl<'code, P> Recover<'code, P> {
+    fn new(parser: P, sink: Sink<'code>) -> Self {
+        Recover { parser, sink }
+    }
+
+    /// End of the invalid input starting at `start`
+    fn skip(data: &[u8], start: usize) -> usize {
+        let mut depth = 0usize;
+        let mut position = start;
+
+        while let Some(&b) = data.get(position) {
+            match b {
+                b'"' => match skip_string(data, position, Some(b'\\')) {
+                    Some(end) => {
+                        position = end;
+                        continue;
+                    }
+                    None => return data.len(),
+                },
+                b'[' | b'{' => depth += 1,
+                b']' | b'}' | b',' if depth == 0 => break,
+                b']' | b'}' => depth -= 1,
+                _ => {}
+            }
+            position += 1;
+        }
+
+        position
+    }
+}
+
+impl<'code, P> Parser<'code> for Recover<'code, P>
+where
+    P: Parser<
+            'code,
+            Cursor = ByteCursor<'code>,
+            Output = Spanned<'code, JsonValue<'code>>,
+            Error = ParsicombError<'code>,
+        >,
+{
+    type Cursor = ByteCursor<'code>;
+    type Output = Spanned<'code, JsonValue<'code>>;
+    type Error = ParsicombError<'code>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let error = match self.parser.parse(cursor) {
+            Ok(result) => return Ok(result),
+            Err(error) => error,
+        };
+        let Some(sink) = &self.sink else {
+            return Err(error);
+        };
+
+        sink.borrow_mut().push(error);
+        let (data, start) = cursor.inner();
+        let end = Self::skip(data, start);
+        let invalid = Spanned::new(JsonValue::Invalid, Span::new(data, start, end));
+        Ok((invalid, ByteCursor::scanned(data, start, end)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorLeaf;
+
+    fn unspanned<'a>(value: &Spanned<'a, JsonValue<'a>>) -> String {
+        match &value.value {
+            JsonValue::Null => "null".to_string(),
+            JsonValue::Bool(b) => b.to_string(),
+            JsonValue::Number(n) => n.to_string(),
+            JsonValue::String(s) => format!("{:?}", s),
+            JsonValue::Array(items) => {
+                let items: Vec<_> = items.iter().map(unspanned).collect();
+                format!("[{}]", items.join(","))
+            }
+            JsonValue::Object(members) => {
+                let members: Vec<_> = members
+                    .iter()
+                    .map(|(key, value)| format!("{:?}:{}", key.value, unspanned(value)))
+                    .collect();
+                format!("{{{}}}", members.join(","))
+            }
+            JsonValue::Invalid => "<invalid>".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_scalars() {
+        assert_eq!(parse(b"null").unwrap().value, JsonValue::Null);
+        assert_eq!(parse(b" true ").unwrap().value, JsonValue::Bool(true));
+        assert_eq!(parse(b"false").unwrap().value, JsonValue::Bool(false));
+        assert_eq!(parse(b"-0").unwrap().value, JsonValue::Number(-0.0));
+        assert_eq!(parse(b"12.5e-1").unwrap().value, JsonValue::Number(1.25));
+        assert_eq!(parse(b"1E3").unwrap().value, JsonValue::Number(1000.0));
+    }
+
+    #[test]
+    fn test_invalid_numbers() {
+        assert!(parse(b"01").is_err());
+        assert!(parse(b"1.").is_err());
+        assert!(parse(b"+1").is_err());
+        assert!(parse(b".5").is_err());
+        assert!(parse(b"1e").is_err());
+    }
+
+    #[test]
+    fn test_strings() {
+        let value = parse(br#""plain""#).unwrap();
+        assert!(matches!(
+            value.value,
+            JsonValue::String(Cow::Borrowed("plain"))
+        ));
+
+        let value = parse(br#""a\"b\\c\/\n\u00e9\ud83e\udd80!""#).unwrap();
+        assert_eq!(
+            value.value,
+            JsonValue::String(Cow::Owned("a\"b\\c/\né🦀!".to_string()))
+        );
+        assert_eq!(parse("\"grüße\"".as_bytes()).unwrap().span.len(), 9);
+    }
+
+    #[test]
+    fn test_invalid_strings() {
+        let error = parse(b"\"abc").unwrap_err();
+        assert_eq!(error.message(), "unterminated string");
+        assert_eq!(error.loc().position(), 4);
+
+        let error = parse(br#""a\x""#).unwrap_err();
+        assert_eq!(error.message(), "invalid escape sequence");
+        assert_eq!(error.loc().position(), 2);
+
+        assert!(parse(br#""\ud83e""#).is_err());
+        assert!(parse(b"\"tab\there\"").is_err());
+    }
+
+    #[test]
+    fn test_invalid_utf8_in_strings() {
+        // Before an escape
+        let error = parse(b"\"ab\xff\\n\"").unwrap_err();
+        assert_eq!(error.message(), "invalid UTF-8 in string");
+        assert_eq!(error.loc().position(), 3);
+
+        // After an escape
+        let error = parse(b"\"a\\nb\xc3\"").unwrap_err();
+        assert_eq!(error.message(), "invalid UTF-8 in string");
+        assert_eq!(error.loc().position(), 5);
+    }
+
+    #[test]
+    fn test_nested_document() {
+        let source = br#" {"a": [1, 2, {"b": null}], "c": {}, "d": [ ]} "#;
+        let document = parse(source).unwrap();
+        assert_eq!(
+            unspanned(&document),
+            r#"{"a":[1,2,{"b":null}],"c":{},"d":[]}"#
+        );
+        assert_eq!(document.span, Span::new(source, 1, source.len() - 1));
+
+        let JsonValue::Object(members) = &document.value else {
+            panic!("expected object");
+        };
+        assert_eq!(members[0].0.span.as_string(), r#""a""#);
+        assert_eq!(members[0].1.span.as_string(), r#"[1, 2, {"b": null}]"#);
+    }
+
+    #[test]
+    fn test_structural_errors() {
+        let error = parse(b"[1, 2,]").unwrap_err();
+        assert_eq!(error.loc().position(), 6);
+
+        let error = parse(br#"{"a" 1}"#).unwrap_err();
+        assert_eq!(error.loc().position(), 5);
+
+        let error = parse(b"[1] x").unwrap_err();
+        assert_eq!(error.message(), "expected end of input, found 'x'");
+    }
+
+    #[test]
+    fn test_recovering_replaces_broken_elements() {
+        let source = br#"{"a": [1, tru, 3], "b": @, "c": "ok"}"#;
+        let (document, errors) = parse_recovering(source);
+
+        let document = document.unwrap();
+        assert_eq!(
+            unspanned(&document),
+            r#"{"a":[1,<invalid>,3],"b":<invalid>,"c":"ok"}"#
+        );
+        let positions: Vec<_> = errors.iter().map(|e| e.loc().position()).collect();
+        // `tru` fails where it stops matching `true`
+        assert_eq!(positions, vec![13, 24]);
+    }
+
+    #[test]
+    fn test_recovering_without_errors_matches_parse() {
+        let source = br#"[{"x": [true, false]}, "y"]"#;
+        let (document, errors) = parse_recovering(source);
+        assert!(errors.is_empty());
+        assert_eq!(document.unwrap(), parse(source).unwrap());
+    }
+
+    #[test]
+    fn test_recovering_gives_up_on_broken_structure() {
+        let (document, errors) = parse_recovering(b"[1, 2");
+        assert!(document.is_none());
+        assert_eq!(errors.len(), 1);
+    }
+}
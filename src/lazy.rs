@@ -1,8 +1,22 @@
+use crate::atomic::Atomic;
+use crate::cursor::Cursor;
+use crate::describe::Description;
+use crate::error::{CodeLoc, ParsicombError};
+use crate::limits;
 use crate::parser::Parser;
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::marker::PhantomData;
+use std::sync::{Arc, Weak};
 
 /// A lazy parser that defers the construction of the actual parser until parse time.
 /// This is useful for breaking mutual recursion between parsers.
+///
+/// Lazy parsers don't count towards `with_recursion_limit`, use [`lazy_limited`] for
+/// rules that must be bounded on untrusted input.
+///
+/// A rule that calls itself before consuming input, like `expr = expr '-' term`,
+/// recurses forever. Write such rules with [`left_recursive`] instead.
 pub struct Lazy<'code, F, P>
 where
     F: Fn() -> P,
@@ -39,6 +53,10 @@ where
         let parser = (self.factory)();
         parser.parse(cursor)
     }
+
+    fn describe(&self) -> Description {
+        Description::lazy::<F, P>(|| (self.factory)().describe())
+    }
 }
 
 /// Create a lazy parser from a factory function
@@ -50,9 +68,295 @@ where
     Lazy::new(factory)
 }
 
+/// A lazy parser that counts as one level of nesting for `with_recursion_limit`
+///
+/// Errors of the inner parser are wrapped in a `ParsicombError::WrappedError`, so a
+/// rule can also fail with `RecursionLimitExceeded` without running. Past the limit
+/// the factory isn't called, so even rules that recurse without consuming input
+/// stop there.
+pub struct LazyLimited<'code, F, P>
+where
+    F: Fn() -> P,
+    P: Parser<'code>,
+{
+    factory: F,
+    _phantom: PhantomData<&'code ()>,
+}
+
+impl<'code, F, P> LazyLimited<'code, F, P>
+where
+    F: Fn() -> P,
+    P: Parser<'code>,
+{
+    /// Create a new limited lazy parser with the given factory function
+    pub fn new(factory: F) -> Self {
+        Self {
+            factory,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<'code, F, P> Parser<'code> for LazyLimited<'code, F, P>
+where
+    F: Fn() -> P,
+    P: Parser<'code>,
+    P::Error: 'code,
+    <P::Cursor as Cursor<'code>>::Element: Atomic + 'code,
+{
+    type Cursor = P::Cursor;
+    type Output = P::Output;
+    type Error = ParsicombError<'code, <P::Cursor as Cursor<'code>>::Element>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let _depth = limits::enter_rule(cursor.position())
+            .map_err(|refused| refused.error(cursor.source()))?;
+        let parser = (self.factory)();
+        parser.parse(cursor).map_err(ParsicombError::wrap)
+    }
+
+    fn describe(&self) -> Description {
+        Description::lazy::<F, P>(|| (self.factory)().describe())
+    }
+}
+
+/// Create a lazy parser that counts towards `with_recursion_limit`
+pub fn lazy_limited<'code, F, P>(factory: F) -> LazyLimited<'code, F, P>
+where
+    F: Fn() -> P,
+    P: Parser<'code>,
+{
+    LazyLimited::new(factory)
+}
+
+// # Left Recursion - Seed Growing
+//
+// `lazy` breaks mutual recursion, but a rule that starts with itself
+// (`expr = expr '-' term | term`) recurses forever because the factory is invoked
+// again at the same position. `left_recursive` implements Warth-style seed growing:
+//
+// 1. The first time the rule runs at a position it plants a failing seed and parses
+//    the body. Any left-recursive call at that position returns the seed, so the
+//    body falls through to its non-recursive alternative.
+// 2. Each success becomes the new seed and the body is parsed again, letting the
+//    recursive alternative extend it by one more step.
+// 3. Growing stops as soon as an attempt fails to consume more input than the seed,
+//    and the longest result wins.
+//
+// The rule receives a `Recursive` handle to itself instead of calling its own
+// constructor, so the seed table is shared between the rule and all recursive
+// references. Only direct left recursion through that handle is supported.
+//
+// This is why it is a separate entry point and not a mode of `lazy`. A `lazy`
+// factory typically calls the function that defines the rule, which builds a new,
+// unrelated parser every time, so a left-recursive call has no way to find the seed
+// of the invocation it belongs to. The handle gives the rule that identity. It
+// refers to the rule through the `GrowRule` trait object, so neither the handle
+// nor the rule's type have to name the body parser, whose type contains the handle.
+// `cached` grammars hand out the same handle, over a `Send + Sync` rule.
+
+/// Rule body shared between a rule and its [`Recursive`] handles
+///
+/// Implemented by the rules behind [`left_recursive`] and
+/// [`cached`](crate::cached::cached), handles only see the rule through this trait.
+pub trait GrowRule<'code, C, O>
+where
+    C: Cursor<'code>,
+    C::Element: Atomic,
+{
+    fn parse_rule(&self, cursor: C) -> Result<(O, C), ParsicombError<'code, C::Element>>;
+}
+
+/// Seeds of the rule invocations that are currently growing, keyed by position
+type Seeds<O, C> = RefCell<HashMap<usize, Option<(O, C)>>>;
+
+struct LeftRecursiveRule<'code, C, O, P> {
+    body: P,
+    seeds: Seeds<O, C>,
+    _phantom: PhantomData<&'code ()>,
+}
+
+impl<'code, C, O, P> LeftRecursiveRule<'code, C, O, P>
+where
+    C: Cursor<'code>,
+    O: Clone,
+{
+    fn plant(&self, position: usize, seed: Option<(O, C)>) {
+        self.seeds.borrow_mut().insert(position, seed);
+    }
+}
+
+impl<'code, C, O, P> GrowRule<'code, C, O> for LeftRecursiveRule<'code, C, O, P>
+where
+    C: Cursor<'code>,
+    C::Element: Atomic + 'code,
+    O: Clone,
+    P: Parser<'code, Cursor = C, Output = O>,
+    P::Error: 'code,
+{
+    fn parse_rule(&self, cursor: C) -> Result<(O, C), ParsicombError<'code, C::Element>> {
+        let position = cursor.position();
+
+        // A left-recursive call at a growing position returns the current seed
+        if let Some(seed) = self.seeds.borrow().get(&position) {
+            return match seed {
+                Some((output, seed_cursor)) => Ok((output.clone(), *seed_cursor)),
+                None => {
+                    let (data, position) = cursor.inner();
+                    Err(ParsicombError::SyntaxError {
+                        message: "left-recursive rule has no seed at this position".into(),
+                        loc: CodeLoc::new(data, position),
+                    })
+                }
+            };
+        }
+
+        self.plant(position, None);
+        let mut best = match self.body.parse(cursor) {
+            Ok(result) => result,
+            Err(error) => {
+                self.seeds.borrow_mut().remove(&position);
+                return Err(ParsicombError::wrap(error));
+            }
+        };
+
+        loop {
+            self.plant(position, Some((best.0.clone(), best.1)));
+            match self.body.parse(cursor) {
+                Ok((output, next_cursor)) if next_cursor.position() > best.1.position() => {
+                    best = (output, next_cursor);
+                }
+                _ => break,
+            }
+        }
+
+        self.seeds.borrow_mut().remove(&position);
+        Ok(best)
+    }
+}
+
+/// A parser for a rule that may refer to itself in leftmost position
+///
+/// Created with [`left_recursive`].
+pub struct LeftRecursive<'code, C, O> {
+    rule: Arc<dyn GrowRule<'code, C, O> + 'code>,
+}
+
+impl<'code, C, O> Parser<'code> for LeftRecursive<'code, C, O>
+where
+    C: Cursor<'code>,
+    C::Element: Atomic + 'code,
+{
+    type Cursor = C;
+    type Output = O;
+    type Error = ParsicombError<'code, C::Element>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        self.rule.parse_rule(cursor)
+    }
+}
+
+/// Reference to the enclosing rule, handed to the builder of a [`left_recursive`]
+/// rule or a [`cached`](crate::cached::cached) grammar
+pub struct Recursive<'code, C, O, R: ?Sized = dyn GrowRule<'code, C, O> + 'code> {
+    rule: Weak<R>,
+    _phantom: PhantomData<fn(&'code (), C) -> O>,
+}
+
+impl<'code, C, O, R: ?Sized> Recursive<'code, C, O, R> {
+    pub(crate) fn new(rule: Weak<R>) -> Self {
+        Recursive {
+            rule,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<'code, C, O, R: ?Sized> Clone for Recursive<'code, C, O, R> {
+    fn clone(&self) -> Self {
+        Recursive::new(Weak::clone(&self.rule))
+    }
+}
+
+impl<'code, C, O, R> Parser<'code> for Recursive<'code, C, O, R>
+where
+    C: Cursor<'code>,
+    C::Element: Atomic + 'code,
+    R: GrowRule<'code, C, O> + ?Sized,
+{
+    type Cursor = C;
+    type Output = O;
+    type Error = ParsicombError<'code, C::Element>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        match self.rule.upgrade() {
+            Some(rule) => rule.parse_rule(cursor),
+            None => {
+                let (data, position) = cursor.inner();
+                Err(ParsicombError::SyntaxError {
+                    message: "recursive rule is no longer alive".into(),
+                    loc: CodeLoc::new(data, position),
+                })
+            }
+        }
+    }
+}
+
+/// Create a rule that may call itself in leftmost position
+///
+/// The builder receives a [`Recursive`] handle that parses the rule being defined.
+/// Results of the rule are grown from a non-recursive seed, so left-associative
+/// grammars can be written directly. Use [`lazy`] for rules that only recurse after
+/// consuming input, left recursion needs the shared handle that `lazy` lacks.
+///
+/// ```
+/// use parsicomb::and::AndExt;
+/// use parsicomb::ascii::i64;
+/// use parsicomb::byte::is_byte;
+/// use parsicomb::lazy::left_recursive;
+/// use parsicomb::map::MapExt;
+/// use parsicomb::or::OrExt;
+/// use parsicomb::{ByteCursor, Parser};
+///
+/// // expr = expr '-' number | number
+/// let expr = left_recursive(|expr| {
+///     expr.and(is_byte(b'-'))
+///         .and(i64())
+///         .map(|((lhs, _), rhs)| lhs - rhs)
+///         .or(i64())
+/// });
+///
+/// let (value, _) = expr.parse(ByteCursor::new(b"10-3-2")).unwrap();
+/// assert_eq!(value, 5);
+/// ```
+pub fn left_recursive<'code, C, O, P, F>(build: F) -> LeftRecursive<'code, C, O>
+where
+    F: FnOnce(Recursive<'code, C, O>) -> P,
+    P: Parser<'code, Cursor = C, Output = O> + 'code,
+    P::Error: 'code,
+    C: Cursor<'code> + 'code,
+    C::Element: Atomic + 'code,
+    O: Clone + 'code,
+{
+    let rule = Arc::new_cyclic(|weak: &Weak<LeftRecursiveRule<'code, C, O, P>>| {
+        let handle: Weak<dyn GrowRule<'code, C, O> + 'code> = weak.clone();
+        LeftRecursiveRule {
+            body: build(Recursive::new(handle)),
+            seeds: RefCell::new(HashMap::new()),
+            _phantom: PhantomData,
+        }
+    });
+    LeftRecursive { rule }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::and::AndExt;
+    use crate::ascii::i64;
+    use crate::between::between;
+    use crate::map::MapExt;
+    use crate::or::OrExt;
     use crate::{byte::is_byte, cursor::Cursor, cursors::ByteCursor, many::many};
 
     #[test]
@@ -69,6 +373,23 @@ mod tests {
         assert_eq!(remaining.position(), 1);
     }
 
+    #[test]
+    fn test_lazy_keeps_inner_error() {
+        use crate::error::ErrorNode;
+
+        let cursor = ByteCursor::new(b"x");
+        let error = lazy(|| is_byte(b'a').or(is_byte(b'b')))
+            .parse(cursor)
+            .unwrap_err();
+        assert_eq!(error.kind(), "OrError");
+
+        let error = lazy_limited(|| is_byte(b'a').or(is_byte(b'b')))
+            .parse(cursor)
+            .unwrap_err();
+        assert_eq!(error.kind(), "ParsicombError");
+        assert_eq!(error.children()[0].kind(), "OrError");
+    }
+
     #[test]
     fn test_lazy_with_many() {
         let input = b"aaaa";
@@ -83,6 +404,71 @@ mod tests {
         assert_eq!(remaining.position(), 4);
     }
 
+    #[test]
+    fn test_left_recursive_is_left_associative() {
+        let data = b"10-3-2";
+        let cursor = ByteCursor::new(data);
+        let expr = left_recursive(|expr| {
+            expr.and(is_byte(b'-'))
+                .and(i64())
+                .map(|((lhs, _), rhs)| lhs - rhs)
+                .or(i64())
+        });
+
+        let (value, remaining) = expr.parse(cursor).unwrap();
+        assert_eq!(value, 5);
+        assert_eq!(remaining.position(), 6);
+    }
+
+    #[test]
+    fn test_left_recursive_seed_only() {
+        let data = b"42+";
+        let cursor = ByteCursor::new(data);
+        let expr = left_recursive(|expr| {
+            expr.and(is_byte(b'-'))
+                .and(i64())
+                .map(|((lhs, _), rhs)| lhs - rhs)
+                .or(i64())
+        });
+
+        let (value, remaining) = expr.parse(cursor).unwrap();
+        assert_eq!(value, 42);
+        assert_eq!(remaining.value().unwrap(), b'+');
+    }
+
+    #[test]
+    fn test_left_recursive_with_nested_recursion() {
+        // expr = expr '-' atom | atom ; atom = '(' expr ')' | number
+        let data = b"20-(5-3)-1";
+        let cursor = ByteCursor::new(data);
+        let expr = left_recursive(|expr| {
+            let atom = between(is_byte(b'('), expr.clone(), is_byte(b')')).or(i64());
+            let atom_again = between(is_byte(b'('), expr.clone(), is_byte(b')')).or(i64());
+            expr.and(is_byte(b'-'))
+                .and(atom)
+                .map(|((lhs, _), rhs)| lhs - rhs)
+                .or(atom_again)
+        });
+
+        let (value, remaining) = expr.parse(cursor).unwrap();
+        assert_eq!(value, 17);
+        assert!(remaining.eos());
+    }
+
+    #[test]
+    fn test_left_recursive_failure() {
+        let data = b"x";
+        let cursor = ByteCursor::new(data);
+        let expr = left_recursive(|expr| {
+            expr.and(is_byte(b'-'))
+                .and(i64())
+                .map(|((lhs, _), rhs)| lhs - rhs)
+                .or(i64())
+        });
+
+        assert!(expr.parse(cursor).is_err());
+    }
+
     #[test]
     fn test_lazy_deferred_construction() {
         // This test verifies that the parser is constructed lazily
@@ -10,39 +10,148 @@
 //! - **Composability**: Small parsers combine into larger ones using combinators
 //! - **Performance**: Efficient byte-level parsing with minimal allocations
 
+// Lets paths generated by the derive macros resolve inside this crate's tests
+#[cfg(all(test, feature = "derive"))]
+extern crate self as parsicomb;
+
 pub mod all;
 pub mod and;
+#[cfg(feature = "arena")]
+pub mod arena;
 pub mod ascii;
 pub mod atomic;
+pub mod attempt;
+pub mod balanced;
+#[cfg(feature = "bench")]
+pub mod bench;
 pub mod between;
+pub mod boxed;
 pub mod byte;
+pub mod cached;
+pub mod choice;
+pub mod code;
+pub mod count;
 pub mod cursor;
 pub mod cursors;
+pub mod cut;
 pub mod default;
+pub mod describe;
+pub mod diagnostic;
+pub mod either;
+pub mod emit;
+pub mod eof;
 pub mod error;
+pub mod expression;
 pub mod filter;
+pub mod fold;
+pub mod formats;
+pub mod fuzz;
+pub mod hint;
+pub mod input;
+pub mod intern;
+#[cfg(feature = "json")]
+pub mod json;
 pub mod lazy;
+pub mod limits;
+pub mod list_until;
+pub mod longest;
 pub mod many;
 pub mod map;
 pub mod map_err;
+pub mod memoize;
+#[cfg(feature = "miette")]
+pub mod miette_diagnostic;
 pub mod not;
+pub mod optional;
 pub mod or;
+pub mod or_labeled;
 pub mod parser;
+pub mod permutation;
 pub mod position;
+pub mod profile;
+#[cfg(feature = "quickcheck")]
+pub mod property;
+pub mod recognize;
+pub mod repetition;
+pub mod report;
 pub mod separated_list;
 pub mod separated_pair;
+pub mod seq;
+pub mod skip;
 pub mod some;
+pub mod source_map;
+pub mod space;
+pub mod state;
+pub mod symbol_table;
+pub mod tag;
 pub mod take_until;
+pub mod testing;
+pub mod token;
+pub mod trace;
 pub mod utf8;
 
 pub use all::all;
 pub use atomic::{Atomic, AtomicParser, atomic};
-pub use between::between;
-pub use cursor::Cursor;
-pub use cursors::{AtomicCursor, ByteCursor};
-pub use error::{CodeLoc, ErrorLeaf, ErrorNode, ParsicombError};
-pub use lazy::{Lazy, lazy};
+pub use attempt::{Attempt, AttemptError, AttemptExt, attempt};
+pub use balanced::{Balanced, balanced};
+pub use between::{between, between_padded};
+pub use boxed::{BoxedExt, BoxedParser};
+pub use cached::{CachedParser, CachedRef, cached};
+pub use choice::choice;
+pub use code::{CodeExt, CodedError, WithCode, with_code};
+pub use count::count;
+pub use cursor::{Checkpoint, Cursor};
+pub use cursors::{AtomicCursor, ByteCursor, CharSliceCursor, CharSource, Located, LocatedCursor};
+pub use cut::{Cut, CutError, CutExt, cut};
+pub use describe::Description;
+pub use diagnostic::{Diagnostic, DiagnosticSink, Severity};
+pub use either::{Either, EitherExt, either};
+pub use emit::{Emit, EmitExt, WithDiagnostics};
+pub use eof::{EndOfInput, ThenEofExt, any, end_of_input, end_of_input_after, eof};
+pub use error::{
+    CodeLoc, DebugTree, EofError, ErrorLeaf, ErrorNode, ErrorTreeExt, MergedError, ParsicombError,
+    Utf16Position,
+};
+pub use expression::{Assoc, Expression, Operator, OperatorTable, expression};
+pub use fold::{fold_many, fold_many1};
+pub use hint::{HintError, HintExt, Hinted, with_hint};
+pub use input::{Encoding, InputBuffer};
+pub use intern::{Intern, InternExt, Interner, Symbol, intern};
+pub use lazy::{Lazy, LazyLimited, LeftRecursive, Recursive, lazy, lazy_limited, left_recursive};
+pub use limits::{
+    CancellationExt, CancellationToken, FuelExt, RecursionLimitExt, WithCancellation, WithFuel,
+    WithRecursionLimit, with_cancellation, with_fuel, with_recursion_limit,
+};
+pub use list_until::{ListUntil, ListUntilError, list_until};
+pub use longest::{Longest, longest};
+pub use memoize::{
+    Memoize, MemoizeExt, ParseContext, ParseContextExt, WithParseContext, memoize,
+    with_parse_context,
+};
+pub use optional::{OptionalExt, optional};
+pub use or_labeled::{OrLabeled, or_labeled};
 pub use parser::Parser;
-pub use position::{Position, PositionExt, Span, position};
-pub use separated_list::separated_list;
-pub use separated_pair::separated_pair;
+#[cfg(feature = "derive")]
+pub use parsicomb_derive::Keywords;
+pub use permutation::{Permutation, permutation};
+pub use position::{MapWithSpan, Position, PositionExt, Span, Spanned, position};
+pub use profile::{
+    ProfileReport, Profiled, ProfiledExt, RuleProfile, enable_profiling, profile, profiled,
+    take_profile,
+};
+pub use recognize::{RecognizeExt, recognize};
+pub use repetition::RepetitionError;
+pub use report::{Report, ReportConfig, report};
+pub use separated_list::{
+    separated_list, separated_list_padded, separated_list_recover, separated_list_with,
+};
+pub use separated_pair::{separated_pair, separated_pair_padded};
+pub use seq::seq;
+pub use skip::{IgnoreExt, skip_many};
+pub use source_map::{ExpansionMap, FileLoc, PositionRemapper, SourceId, SourceMap};
+pub use space::{SkipSpace, Space, skip_space, space};
+pub use state::{RollbackState, State, StateExt, WithState, with_state};
+pub use symbol_table::{SymbolTable, symbol_table};
+pub use tag::tag_slice;
+pub use token::{Token, TokenParser, token};
+pub use trace::{RecordTrace, RuleEvent, TracedExt, capture_trace, record_trace, traced};
@@ -0,0 +1,936 @@
+use crate::atomic::Atomic;
+use crate::cursor::Cursor;
+use crate::error::{CodeLoc, ParsicombError};
+use crate::parser::Parser;
+use crate::position::Span;
+use std::cell::{Cell, RefCell};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::Instant;
+
+// # Resource Limits - Guaranteed Termination
+//
+// Backtracking grammars can take exponential time on crafted input, and deeply
+// nested input can overflow the stack of recursive grammars. Services that parse
+// untrusted input can bound both:
+//
+// - `.with_fuel(limit)` burns one unit of fuel for every element read from an
+//   `AtomicCursor` while the wrapped parser runs.
+// - `.with_recursion_limit(limit)` bounds how deeply `lazy_limited` rules may nest.
+// - `.with_cancellation(token)` polls a `CancellationToken` that another thread
+//   can cancel, or that expires at a deadline.
+//
+// Once a limit is hit the parse is aborted: every further read fails and no further
+// `lazy_limited` rule runs, so all parsers below the wrapper fail fast and unwind,
+// even those that recurse without reading. The wrapper reports
+// `ParsicombError::FuelExhausted`, `ParsicombError::RecursionLimitExceeded` or
+// `ParsicombError::Cancelled` at the position where the limit was hit, regardless
+// of what the inner parser returned. Plain `lazy` rules are not counted.
+//
+// The limits live in thread-locals so they need no changes to the `Parser` trait.
+// A global count of installed wrappers keeps them opt-in: while no wrapper runs on
+// any thread, reads and rules only pay for one relaxed load of that count. Nested
+// limits are capped by the enclosing ones, and fuel burned inside is charged to both.
+// Cancellation tokens are polled on entering a rule and every `POLL_INTERVAL` reads,
+// so a cancelled parse stops within a bounded amount of work.
+//
+// Slice scanners such as `take_whitespace`, `take_until_string` or the `formats`
+// parsers skip the cursor and look at the input directly. They charge every element
+// they looked at in one call instead, through `AtomicCursor::scanned`, so they burn
+// the same fuel and are polled for cancellation as if they had read each element.
+
+/// Budget value meaning no limit is installed
+const UNLIMITED: usize = usize::MAX;
+
+/// Element reads between two polls of the installed cancellation tokens
+const POLL_INTERVAL: usize = 256;
+
+/// Number of limit scopes currently installed on any thread
+static INSTALLED: AtomicUsize = AtomicUsize::new(0);
+
+/// Whether a limit scope may be installed on this thread
+#[inline]
+fn limits_installed() -> bool {
+    INSTALLED.load(Ordering::Relaxed) != 0
+}
+
+/// The limit that aborted a parse
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Limit {
+    Fuel,
+    Recursion,
+    Cancellation,
+}
+
+impl Limit {
+    /// The error a cursor returns for reads after the parse was aborted
+    pub(crate) fn error<'code, T: Atomic>(
+        self,
+        loc: CodeLoc<'code, T>,
+    ) -> ParsicombError<'code, T> {
+        match self {
+            Limit::Fuel => ParsicombError::FuelExhausted(loc),
+            Limit::Recursion => ParsicombError::RecursionLimitExceeded(Span::new(
+                loc.source(),
+                loc.position(),
+                loc.position(),
+            )),
+            Limit::Cancellation => ParsicombError::Cancelled(loc),
+        }
+    }
+}
+
+thread_local! {
+    /// Remaining element reads, `UNLIMITED` outside of `with_fuel`
+    static FUEL: Cell<usize> = const { Cell::new(UNLIMITED) };
+    /// Number of `lazy_limited` rules currently being parsed
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+    /// Deepest allowed rule nesting, `UNLIMITED` outside of `with_recursion_limit`
+    static MAX_DEPTH: Cell<usize> = const { Cell::new(UNLIMITED) };
+    /// Start of the innermost counted rule being parsed
+    static RULE_START: Cell<Option<usize>> = const { Cell::new(None) };
+    /// Start of the deepest rule that ran when the recursion limit was exceeded
+    static EXCEEDED_RULE: Cell<usize> = const { Cell::new(0) };
+    /// The limit that aborted the current parse and where it was hit
+    static ABORTED: Cell<Option<(Limit, usize)>> = const { Cell::new(None) };
+    /// Tokens of the enclosing `with_cancellation` scopes, innermost last
+    static TOKENS: RefCell<Vec<CancellationToken>> = const { RefCell::new(Vec::new()) };
+    /// Reads left until the tokens are polled again, `UNLIMITED` without tokens
+    static POLL: Cell<usize> = const { Cell::new(UNLIMITED) };
+}
+
+/// Poll the cancellation tokens once every `POLL_INTERVAL` reads, aborting if one fired
+fn poll_cancellation(position: usize, reads: usize) -> Result<(), Limit> {
+    let due = POLL.with(|poll| match poll.get() {
+        UNLIMITED => false,
+        remaining if remaining < reads => {
+            poll.set(POLL_INTERVAL);
+            true
+        }
+        remaining => {
+            poll.set(remaining - reads);
+            false
+        }
+    });
+    if due && TOKENS.with(|tokens| tokens.borrow().iter().any(|t| t.is_cancelled())) {
+        ABORTED.with(|aborted| aborted.set(Some((Limit::Cancellation, position))));
+        return Err(Limit::Cancellation);
+    }
+    Ok(())
+}
+
+/// Check whether an element at `position` may be read, burning one unit of fuel
+#[inline]
+pub(crate) fn check_read(position: usize) -> Result<(), Limit> {
+    if !limits_installed() {
+        return Ok(());
+    }
+    check_read_limited(position)
+}
+
+/// Slow path of `check_read` taken while limits are installed
+fn check_read_limited(position: usize) -> Result<(), Limit> {
+    if let Some((limit, _)) = ABORTED.with(|aborted| aborted.get()) {
+        return Err(limit);
+    }
+    poll_cancellation(position, 1)?;
+    FUEL.with(|fuel| match fuel.get() {
+        UNLIMITED => Ok(()),
+        0 => {
+            ABORTED.with(|aborted| aborted.set(Some((Limit::Fuel, position))));
+            Err(Limit::Fuel)
+        }
+        remaining => {
+            fuel.set(remaining - 1);
+            Ok(())
+        }
+    })
+}
+
+/// Check whether `count` elements from `position` on may be read at once
+///
+/// Slice scanners read past the cursor, so they charge for the elements they looked
+/// at in one call, burning one unit of fuel per element like `check_read`. On failure
+/// returns the limit together with the position where it was hit.
+#[inline]
+pub(crate) fn check_scan(position: usize, count: usize) -> Result<(), (Limit, usize)> {
+    if !limits_installed() || count == 0 {
+        return Ok(());
+    }
+    check_scan_limited(position, count)
+}
+
+/// Slow path of `check_scan` taken while limits are installed
+fn check_scan_limited(position: usize, count: usize) -> Result<(), (Limit, usize)> {
+    if let Some((limit, _)) = ABORTED.with(|aborted| aborted.get()) {
+        return Err((limit, position));
+    }
+    poll_cancellation(position, count).map_err(|limit| (limit, position))?;
+    FUEL.with(|fuel| match fuel.get() {
+        UNLIMITED => Ok(()),
+        remaining if remaining < count => {
+            // The fuel ran out part way through the scanned elements
+            let exhausted = position + remaining;
+            fuel.set(0);
+            ABORTED.with(|aborted| aborted.set(Some((Limit::Fuel, exhausted))));
+            Err((Limit::Fuel, exhausted))
+        }
+        remaining => {
+            fuel.set(remaining - count);
+            Ok(())
+        }
+    })
+}
+
+/// Tracks one level of rule nesting until dropped
+pub(crate) struct RuleDepth {
+    /// Rules entered while no limits were installed are not counted
+    counted: bool,
+    /// Start of the enclosing rule, restored when this one is left
+    enclosing_start: Option<usize>,
+}
+
+/// A rule that was not entered because the parse is aborted
+pub(crate) struct Refused {
+    limit: Limit,
+    rule_start: usize,
+    position: usize,
+}
+
+impl Refused {
+    /// The error the refused rule fails with
+    ///
+    /// Exceeding the recursion limit spans the deepest rule that ran, from its start
+    /// to where the refused rule would have started.
+    pub(crate) fn error<'code, T: Atomic>(self, source: &'code [T]) -> ParsicombError<'code, T> {
+        match self.limit {
+            Limit::Recursion => ParsicombError::RecursionLimitExceeded(Span::new(
+                source,
+                self.rule_start,
+                self.position,
+            )),
+            limit => limit.error(CodeLoc::new(source, self.position)),
+        }
+    }
+}
+
+/// Enter a recursive rule starting at `position`
+///
+/// Refuses to enter the rule if this exceeds the recursion limit, or if the parse was
+/// already aborted. A refused rule must fail without running, otherwise recursion that
+/// consumes no input, like left recursion, would still overflow the stack.
+pub(crate) fn enter_rule(position: usize) -> Result<RuleDepth, Refused> {
+    if !limits_installed() {
+        return Ok(RuleDepth {
+            counted: false,
+            enclosing_start: None,
+        });
+    }
+    // Polling here too catches grammars that recurse without reading
+    if ABORTED.with(|aborted| aborted.get()).is_none() {
+        let _ = poll_cancellation(position, 1);
+    }
+    let rule_start = RULE_START.with(|start| start.get()).unwrap_or(position);
+    if let Some((limit, _)) = ABORTED.with(|aborted| aborted.get()) {
+        return Err(Refused {
+            limit,
+            rule_start,
+            position,
+        });
+    }
+
+    let depth = DEPTH.with(|depth| depth.get()) + 1;
+    if depth > MAX_DEPTH.with(|max| max.get()) {
+        ABORTED.with(|aborted| aborted.set(Some((Limit::Recursion, position))));
+        EXCEEDED_RULE.with(|exceeded| exceeded.set(rule_start));
+        return Err(Refused {
+            limit: Limit::Recursion,
+            rule_start,
+            position,
+        });
+    }
+    DEPTH.with(|current| current.set(depth));
+    Ok(RuleDepth {
+        counted: true,
+        enclosing_start: RULE_START.with(|start| start.replace(Some(position))),
+    })
+}
+
+impl Drop for RuleDepth {
+    fn drop(&mut self) {
+        if self.counted {
+            DEPTH.with(|depth| depth.set(depth.get() - 1));
+            RULE_START.with(|start| start.set(self.enclosing_start));
+        }
+    }
+}
+
+/// Position where this scope's limit aborted the parse, if it did
+fn aborted_at(limit: Limit) -> Option<usize> {
+    match ABORTED.with(|aborted| aborted.get()) {
+        Some((aborted, position)) if aborted == limit => Some(position),
+        _ => None,
+    }
+}
+
+/// Installs a fuel budget and restores the enclosing one when dropped, also on panic
+struct FuelScope {
+    previous: usize,
+    previously_aborted: bool,
+    start: usize,
+}
+
+impl FuelScope {
+    fn install(limit: usize) -> Self {
+        INSTALLED.fetch_add(1, Ordering::Relaxed);
+        let previous = FUEL.with(|fuel| fuel.get());
+        let start = limit.min(previous);
+        FUEL.with(|fuel| fuel.set(start));
+        FuelScope {
+            previous,
+            previously_aborted: ABORTED.with(|aborted| aborted.get()).is_some(),
+            start,
+        }
+    }
+
+    /// Position where the fuel of this scope ran out, if it did
+    fn exhausted_at(&self) -> Option<usize> {
+        if self.previously_aborted {
+            return None;
+        }
+        aborted_at(Limit::Fuel)
+    }
+}
+
+impl Drop for FuelScope {
+    fn drop(&mut self) {
+        let used = self.start - FUEL.with(|fuel| fuel.get());
+        let remaining = match self.previous {
+            UNLIMITED => UNLIMITED,
+            previous => previous - used,
+        };
+        FUEL.with(|fuel| fuel.set(remaining));
+
+        // If the enclosing tank is now empty it ran out at the same spot
+        if !self.previously_aborted && remaining != 0 && aborted_at(Limit::Fuel).is_some() {
+            ABORTED.with(|aborted| aborted.set(None));
+        }
+        INSTALLED.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Installs a recursion limit and restores the enclosing one when dropped
+struct RecursionScope {
+    previous: usize,
+    previously_aborted: bool,
+}
+
+impl RecursionScope {
+    fn install(limit: usize) -> Self {
+        INSTALLED.fetch_add(1, Ordering::Relaxed);
+        let previous = MAX_DEPTH.with(|max| max.get());
+        let depth = DEPTH.with(|depth| depth.get());
+        MAX_DEPTH.with(|max| max.set(depth.saturating_add(limit).min(previous)));
+        RecursionScope {
+            previous,
+            previously_aborted: ABORTED.with(|aborted| aborted.get()).is_some(),
+        }
+    }
+
+    /// Position of the rule that exceeded the limit, if one did
+    fn exceeded_at(&self) -> Option<usize> {
+        if self.previously_aborted {
+            return None;
+        }
+        aborted_at(Limit::Recursion)
+    }
+}
+
+impl Drop for RecursionScope {
+    fn drop(&mut self) {
+        MAX_DEPTH.with(|max| max.set(self.previous));
+        if !self.previously_aborted && aborted_at(Limit::Recursion).is_some() {
+            ABORTED.with(|aborted| aborted.set(None));
+        }
+        INSTALLED.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Run `parse` under a fuel budget and a recursion limit
+///
+/// Returns the result together with the limit that aborted the parse, if one did,
+/// and the position where it was hit.
+pub(crate) fn run_limited<R>(
+    fuel: usize,
+    recursion_limit: usize,
+    parse: impl FnOnce() -> R,
+) -> (R, Option<(Limit, usize)>) {
+    let fuel_scope = FuelScope::install(fuel);
+    let recursion_scope = RecursionScope::install(recursion_limit);
+    let result = parse();
+
+    let aborted = match recursion_scope.exceeded_at() {
+        Some(position) => Some((Limit::Recursion, position)),
+        None => fuel_scope
+            .exhausted_at()
+            .map(|position| (Limit::Fuel, position)),
+    };
+    (result, aborted)
+}
+
+/// Installs a cancellation token and removes it again when dropped
+struct CancellationScope {
+    previous_poll: usize,
+    previously_aborted: bool,
+}
+
+impl CancellationScope {
+    fn install(token: &CancellationToken) -> Self {
+        INSTALLED.fetch_add(1, Ordering::Relaxed);
+        TOKENS.with(|tokens| tokens.borrow_mut().push(token.clone()));
+        let previous_poll = POLL.with(|poll| poll.replace(0));
+        CancellationScope {
+            previous_poll,
+            previously_aborted: ABORTED.with(|aborted| aborted.get()).is_some(),
+        }
+    }
+
+    /// Position reached when the parse was cancelled, if it was
+    fn cancelled_at(&self) -> Option<usize> {
+        if self.previously_aborted {
+            return None;
+        }
+        aborted_at(Limit::Cancellation)
+    }
+}
+
+impl Drop for CancellationScope {
+    fn drop(&mut self) {
+        TOKENS.with(|tokens| tokens.borrow_mut().pop());
+        POLL.with(|poll| match self.previous_poll {
+            UNLIMITED => poll.set(UNLIMITED),
+            _ => poll.set(poll.get().min(self.previous_poll)),
+        });
+        if !self.previously_aborted && aborted_at(Limit::Cancellation).is_some() {
+            ABORTED.with(|aborted| aborted.set(None));
+        }
+        INSTALLED.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Handle for cancelling a parse from another thread
+///
+/// Clones share the same state, so keep one clone to call [`cancel`](Self::cancel)
+/// on and pass another to `with_cancellation`.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+    deadline: Option<Instant>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A token that cancels itself once `deadline` has passed
+    pub fn with_deadline(deadline: Instant) -> Self {
+        CancellationToken {
+            cancelled: Arc::default(),
+            deadline: Some(deadline),
+        }
+    }
+
+    /// Request every parse using this token to stop
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether the token was cancelled or its deadline has passed
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+            || self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+}
+
+/// Parser combinator that aborts the wrapped parser when a token is cancelled
+pub struct WithCancellation<P> {
+    parser: P,
+    token: CancellationToken,
+}
+
+impl<P> WithCancellation<P> {
+    pub fn new(parser: P, token: CancellationToken) -> Self {
+        WithCancellation { parser, token }
+    }
+}
+
+impl<'code, P> Parser<'code> for WithCancellation<P>
+where
+    P: Parser<'code>,
+    P::Error: 'code,
+    <P::Cursor as Cursor<'code>>::Element: Atomic + 'code,
+{
+    type Cursor = P::Cursor;
+    type Output = P::Output;
+    type Error = ParsicombError<'code, <P::Cursor as Cursor<'code>>::Element>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let scope = CancellationScope::install(&self.token);
+        let result = self.parser.parse(cursor);
+
+        if let Some(position) = scope.cancelled_at() {
+            return Err(ParsicombError::Cancelled(CodeLoc::new(
+                cursor.source(),
+                position,
+            )));
+        }
+        result.map_err(ParsicombError::wrap)
+    }
+}
+
+/// Convenience function to make a parser stop once `token` is cancelled
+pub fn with_cancellation<'code, P>(parser: P, token: CancellationToken) -> WithCancellation<P>
+where
+    P: Parser<'code>,
+{
+    WithCancellation::new(parser, token)
+}
+
+/// Extension trait to add .with_cancellation() method support for parsers
+pub trait CancellationExt<'code>: Parser<'code> + Sized {
+    fn with_cancellation(self, token: CancellationToken) -> WithCancellation<Self> {
+        WithCancellation::new(self, token)
+    }
+}
+
+/// Implement CancellationExt for all parsers
+impl<'code, P> CancellationExt<'code> for P where P: Parser<'code> {}
+
+/// Parser combinator that aborts the wrapped parser after a fixed number of reads
+pub struct WithFuel<P> {
+    parser: P,
+    limit: usize,
+}
+
+impl<P> WithFuel<P> {
+    pub fn new(parser: P, limit: usize) -> Self {
+        WithFuel { parser, limit }
+    }
+}
+
+impl<'code, P> Parser<'code> for WithFuel<P>
+where
+    P: Parser<'code>,
+    P::Error: 'code,
+    <P::Cursor as Cursor<'code>>::Element: Atomic + 'code,
+{
+    type Cursor = P::Cursor;
+    type Output = P::Output;
+    type Error = ParsicombError<'code, <P::Cursor as Cursor<'code>>::Element>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let scope = FuelScope::install(self.limit);
+        let result = self.parser.parse(cursor);
+
+        if let Some(position) = scope.exhausted_at() {
+            return Err(ParsicombError::FuelExhausted(CodeLoc::new(
+                cursor.source(),
+                position,
+            )));
+        }
+        result.map_err(ParsicombError::wrap)
+    }
+}
+
+/// Convenience function to limit a parser to `limit` element reads
+pub fn with_fuel<'code, P>(parser: P, limit: usize) -> WithFuel<P>
+where
+    P: Parser<'code>,
+{
+    WithFuel::new(parser, limit)
+}
+
+/// Extension trait to add .with_fuel() method support for parsers
+pub trait FuelExt<'code>: Parser<'code> + Sized {
+    fn with_fuel(self, limit: usize) -> WithFuel<Self> {
+        WithFuel::new(self, limit)
+    }
+}
+
+/// Implement FuelExt for all parsers
+impl<'code, P> FuelExt<'code> for P where P: Parser<'code> {}
+
+/// Parser combinator that aborts the wrapped parser when `lazy_limited` rules nest too deeply
+pub struct WithRecursionLimit<P> {
+    parser: P,
+    limit: usize,
+}
+
+impl<P> WithRecursionLimit<P> {
+    pub fn new(parser: P, limit: usize) -> Self {
+        WithRecursionLimit { parser, limit }
+    }
+}
+
+impl<'code, P> Parser<'code> for WithRecursionLimit<P>
+where
+    P: Parser<'code>,
+    P::Error: 'code,
+    <P::Cursor as Cursor<'code>>::Element: Atomic + 'code,
+{
+    type Cursor = P::Cursor;
+    type Output = P::Output;
+    type Error = ParsicombError<'code, <P::Cursor as Cursor<'code>>::Element>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let scope = RecursionScope::install(self.limit);
+        let result = self.parser.parse(cursor);
+
+        if let Some(position) = scope.exceeded_at() {
+            let rule_start = EXCEEDED_RULE.with(|exceeded| exceeded.get());
+            return Err(ParsicombError::RecursionLimitExceeded(Span::new(
+                cursor.source(),
+                rule_start,
+                position,
+            )));
+        }
+        result.map_err(ParsicombError::wrap)
+    }
+}
+
+/// Convenience function to limit how deeply `lazy_limited` rules inside a parser may nest
+pub fn with_recursion_limit<'code, P>(parser: P, limit: usize) -> WithRecursionLimit<P>
+where
+    P: Parser<'code>,
+{
+    WithRecursionLimit::new(parser, limit)
+}
+
+/// Extension trait to add .with_recursion_limit() method support for parsers
+pub trait RecursionLimitExt<'code>: Parser<'code> + Sized {
+    fn with_recursion_limit(self, limit: usize) -> WithRecursionLimit<Self> {
+        WithRecursionLimit::new(self, limit)
+    }
+}
+
+/// Implement RecursionLimitExt for all parsers
+impl<'code, P> RecursionLimitExt<'code> for P where P: Parser<'code> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ByteCursor;
+    use crate::and::AndExt;
+    use crate::ascii::take_whitespace;
+    use crate::byte::is_byte;
+    use crate::error::{ErrorLeaf, ErrorNode};
+    use crate::lazy::lazy_limited;
+    use crate::many::many;
+    use crate::map::MapExt;
+    use crate::or::OrExt;
+    use crate::parser::Parser;
+    use crate::utf8::take_until_string;
+    use std::time::Duration;
+
+    #[test]
+    fn test_enough_fuel_succeeds() {
+        let data = b"aaa";
+        let cursor = ByteCursor::new(data);
+        let parser = many(is_byte(b'a')).with_fuel(10);
+
+        let (bytes, cursor) = parser.parse(cursor).unwrap();
+        assert_eq!(bytes, vec![b'a', b'a', b'a']);
+        assert!(cursor.eos());
+    }
+
+    #[test]
+    fn test_fuel_exhausted() {
+        let data = b"aaaaaaaa";
+        let cursor = ByteCursor::new(data);
+        let parser = many(is_byte(b'a')).with_fuel(3);
+
+        let error = parser.parse(cursor).unwrap_err();
+        assert!(matches!(error, ParsicombError::FuelExhausted(_)));
+        assert_eq!(error.loc().position(), 3);
+        assert_eq!(error.message(), "parser ran out of fuel");
+    }
+
+    #[test]
+    fn test_fuel_stops_pathological_backtracking() {
+        // Every level tries the same rule twice, so plain parsing takes 2^n steps
+        fn blowup<'code>() -> impl Parser<'code, Cursor = ByteCursor<'code>, Output = usize> {
+            lazy_limited(|| {
+                is_byte(b'a')
+                    .and(blowup())
+                    .and(is_byte(b'!'))
+                    .map(|((_, n), _)| n + 1)
+                    .or(is_byte(b'a').and(blowup()).map(|(_, n)| n + 1))
+                    .or(is_byte(b'b').map(|_| 0))
+            })
+        }
+
+        let data = [b'a'; 40];
+        let cursor = ByteCursor::new(&data);
+        let error = blowup().with_fuel(10_000).parse(cursor).unwrap_err();
+        assert!(matches!(error, ParsicombError::FuelExhausted(_)));
+    }
+
+    #[test]
+    fn test_eos_burns_no_fuel() {
+        let cursor = ByteCursor::new(b"ab");
+        let scope = FuelScope::install(1);
+
+        for _ in 0..10 {
+            assert!(!cursor.eos());
+        }
+        assert_eq!(cursor.value().unwrap(), b'a');
+        assert!(cursor.value().is_err());
+        // An aborted parse is not the end of the input
+        assert!(!cursor.eos());
+        assert_eq!(scope.exhausted_at(), Some(0));
+    }
+
+    #[test]
+    fn test_slice_scanners_burn_fuel() {
+        let data = b"        a";
+        let (text, _) = take_whitespace()
+            .with_fuel(8)
+            .parse(ByteCursor::new(data))
+            .unwrap();
+        assert_eq!(text.len(), 8);
+
+        let error = take_whitespace()
+            .with_fuel(3)
+            .parse(ByteCursor::new(data))
+            .unwrap_err();
+        assert!(matches!(error, ParsicombError::FuelExhausted(_)));
+        assert_eq!(error.loc().position(), 3);
+
+        // Looking for a terminator that never comes reads the whole input
+        let data = [b'x'; 100];
+        let error = take_until_string("*/")
+            .with_fuel(10)
+            .parse(ByteCursor::new(&data))
+            .unwrap_err();
+        assert!(matches!(error, ParsicombError::FuelExhausted(_)));
+        assert_eq!(error.loc().position(), 10);
+    }
+
+    #[test]
+    fn test_budget_is_restored() {
+        let data = b"aaaa";
+        let cursor = ByteCursor::new(data);
+        assert!(many(is_byte(b'a')).with_fuel(1).parse(cursor).is_err());
+
+        // Without a wrapper parsing is unlimited again
+        let (bytes, _) = many(is_byte(b'a')).parse(cursor).unwrap();
+        assert_eq!(bytes.len(), 4);
+    }
+
+    #[test]
+    fn test_nested_budgets_share_fuel() {
+        let data = b"aaaaaa";
+        let cursor = ByteCursor::new(data);
+
+        // The inner budget is capped by the outer one
+        let parser = with_fuel(many(is_byte(b'a')).with_fuel(100), 2);
+        let error = parser.parse(cursor).unwrap_err();
+        assert!(matches!(error, ParsicombError::FuelExhausted(_)));
+
+        // Fuel burned by the inner parser is charged to the outer budget
+        let parser = with_fuel(
+            is_byte(b'a')
+                .and(is_byte(b'a'))
+                .with_fuel(2)
+                .and(many(is_byte(b'a'))),
+            3,
+        );
+        let error = parser.parse(cursor).unwrap_err();
+        assert_eq!(error.loc().position(), 3);
+    }
+
+    fn parens<'code>() -> impl Parser<'code, Cursor = ByteCursor<'code>, Output = usize> {
+        lazy_limited(|| {
+            is_byte(b'(')
+                .and(parens())
+                .and(is_byte(b')'))
+                .map(|((_, depth), _)| depth + 1)
+                .or(is_byte(b'x').map(|_| 0))
+        })
+    }
+
+    #[test]
+    fn test_recursion_within_limit() {
+        let data = b"(((x)))";
+        let cursor = ByteCursor::new(data);
+
+        let (depth, cursor) = parens().with_recursion_limit(4).parse(cursor).unwrap();
+        assert_eq!(depth, 3);
+        assert!(cursor.eos());
+    }
+
+    #[test]
+    fn test_recursion_limit_exceeded() {
+        let data = b"((((x))))";
+        let cursor = ByteCursor::new(data);
+
+        let error = parens().with_recursion_limit(3).parse(cursor).unwrap_err();
+        assert!(matches!(error, ParsicombError::RecursionLimitExceeded(_)));
+        // The fourth rule starts after three open parens
+        assert_eq!(error.loc().position(), 3);
+        assert_eq!(error.message(), "recursion limit exceeded");
+    }
+
+    #[test]
+    fn test_recursion_limit_spans_deepest_rule() {
+        let error = parens()
+            .with_recursion_limit(3)
+            .parse(ByteCursor::new(b"((((x))))"))
+            .unwrap_err();
+        let ParsicombError::RecursionLimitExceeded(span) = error else {
+            panic!("expected RecursionLimitExceeded, got {:?}", error);
+        };
+        // The third rule starts at the third paren and tried to nest after it
+        assert_eq!((span.start, span.end), (2, 3));
+    }
+
+    /// `rec = rec | 'x'`, which recurses through `lazy_limited` without reading
+    struct LeftRecursive;
+
+    impl<'code> Parser<'code> for LeftRecursive {
+        type Cursor = ByteCursor<'code>;
+        type Output = u8;
+        type Error = ParsicombError<'code>;
+
+        fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+            lazy_limited(|| LeftRecursive)
+                .or(is_byte(b'x'))
+                .parse(cursor)
+                .map_err(ParsicombError::wrap)
+        }
+    }
+
+    #[test]
+    fn test_recursion_limit_stops_left_recursion() {
+        let error = LeftRecursive
+            .with_recursion_limit(100)
+            .parse(ByteCursor::new(b"x"))
+            .unwrap_err();
+        let ParsicombError::RecursionLimitExceeded(span) = error else {
+            panic!("expected RecursionLimitExceeded, got {:?}", error);
+        };
+        assert_eq!((span.start, span.end), (0, 0));
+    }
+
+    #[test]
+    fn test_deeply_nested_input_does_not_overflow() {
+        let mut data = vec![b'('; 100_000];
+        data.push(b'x');
+        let cursor = ByteCursor::new(&data);
+
+        let error = with_recursion_limit(parens(), 200)
+            .parse(cursor)
+            .unwrap_err();
+        assert_eq!(error.loc().position(), 200);
+
+        // Limits are lifted again once the wrapper returns
+        let (depth, _) = parens().parse(ByteCursor::new(b"((x))")).unwrap();
+        assert_eq!(depth, 2);
+    }
+
+    #[test]
+    fn test_cancelled_token_stops_parse() {
+        let data = vec![b'a'; 10_000];
+        let token = CancellationToken::new();
+        token.cancel();
+
+        let error = many(is_byte(b'a'))
+            .with_cancellation(token)
+            .parse(ByteCursor::new(&data))
+            .unwrap_err();
+        assert!(matches!(error, ParsicombError::Cancelled(_)));
+        assert_eq!(error.loc().position(), 0);
+        assert_eq!(error.message(), "parsing was cancelled");
+
+        // Polling stops with the wrapper
+        let (bytes, _) = many(is_byte(b'a')).parse(ByteCursor::new(&data)).unwrap();
+        assert_eq!(bytes.len(), 10_000);
+    }
+
+    #[test]
+    fn test_cancelled_token_stops_slice_scanners() {
+        let data = vec![b' '; 10_000];
+        let token = CancellationToken::new();
+        token.cancel();
+
+        let error = take_whitespace()
+            .with_cancellation(token)
+            .parse(ByteCursor::new(&data))
+            .unwrap_err();
+        assert!(matches!(error, ParsicombError::Cancelled(_)));
+        assert_eq!(error.loc().position(), 0);
+    }
+
+    #[test]
+    fn test_cancel_from_another_thread() {
+        let token = CancellationToken::new();
+        let remote = token.clone();
+        let counter = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let seen = std::sync::Arc::clone(&counter);
+
+        let canceller = std::thread::spawn(move || {
+            while seen.load(Ordering::Relaxed) < 1_000 {
+                std::thread::yield_now();
+            }
+            remote.cancel();
+        });
+
+        // Loops over the input until cancelled
+        let data = b"ab";
+        let parser = many(is_byte(b'a').map(|_| {
+            counter.fetch_add(1, Ordering::Relaxed);
+        }))
+        .with_cancellation(token);
+        let result = (0..).find_map(|_| parser.parse(ByteCursor::new(data)).err());
+        canceller.join().unwrap();
+
+        assert!(matches!(result, Some(ParsicombError::Cancelled(_))));
+    }
+
+    #[test]
+    fn test_expired_deadline_cancels() {
+        let data = b"((((x))))";
+        let token = CancellationToken::with_deadline(Instant::now());
+
+        let error = parens()
+            .with_cancellation(token)
+            .parse(ByteCursor::new(data))
+            .unwrap_err();
+        assert!(matches!(error, ParsicombError::Cancelled(_)));
+
+        let token = CancellationToken::with_deadline(Instant::now() + Duration::from_secs(3600));
+        let (depth, _) = parens()
+            .with_cancellation(token)
+            .parse(ByteCursor::new(data))
+            .unwrap();
+        assert_eq!(depth, 4);
+    }
+
+    #[test]
+    fn test_recursion_limit_inside_fuel() {
+        let data = b"(((((x)))))";
+        let cursor = ByteCursor::new(data);
+
+        let error = parens()
+            .with_recursion_limit(2)
+            .with_fuel(1_000)
+            .parse(cursor)
+            .unwrap_err();
+        assert!(matches!(error, ParsicombError::WrappedError { .. }));
+        assert_eq!(error.likely_error().loc().position(), 2);
+        assert_eq!(error.likely_error().message(), "recursion limit exceeded");
+    }
+}
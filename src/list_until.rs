@@ -0,0 +1,283 @@
+use crate::atomic::Atomic;
+use crate::cursor::Cursor;
+use crate::error::{ErrorLeaf, ErrorNode, MergedError, furthest_only, merge_furthest};
+use crate::parser::Parser;
+use crate::position::Span;
+use std::cell::OnceCell;
+use std::fmt;
+
+/// Error type for ListUntil parser
+pub enum ListUntilError<'code, T: Atomic, EI, ES, ET> {
+    /// An item failed, either the first one or one after a separator
+    Item(EI),
+    /// After an item, neither a separator nor the terminator matched
+    SeparatorOrTerminator {
+        separator: ES,
+        terminator: ET,
+        /// Merged error for ties, built on first use
+        merged: OnceCell<MergedError<'code, T>>,
+    },
+}
+
+impl<'code, T: Atomic, EI, ES, ET> fmt::Debug for ListUntilError<'code, T, EI, ES, ET>
+where
+    EI: fmt::Display,
+    ES: fmt::Display,
+    ET: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ListUntilError::Item(e) => f.debug_tuple("Item").field(&e.to_string()).finish(),
+            ListUntilError::SeparatorOrTerminator {
+                separator,
+                terminator,
+                ..
+            } => f
+                .debug_struct("SeparatorOrTerminator")
+                .field("separator", &separator.to_string())
+                .field("terminator", &terminator.to_string())
+                .finish(),
+        }
+    }
+}
+
+impl<'code, T, EI, ES, ET> fmt::Display for ListUntilError<'code, T, EI, ES, ET>
+where
+    T: Atomic + 'code,
+    EI: ErrorNode<'code, Element = T> + fmt::Display,
+    ES: ErrorNode<'code, Element = T> + fmt::Display,
+    ET: ErrorNode<'code, Element = T> + fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ListUntilError::Item(e) => write!(f, "Item failed: {}", e),
+            ListUntilError::SeparatorOrTerminator { .. } => write!(f, "{}", self.likely_error()),
+        }
+    }
+}
+
+impl<'code, T, EI, ES, ET> std::error::Error for ListUntilError<'code, T, EI, ES, ET>
+where
+    T: Atomic + 'code,
+    EI: ErrorNode<'code, Element = T> + fmt::Display,
+    ES: ErrorNode<'code, Element = T> + fmt::Display,
+    ET: ErrorNode<'code, Element = T> + fmt::Display,
+{
+}
+
+impl<'code, T, EI, ES, ET> ErrorNode<'code> for ListUntilError<'code, T, EI, ES, ET>
+where
+    T: Atomic + 'code,
+    EI: ErrorNode<'code, Element = T> + fmt::Display,
+    ES: ErrorNode<'code, Element = T> + fmt::Display,
+    ET: ErrorNode<'code, Element = T> + fmt::Display,
+{
+    type Element = T;
+
+    fn likely_error(&self) -> &dyn ErrorLeaf<'code, Element = T> {
+        match self {
+            ListUntilError::Item(e) => e.likely_error(),
+            ListUntilError::SeparatorOrTerminator { merged, .. } => {
+                merge_furthest(self.furthest_leaves(), merged)
+            }
+        }
+    }
+
+    fn furthest_leaves(&self) -> Vec<&dyn ErrorLeaf<'code, Element = T>> {
+        match self {
+            ListUntilError::Item(e) => e.furthest_leaves(),
+            ListUntilError::SeparatorOrTerminator {
+                separator,
+                terminator,
+                ..
+            } => {
+                let mut leaves = separator.furthest_leaves();
+                leaves.extend(terminator.furthest_leaves());
+                furthest_only(leaves)
+            }
+        }
+    }
+
+    fn children(&self) -> Vec<&dyn ErrorNode<'code, Element = T>> {
+        match self {
+            ListUntilError::Item(e) => vec![e],
+            ListUntilError::SeparatorOrTerminator {
+                separator,
+                terminator,
+                ..
+            } => vec![separator, terminator],
+        }
+    }
+}
+
+/// Parser combinator for `item (separator item)* terminator` in a single pass
+///
+/// Returns the items together with the span of the terminator. When an item is
+/// followed by neither a separator nor the terminator, the two failures are merged
+/// into one error such as `expected ',' or ']', found 'x'`, rather than the generic
+/// close delimiter failure of `between(open, separated_list(..), close)`.
+///
+/// Example:
+/// ```
+/// use parsicomb::ascii::i64;
+/// use parsicomb::list_until::list_until;
+/// use parsicomb::utf8::string::is_string;
+/// use parsicomb::{ByteCursor, ErrorNode, Parser};
+///
+/// let list = list_until(i64(), is_string(","), is_string("]"));
+///
+/// let ((items, close), _) = list.parse(ByteCursor::new(b"1,2,3]")).unwrap();
+/// assert_eq!(items, vec![1, 2, 3]);
+/// assert_eq!(close.start, 5);
+///
+/// let error = list.parse(ByteCursor::new(b"1,2;3]")).unwrap_err();
+/// assert!(error.likely_error().message().starts_with("expected ',' or ']'"));
+/// ```
+pub struct ListUntil<P, PS, PT> {
+    parser: P,
+    separator: PS,
+    terminator: PT,
+}
+
+impl<P, PS, PT> ListUntil<P, PS, PT> {
+    pub fn new(parser: P, separator: PS, terminator: PT) -> Self {
+        ListUntil {
+            parser,
+            separator,
+            terminator,
+        }
+    }
+}
+
+impl<'code, P, PS, PT> Parser<'code> for ListUntil<P, PS, PT>
+where
+    P: Parser<'code>,
+    PS: Parser<'code, Cursor = P::Cursor>,
+    PT: Parser<'code, Cursor = P::Cursor>,
+    <P::Cursor as Cursor<'code>>::Element: Atomic + 'code,
+{
+    type Cursor = P::Cursor;
+    type Output = (
+        Vec<P::Output>,
+        Span<'code, <P::Cursor as Cursor<'code>>::Element>,
+    );
+    type Error = ListUntilError<
+        'code,
+        <P::Cursor as Cursor<'code>>::Element,
+        P::Error,
+        PS::Error,
+        PT::Error,
+    >;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let mut items = Vec::new();
+        let (item, mut cursor) = self.parser.parse(cursor).map_err(ListUntilError::Item)?;
+        items.push(item);
+
+        loop {
+            let separator = match self.separator.parse(cursor) {
+                Ok((_, next_cursor)) => {
+                    let (item, next_cursor) = self
+                        .parser
+                        .parse(next_cursor)
+                        .map_err(ListUntilError::Item)?;
+                    items.push(item);
+                    cursor = next_cursor;
+                    continue;
+                }
+                Err(error) => error,
+            };
+
+            return match self.terminator.parse(cursor) {
+                Ok((_, next_cursor)) => {
+                    let (data, start) = cursor.inner();
+                    let span = Span::new(data, start, next_cursor.position());
+                    Ok(((items, span), next_cursor))
+                }
+                Err(terminator) => Err(ListUntilError::SeparatorOrTerminator {
+                    separator,
+                    terminator,
+                    merged: OnceCell::new(),
+                }),
+            };
+        }
+    }
+}
+
+/// Creates a parser for items separated by `separator` and ended by `terminator`
+pub fn list_until<'code, P, PS, PT>(
+    parser: P,
+    separator: PS,
+    terminator: PT,
+) -> ListUntil<P, PS, PT>
+where
+    P: Parser<'code>,
+    PS: Parser<'code, Cursor = P::Cursor>,
+    PT: Parser<'code, Cursor = P::Cursor>,
+{
+    ListUntil::new(parser, separator, terminator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ByteCursor;
+    use crate::ascii::i64;
+    use crate::utf8::string::is_string;
+
+    #[test]
+    fn test_list_until_single_item() {
+        let data = b"7;rest";
+        let parser = list_until(i64(), is_string(","), is_string(";"));
+
+        let ((items, close), cursor) = parser.parse(ByteCursor::new(data)).unwrap();
+        assert_eq!(items, vec![7]);
+        assert_eq!((close.start, close.end), (1, 2));
+        assert_eq!(cursor.position(), 2);
+    }
+
+    #[test]
+    fn test_list_until_multi_byte_terminator() {
+        let data = b"1, 2, 3 end";
+        let parser = list_until(i64(), is_string(", "), is_string(" end"));
+
+        let ((items, close), cursor) = parser.parse(ByteCursor::new(data)).unwrap();
+        assert_eq!(items, vec![1, 2, 3]);
+        assert_eq!(close.as_string(), " end");
+        assert!(cursor.eos());
+    }
+
+    #[test]
+    fn test_list_until_merges_separator_and_terminator() {
+        let data = b"1,2 3]";
+        let parser = list_until(i64(), is_string(","), is_string("]"));
+
+        let error = parser.parse(ByteCursor::new(data)).unwrap_err();
+        assert!(matches!(
+            error,
+            ListUntilError::SeparatorOrTerminator { .. }
+        ));
+        let leaf = error.likely_error();
+        assert_eq!(leaf.loc().position(), 3);
+        assert!(leaf.message().starts_with("expected ',' or ']'"));
+    }
+
+    #[test]
+    fn test_list_until_item_error_after_separator() {
+        let data = b"1,]";
+        let parser = list_until(i64(), is_string(","), is_string("]"));
+
+        let error = parser.parse(ByteCursor::new(data)).unwrap_err();
+        assert!(matches!(error, ListUntilError::Item(_)));
+        assert_eq!(error.likely_error().loc().position(), 2);
+    }
+
+    #[test]
+    fn test_list_until_unterminated_at_end_of_input() {
+        let data = b"1,2";
+        let parser = list_until(i64(), is_string(","), is_string("]"));
+
+        let error = parser.parse(ByteCursor::new(data)).unwrap_err();
+        assert_eq!(error.likely_error().loc().position(), 3);
+    }
+}
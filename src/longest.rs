@@ -0,0 +1,267 @@
+use super::parser::Parser;
+use crate::atomic::Atomic;
+use crate::choice::ChoiceError;
+use crate::cursor::Cursor;
+use crate::error::{CodeLoc, ErrorNode, ParsicombError};
+
+// # Longest Combinator - Maximal Munch
+//
+// `or` and `choice` commit to the first alternative that succeeds, so `choice((gt, ge))`
+// reads `>=` as `>` followed by a stray `=`. `longest((gt, ge))` runs every
+// alternative from the same position and keeps the one whose cursor ended up furthest.
+// Ties go to the alternative listed first.
+//
+// Every alternative is always run, so this costs the sum of all alternatives rather
+// than stopping at the first success. If all of them fail the errors are reported
+// like a `choice`.
+
+/// Collections of alternative parsers that can be raced by [`Longest`]
+///
+/// Implemented for tuples of 2 to 8 parsers with the same output type, and for
+/// arrays and vectors of parsers of a single type.
+pub trait LongestParsers<'code> {
+    type Element: Atomic + 'code;
+    type Cursor: Cursor<'code, Element = Self::Element>;
+    type Output;
+
+    /// Run every alternative and return the success that consumed the most input
+    #[allow(clippy::type_complexity)]
+    fn parse_longest(
+        &self,
+        cursor: Self::Cursor,
+    ) -> Result<(Self::Output, Self::Cursor), ChoiceError<'code, Self::Element>>;
+}
+
+/// Keep `candidate` if it ended strictly further than the current best
+fn keep_longest<'code, O, C: Cursor<'code>>(best: &mut Option<(O, C)>, candidate: (O, C)) {
+    match best {
+        Some((_, cursor)) if cursor.position() >= candidate.1.position() => {}
+        _ => *best = Some(candidate),
+    }
+}
+
+macro_rules! impl_longest_parsers {
+    ($($parser:ident $index:tt),+) => {
+        impl<'code, C, O, $($parser),+> LongestParsers<'code> for ($($parser,)+)
+        where
+            C: Cursor<'code>,
+            C::Element: Atomic + 'code,
+            $($parser: Parser<'code, Cursor = C, Output = O>, $parser::Error: 'code,)+
+        {
+            type Element = C::Element;
+            type Cursor = C;
+            type Output = O;
+
+            fn parse_longest(
+                &self,
+                cursor: Self::Cursor,
+            ) -> Result<(Self::Output, Self::Cursor), ChoiceError<'code, C::Element>> {
+                let mut best = None;
+                let mut errors: Vec<Box<dyn ErrorNode<'code, Element = C::Element> + 'code>> =
+                    Vec::new();
+
+                $(
+                    match self.$index.parse(cursor) {
+                        Ok(result) => keep_longest(&mut best, result),
+                        Err(error) => errors.push(Box::new(error)),
+                    }
+                )+
+
+                best.ok_or_else(|| ChoiceError::new(errors))
+            }
+        }
+    };
+}
+
+impl_longest_parsers!(P0 0, P1 1);
+impl_longest_parsers!(P0 0, P1 1, P2 2);
+impl_longest_parsers!(P0 0, P1 1, P2 2, P3 3);
+impl_longest_parsers!(P0 0, P1 1, P2 2, P3 3, P4 4);
+impl_longest_parsers!(P0 0, P1 1, P2 2, P3 3, P4 4, P5 5);
+impl_longest_parsers!(P0 0, P1 1, P2 2, P3 3, P4 4, P5 5, P6 6);
+impl_longest_parsers!(P0 0, P1 1, P2 2, P3 3, P4 4, P5 5, P6 6, P7 7);
+
+/// Run each parser of a slice and keep the success that consumed the most input
+#[allow(clippy::type_complexity)]
+fn parse_slice<'code, P>(
+    parsers: &[P],
+    cursor: P::Cursor,
+) -> Result<(P::Output, P::Cursor), ChoiceError<'code, <P::Cursor as Cursor<'code>>::Element>>
+where
+    P: Parser<'code>,
+    P::Error: 'code,
+    <P::Cursor as Cursor<'code>>::Element: Atomic + 'code,
+{
+    let mut best = None;
+    let mut errors: Vec<Box<dyn ErrorNode<'code, Element = _> + 'code>> = Vec::new();
+
+    if parsers.is_empty() {
+        errors.push(Box::new(ParsicombError::SyntaxError {
+            message: "no alternatives to choose from".into(),
+            loc: CodeLoc::new(cursor.source(), cursor.position()),
+        }));
+    }
+
+    for parser in parsers {
+        match parser.parse(cursor) {
+            Ok(result) => keep_longest(&mut best, result),
+            Err(error) => errors.push(Box::new(error)),
+        }
+    }
+
+    best.ok_or_else(|| ChoiceError::new(errors))
+}
+
+impl<'code, P, const N: usize> LongestParsers<'code> for [P; N]
+where
+    P: Parser<'code>,
+    P::Error: 'code,
+    <P::Cursor as Cursor<'code>>::Element: Atomic + 'code,
+{
+    type Element = <P::Cursor as Cursor<'code>>::Element;
+    type Cursor = P::Cursor;
+    type Output = P::Output;
+
+    fn parse_longest(
+        &self,
+        cursor: Self::Cursor,
+    ) -> Result<(Self::Output, Self::Cursor), ChoiceError<'code, Self::Element>> {
+        parse_slice(self, cursor)
+    }
+}
+
+impl<'code, P> LongestParsers<'code> for Vec<P>
+where
+    P: Parser<'code>,
+    P::Error: 'code,
+    <P::Cursor as Cursor<'code>>::Element: Atomic + 'code,
+{
+    type Element = <P::Cursor as Cursor<'code>>::Element;
+    type Cursor = P::Cursor;
+    type Output = P::Output;
+
+    fn parse_longest(
+        &self,
+        cursor: Self::Cursor,
+    ) -> Result<(Self::Output, Self::Cursor), ChoiceError<'code, Self::Element>> {
+        parse_slice(self, cursor)
+    }
+}
+
+/// Parser combinator that runs every alternative and keeps the one that consumed the most input
+///
+/// Example:
+/// ```
+/// use parsicomb::utf8::string::is_string;
+/// use parsicomb::{ByteCursor, Cursor, Parser, longest};
+///
+/// let data = b">=1";
+/// let cursor = ByteCursor::new(data);
+/// let (op, cursor) = longest((is_string(">"), is_string(">="))).parse(cursor).unwrap();
+/// assert_eq!(op, ">=");
+/// assert_eq!(cursor.position(), 2);
+/// ```
+pub struct Longest<T> {
+    parsers: T,
+}
+
+impl<T> Longest<T> {
+    pub fn new(parsers: T) -> Self {
+        Longest { parsers }
+    }
+}
+
+impl<'code, T> Parser<'code> for Longest<T>
+where
+    T: LongestParsers<'code>,
+{
+    type Cursor = T::Cursor;
+    type Output = T::Output;
+    type Error = ChoiceError<'code, T::Element>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        self.parsers.parse_longest(cursor)
+    }
+}
+
+/// Convenience function to create a Longest parser from a tuple, array or vector of parsers
+pub fn longest<'code, T>(parsers: T) -> Longest<T>
+where
+    T: LongestParsers<'code>,
+{
+    Longest::new(parsers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ByteCursor;
+    use crate::byte::is_byte;
+    use crate::choice::choice;
+    use crate::map::MapExt;
+    use crate::utf8::string::is_string;
+
+    #[test]
+    fn test_longest_beats_first_match() {
+        let data = b">=";
+
+        let (op, _) = choice((is_string(">"), is_string(">=")))
+            .parse(ByteCursor::new(data))
+            .unwrap();
+        assert_eq!(op, ">");
+
+        let (op, cursor) = longest((is_string(">"), is_string(">=")))
+            .parse(ByteCursor::new(data))
+            .unwrap();
+        assert_eq!(op, ">=");
+        assert!(cursor.eos());
+    }
+
+    #[test]
+    fn test_longest_tie_goes_to_first() {
+        let data = b"ab";
+        let first = is_string("ab").map(|_| 1);
+        let second = is_string("ab").map(|_| 2);
+
+        let (value, _) = longest((first, second))
+            .parse(ByteCursor::new(data))
+            .unwrap();
+        assert_eq!(value, 1);
+    }
+
+    #[test]
+    fn test_longest_vec_skips_failures() {
+        let data = b"<<=";
+        let operators = vec![
+            is_string("<"),
+            is_string("<<="),
+            is_string("<="),
+            is_string("<<"),
+        ];
+
+        let (op, _) = longest(operators).parse(ByteCursor::new(data)).unwrap();
+        assert_eq!(op, "<<=");
+    }
+
+    #[test]
+    fn test_longest_all_fail_merges_errors() {
+        let data = b"x";
+        let error = longest((is_byte(b'a'), is_byte(b'b')))
+            .parse(ByteCursor::new(data))
+            .unwrap_err();
+
+        assert_eq!(error.alternatives().len(), 2);
+        assert!(
+            error
+                .to_string()
+                .contains("expected byte 0x61 ('a') or byte 0x62 ('b'), found 0x78 ('x')")
+        );
+    }
+
+    #[test]
+    fn test_longest_empty_vec_fails() {
+        let parsers: Vec<crate::byte::IsByteParser> = Vec::new();
+        let error = longest(parsers).parse(ByteCursor::new(b"a")).unwrap_err();
+        assert!(error.to_string().contains("no alternatives"));
+    }
+}
@@ -1,6 +1,14 @@
 use super::parser::Parser;
+use crate::atomic::Atomic;
+use crate::cursor::Cursor;
+use crate::describe::Description;
+use crate::error::{ErrorNode, ParsicombError};
+use crate::position::Span;
 
 /// Parser combinator that matches zero or more occurrences of the given parser
+///
+/// The first failing item ends the repetition, unless its error is
+/// [committed](crate::cut::CutExt::cut), in which case the error is returned.
 pub struct Many<P> {
     parser: P,
 }
@@ -22,21 +30,24 @@ where
     fn parse(&self, mut cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
         let mut results = Vec::new();
 
+        // Many matches zero or more, so errors end the repetition unless they are committed
         loop {
             match self.parser.parse(cursor) {
                 Ok((value, next_cursor)) => {
                     results.push(value);
                     cursor = next_cursor;
                 }
-                Err(_) => {
-                    // Many matches zero or more, so error is not propagated
-                    break;
-                }
+                Err(error) if error.is_committed() => return Err(error),
+                Err(_) => break,
             }
         }
 
         Ok((results, cursor))
     }
+
+    fn describe(&self) -> Description {
+        Description::repeat(0, self.parser.describe())
+    }
 }
 
 /// Convenience function to create a Many parser
@@ -47,12 +58,126 @@ where
     Many::new(parser)
 }
 
+/// Parser combinator like [`Many`] that fails when there are more than `max_items` matches
+///
+/// Guards against untrusted input that repeats an element until memory runs out.
+/// The error is a `ParsicombError::InvalidValue` spanning the oversized region,
+/// from the start of the repetition to the end of the first item over the limit.
+/// Committed item errors are wrapped in a `ParsicombError::WrappedError`.
+pub struct ManyLimited<P> {
+    parser: P,
+    max_items: usize,
+}
+
+impl<P> ManyLimited<P> {
+    pub fn new(parser: P, max_items: usize) -> Self {
+        ManyLimited { parser, max_items }
+    }
+}
+
+impl<'code, P> Parser<'code> for ManyLimited<P>
+where
+    P: Parser<'code>,
+    P::Error: 'code,
+    <P::Cursor as Cursor<'code>>::Element: Atomic + 'code,
+{
+    type Cursor = P::Cursor;
+    type Output = Vec<P::Output>;
+    type Error = ParsicombError<'code, <P::Cursor as Cursor<'code>>::Element>;
+
+    fn parse(&self, mut cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let start = cursor.position();
+        let mut results = Vec::new();
+
+        loop {
+            let (value, next_cursor) = match self.parser.parse(cursor) {
+                Ok(result) => result,
+                Err(error) if error.is_committed() => return Err(ParsicombError::wrap(error)),
+                Err(_) => break,
+            };
+            if results.len() == self.max_items {
+                return Err(ParsicombError::InvalidValue {
+                    message: format!("more than {} repetitions", self.max_items).into(),
+                    span: Span::new(next_cursor.source(), start, next_cursor.position()),
+                });
+            }
+            results.push(value);
+            cursor = next_cursor;
+        }
+
+        Ok((results, cursor))
+    }
+
+    fn describe(&self) -> Description {
+        Description::repeat(0, self.parser.describe())
+    }
+}
+
+/// Convenience function to create a ManyLimited parser
+pub fn many_limited<'code, P>(parser: P, max_items: usize) -> ManyLimited<P>
+where
+    P: Parser<'code>,
+{
+    ManyLimited::new(parser, max_items)
+}
+
+/// Parser combinator like [`Many`] that also returns the error that ended the repetition
+///
+/// The output is the items parsed so far together with the error of the attempt
+/// that failed, or `None` if the items reach the end of the input. It never fails,
+/// so callers such as REPLs can use what parsed and still report what did not.
+pub struct ManyPartial<P> {
+    parser: P,
+}
+
+impl<P> ManyPartial<P> {
+    pub fn new(parser: P) -> Self {
+        ManyPartial { parser }
+    }
+}
+
+impl<'code, P> Parser<'code> for ManyPartial<P>
+where
+    P: Parser<'code>,
+{
+    type Cursor = P::Cursor;
+    type Output = (Vec<P::Output>, Option<P::Error>);
+    type Error = P::Error;
+
+    fn parse(&self, mut cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let mut results = Vec::new();
+
+        loop {
+            if cursor.eos() {
+                return Ok(((results, None), cursor));
+            }
+            match self.parser.parse(cursor) {
+                Ok((value, next_cursor)) => {
+                    results.push(value);
+                    cursor = next_cursor;
+                }
+                Err(error) => return Ok(((results, Some(error)), cursor)),
+            }
+        }
+    }
+}
+
+/// Convenience function to create a ManyPartial parser
+pub fn many_partial<'code, P>(parser: P) -> ManyPartial<P>
+where
+    P: Parser<'code>,
+{
+    ManyPartial::new(parser)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::ByteCursor;
     use crate::Cursor;
+    use crate::and::AndExt;
     use crate::byte::{ByteParser, is_byte};
+    use crate::error::ErrorNode;
 
     #[test]
     fn test_many_zero_matches() {
@@ -119,4 +244,52 @@ mod tests {
         assert_eq!(results, vec![]);
         assert!(matches!(cursor, ByteCursor::EndOfFile { .. }));
     }
+
+    #[test]
+    fn test_many_limited_within_limit() {
+        let data = b"aaab";
+        let parser = many_limited(is_byte(b'a'), 3);
+
+        let (results, cursor) = parser.parse(ByteCursor::new(data)).unwrap();
+        assert_eq!(results, vec![b'a'; 3]);
+        assert_eq!(cursor.value().unwrap(), b'b');
+    }
+
+    #[test]
+    fn test_many_limited_reports_oversized_region() {
+        let data = b"xaaaa";
+        let cursor = ByteCursor::new(data).next();
+        let parser = many_limited(is_byte(b'a'), 2);
+
+        let error = parser.parse(cursor).unwrap_err();
+        match error {
+            ParsicombError::InvalidValue { message, span } => {
+                assert_eq!(message, "more than 2 repetitions");
+                assert_eq!(span, Span::new(data, 1, 4));
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_many_partial_keeps_items_and_error() {
+        let data = b"ababac";
+        let parser = many_partial(is_byte(b'a').and(is_byte(b'b')));
+
+        let ((results, error), cursor) = parser.parse(ByteCursor::new(data)).unwrap();
+        assert_eq!(results, vec![(b'a', b'b'); 2]);
+        assert_eq!(error.unwrap().likely_error().loc().position(), 5);
+        assert_eq!(cursor.position(), 4);
+    }
+
+    #[test]
+    fn test_many_partial_to_end_of_input() {
+        let data = b"aaa";
+        let parser = many_partial(is_byte(b'a'));
+
+        let ((results, error), cursor) = parser.parse(ByteCursor::new(data)).unwrap();
+        assert_eq!(results, vec![b'a'; 3]);
+        assert!(error.is_none());
+        assert!(cursor.eos());
+    }
 }
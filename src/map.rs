@@ -1,4 +1,11 @@
 use super::parser::Parser;
+use crate::atomic::Atomic;
+use crate::cursor::Cursor;
+use crate::describe::Description;
+use crate::error::{ErrorNode, ParsicombError};
+use crate::filter::FilterError;
+use crate::position::Span;
+use std::borrow::Cow;
 
 /// Parser combinator that transforms the output of a parser using a mapping function
 pub struct Map<P, F> {
@@ -26,6 +33,10 @@ where
         let mapped_value = (self.mapper)(value);
         Ok((mapped_value, cursor))
     }
+
+    fn describe(&self) -> Description {
+        self.parser.describe()
+    }
 }
 
 /// Convenience function to create a Map parser
@@ -50,11 +61,82 @@ pub trait MapExt<'code>: Parser<'code> + Sized {
 /// Implement MapExt for all parsers
 impl<'code, P> MapExt<'code> for P where P: Parser<'code> {}
 
+/// Parser combinator that transforms the output with a projection that may reject it
+///
+/// A rejected value becomes a `ParsicombError::InvalidValue` spanning all input the
+/// child parser consumed, carrying the projection's message.
+pub struct TryMap<P, F> {
+    parser: P,
+    mapper: F,
+}
+
+impl<P, F> TryMap<P, F> {
+    pub fn new(parser: P, mapper: F) -> Self {
+        TryMap { parser, mapper }
+    }
+}
+
+impl<'code, P, F, U> Parser<'code> for TryMap<P, F>
+where
+    P: Parser<'code>,
+    <P::Cursor as Cursor<'code>>::Element: Atomic + 'code,
+    P::Error: ErrorNode<'code, Element = <P::Cursor as Cursor<'code>>::Element>,
+    F: Fn(P::Output) -> Result<U, Cow<'static, str>>,
+{
+    type Cursor = P::Cursor;
+    type Output = U;
+    type Error = FilterError<'code, P::Error, <P::Cursor as Cursor<'code>>::Element>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (value, new_cursor) = self
+            .parser
+            .parse(cursor)
+            .map_err(FilterError::ParserError)?;
+
+        match (self.mapper)(value) {
+            Ok(mapped_value) => Ok((mapped_value, new_cursor)),
+            Err(message) => {
+                let (data, start) = cursor.inner();
+                Err(FilterError::FilterFailed(ParsicombError::InvalidValue {
+                    message,
+                    span: Span::new(data, start, new_cursor.position()),
+                }))
+            }
+        }
+    }
+
+    fn describe(&self) -> Description {
+        self.parser.describe()
+    }
+}
+
+/// Convenience function to create a TryMap parser
+pub fn try_map<'code, P, F, U>(parser: P, mapper: F) -> TryMap<P, F>
+where
+    P: Parser<'code>,
+    F: Fn(P::Output) -> Result<U, Cow<'static, str>>,
+{
+    TryMap::new(parser, mapper)
+}
+
+/// Extension trait to add .try_map() method support for parsers
+pub trait TryMapExt<'code>: Parser<'code> + Sized {
+    fn try_map<F, U>(self, mapper: F) -> TryMap<Self, F>
+    where
+        F: Fn(Self::Output) -> Result<U, Cow<'static, str>>,
+    {
+        TryMap::new(self, mapper)
+    }
+}
+
+/// Implement TryMapExt for all parsers
+impl<'code, P> TryMapExt<'code> for P where P: Parser<'code> {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::ByteCursor;
-    use crate::ascii::i64;
+    use crate::ascii::{i64, u64};
     use crate::byte::is_byte;
     use crate::or::OrExt;
 
@@ -119,7 +201,7 @@ mod tests {
 
         // Create parsers that map to a common enum type
         let letter_parser = is_byte(b'A').map(|byte| Token::Letter(byte as char));
-        let number_parser = i64().map(|num| Token::Number(num));
+        let number_parser = i64().map(Token::Number);
         let special_parser = is_byte(b'!').map(|byte| Token::Special(byte as char));
 
         // Now we can use or() since they all return Token
@@ -150,4 +232,55 @@ mod tests {
         assert_eq!(ch, '9');
         assert!(matches!(cursor, ByteCursor::EndOfFile { .. }));
     }
+
+    #[derive(Debug, PartialEq)]
+    enum Weekday {
+        Monday,
+        Tuesday,
+    }
+
+    fn weekday(n: u64) -> Result<Weekday, Cow<'static, str>> {
+        match n {
+            1 => Ok(Weekday::Monday),
+            2 => Ok(Weekday::Tuesday),
+            _ => Err(format!("no weekday numbered {}", n).into()),
+        }
+    }
+
+    #[test]
+    fn test_try_map_success() {
+        let data = b"2;";
+        let cursor = ByteCursor::new(data);
+
+        let (day, cursor) = u64().try_map(weekday).parse(cursor).unwrap();
+        assert_eq!(day, Weekday::Tuesday);
+        assert_eq!(cursor.value().unwrap(), b';');
+    }
+
+    #[test]
+    fn test_try_map_failure_spans_consumed_input() {
+        let data = b"x=42;";
+        let cursor = ByteCursor::new(data).next().next();
+
+        let error = try_map(u64(), weekday).parse(cursor).unwrap_err();
+        match &error {
+            FilterError::FilterFailed(ParsicombError::InvalidValue { message, span }) => {
+                assert_eq!(message, "no weekday numbered 42");
+                assert_eq!((span.start, span.end), (2, 4));
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+        assert_eq!(error.likely_error().loc().position(), 2);
+        assert!(error.to_string().contains("Invalid value"));
+    }
+
+    #[test]
+    fn test_try_map_passes_child_error_through() {
+        let data = b"abc";
+        let error = u64()
+            .try_map(weekday)
+            .parse(ByteCursor::new(data))
+            .unwrap_err();
+        assert!(matches!(error, FilterError::ParserError(_)));
+    }
 }
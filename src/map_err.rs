@@ -1,8 +1,72 @@
 use super::parser::Parser;
+use crate::atomic::Atomic;
 use crate::cursor::Cursor;
-use crate::error::ErrorNode;
+use crate::describe::Description;
+use crate::error::{ErrorLeaf, ErrorNode};
 use std::fmt;
 
+/// Error type for MapErr parser holding the mapped error
+///
+/// Remembers whether the original error was committed, so a cut parser stays
+/// committed when its error is replaced, see [`ErrorNode::is_committed`].
+pub struct MapErrError<E> {
+    error: E,
+    committed: bool,
+}
+
+impl<E> MapErrError<E> {
+    /// The error returned by the mapping function
+    pub fn inner(&self) -> &E {
+        &self.error
+    }
+
+    /// Unwrap the error returned by the mapping function
+    pub fn into_inner(self) -> E {
+        self.error
+    }
+}
+
+impl<E: fmt::Debug> fmt::Debug for MapErrError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MapErrError")
+            .field("error", &self.error)
+            .field("committed", &self.committed)
+            .finish()
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for MapErrError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.error)
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> std::error::Error for MapErrError<E> {}
+
+impl<'code, E, T> ErrorNode<'code> for MapErrError<E>
+where
+    E: ErrorNode<'code, Element = T>,
+    T: Atomic + 'code,
+{
+    type Element = T;
+
+    fn likely_error(&self) -> &dyn ErrorLeaf<'code, Element = T> {
+        self.error.likely_error()
+    }
+
+    fn furthest_leaves(&self) -> Vec<&dyn ErrorLeaf<'code, Element = T>> {
+        self.error.furthest_leaves()
+    }
+
+    fn children(&self) -> Vec<&dyn ErrorNode<'code, Element = T>> {
+        vec![&self.error]
+    }
+
+    fn is_committed(&self) -> bool {
+        self.committed || self.error.is_committed()
+    }
+}
+
 /// Parser combinator that transforms the error of a parser using a mapping function
 pub struct MapErr<P, F> {
     parser: P,
@@ -27,18 +91,26 @@ where
     }
 }
 
-impl<'code, P, F, E1, E2> Parser<'code> for MapErr<P, F>
+impl<'code, P, F, E2> Parser<'code> for MapErr<P, F>
 where
-    P: Parser<'code, Error = E1>,
-    F: Fn(E1) -> E2,
+    P: Parser<'code>,
+    F: Fn(P::Error) -> E2,
     E2: std::error::Error + ErrorNode<'code, Element = <P::Cursor as Cursor<'code>>::Element>,
+    <P::Cursor as Cursor<'code>>::Element: Atomic + 'code,
 {
     type Cursor = P::Cursor;
     type Output = P::Output;
-    type Error = E2;
+    type Error = MapErrError<E2>;
 
     fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
-        self.parser.parse(cursor).map_err(&self.mapper)
+        self.parser.parse(cursor).map_err(|error| MapErrError {
+            committed: error.is_committed(),
+            error: (self.mapper)(error),
+        })
+    }
+
+    fn describe(&self) -> Description {
+        self.parser.describe()
     }
 }
 
@@ -148,7 +220,7 @@ mod tests {
 
         assert!(result.is_err());
         assert_eq!(
-            result.unwrap_err(),
+            result.unwrap_err().into_inner(),
             CustomError::Simple("mapped error".to_string())
         );
     }
@@ -176,7 +248,7 @@ mod tests {
         let result = parser.parse(cursor);
 
         assert!(result.is_err());
-        assert_eq!(result.unwrap_err(), CustomError::WithCode(404));
+        assert_eq!(result.unwrap_err().into_inner(), CustomError::WithCode(404));
     }
 
     #[test]
@@ -190,7 +262,7 @@ mod tests {
 
         let result = parser.parse(cursor);
         assert!(result.is_err());
-        assert_eq!(result.unwrap_err(), CustomError::WithCode(500));
+        assert_eq!(result.unwrap_err().into_inner(), CustomError::WithCode(500));
     }
 
     #[test]
@@ -203,7 +275,7 @@ mod tests {
 
         let result = parser.parse(cursor);
         assert!(result.is_err());
-        let error_msg = match result.unwrap_err() {
+        let error_msg = match result.unwrap_err().into_inner() {
             CustomError::Simple(msg) => msg,
             _ => panic!("Expected Simple error"),
         };
@@ -222,7 +294,7 @@ mod tests {
 
         assert!(result.is_err());
         assert_eq!(
-            result.unwrap_err(),
+            result.unwrap_err().into_inner(),
             CustomError::Simple("string error".to_string())
         );
     }
@@ -237,6 +309,21 @@ mod tests {
         let result = parser.parse(cursor);
 
         assert!(result.is_err());
-        assert_eq!(result.unwrap_err(), CustomError::WithCode(42));
+        assert_eq!(result.unwrap_err().into_inner(), CustomError::WithCode(42));
+    }
+
+    #[test]
+    fn test_map_err_keeps_cut_committed_inside_many() {
+        use crate::and::AndExt;
+        use crate::ascii::i64;
+        use crate::byte::is_byte;
+        use crate::cut::CutExt;
+        use crate::many::many;
+
+        let negative = is_byte(b'-')
+            .and(i64().cut())
+            .map_err(|_| CustomError::Simple("expected a negative number".to_string()));
+        let error = many(negative).parse(ByteCursor::new(b"-1-x")).unwrap_err();
+        assert!(error.is_committed());
     }
 }
@@ -0,0 +1,473 @@
+use crate::atomic::Atomic;
+use crate::cursor::Cursor;
+use crate::describe::Description;
+use crate::error::{ErrorLeaf, ErrorNode};
+use crate::parser::Parser;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fmt;
+use std::marker::PhantomData;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// # Memoize Combinator - Packrat Parsing
+//
+// Grammars that lean heavily on `or` re-parse the same prefix once per failing
+// alternative. Wrapping shared sub-rules in `memoize` caches each result by cursor
+// position so every (rule, position) pair is parsed at most once, which gives
+// packrat-style linear behavior for backtracking-heavy grammars.
+//
+// Caching is scoped to a `ParseContext`, installed for one parse with
+// `.with_parse_context()` or held open with `ParseContext::enter()`. Every context
+// gets a fresh id, and a memoized rule only answers from entries recorded under the
+// active context, so results never leak from one parse into the next even when a new
+// input reuses the address of an old one. Outside of a context `memoize` simply runs
+// the wrapped parser.
+//
+// The entries themselves live in the `Memoize` instance, which acts as the parser id:
+// outputs borrow from the source, so they cannot be stored type-erased in the
+// thread-local context. Rules share a cache by sharing the instance, through `&P` or
+// `Rc<P>`, and a grammar built once can be reused across parses.
+
+/// Source for the ids of parse contexts
+static NEXT_CONTEXT: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    /// Id of the parse context active on this thread, if any
+    static ACTIVE_CONTEXT: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// Scope in which memoized rules cache their results
+///
+/// Entering a context while another one is active on the thread joins the enclosing
+/// context, so a sub-grammar that installs its own context keeps sharing the cache of
+/// a larger parse. The context ends when the outermost guard is dropped.
+pub struct ParseContext {
+    /// Whether this guard opened the context, as opposed to joining an enclosing one
+    opened: bool,
+    /// Contexts are per thread, so the guard must stay on the thread that entered it
+    _not_send: PhantomData<*const ()>,
+}
+
+impl ParseContext {
+    /// Enter a parse context on this thread until the returned guard is dropped
+    pub fn enter() -> Self {
+        let opened = ACTIVE_CONTEXT.with(|active| {
+            if active.get().is_some() {
+                return false;
+            }
+            active.set(Some(NEXT_CONTEXT.fetch_add(1, Ordering::Relaxed)));
+            true
+        });
+        ParseContext {
+            opened,
+            _not_send: PhantomData,
+        }
+    }
+
+    /// Id of the context active on this thread, if any
+    fn active() -> Option<usize> {
+        ACTIVE_CONTEXT.with(|active| active.get())
+    }
+}
+
+impl Drop for ParseContext {
+    fn drop(&mut self) {
+        if self.opened {
+            ACTIVE_CONTEXT.with(|active| active.set(None));
+        }
+    }
+}
+
+/// Parser combinator that runs the wrapped parser inside a [`ParseContext`]
+pub struct WithParseContext<P> {
+    parser: P,
+}
+
+impl<P> WithParseContext<P> {
+    pub fn new(parser: P) -> Self {
+        WithParseContext { parser }
+    }
+}
+
+impl<'code, P> Parser<'code> for WithParseContext<P>
+where
+    P: Parser<'code>,
+{
+    type Cursor = P::Cursor;
+    type Output = P::Output;
+    type Error = P::Error;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let _context = ParseContext::enter();
+        self.parser.parse(cursor)
+    }
+
+    fn describe(&self) -> Description {
+        self.parser.describe()
+    }
+}
+
+/// Convenience function to run a parser inside a fresh parse context
+pub fn with_parse_context<'code, P>(parser: P) -> WithParseContext<P>
+where
+    P: Parser<'code>,
+{
+    WithParseContext::new(parser)
+}
+
+/// Extension trait to add .with_parse_context() method support for parsers
+pub trait ParseContextExt<'code>: Parser<'code> + Sized {
+    fn with_parse_context(self) -> WithParseContext<Self> {
+        WithParseContext::new(self)
+    }
+}
+
+/// Implement ParseContextExt for all parsers
+impl<'code, P> ParseContextExt<'code> for P where P: Parser<'code> {}
+
+/// Error type for Memoize that shares one cached failure between repeated lookups
+pub struct MemoizeError<E>(Rc<E>);
+
+impl<E> MemoizeError<E> {
+    /// Access the cached error produced by the wrapped parser
+    pub fn inner(&self) -> &E {
+        &self.0
+    }
+}
+
+impl<E: fmt::Debug> fmt::Debug for MemoizeError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("MemoizeError").field(&self.0).finish()
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for MemoizeError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<E: std::error::Error> std::error::Error for MemoizeError<E> {}
+
+impl<'code, E, T: Atomic + 'code> ErrorNode<'code> for MemoizeError<E>
+where
+    E: ErrorNode<'code, Element = T>,
+{
+    type Element = T;
+
+    fn likely_error(&self) -> &dyn ErrorLeaf<'code, Element = Self::Element> {
+        self.0.likely_error()
+    }
+
+    fn children(&self) -> Vec<&dyn ErrorNode<'code, Element = Self::Element>> {
+        vec![self.0.as_ref()]
+    }
+}
+
+type MemoEntry<O, C, E> = Result<(O, C), Rc<E>>;
+
+/// Address and length of the source, and the position within it
+type MemoKey = (usize, usize, usize);
+
+/// Cached parse results for a single parse context
+struct MemoTable<O, C, E> {
+    /// Id of the context the entries were recorded under
+    context: Option<usize>,
+    entries: HashMap<MemoKey, MemoEntry<O, C, E>>,
+}
+
+/// Parser combinator that caches the result of the wrapped parser per input position
+///
+/// Successful outputs are cloned out of the cache, failures are shared through
+/// [`MemoizeError`], so the wrapped parser runs at most once per position within a
+/// [`ParseContext`]. Outside of a context every call runs the wrapped parser.
+pub struct Memoize<'code, P>
+where
+    P: Parser<'code>,
+{
+    parser: P,
+    table: RefCell<MemoTable<P::Output, P::Cursor, P::Error>>,
+}
+
+impl<'code, P> Memoize<'code, P>
+where
+    P: Parser<'code>,
+{
+    pub fn new(parser: P) -> Self {
+        Memoize {
+            parser,
+            table: RefCell::new(MemoTable {
+                context: None,
+                entries: HashMap::new(),
+            }),
+        }
+    }
+
+    /// Drop all cached results
+    pub fn clear(&self) {
+        let mut table = self.table.borrow_mut();
+        table.context = None;
+        table.entries.clear();
+    }
+}
+
+/// Produce a fresh result from a cached entry
+fn lookup<O: Clone, C: Copy, E>(entry: &MemoEntry<O, C, E>) -> Result<(O, C), MemoizeError<E>> {
+    match entry {
+        Ok((output, cursor)) => Ok((output.clone(), *cursor)),
+        Err(error) => Err(MemoizeError(Rc::clone(error))),
+    }
+}
+
+impl<'code, P> Parser<'code> for Memoize<'code, P>
+where
+    P: Parser<'code>,
+    P::Output: Clone,
+    P::Error: 'code,
+    <P::Cursor as Cursor<'code>>::Element: Atomic + 'code,
+{
+    type Cursor = P::Cursor;
+    type Output = P::Output;
+    type Error = MemoizeError<P::Error>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let Some(context) = ParseContext::active() else {
+            return self
+                .parser
+                .parse(cursor)
+                .map_err(|e| MemoizeError(Rc::new(e)));
+        };
+        let source = cursor.source();
+        let key = (source.as_ptr() as usize, source.len(), cursor.position());
+
+        {
+            let mut table = self.table.borrow_mut();
+            if table.context != Some(context) {
+                table.entries.clear();
+                table.context = Some(context);
+            }
+            if let Some(entry) = table.entries.get(&key) {
+                return lookup(entry);
+            }
+        }
+
+        // The table borrow is released here since the wrapped parser may recurse into us
+        let entry = self.parser.parse(cursor).map_err(Rc::new);
+        let result = lookup(&entry);
+        self.table.borrow_mut().entries.insert(key, entry);
+        result
+    }
+
+    fn describe(&self) -> Description {
+        self.parser.describe()
+    }
+}
+
+/// Convenience function to create a Memoize parser
+pub fn memoize<'code, P>(parser: P) -> Memoize<'code, P>
+where
+    P: Parser<'code>,
+{
+    Memoize::new(parser)
+}
+
+/// Extension trait to add .memoize() method support for parsers
+pub trait MemoizeExt<'code>: Parser<'code> + Sized {
+    fn memoize(self) -> Memoize<'code, Self> {
+        Memoize::new(self)
+    }
+}
+
+/// Implement MemoizeExt for all parsers
+impl<'code, P> MemoizeExt<'code> for P where P: Parser<'code> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ByteCursor;
+    use crate::and::AndExt;
+    use crate::byte::is_byte;
+    use crate::map::MapExt;
+    use crate::or::OrExt;
+    use crate::utf8::string::is_string;
+    use std::cell::Cell;
+
+    /// Parser that counts how often it is invoked
+    struct Counting<'a, P> {
+        parser: P,
+        calls: &'a Cell<usize>,
+    }
+
+    impl<'code, P: Parser<'code>> Parser<'code> for Counting<'_, P> {
+        type Cursor = P::Cursor;
+        type Output = P::Output;
+        type Error = P::Error;
+
+        fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+            self.calls.set(self.calls.get() + 1);
+            self.parser.parse(cursor)
+        }
+    }
+
+    #[test]
+    fn test_memoize_returns_same_result() {
+        let data = b"hello world";
+        let cursor = ByteCursor::new(data);
+        let parser = memoize(is_string("hello"));
+
+        let (first, first_cursor) = parser.parse(cursor).unwrap();
+        let (second, second_cursor) = parser.parse(cursor).unwrap();
+        assert_eq!(first, second);
+        assert_eq!(first_cursor.position(), 5);
+        assert_eq!(second_cursor.position(), 5);
+    }
+
+    #[test]
+    fn test_memoize_parses_each_position_once() {
+        let calls = Cell::new(0);
+        let data = b"abc";
+        let cursor = ByteCursor::new(data);
+        let parser = Counting {
+            parser: is_string("abc"),
+            calls: &calls,
+        }
+        .memoize();
+
+        let _context = ParseContext::enter();
+        parser.parse(cursor).unwrap();
+        parser.parse(cursor).unwrap();
+        parser.parse(cursor).unwrap();
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn test_memoize_caches_failures() {
+        let calls = Cell::new(0);
+        let data = b"xyz";
+        let cursor = ByteCursor::new(data);
+        let parser = Counting {
+            parser: is_byte(b'a'),
+            calls: &calls,
+        }
+        .memoize();
+
+        let _context = ParseContext::enter();
+        assert!(parser.parse(cursor).is_err());
+        let error = parser.parse(cursor).unwrap_err();
+        assert_eq!(calls.get(), 1);
+        assert_eq!(error.likely_error().loc().position(), 0);
+        assert!(error.to_string().contains("expected byte 0x61"));
+    }
+
+    #[test]
+    fn test_memoize_shared_prefix_in_or_chain() {
+        let calls = Cell::new(0);
+        let data = b"key=3";
+        let cursor = ByteCursor::new(data);
+
+        // Both alternatives start with the same rule, which is only parsed once
+        let key = Rc::new(
+            Counting {
+                parser: is_string("key"),
+                calls: &calls,
+            }
+            .memoize(),
+        );
+        let first = SharedRule(Rc::clone(&key))
+            .and(is_byte(b':'))
+            .map(|(k, _)| k);
+        let second = SharedRule(Rc::clone(&key))
+            .and(is_byte(b'='))
+            .map(|(k, _)| k);
+        let parser = first.or(second).with_parse_context();
+
+        let (matched, cursor) = parser.parse(cursor).unwrap();
+        assert_eq!(matched, "key");
+        assert_eq!(cursor.position(), 4);
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn test_memoize_resets_between_contexts() {
+        let calls = Cell::new(0);
+        let data = b"a";
+        let cursor = ByteCursor::new(data);
+        let parser = Counting {
+            parser: is_byte(b'a'),
+            calls: &calls,
+        }
+        .memoize()
+        .with_parse_context();
+
+        // Every parse gets a fresh context, so nothing is answered from the previous one
+        parser.parse(cursor).unwrap();
+        parser.parse(cursor).unwrap();
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[test]
+    fn test_memoize_without_context_runs_every_time() {
+        let calls = Cell::new(0);
+        let data = b"a";
+        let cursor = ByteCursor::new(data);
+        let parser = Counting {
+            parser: is_byte(b'a'),
+            calls: &calls,
+        }
+        .memoize();
+
+        parser.parse(cursor).unwrap();
+        parser.parse(cursor).unwrap();
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[test]
+    fn test_nested_contexts_share_cache() {
+        let calls = Cell::new(0);
+        let data = b"a";
+        let cursor = ByteCursor::new(data);
+        let rule = Counting {
+            parser: is_byte(b'a'),
+            calls: &calls,
+        }
+        .memoize();
+
+        let _outer = ParseContext::enter();
+        rule.parse(cursor).unwrap();
+        (&rule).with_parse_context().parse(cursor).unwrap();
+        rule.parse(cursor).unwrap();
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn test_memoize_clear() {
+        let calls = Cell::new(0);
+        let data = b"a";
+        let cursor = ByteCursor::new(data);
+        let parser = Counting {
+            parser: is_byte(b'a'),
+            calls: &calls,
+        }
+        .memoize();
+
+        let _context = ParseContext::enter();
+        parser.parse(cursor).unwrap();
+        parser.clear();
+        parser.parse(cursor).unwrap();
+        assert_eq!(calls.get(), 2);
+    }
+
+    /// Lets several combinators share one memoized rule
+    struct SharedRule<P>(Rc<P>);
+
+    impl<'code, P: Parser<'code>> Parser<'code> for SharedRule<P> {
+        type Cursor = P::Cursor;
+        type Output = P::Output;
+        type Error = P::Error;
+
+        fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+            self.0.parse(cursor)
+        }
+    }
+}
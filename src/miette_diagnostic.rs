@@ -0,0 +1,209 @@
+//! # miette Integration
+//!
+//! Enabled with the `miette` feature. [`MietteDiagnostic`] turns any byte-level
+//! error tree into a [`miette::Diagnostic`] carrying the source code, a label at
+//! the error location and optional help text, so parse errors can be returned as
+//! `miette::Result` and rendered by whichever report handler the application uses.
+//!
+//! The diagnostic owns a copy of the source, which makes it `'static` and lets it
+//! be converted into a `miette::Report`.
+
+use crate::error::{ErrorNode, ParsicombError};
+use miette::{Diagnostic, LabeledSpan, NamedSource, SourceCode};
+use std::fmt;
+
+enum Source {
+    Anonymous(Vec<u8>),
+    Named(NamedSource<Vec<u8>>),
+}
+
+/// A parse error prepared for reporting through miette
+pub struct MietteDiagnostic {
+    message: String,
+    source: Source,
+    offset: usize,
+    len: usize,
+    label: Option<String>,
+    help: Option<String>,
+    code: Option<String>,
+}
+
+impl MietteDiagnostic {
+    /// Create a diagnostic for the likely error of an error tree
+    pub fn new<'code, E>(error: &E) -> Self
+    where
+        E: ErrorNode<'code, Element = u8> + ?Sized,
+    {
+        let leaf = error.likely_error();
+        let loc = leaf.loc();
+        MietteDiagnostic {
+            message: leaf.message().into_owned(),
+            source: Source::Anonymous(loc.source().to_vec()),
+            offset: loc.position(),
+            len: 0,
+            label: Some("here".to_string()),
+            help: None,
+            code: error.code().map(|code| code.into_owned()),
+        }
+    }
+
+    /// Name the source, typically with its file path
+    pub fn with_source_name(mut self, name: impl AsRef<str>) -> Self {
+        let code = match self.source {
+            Source::Anonymous(code) => code,
+            Source::Named(named) => named.inner().clone(),
+        };
+        self.source = Source::Named(NamedSource::new(name, code));
+        self
+    }
+
+    /// Set the number of bytes the label covers, starting at the error position
+    pub fn with_len(mut self, len: usize) -> Self {
+        self.len = len;
+        self
+    }
+
+    /// Replace the text attached to the source label
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Attach help text shown below the snippet
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+}
+
+impl fmt::Debug for MietteDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MietteDiagnostic")
+            .field("message", &self.message)
+            .field("offset", &self.offset)
+            .field("len", &self.len)
+            .field("label", &self.label)
+            .field("help", &self.help)
+            .field("code", &self.code)
+            .finish()
+    }
+}
+
+impl fmt::Display for MietteDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for MietteDiagnostic {}
+
+impl Diagnostic for MietteDiagnostic {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        self.code
+            .as_ref()
+            .map(|code| Box::new(code) as Box<dyn fmt::Display>)
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        self.help
+            .as_ref()
+            .map(|help| Box::new(help) as Box<dyn fmt::Display>)
+    }
+
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        match &self.source {
+            Source::Anonymous(code) => Some(code),
+            Source::Named(named) => Some(named),
+        }
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let label = LabeledSpan::new_primary_with_span(self.label.clone(), (self.offset, self.len));
+        Some(Box::new(std::iter::once(label)))
+    }
+}
+
+impl<'code> From<ParsicombError<'code>> for MietteDiagnostic {
+    fn from(error: ParsicombError<'code>) -> Self {
+        MietteDiagnostic::new(&error)
+    }
+}
+
+/// Extension trait to convert byte-level parse errors into miette diagnostics
+pub trait IntoMietteDiagnostic<'code>: ErrorNode<'code, Element = u8> {
+    fn into_miette(self) -> MietteDiagnostic
+    where
+        Self: Sized,
+    {
+        MietteDiagnostic::new(&self)
+    }
+}
+
+/// Implement IntoMietteDiagnostic for all byte-level error nodes
+impl<'code, E> IntoMietteDiagnostic<'code> for E where E: ErrorNode<'code, Element = u8> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::and::AndExt;
+    use crate::byte::is_byte;
+    use crate::utf8::string::is_string;
+    use crate::{ByteCursor, Parser};
+
+    #[test]
+    fn test_diagnostic_from_error() {
+        let data = b"let x = 1";
+        let cursor = ByteCursor::new(data);
+        let error = is_string("let")
+            .and(is_byte(b'!'))
+            .parse(cursor)
+            .unwrap_err();
+
+        let diagnostic = error.into_miette();
+        assert_eq!(
+            diagnostic.to_string(),
+            "expected byte 0x21 ('!'), found 0x20 (' ')"
+        );
+
+        let labels: Vec<_> = diagnostic.labels().unwrap().collect();
+        assert_eq!(labels.len(), 1);
+        assert_eq!(labels[0].offset(), 3);
+        assert_eq!(labels[0].len(), 0);
+        assert_eq!(labels[0].label(), Some("here"));
+        assert!(labels[0].primary());
+    }
+
+    #[test]
+    fn test_diagnostic_source_and_help() {
+        let data = b"a\nb";
+        let error = ParsicombError::SyntaxError {
+            message: "unexpected token".into(),
+            loc: crate::CodeLoc::new(data, 2),
+        };
+
+        let diagnostic = MietteDiagnostic::from(error)
+            .with_source_name("input.txt")
+            .with_len(1)
+            .with_label("this token")
+            .with_help("remove it");
+
+        assert_eq!(diagnostic.help().unwrap().to_string(), "remove it");
+        let source = diagnostic.source_code().unwrap();
+        let contents = source.read_span(&(2, 1).into(), 0, 0).unwrap();
+        assert_eq!(contents.data(), b"b");
+        assert_eq!(contents.name(), Some("input.txt"));
+
+        let label = diagnostic.labels().unwrap().next().unwrap();
+        assert_eq!(label.len(), 1);
+        assert_eq!(label.label(), Some("this token"));
+    }
+
+    #[test]
+    fn test_diagnostic_into_report() {
+        let data = b"x";
+        let error = is_byte(b'y').parse(ByteCursor::new(data)).unwrap_err();
+
+        let report = miette::Report::new(error.into_miette());
+        assert!(report.to_string().contains("expected byte 0x79"));
+    }
+}
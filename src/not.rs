@@ -1,7 +1,10 @@
 use super::parser::Parser;
 use crate::atomic::Atomic;
 use crate::cursor::Cursor;
-use crate::error::{CodeLoc, ParsicombError};
+use crate::describe::Description;
+use crate::error::{CodeLoc, ErrorNode, ParsicombError};
+use crate::filter::FilterError;
+use crate::position::Span;
 use std::borrow::Cow;
 
 /// Parser combinator that performs negative lookahead
@@ -45,6 +48,10 @@ where
             }
         }
     }
+
+    fn describe(&self) -> Description {
+        Description::lookahead(true, self.parser.describe())
+    }
 }
 
 /// Convenience function to create a Not parser for negative lookahead
@@ -65,11 +72,168 @@ pub trait NotExt<'code>: Parser<'code> + Sized {
 /// Implement NotExt for all parsers
 impl<'code, P> NotExt<'code> for P where P: Parser<'code> {}
 
+/// Parser combinator that requires a match to be followed by `lookahead`
+///
+/// The lookahead runs right after the match and never consumes input. If it fails,
+/// its error is reported at the position where it was tried.
+pub struct FollowedBy<P, L> {
+    parser: P,
+    lookahead: L,
+}
+
+impl<P, L> FollowedBy<P, L> {
+    pub fn new(parser: P, lookahead: L) -> Self {
+        FollowedBy { parser, lookahead }
+    }
+}
+
+impl<'code, P, L> Parser<'code> for FollowedBy<P, L>
+where
+    P: Parser<'code>,
+    <P::Cursor as Cursor<'code>>::Element: Atomic + 'code,
+    P::Error: ErrorNode<'code, Element = <P::Cursor as Cursor<'code>>::Element>,
+    L: Parser<'code, Cursor = P::Cursor>,
+    L::Error: 'code,
+{
+    type Cursor = P::Cursor;
+    type Output = P::Output;
+    type Error = FilterError<'code, P::Error, <P::Cursor as Cursor<'code>>::Element>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (value, cursor) = self
+            .parser
+            .parse(cursor)
+            .map_err(FilterError::ParserError)?;
+
+        match self.lookahead.parse(cursor) {
+            Ok(_) => Ok((value, cursor)),
+            Err(error) => Err(FilterError::FilterFailed(ParsicombError::wrap(error))),
+        }
+    }
+
+    fn describe(&self) -> Description {
+        Description::sequence(
+            self.parser.describe(),
+            Description::lookahead(false, self.lookahead.describe()),
+        )
+    }
+}
+
+/// Parser combinator that rejects a match followed by `lookahead`
+///
+/// The lookahead runs right after the match and never consumes input. If it
+/// succeeds, the error names the text it matched, e.g. `must not be followed by
+/// '('`; use [`NotFollowedBy::with_message`] to describe the rule instead.
+pub struct NotFollowedBy<P, L> {
+    parser: P,
+    lookahead: L,
+    message: Option<Cow<'static, str>>,
+}
+
+impl<P, L> NotFollowedBy<P, L> {
+    pub fn new(parser: P, lookahead: L) -> Self {
+        NotFollowedBy {
+            parser,
+            lookahead,
+            message: None,
+        }
+    }
+
+    /// Replace the generated error message, e.g. "identifier must not be followed by '('"
+    pub fn with_message(mut self, message: impl Into<Cow<'static, str>>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+}
+
+impl<'code, P, L> Parser<'code> for NotFollowedBy<P, L>
+where
+    P: Parser<'code>,
+    <P::Cursor as Cursor<'code>>::Element: Atomic + 'code,
+    P::Error: ErrorNode<'code, Element = <P::Cursor as Cursor<'code>>::Element>,
+    L: Parser<'code, Cursor = P::Cursor>,
+{
+    type Cursor = P::Cursor;
+    type Output = P::Output;
+    type Error = FilterError<'code, P::Error, <P::Cursor as Cursor<'code>>::Element>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (value, cursor) = self
+            .parser
+            .parse(cursor)
+            .map_err(FilterError::ParserError)?;
+
+        match self.lookahead.parse(cursor) {
+            Err(_) => Ok((value, cursor)),
+            Ok((_, lookahead_cursor)) => {
+                let (data, position) = cursor.inner();
+                let message = match &self.message {
+                    Some(message) => message.clone(),
+                    None => {
+                        let span = Span::new(data, position, lookahead_cursor.position());
+                        format!("must not be followed by '{}'", span.as_string()).into()
+                    }
+                };
+                Err(FilterError::FilterFailed(ParsicombError::SyntaxError {
+                    message,
+                    loc: CodeLoc::new(data, position),
+                }))
+            }
+        }
+    }
+
+    fn describe(&self) -> Description {
+        Description::sequence(
+            self.parser.describe(),
+            Description::lookahead(true, self.lookahead.describe()),
+        )
+    }
+}
+
+/// Convenience function to create a FollowedBy parser
+pub fn followed_by<'code, P, L>(parser: P, lookahead: L) -> FollowedBy<P, L>
+where
+    P: Parser<'code>,
+    L: Parser<'code, Cursor = P::Cursor>,
+{
+    FollowedBy::new(parser, lookahead)
+}
+
+/// Convenience function to create a NotFollowedBy parser
+pub fn not_followed_by<'code, P, L>(parser: P, lookahead: L) -> NotFollowedBy<P, L>
+where
+    P: Parser<'code>,
+    L: Parser<'code, Cursor = P::Cursor>,
+{
+    NotFollowedBy::new(parser, lookahead)
+}
+
+/// Extension trait to add .followed_by() and .not_followed_by() method support for parsers
+pub trait FollowedByExt<'code>: Parser<'code> + Sized {
+    fn followed_by<L>(self, lookahead: L) -> FollowedBy<Self, L>
+    where
+        L: Parser<'code, Cursor = Self::Cursor>,
+    {
+        FollowedBy::new(self, lookahead)
+    }
+
+    fn not_followed_by<L>(self, lookahead: L) -> NotFollowedBy<Self, L>
+    where
+        L: Parser<'code, Cursor = Self::Cursor>,
+    {
+        NotFollowedBy::new(self, lookahead)
+    }
+}
+
+/// Implement FollowedByExt for all parsers
+impl<'code, P> FollowedByExt<'code> for P where P: Parser<'code> {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::ByteCursor;
     use crate::and::AndExt;
+    use crate::ascii::take_ident;
     use crate::byte::{byte, is_byte};
     use crate::many::many;
     use crate::map::MapExt;
@@ -207,4 +371,49 @@ mod tests {
         assert_eq!(cursor_after.position(), 4);
         assert_eq!(cursor_after.value().unwrap(), b' ');
     }
+
+    #[test]
+    fn test_followed_by_does_not_consume() {
+        let data = b"call(x)";
+        let cursor = ByteCursor::new(data);
+        let parser = take_ident().followed_by(is_byte(b'('));
+
+        let (name, cursor) = parser.parse(cursor).unwrap();
+        assert_eq!(name, "call");
+        assert_eq!(cursor.value().unwrap(), b'(');
+    }
+
+    #[test]
+    fn test_followed_by_reports_lookahead_position() {
+        let data = b"call x";
+        let error = followed_by(take_ident(), is_byte(b'('))
+            .parse(ByteCursor::new(data))
+            .unwrap_err();
+        assert!(matches!(error, FilterError::FilterFailed(_)));
+        assert_eq!(error.likely_error().loc().position(), 4);
+    }
+
+    #[test]
+    fn test_not_followed_by_names_matched_text() {
+        let parser = take_ident().not_followed_by(is_byte(b'('));
+        let (name, cursor) = parser.parse(ByteCursor::new(b"x + 1")).unwrap();
+        assert_eq!(name, "x");
+        assert_eq!(cursor.position(), 1);
+
+        let error = parser.parse(ByteCursor::new(b"f(1)")).unwrap_err();
+        let leaf = error.likely_error();
+        assert_eq!(leaf.message(), "must not be followed by '('");
+        assert_eq!(leaf.loc().position(), 1);
+    }
+
+    #[test]
+    fn test_not_followed_by_custom_message() {
+        let parser = not_followed_by(take_ident(), is_byte(b'('))
+            .with_message("identifier must not be followed by '('");
+        let error = parser.parse(ByteCursor::new(b"f(")).unwrap_err();
+        assert_eq!(
+            error.likely_error().message(),
+            "identifier must not be followed by '('"
+        );
+    }
 }
@@ -0,0 +1,97 @@
+use super::parser::Parser;
+use crate::describe::Description;
+
+/// Parser combinator that makes the given parser optional
+///
+/// Succeeds with `Some(output)` if the parser matches and with `None` without
+/// consuming input if it fails, so it never returns an error.
+pub struct Optional<P> {
+    parser: P,
+}
+
+impl<P> Optional<P> {
+    pub fn new(parser: P) -> Self {
+        Optional { parser }
+    }
+}
+
+impl<'code, P> Parser<'code> for Optional<P>
+where
+    P: Parser<'code>,
+{
+    type Cursor = P::Cursor;
+    type Output = Option<P::Output>;
+    type Error = P::Error;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        match self.parser.parse(cursor) {
+            Ok((value, next_cursor)) => Ok((Some(value), next_cursor)),
+            Err(_) => Ok((None, cursor)),
+        }
+    }
+
+    fn describe(&self) -> Description {
+        Description::optional(self.parser.describe())
+    }
+}
+
+/// Convenience function to create an Optional parser
+pub fn optional<'code, P>(parser: P) -> Optional<P>
+where
+    P: Parser<'code>,
+{
+    Optional::new(parser)
+}
+
+/// Extension trait to add .optional() method support for parsers
+pub trait OptionalExt<'code>: Parser<'code> + Sized {
+    fn optional(self) -> Optional<Self> {
+        Optional::new(self)
+    }
+}
+
+/// Implement OptionalExt for all parsers
+impl<'code, P> OptionalExt<'code> for P where P: Parser<'code> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ByteCursor;
+    use crate::Cursor;
+    use crate::and::AndExt;
+    use crate::ascii::u64;
+    use crate::byte::is_byte;
+
+    #[test]
+    fn test_optional_present() {
+        let data = b"-5";
+        let cursor = ByteCursor::new(data);
+        let parser = optional(is_byte(b'-')).and(u64());
+
+        let ((sign, value), cursor) = parser.parse(cursor).unwrap();
+        assert_eq!(sign, Some(b'-'));
+        assert_eq!(value, 5);
+        assert!(cursor.eos());
+    }
+
+    #[test]
+    fn test_optional_absent_does_not_consume() {
+        let data = b"5";
+        let cursor = ByteCursor::new(data);
+        let parser = is_byte(b'-').optional();
+
+        let (sign, cursor) = parser.parse(cursor).unwrap();
+        assert_eq!(sign, None);
+        assert_eq!(cursor.value().unwrap(), b'5');
+    }
+
+    #[test]
+    fn test_optional_empty_input() {
+        let data = b"";
+        let cursor = ByteCursor::new(data);
+
+        let (sign, cursor) = optional(is_byte(b'-')).parse(cursor).unwrap();
+        assert_eq!(sign, None);
+        assert!(cursor.eos());
+    }
+}
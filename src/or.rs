@@ -1,7 +1,12 @@
 use super::parser::Parser;
+#[cfg(feature = "arena")]
+use crate::arena::ParserArena;
 use crate::atomic::Atomic;
+use crate::boxed::ParserNode;
 use crate::cursor::Cursor;
-use crate::error::{ErrorLeaf, ErrorNode};
+use crate::describe::Description;
+use crate::error::{ErrorLeaf, ErrorNode, MergedError, furthest_only, merge_furthest};
+use std::cell::OnceCell;
 use std::fmt;
 
 // # Or Combinator - Dynamic Dispatch for Compile Time Performance
@@ -42,6 +47,10 @@ use std::fmt;
 // Errors are stored as `Box<dyn ErrorNode>` and only converted to concrete types
 // when displaying errors (via `likely_error()`). This avoids cloning during
 // error propagation while preserving full error information.
+//
+// When several branches of a chain fail at the same furthest position, the leaves
+// of the whole chain are gathered and merged into one `MergedError`, e.g.
+// `expected number, string or '('`, instead of reporting whichever branch came first.
 
 /// Error type for Or parser that can wrap errors from both parsers when both fail
 pub enum OrError<'code, T: Atomic> {
@@ -49,13 +58,28 @@ pub enum OrError<'code, T: Atomic> {
     BothFailed {
         first: Box<dyn ErrorNode<'code, Element = T> + 'code>,
         second: Box<dyn ErrorNode<'code, Element = T> + 'code>,
+        /// Merged error for ties, built on first use
+        merged: OnceCell<MergedError<'code, T>>,
     },
 }
 
+impl<'code, T: Atomic> OrError<'code, T> {
+    pub fn both_failed(
+        first: impl ErrorNode<'code, Element = T> + 'code,
+        second: impl ErrorNode<'code, Element = T> + 'code,
+    ) -> Self {
+        OrError::BothFailed {
+            first: Box::new(first),
+            second: Box::new(second),
+            merged: OnceCell::new(),
+        }
+    }
+}
+
 impl<'code, T: Atomic> std::fmt::Debug for OrError<'code, T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            OrError::BothFailed { first, second } => f
+            OrError::BothFailed { first, second, .. } => f
                 .debug_struct("BothFailed")
                 .field("first", &format!("{}", &**first))
                 .field("second", &format!("{}", &**second))
@@ -64,21 +88,15 @@ impl<'code, T: Atomic> std::fmt::Debug for OrError<'code, T> {
     }
 }
 
-impl<'code, T: Atomic> fmt::Display for OrError<'code, T> {
+// Both branches are rendered through the likely error, so tied branches show up as
+// one snippet listing every expectation once instead of one snippet per branch
+impl<'code, T: Atomic + 'code> fmt::Display for OrError<'code, T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            OrError::BothFailed { first, second } => {
-                write!(
-                    f,
-                    "Both parsers failed - First: {}, Second: {}",
-                    &**first, &**second
-                )
-            }
-        }
+        write!(f, "{}", self.likely_error())
     }
 }
 
-impl<'code, T: Atomic> std::error::Error for OrError<'code, T> {}
+impl<'code, T: Atomic + 'code> std::error::Error for OrError<'code, T> {}
 
 // OrError implements ErrorNode to enable furthest-error selection
 impl<'code, T: Atomic + 'code> ErrorNode<'code> for OrError<'code, T> {
@@ -86,24 +104,31 @@ impl<'code, T: Atomic + 'code> ErrorNode<'code> for OrError<'code, T> {
 
     fn likely_error(&self) -> &dyn ErrorLeaf<'code, Element = Self::Element> {
         match self {
-            OrError::BothFailed { first, second } => {
-                let first_base = first.as_ref().likely_error();
-                let second_base = second.as_ref().likely_error();
-
-                if first_base.loc().position() >= second_base.loc().position() {
-                    first_base
-                } else {
-                    second_base
-                }
+            OrError::BothFailed { merged, .. } => merge_furthest(self.furthest_leaves(), merged),
+        }
+    }
+
+    fn furthest_leaves(&self) -> Vec<&dyn ErrorLeaf<'code, Element = Self::Element>> {
+        match self {
+            OrError::BothFailed { first, second, .. } => {
+                let mut leaves = first.furthest_leaves();
+                leaves.extend(second.furthest_leaves());
+                furthest_only(leaves)
             }
         }
     }
+
+    fn children(&self) -> Vec<&dyn ErrorNode<'code, Element = Self::Element>> {
+        match self {
+            OrError::BothFailed { first, second, .. } => vec![first.as_ref(), second.as_ref()],
+        }
+    }
 }
 
 /// Parser combinator that tries the first parser, and if it fails, tries the second parser
 pub struct Or<'code, C, O, E1, E2> {
-    parser1: Box<dyn Parser<'code, Cursor = C, Output = O, Error = E1> + 'code>,
-    parser2: Box<dyn Parser<'code, Cursor = C, Output = O, Error = E2> + 'code>,
+    parser1: ParserNode<'code, C, O, E1>,
+    parser2: ParserNode<'code, C, O, E2>,
 }
 
 impl<'code, C, O, E1, E2> Or<'code, C, O, E1, E2> {
@@ -113,8 +138,21 @@ impl<'code, C, O, E1, E2> Or<'code, C, O, E1, E2> {
         P2: Parser<'code, Cursor = C, Output = O, Error = E2> + 'code,
     {
         Or {
-            parser1: Box::new(parser1),
-            parser2: Box::new(parser2),
+            parser1: ParserNode::boxed(parser1),
+            parser2: ParserNode::boxed(parser2),
+        }
+    }
+
+    /// Like `new`, but places both parsers in `arena` instead of boxing them
+    #[cfg(feature = "arena")]
+    pub fn new_in<P1, P2>(arena: &'code ParserArena, parser1: P1, parser2: P2) -> Self
+    where
+        P1: Parser<'code, Cursor = C, Output = O, Error = E1> + 'code,
+        P2: Parser<'code, Cursor = C, Output = O, Error = E2> + 'code,
+    {
+        Or {
+            parser1: ParserNode::arena(arena, parser1),
+            parser2: ParserNode::arena(arena, parser2),
         }
     }
 }
@@ -135,13 +173,14 @@ where
             Ok(result) => Ok(result),
             Err(first_error) => match self.parser2.parse(cursor) {
                 Ok(result) => Ok(result),
-                Err(second_error) => Err(OrError::BothFailed {
-                    first: Box::new(first_error),
-                    second: Box::new(second_error),
-                }),
+                Err(second_error) => Err(OrError::both_failed(first_error, second_error)),
             },
         }
     }
+
+    fn describe(&self) -> Description {
+        Description::choice(self.parser1.describe(), self.parser2.describe())
+    }
 }
 
 /// Extension trait to add .or() method support for parsers
@@ -153,6 +192,14 @@ pub trait OrExt<'code>: Parser<'code> + Sized {
     {
         Or::new(self, other)
     }
+
+    /// Statically dispatched `.or()`, see [`OrStatic`] for the trade-off
+    fn or_static<P>(self, other: P) -> OrStatic<Self, P>
+    where
+        P: Parser<'code, Output = Self::Output, Cursor = Self::Cursor>,
+    {
+        OrStatic::new(self, other)
+    }
 }
 
 /// Implement OrExt for all parsers
@@ -170,6 +217,153 @@ where
     Or::new(parser1, parser2)
 }
 
+// ## Static Dispatch Variant
+//
+// `OrStatic` keeps both alternatives and both errors as plain generics, giving
+// `OrStatic<OrStatic<A, B>, C>` for `a.or_static(b).or_static(c)`. A successful
+// parse allocates nothing and can be inlined end to end; a failed one only
+// allocates if the merged error is displayed. This brings back the nested types
+// and compile times described above, so reserve it for small, hot grammars.
+
+/// Error type for OrStatic parser holding both branch errors inline
+pub struct OrStaticError<'code, T: Atomic, E1, E2> {
+    first: E1,
+    second: E2,
+    /// Merged error for ties, built on first use
+    merged: OnceCell<MergedError<'code, T>>,
+}
+
+impl<'code, T: Atomic, E1, E2> OrStaticError<'code, T, E1, E2> {
+    /// Error from the first alternative
+    pub fn first(&self) -> &E1 {
+        &self.first
+    }
+
+    /// Error from the second alternative
+    pub fn second(&self) -> &E2 {
+        &self.second
+    }
+}
+
+impl<'code, T: Atomic, E1: fmt::Display, E2: fmt::Display> fmt::Debug
+    for OrStaticError<'code, T, E1, E2>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BothFailed")
+            .field("first", &self.first.to_string())
+            .field("second", &self.second.to_string())
+            .finish()
+    }
+}
+
+impl<'code, T, E1, E2> fmt::Display for OrStaticError<'code, T, E1, E2>
+where
+    T: Atomic + 'code,
+    E1: ErrorNode<'code, Element = T> + fmt::Display,
+    E2: ErrorNode<'code, Element = T> + fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.likely_error())
+    }
+}
+
+impl<'code, T, E1, E2> std::error::Error for OrStaticError<'code, T, E1, E2>
+where
+    T: Atomic + 'code,
+    E1: ErrorNode<'code, Element = T> + fmt::Display,
+    E2: ErrorNode<'code, Element = T> + fmt::Display,
+{
+}
+
+impl<'code, T, E1, E2> ErrorNode<'code> for OrStaticError<'code, T, E1, E2>
+where
+    T: Atomic + 'code,
+    E1: ErrorNode<'code, Element = T> + fmt::Display,
+    E2: ErrorNode<'code, Element = T> + fmt::Display,
+{
+    type Element = T;
+
+    fn likely_error(&self) -> &dyn ErrorLeaf<'code, Element = Self::Element> {
+        merge_furthest(self.furthest_leaves(), &self.merged)
+    }
+
+    fn furthest_leaves(&self) -> Vec<&dyn ErrorLeaf<'code, Element = Self::Element>> {
+        let mut leaves = self.first.furthest_leaves();
+        leaves.extend(self.second.furthest_leaves());
+        furthest_only(leaves)
+    }
+
+    fn children(&self) -> Vec<&dyn ErrorNode<'code, Element = Self::Element>> {
+        vec![&self.first, &self.second]
+    }
+}
+
+/// Parser combinator that tries two alternatives without boxing them
+///
+/// Behaves exactly like [`Or`], including merging tied errors, but stores both
+/// parsers inline. Prefer `.or()` unless profiling shows the boxing matters.
+///
+/// Example:
+/// ```
+/// use parsicomb::byte::is_byte;
+/// use parsicomb::ByteCursor;
+/// use parsicomb::or::OrExt;
+/// use parsicomb::parser::Parser;
+///
+/// let sign = is_byte(b'+').or_static(is_byte(b'-'));
+/// let (byte, _) = sign.parse(ByteCursor::new(b"-1")).unwrap();
+/// assert_eq!(byte, b'-');
+/// ```
+pub struct OrStatic<P1, P2> {
+    parser1: P1,
+    parser2: P2,
+}
+
+impl<P1, P2> OrStatic<P1, P2> {
+    pub fn new(parser1: P1, parser2: P2) -> Self {
+        OrStatic { parser1, parser2 }
+    }
+}
+
+impl<'code, P1, P2> Parser<'code> for OrStatic<P1, P2>
+where
+    P1: Parser<'code>,
+    P2: Parser<'code, Output = P1::Output, Cursor = P1::Cursor>,
+    <P1::Cursor as Cursor<'code>>::Element: Atomic + 'code,
+{
+    type Cursor = P1::Cursor;
+    type Output = P1::Output;
+    type Error = OrStaticError<'code, <P1::Cursor as Cursor<'code>>::Element, P1::Error, P2::Error>;
+
+    #[inline]
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        match self.parser1.parse(cursor) {
+            Ok(result) => Ok(result),
+            Err(first) => match self.parser2.parse(cursor) {
+                Ok(result) => Ok(result),
+                Err(second) => Err(OrStaticError {
+                    first,
+                    second,
+                    merged: OnceCell::new(),
+                }),
+            },
+        }
+    }
+
+    fn describe(&self) -> Description {
+        Description::choice(self.parser1.describe(), self.parser2.describe())
+    }
+}
+
+/// Convenience function to create an OrStatic parser
+pub fn or_static<'code, P1, P2>(parser1: P1, parser2: P2) -> OrStatic<P1, P2>
+where
+    P1: Parser<'code>,
+    P2: Parser<'code, Output = P1::Output, Cursor = P1::Cursor>,
+{
+    OrStatic::new(parser1, parser2)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -267,10 +461,7 @@ mod tests {
             loc: CodeLoc::new(data, 2), // position 2 (further)
         };
 
-        let or_error = OrError::BothFailed {
-            first: Box::new(error1),
-            second: Box::new(error2),
-        };
+        let or_error = OrError::both_failed(error1, error2);
         let furthest = or_error.likely_error();
 
         assert_eq!(furthest.loc().position(), 2);
@@ -289,10 +480,7 @@ mod tests {
             loc: CodeLoc::new(data, 1), // position 1
         };
 
-        let or_error = OrError::BothFailed {
-            first: Box::new(error1),
-            second: Box::new(error2),
-        };
+        let or_error = OrError::both_failed(error1, error2);
         let furthest = or_error.likely_error();
 
         assert_eq!(furthest.loc().position(), 3);
@@ -318,14 +506,8 @@ mod tests {
         };
 
         // Build the nested structure
-        let inner_or = OrError::BothFailed {
-            first: Box::new(error1),
-            second: Box::new(error2),
-        };
-        let outer_or = OrError::BothFailed {
-            first: Box::new(inner_or),
-            second: Box::new(error3),
-        };
+        let inner_or = OrError::both_failed(error1, error2);
+        let outer_or = OrError::both_failed(inner_or, error3);
 
         // Use the new ErrorBranch system - this automatically handles recursion!
         let furthest = outer_or.likely_error();
@@ -404,4 +586,79 @@ mod tests {
             "furthest() should traverse complex Or<Filter<And<...>>> structures"
         );
     }
+
+    #[test]
+    fn test_or_merges_ties_across_chain() {
+        let data = b"x";
+        let parser = is_byte(b'a').or(is_byte(b'b')).or(is_byte(b'c'));
+
+        let error = parser.parse(ByteCursor::new(data)).unwrap_err();
+        let leaf = error.likely_error();
+        assert_eq!(
+            leaf.message(),
+            "expected byte 0x61 ('a'), byte 0x62 ('b') or byte 0x63 ('c'), found 0x78 ('x')"
+        );
+        assert_eq!(leaf.loc().position(), 0);
+    }
+
+    #[test]
+    fn test_or_display_renders_ties_once() {
+        let data = b"x";
+        let parser = is_byte(b'a').or(is_byte(b'b')).or(is_byte(b'a'));
+
+        let rendered = parser.parse(ByteCursor::new(data)).unwrap_err().to_string();
+        assert!(rendered.starts_with(
+            "Syntax error at line 1, byte offset 0: \
+             expected byte 0x61 ('a') or byte 0x62 ('b'), found 0x78 ('x')\n"
+        ));
+        assert_eq!(rendered.matches("^--- here").count(), 1);
+    }
+
+    #[test]
+    fn test_or_deduplicates_and_ignores_shorter_branches() {
+        let data = b"ax";
+        let parser = is_byte(b'a')
+            .and(is_byte(b'b'))
+            .or(is_byte(b'a').and(is_byte(b'b')))
+            .or(is_byte(b'a').and(is_byte(b'c')))
+            .or(is_byte(b'z').and(is_byte(b'z')));
+
+        let error = parser.parse(ByteCursor::new(data)).unwrap_err();
+        let leaf = error.likely_error();
+        assert_eq!(
+            leaf.message(),
+            "expected byte 0x62 ('b') or byte 0x63 ('c'), found 0x78 ('x')"
+        );
+        assert_eq!(leaf.loc().position(), 1);
+    }
+
+    #[test]
+    fn test_or_static_picks_matching_branch() {
+        let parser = is_byte(b'a')
+            .or_static(is_byte(b'b'))
+            .or_static(is_byte(b'c'));
+
+        let (byte, cursor) = parser.parse(ByteCursor::new(b"cd")).unwrap();
+        assert_eq!(byte, b'c');
+        assert_eq!(cursor.value().unwrap(), b'd');
+    }
+
+    #[test]
+    fn test_or_static_merges_ties_like_or() {
+        let data = b"x";
+        let parser = or_static(is_byte(b'a'), is_byte(b'b')).or_static(is_byte(b'c'));
+
+        let error = parser.parse(ByteCursor::new(data)).unwrap_err();
+        assert_eq!(
+            error.likely_error().message(),
+            "expected byte 0x61 ('a'), byte 0x62 ('b') or byte 0x63 ('c'), found 0x78 ('x')"
+        );
+        assert_eq!(
+            error.first().second().to_string(),
+            is_byte(b'b')
+                .parse(ByteCursor::new(data))
+                .unwrap_err()
+                .to_string()
+        );
+    }
 }
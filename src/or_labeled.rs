@@ -0,0 +1,333 @@
+use super::parser::Parser;
+use crate::atomic::Atomic;
+use crate::cursor::Cursor;
+use crate::error::{CodeLoc, ErrorLeaf, ErrorNode, ParsicombError, join_alternatives};
+use std::cell::OnceCell;
+use std::fmt;
+
+// # Labeled Alternatives
+//
+// When `or` or `choice` fail, the message is assembled from whatever the branches
+// reported deep inside, e.g. `expected '[' or '{'` or an error from halfway through an
+// array. `or_labeled([("array", array), ("object", object)])` names each branch and,
+// if all of them fail, reports `expected array or object, found 'x'` at the position
+// where the alternatives started. The branch errors are kept for tooling that wants
+// the details.
+
+/// Error type for OrLabeled parser naming every alternative that was tried
+pub struct LabeledError<'code, T: Atomic> {
+    loc: CodeLoc<'code, T>,
+    branches: Vec<(&'static str, Box<dyn ErrorNode<'code, Element = T> + 'code>)>,
+    expected: OnceCell<ParsicombError<'code, T>>,
+}
+
+impl<'code, T: Atomic + 'code> LabeledError<'code, T> {
+    fn new(
+        loc: CodeLoc<'code, T>,
+        branches: Vec<(&'static str, Box<dyn ErrorNode<'code, Element = T> + 'code>)>,
+    ) -> Self {
+        LabeledError {
+            loc,
+            branches,
+            expected: OnceCell::new(),
+        }
+    }
+
+    /// Labels of all alternatives in the order they were tried
+    pub fn labels(&self) -> Vec<&'static str> {
+        self.branches.iter().map(|(label, _)| *label).collect()
+    }
+
+    /// Label and error of every alternative in the order they were tried
+    pub fn branches(
+        &self,
+    ) -> impl Iterator<Item = (&'static str, &(dyn ErrorNode<'code, Element = T> + 'code))> {
+        self.branches
+            .iter()
+            .map(|(label, error)| (*label, error.as_ref()))
+    }
+}
+
+impl<'code, T: Atomic + 'code> fmt::Debug for LabeledError<'code, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(
+                self.branches
+                    .iter()
+                    .map(|(label, error)| (label, format!("{}", &**error))),
+            )
+            .finish()
+    }
+}
+
+impl<'code, T: Atomic + 'code> fmt::Display for LabeledError<'code, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.likely_error())
+    }
+}
+
+impl<'code, T: Atomic + 'code> std::error::Error for LabeledError<'code, T> {}
+
+impl<'code, T: Atomic + 'code> ErrorNode<'code> for LabeledError<'code, T> {
+    type Element = T;
+
+    fn likely_error(&self) -> &dyn ErrorLeaf<'code, Element = Self::Element> {
+        self.expected.get_or_init(|| {
+            let source = self.loc.source();
+            let position = self.loc.position();
+            let found = match source.get(position..position + 1) {
+                Some(slice) => format!("'{}'", T::format_slice(slice)),
+                None => "end of input".to_string(),
+            };
+
+            let expected = join_alternatives(self.labels().into_iter());
+
+            ParsicombError::SyntaxError {
+                message: format!("expected {}, found {}", expected, found).into(),
+                loc: self.loc,
+            }
+        })
+    }
+
+    fn children(&self) -> Vec<&dyn ErrorNode<'code, Element = Self::Element>> {
+        self.branches
+            .iter()
+            .map(|(_, error)| error.as_ref())
+            .collect()
+    }
+}
+
+/// Collections of labeled alternatives that can be tried in order by [`OrLabeled`]
+///
+/// Implemented for tuples of 2 to 8 `(label, parser)` pairs with the same output
+/// type, and for arrays and vectors of pairs with a single parser type.
+pub trait LabeledParsers<'code> {
+    type Element: Atomic + 'code;
+    type Cursor: Cursor<'code, Element = Self::Element>;
+    type Output;
+
+    /// Try every alternative in order and return the first success
+    #[allow(clippy::type_complexity)]
+    fn parse_labeled(
+        &self,
+        cursor: Self::Cursor,
+    ) -> Result<(Self::Output, Self::Cursor), LabeledError<'code, Self::Element>>;
+}
+
+macro_rules! impl_labeled_parsers {
+    ($($parser:ident $index:tt),+) => {
+        impl<'code, C, O, $($parser),+> LabeledParsers<'code>
+            for ($((&'static str, $parser),)+)
+        where
+            C: Cursor<'code>,
+            C::Element: Atomic + 'code,
+            $($parser: Parser<'code, Cursor = C, Output = O>, $parser::Error: 'code,)+
+        {
+            type Element = C::Element;
+            type Cursor = C;
+            type Output = O;
+
+            fn parse_labeled(
+                &self,
+                cursor: Self::Cursor,
+            ) -> Result<(Self::Output, Self::Cursor), LabeledError<'code, C::Element>> {
+                let mut branches: Vec<(
+                    &'static str,
+                    Box<dyn ErrorNode<'code, Element = C::Element> + 'code>,
+                )> = Vec::new();
+
+                $(
+                    let (label, parser) = &self.$index;
+                    match parser.parse(cursor) {
+                        Ok(result) => return Ok(result),
+                        Err(error) => branches.push((*label, Box::new(error))),
+                    }
+                )+
+
+                Err(LabeledError::new(
+                    CodeLoc::new(cursor.source(), cursor.position()),
+                    branches,
+                ))
+            }
+        }
+    };
+}
+
+impl_labeled_parsers!(P0 0, P1 1);
+impl_labeled_parsers!(P0 0, P1 1, P2 2);
+impl_labeled_parsers!(P0 0, P1 1, P2 2, P3 3);
+impl_labeled_parsers!(P0 0, P1 1, P2 2, P3 3, P4 4);
+impl_labeled_parsers!(P0 0, P1 1, P2 2, P3 3, P4 4, P5 5);
+impl_labeled_parsers!(P0 0, P1 1, P2 2, P3 3, P4 4, P5 5, P6 6);
+impl_labeled_parsers!(P0 0, P1 1, P2 2, P3 3, P4 4, P5 5, P6 6, P7 7);
+
+/// Try each labeled parser of a slice in order, collecting the errors of those that fail
+#[allow(clippy::type_complexity)]
+fn parse_slice<'code, P>(
+    parsers: &[(&'static str, P)],
+    cursor: P::Cursor,
+) -> Result<(P::Output, P::Cursor), LabeledError<'code, <P::Cursor as Cursor<'code>>::Element>>
+where
+    P: Parser<'code>,
+    P::Error: 'code,
+    <P::Cursor as Cursor<'code>>::Element: Atomic + 'code,
+{
+    let mut branches: Vec<(&'static str, Box<dyn ErrorNode<'code, Element = _> + 'code>)> =
+        Vec::new();
+
+    for (label, parser) in parsers {
+        match parser.parse(cursor) {
+            Ok(result) => return Ok(result),
+            Err(error) => branches.push((*label, Box::new(error))),
+        }
+    }
+
+    Err(LabeledError::new(
+        CodeLoc::new(cursor.source(), cursor.position()),
+        branches,
+    ))
+}
+
+impl<'code, P, const N: usize> LabeledParsers<'code> for [(&'static str, P); N]
+where
+    P: Parser<'code>,
+    P::Error: 'code,
+    <P::Cursor as Cursor<'code>>::Element: Atomic + 'code,
+{
+    type Element = <P::Cursor as Cursor<'code>>::Element;
+    type Cursor = P::Cursor;
+    type Output = P::Output;
+
+    fn parse_labeled(
+        &self,
+        cursor: Self::Cursor,
+    ) -> Result<(Self::Output, Self::Cursor), LabeledError<'code, Self::Element>> {
+        parse_slice(self, cursor)
+    }
+}
+
+impl<'code, P> LabeledParsers<'code> for Vec<(&'static str, P)>
+where
+    P: Parser<'code>,
+    P::Error: 'code,
+    <P::Cursor as Cursor<'code>>::Element: Atomic + 'code,
+{
+    type Element = <P::Cursor as Cursor<'code>>::Element;
+    type Cursor = P::Cursor;
+    type Output = P::Output;
+
+    fn parse_labeled(
+        &self,
+        cursor: Self::Cursor,
+    ) -> Result<(Self::Output, Self::Cursor), LabeledError<'code, Self::Element>> {
+        parse_slice(self, cursor)
+    }
+}
+
+/// Parser combinator that tries labeled alternatives in order and reports failures by label
+///
+/// Example:
+/// ```
+/// use parsicomb::byte::is_byte;
+/// use parsicomb::{ByteCursor, Parser, or_labeled};
+///
+/// let data = b"x";
+/// let cursor = ByteCursor::new(data);
+/// let error = or_labeled([("array", is_byte(b'[')), ("object", is_byte(b'{'))])
+///     .parse(cursor)
+///     .unwrap_err();
+/// assert!(error.to_string().contains("expected array or object, found 'x'"));
+/// ```
+pub struct OrLabeled<T> {
+    parsers: T,
+}
+
+impl<T> OrLabeled<T> {
+    pub fn new(parsers: T) -> Self {
+        OrLabeled { parsers }
+    }
+}
+
+impl<'code, T> Parser<'code> for OrLabeled<T>
+where
+    T: LabeledParsers<'code>,
+{
+    type Cursor = T::Cursor;
+    type Output = T::Output;
+    type Error = LabeledError<'code, T::Element>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        self.parsers.parse_labeled(cursor)
+    }
+}
+
+/// Convenience function to create an OrLabeled parser from `(label, parser)` pairs
+pub fn or_labeled<'code, T>(parsers: T) -> OrLabeled<T>
+where
+    T: LabeledParsers<'code>,
+{
+    OrLabeled::new(parsers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ByteCursor;
+    use crate::and::AndExt;
+    use crate::byte::is_byte;
+    use crate::map::MapExt;
+    use crate::utf8::string::is_string;
+
+    #[test]
+    fn test_or_labeled_first_match_wins() {
+        let data = b"{}";
+        let parser = or_labeled((
+            ("array", is_string("[]").map(|_| 1)),
+            ("object", is_string("{}").map(|_| 2)),
+        ));
+
+        let (value, cursor) = parser.parse(ByteCursor::new(data)).unwrap();
+        assert_eq!(value, 2);
+        assert!(cursor.eos());
+    }
+
+    #[test]
+    fn test_or_labeled_hides_internal_failures() {
+        // The array branch gets two bytes in before failing, the message still names both
+        let data = b"[1x";
+        let array = is_byte(b'[')
+            .and(is_byte(b'1'))
+            .and(is_byte(b']'))
+            .map(|_| ());
+        let object = is_byte(b'{').map(|_| ());
+        let parser = or_labeled((("array", array), ("object", object)));
+
+        let error = parser.parse(ByteCursor::new(data)).unwrap_err();
+        assert_eq!(error.labels(), ["array", "object"]);
+        assert_eq!(error.likely_error().loc().position(), 0);
+        assert_eq!(
+            error.likely_error().message(),
+            "expected array or object, found '['"
+        );
+
+        let (label, branch) = error.branches().next().unwrap();
+        assert_eq!(label, "array");
+        assert_eq!(branch.likely_error().loc().position(), 2);
+    }
+
+    #[test]
+    fn test_or_labeled_at_end_of_input() {
+        let data = b"";
+        let parser = or_labeled(vec![
+            ("number", is_byte(b'1')),
+            ("string", is_byte(b'"')),
+            ("boolean", is_byte(b't')),
+        ]);
+
+        let error = parser.parse(ByteCursor::new(data)).unwrap_err();
+        assert_eq!(
+            error.likely_error().message(),
+            "expected number, string or boolean, found end of input"
+        );
+    }
+}
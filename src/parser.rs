@@ -1,6 +1,12 @@
+use crate::atomic::Atomic;
 use crate::cursor::Cursor;
-use crate::error::ErrorNode;
+use crate::cursors::AtomicCursor;
+use crate::describe::Description;
+use crate::eof::{at_eof, expected_eof};
+use crate::error::{ErrorNode, ParsicombError};
+use crate::position::Span;
 use std::error::Error;
+use std::rc::Rc;
 
 /// Core parser trait for parser combinators
 pub trait Parser<'code> {
@@ -18,4 +24,141 @@ pub trait Parser<'code> {
     /// Returns Ok with the parsed value and updated cursor on success,
     /// or Err if the parse fails. Failures should not consume input.
     fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error>;
+
+    /// Describe what this parser matches, for exporting grammars
+    ///
+    /// Defaults to an opaque node named after the parser's type. Combinators that
+    /// shape a grammar override this to report their children, see
+    /// [`describe`](crate::describe).
+    fn describe(&self) -> Description {
+        Description::opaque::<Self>()
+    }
+
+    /// Parse all of `input`, the usual top-level entry point
+    ///
+    /// Fails if input is left over. Parse errors are wrapped as they are, so the
+    /// whole error tree stays available for reporting.
+    /// Returns the output with the span of the whole input.
+    fn parse_to_end<T>(
+        &self,
+        input: &'code [T],
+    ) -> Result<(Self::Output, Span<'code, T>), ParsicombError<'code, T>>
+    where
+        Self: Parser<'code, Cursor = AtomicCursor<'code, T>> + Sized,
+        Self::Error: 'code,
+        T: Atomic + 'code,
+    {
+        let (output, cursor) = self
+            .parse(AtomicCursor::new(input))
+            .map_err(ParsicombError::wrap)?;
+
+        if !at_eof(cursor) {
+            return Err(expected_eof(cursor));
+        }
+
+        Ok((output, Span::new(input, 0, input.len())))
+    }
+}
+
+/// Forward parsing through shared references so a parser can be reused by reference
+impl<'code, P> Parser<'code> for &P
+where
+    P: Parser<'code> + ?Sized,
+{
+    type Cursor = P::Cursor;
+    type Output = P::Output;
+    type Error = P::Error;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        (**self).parse(cursor)
+    }
+
+    fn describe(&self) -> Description {
+        (**self).describe()
+    }
+}
+
+/// Forward parsing through boxes, including `Box<dyn Parser>`
+impl<'code, P> Parser<'code> for Box<P>
+where
+    P: Parser<'code> + ?Sized,
+{
+    type Cursor = P::Cursor;
+    type Output = P::Output;
+    type Error = P::Error;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        (**self).parse(cursor)
+    }
+
+    fn describe(&self) -> Description {
+        (**self).describe()
+    }
+}
+
+/// Forward parsing through `Rc` so one rule can be shared by several combinators
+impl<'code, P> Parser<'code> for Rc<P>
+where
+    P: Parser<'code> + ?Sized,
+{
+    type Cursor = P::Cursor;
+    type Output = P::Output;
+    type Error = P::Error;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        (**self).parse(cursor)
+    }
+
+    fn describe(&self) -> Description {
+        (**self).describe()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ByteCursor;
+    use crate::and::AndExt;
+    use crate::ascii::u64;
+    use crate::byte::is_byte;
+    use crate::cut::CutExt;
+    use crate::error::ErrorLeaf;
+    use crate::or::OrExt;
+    use crate::skip::skip_many;
+    use crate::utf8::string::is_string;
+
+    #[test]
+    fn test_parse_to_end() {
+        let (value, span) = u64().parse_to_end(b"42").unwrap();
+        assert_eq!(value, 42);
+        assert_eq!((span.start, span.end), (0, 2));
+    }
+
+    #[test]
+    fn test_parse_to_end_rejects_leftover() {
+        let error = u64().parse_to_end(b"42x").unwrap_err();
+        assert_eq!(error.message(), "expected end of input, found 'x'");
+        assert_eq!(error.position(), 2);
+    }
+
+    #[test]
+    fn test_parse_to_end_keeps_error_tree() {
+        let parser = is_byte(b'a').or(is_byte(b'b'));
+        let error = parser.parse_to_end(b"c").unwrap_err();
+        assert!(matches!(error, ParsicombError::WrappedError { .. }));
+        assert_eq!(error.children().len(), 1);
+        assert_eq!(error.children()[0].children().len(), 2);
+        assert_eq!(error.position(), 0);
+    }
+
+    #[test]
+    fn test_repetition_returns_committed_errors_near_end_of_input() {
+        // Only two bytes are left for an item of at least four, but its cut still fires
+        let item = is_byte(b'-').and(is_byte(b'x').cut()).and(is_string("abc"));
+        let parser = skip_many(item);
+
+        let error = parser.parse(ByteCursor::new(b"-xabc-y")).unwrap_err();
+        assert!(error.is_committed());
+        assert_eq!(error.likely_error().loc().position(), 6);
+    }
 }
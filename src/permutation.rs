@@ -0,0 +1,262 @@
+use super::parser::Parser;
+use crate::atomic::Atomic;
+use crate::cursor::Cursor;
+use crate::error::{ErrorLeaf, ErrorNode, MergedError, furthest_only, merge_furthest};
+use std::cell::OnceCell;
+use std::fmt;
+
+// # Permutation Combinator
+//
+// `permutation((a, b, c))` matches every component exactly once, in any order, and
+// returns the outputs in the order the components were given. Each round tries the
+// components that have not matched yet and takes the first that succeeds, so
+// matching is greedy: a component that could swallow another's input should come
+// later in the tuple.
+//
+// If no remaining component matches, the error lists which components are missing
+// and merges their errors like `choice` does.
+
+/// Error type for Permutation parser recording the components that did not match
+pub struct PermutationError<'code, T: Atomic> {
+    missing: Vec<(usize, Box<dyn ErrorNode<'code, Element = T> + 'code>)>,
+    merged: OnceCell<MergedError<'code, T>>,
+}
+
+impl<'code, T: Atomic + 'code> PermutationError<'code, T> {
+    fn new(missing: Vec<(usize, Box<dyn ErrorNode<'code, Element = T> + 'code>)>) -> Self {
+        PermutationError {
+            missing,
+            merged: OnceCell::new(),
+        }
+    }
+
+    /// Indices of the components that were not matched, in tuple order
+    pub fn missing(&self) -> Vec<usize> {
+        self.missing.iter().map(|(index, _)| *index).collect()
+    }
+
+    /// Errors of the missing components at the position where matching stopped
+    pub fn errors(&self) -> impl Iterator<Item = &(dyn ErrorNode<'code, Element = T> + 'code)> {
+        self.missing.iter().map(|(_, error)| error.as_ref())
+    }
+}
+
+impl<'code, T: Atomic + 'code> fmt::Debug for PermutationError<'code, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(
+                self.missing
+                    .iter()
+                    .map(|(index, error)| (index, format!("{}", &**error))),
+            )
+            .finish()
+    }
+}
+
+impl<'code, T: Atomic + 'code> fmt::Display for PermutationError<'code, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Missing permutation components {:?}: {}",
+            self.missing(),
+            self.likely_error()
+        )
+    }
+}
+
+impl<'code, T: Atomic + 'code> std::error::Error for PermutationError<'code, T> {}
+
+impl<'code, T: Atomic + 'code> ErrorNode<'code> for PermutationError<'code, T> {
+    type Element = T;
+
+    fn likely_error(&self) -> &dyn ErrorLeaf<'code, Element = Self::Element> {
+        merge_furthest(self.furthest_leaves(), &self.merged)
+    }
+
+    fn furthest_leaves(&self) -> Vec<&dyn ErrorLeaf<'code, Element = Self::Element>> {
+        furthest_only(
+            self.missing
+                .iter()
+                .flat_map(|(_, error)| error.furthest_leaves())
+                .collect(),
+        )
+    }
+
+    fn children(&self) -> Vec<&dyn ErrorNode<'code, Element = Self::Element>> {
+        self.missing
+            .iter()
+            .map(|(_, error)| error.as_ref())
+            .collect()
+    }
+}
+
+/// Tuples of parsers that can be matched in any order by [`Permutation`]
+///
+/// Implemented for tuples of 2 to 8 parsers sharing a cursor type.
+pub trait PermutationParsers<'code> {
+    type Element: Atomic + 'code;
+    type Cursor: Cursor<'code, Element = Self::Element>;
+    type Output;
+
+    /// Match every component once in any order
+    #[allow(clippy::type_complexity)]
+    fn parse_permutation(
+        &self,
+        cursor: Self::Cursor,
+    ) -> Result<(Self::Output, Self::Cursor), PermutationError<'code, Self::Element>>;
+}
+
+macro_rules! impl_permutation_parsers {
+    ($($parser:ident $index:tt),+) => {
+        impl<'code, C, $($parser),+> PermutationParsers<'code> for ($($parser,)+)
+        where
+            C: Cursor<'code>,
+            C::Element: Atomic + 'code,
+            $($parser: Parser<'code, Cursor = C>, $parser::Error: 'code,)+
+        {
+            type Element = C::Element;
+            type Cursor = C;
+            type Output = ($($parser::Output,)+);
+
+            fn parse_permutation(
+                &self,
+                cursor: Self::Cursor,
+            ) -> Result<(Self::Output, Self::Cursor), PermutationError<'code, C::Element>> {
+                let mut outputs = ($(None::<$parser::Output>,)+);
+                let mut cursor = cursor;
+
+                loop {
+                    let mut missing: Vec<(usize, Box<dyn ErrorNode<'code, Element = C::Element> + 'code>)> =
+                        Vec::new();
+
+                    $(
+                        if outputs.$index.is_none() {
+                            match self.$index.parse(cursor) {
+                                Ok((value, next_cursor)) => {
+                                    outputs.$index = Some(value);
+                                    cursor = next_cursor;
+                                    continue;
+                                }
+                                Err(error) => missing.push(($index, Box::new(error))),
+                            }
+                        }
+                    )+
+
+                    if !missing.is_empty() {
+                        return Err(PermutationError::new(missing));
+                    }
+                    return Ok((
+                        ($(outputs.$index.expect("every component matched"),)+),
+                        cursor,
+                    ));
+                }
+            }
+        }
+    };
+}
+
+impl_permutation_parsers!(P0 0, P1 1);
+impl_permutation_parsers!(P0 0, P1 1, P2 2);
+impl_permutation_parsers!(P0 0, P1 1, P2 2, P3 3);
+impl_permutation_parsers!(P0 0, P1 1, P2 2, P3 3, P4 4);
+impl_permutation_parsers!(P0 0, P1 1, P2 2, P3 3, P4 4, P5 5);
+impl_permutation_parsers!(P0 0, P1 1, P2 2, P3 3, P4 4, P5 5, P6 6);
+impl_permutation_parsers!(P0 0, P1 1, P2 2, P3 3, P4 4, P5 5, P6 6, P7 7);
+
+/// Parser combinator that matches all components exactly once in any order
+///
+/// Example:
+/// ```
+/// use parsicomb::byte::is_byte;
+/// use parsicomb::{ByteCursor, Parser, permutation};
+///
+/// let data = b"cab";
+/// let cursor = ByteCursor::new(data);
+/// let parser = permutation((is_byte(b'a'), is_byte(b'b'), is_byte(b'c')));
+/// let ((a, b, c), _) = parser.parse(cursor).unwrap();
+/// assert_eq!((a, b, c), (b'a', b'b', b'c'));
+/// ```
+pub struct Permutation<T> {
+    parsers: T,
+}
+
+impl<T> Permutation<T> {
+    pub fn new(parsers: T) -> Self {
+        Permutation { parsers }
+    }
+}
+
+impl<'code, T> Parser<'code> for Permutation<T>
+where
+    T: PermutationParsers<'code>,
+{
+    type Cursor = T::Cursor;
+    type Output = T::Output;
+    type Error = PermutationError<'code, T::Element>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        self.parsers.parse_permutation(cursor)
+    }
+}
+
+/// Convenience function to create a Permutation parser from a tuple of parsers
+pub fn permutation<'code, T>(parsers: T) -> Permutation<T>
+where
+    T: PermutationParsers<'code>,
+{
+    Permutation::new(parsers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ByteCursor;
+    use crate::and::AndExt;
+    use crate::ascii::u64;
+    use crate::byte::is_byte;
+    use crate::map::MapExt;
+    use crate::utf8::string::is_string;
+
+    #[test]
+    fn test_any_order_mixed_outputs() {
+        let width = is_string("w=").and(u64()).map(|(_, n)| n);
+        let height = is_string("h=").and(u64()).map(|(_, n)| n);
+        let flag = is_string("!").map(|_| true);
+        let parser = permutation((width, height, flag));
+
+        for data in [&b"w=3h=4!"[..], b"!h=4w=3", b"h=4!w=3"] {
+            let ((w, h, flag), cursor) = parser.parse(ByteCursor::new(data)).unwrap();
+            assert_eq!((w, h, flag), (3, 4, true));
+            assert!(cursor.eos());
+        }
+    }
+
+    #[test]
+    fn test_each_component_matches_once() {
+        let data = b"aab";
+        let parser = permutation((is_byte(b'a'), is_byte(b'b')));
+
+        let error = parser.parse(ByteCursor::new(data)).unwrap_err();
+        assert_eq!(error.missing(), [1]);
+        assert_eq!(error.likely_error().loc().position(), 1);
+    }
+
+    #[test]
+    fn test_reports_missing_components() {
+        let data = b"bx";
+        let parser = permutation((is_byte(b'a'), is_byte(b'b'), is_byte(b'c')));
+
+        let error = parser.parse(ByteCursor::new(data)).unwrap_err();
+        assert_eq!(error.missing(), [0, 2]);
+        assert_eq!(error.errors().count(), 2);
+        assert_eq!(
+            error.likely_error().message(),
+            "expected byte 0x61 ('a') or byte 0x63 ('c'), found 0x78 ('x')"
+        );
+        assert!(
+            error
+                .to_string()
+                .starts_with("Missing permutation components [0, 2]")
+        );
+    }
+}
@@ -1,5 +1,7 @@
 use crate::atomic::Atomic;
 use crate::cursor::Cursor;
+use crate::cursors::AtomicCursor;
+use crate::error::{CodeLoc, Utf16Position};
 use crate::parser::Parser;
 
 /// Represents a span in the source code with start and end positions
@@ -39,6 +41,62 @@ impl<'code, T: Atomic> Span<'code, T> {
     pub fn as_string(&self) -> String {
         T::format_slice(self.slice())
     }
+
+    /// Location of the first element of the span
+    pub fn start_loc(&self) -> CodeLoc<'code, T> {
+        CodeLoc::new(self.source, self.start)
+    }
+
+    /// Location just past the last element of the span
+    pub fn end_loc(&self) -> CodeLoc<'code, T> {
+        CodeLoc::new(self.source, self.end)
+    }
+
+    /// Cursor at the start of the span over the whole source
+    ///
+    /// Lets a sub-parser run on the spanned content while its positions and errors
+    /// stay relative to the full input. The cursor is not limited to the span, so
+    /// the parser should stop at or check against `end`.
+    pub fn cursor(&self) -> AtomicCursor<'code, T> {
+        AtomicCursor::at(self.source, self.start)
+    }
+}
+
+impl<'code> Span<'code, u8> {
+    /// Start and end of the span as UTF-16 line and column pairs, as used by LSP
+    pub fn utf16_range(&self) -> (Utf16Position, Utf16Position) {
+        (
+            self.start_loc().utf16_position(),
+            self.end_loc().utf16_position(),
+        )
+    }
+}
+
+/// A value paired with the span of source it was parsed from
+///
+/// Intended for embedding directly in AST nodes, usually built with
+/// `.map_with_span(Spanned::new)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Spanned<'code, V, T: Atomic = u8> {
+    /// The parsed value
+    pub value: V,
+    /// Where in the source the value came from
+    pub span: Span<'code, T>,
+}
+
+impl<'code, V, T: Atomic> Spanned<'code, V, T> {
+    /// Create a new spanned value
+    pub fn new(value: V, span: Span<'code, T>) -> Self {
+        Spanned { value, span }
+    }
+
+    /// Transform the value while keeping its span
+    pub fn map<U>(self, f: impl FnOnce(V) -> U) -> Spanned<'code, U, T> {
+        Spanned {
+            value: f(self.value),
+            span: self.span,
+        }
+    }
 }
 
 /// A parser combinator that captures the position span of a successful parse
@@ -76,12 +134,53 @@ where
     }
 }
 
+/// A parser combinator that maps the output of a parser together with its span
+pub struct MapWithSpan<P, F> {
+    parser: P,
+    mapper: F,
+}
+
+impl<P, F> MapWithSpan<P, F> {
+    pub fn new(parser: P, mapper: F) -> Self {
+        MapWithSpan { parser, mapper }
+    }
+}
+
+impl<'code, P, F, U> Parser<'code> for MapWithSpan<P, F>
+where
+    P: Parser<'code>,
+    <P::Cursor as Cursor<'code>>::Element: Atomic + 'code,
+    F: Fn(P::Output, Span<'code, <P::Cursor as Cursor<'code>>::Element>) -> U,
+{
+    type Cursor = P::Cursor;
+    type Output = U;
+    type Error = P::Error;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let start_pos = cursor.position();
+        let source = cursor.source();
+        let (output, new_cursor) = self.parser.parse(cursor)?;
+
+        let span = Span::new(source, start_pos, new_cursor.position());
+        Ok(((self.mapper)(output, span), new_cursor))
+    }
+}
+
 /// Extension trait to add position tracking to any parser
 pub trait PositionExt<'code>: Parser<'code> + Sized {
     /// Wrap this parser to capture its position span
     fn with_position(self) -> Position<Self> {
         Position::new(self)
     }
+
+    /// Map the output of this parser together with the span it was parsed from
+    fn map_with_span<F, U>(self, mapper: F) -> MapWithSpan<Self, F>
+    where
+        <Self::Cursor as Cursor<'code>>::Element: Atomic + 'code,
+        F: Fn(Self::Output, Span<'code, <Self::Cursor as Cursor<'code>>::Element>) -> U,
+    {
+        MapWithSpan::new(self, mapper)
+    }
 }
 
 impl<'code, P> PositionExt<'code> for P where P: Parser<'code> {}
@@ -126,6 +225,26 @@ mod tests {
         assert_eq!(span.as_string(), "world");
     }
 
+    #[test]
+    fn test_span_locations() {
+        let data = b"one\ntwo three";
+        let span = Span::new(data, 8, 13);
+
+        assert_eq!(span.start_loc().line(), 2);
+        assert_eq!(span.start_loc().column_utf8(), 5);
+        assert_eq!(span.end_loc().position(), 13);
+        assert_eq!(span.end_loc().column_utf8(), 10);
+    }
+
+    #[test]
+    fn test_span_utf16_range() {
+        let data = "let s = \"😀\";\n  ok".as_bytes();
+        let (start, end) = Span::new(data, 8, data.len()).utf16_range();
+
+        assert_eq!((start.line, start.character), (0, 8));
+        assert_eq!((end.line, end.character), (1, 4));
+    }
+
     #[test]
     fn test_position_single_byte() {
         let data = b"hello";
@@ -203,4 +322,57 @@ mod tests {
         let result = parser.parse(cursor);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_map_with_span_into_spanned() {
+        use crate::utf8::string::is_string;
+
+        let data = b"let x";
+        let cursor = ByteCursor::new(data);
+        let parser = is_string("let").map_with_span(Spanned::new);
+
+        let (spanned, cursor) = parser.parse(cursor).unwrap();
+        assert_eq!(spanned.value, "let");
+        assert_eq!(spanned.span, Span::new(data, 0, 3));
+        assert_eq!(cursor.position(), 3);
+    }
+
+    #[test]
+    fn test_map_with_span_builds_node() {
+        #[derive(Debug, PartialEq)]
+        struct Node<'code> {
+            byte: u8,
+            span: Span<'code>,
+        }
+
+        let data = b"ab";
+        let cursor = ByteCursor::new(data).next();
+        let parser = is_byte(b'b').map_with_span(|byte, span| Node { byte, span });
+
+        let (node, _) = parser.parse(cursor).unwrap();
+        assert_eq!(
+            node,
+            Node {
+                byte: b'b',
+                span: Span::new(data, 1, 2)
+            }
+        );
+    }
+
+    #[test]
+    fn test_spanned_map_keeps_span() {
+        let data = b"7";
+        let spanned = Spanned::new(b'7', Span::new(data, 0, 1)).map(|b| (b - b'0') as u32);
+        assert_eq!(spanned.value, 7);
+        assert_eq!(spanned.span, Span::new(data, 0, 1));
+    }
+
+    #[test]
+    fn test_map_with_span_error_propagation() {
+        let data = b"xyz";
+        let cursor = ByteCursor::new(data);
+        let parser = is_byte(b'a').map_with_span(Spanned::new);
+
+        assert!(parser.parse(cursor).is_err());
+    }
 }
@@ -0,0 +1,360 @@
+//! # Property Testing with quickcheck
+//!
+//! Hand-written test inputs tend to cover the cases the grammar author already
+//! thought of. This module, enabled with the `quickcheck` feature, generates inputs
+//! instead:
+//!
+//! - [`InputGen`] describes valid inputs with the same building blocks as a grammar
+//!   (literals, alternatives, sequences, repetition and character ranges) and
+//!   generates random strings from that description.
+//! - [`AsciiText`] and [`Utf8Text`] are `Arbitrary` strings for plain fuzzing, and
+//!   [`with_noise`] corrupts valid inputs with a controlled amount of edits.
+//! - [`check_round_trip`] and [`assert_round_trips`] check that printing a parsed
+//!   value and parsing it again gives the same value.
+
+use quickcheck::{Arbitrary, Gen};
+use std::fmt;
+
+/// Description of a set of inputs that random strings are generated from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputGen {
+    /// Exactly this text
+    Literal(String),
+    /// One character from any of the inclusive ranges
+    Chars(Vec<(char, char)>),
+    /// One of the alternatives, picked uniformly
+    OneOf(Vec<InputGen>),
+    /// All parts one after another
+    Seq(Vec<InputGen>),
+    /// Between `min` and `max` repetitions, joined by `separator`
+    Repeat {
+        item: Box<InputGen>,
+        separator: Option<Box<InputGen>>,
+        min: usize,
+        max: usize,
+    },
+}
+
+/// Generator for exactly `text`
+pub fn literal(text: impl Into<String>) -> InputGen {
+    InputGen::Literal(text.into())
+}
+
+/// Generator for one character between `low` and `high`, inclusive
+pub fn char_range(low: char, high: char) -> InputGen {
+    InputGen::Chars(vec![(low, high)])
+}
+
+/// Generator for one character from any of the ranges
+pub fn char_ranges(ranges: impl Into<Vec<(char, char)>>) -> InputGen {
+    InputGen::Chars(ranges.into())
+}
+
+/// Generator picking one of the alternatives
+pub fn one_of(alternatives: impl Into<Vec<InputGen>>) -> InputGen {
+    InputGen::OneOf(alternatives.into())
+}
+
+/// Generator concatenating all parts
+pub fn seq(parts: impl Into<Vec<InputGen>>) -> InputGen {
+    InputGen::Seq(parts.into())
+}
+
+/// Generator repeating `item` between `min` and `max` times
+pub fn repeat(item: InputGen, min: usize, max: usize) -> InputGen {
+    InputGen::Repeat {
+        item: Box::new(item),
+        separator: None,
+        min,
+        max: max.max(min),
+    }
+}
+
+/// Generator for `min` to `max` items separated by `separator`
+pub fn separated(item: InputGen, separator: InputGen, min: usize, max: usize) -> InputGen {
+    InputGen::Repeat {
+        item: Box::new(item),
+        separator: Some(Box::new(separator)),
+        min,
+        max: max.max(min),
+    }
+}
+
+/// Uniform random number in `0..bound`, `bound` must not be 0
+fn below(g: &mut Gen, bound: usize) -> usize {
+    (u64::arbitrary(g) % bound as u64) as usize
+}
+
+impl InputGen {
+    /// Generate one random input
+    pub fn generate(&self, g: &mut Gen) -> String {
+        let mut out = String::new();
+        self.generate_into(g, &mut out);
+        out
+    }
+
+    fn generate_into(&self, g: &mut Gen, out: &mut String) {
+        match self {
+            InputGen::Literal(text) => out.push_str(text),
+            InputGen::Chars(ranges) => {
+                let ranges: Vec<_> = ranges.iter().filter(|(low, high)| low <= high).collect();
+                if ranges.is_empty() {
+                    return;
+                }
+                let &&(low, high) = g.choose(&ranges).expect("ranges is not empty");
+                let span = high as u32 - low as u32 + 1;
+                // Surrogates are skipped by falling back to the start of the range
+                let c = char::from_u32(low as u32 + u32::arbitrary(g) % span).unwrap_or(low);
+                out.push(c);
+            }
+            InputGen::OneOf(alternatives) => {
+                if let Some(alternative) = g.choose(alternatives) {
+                    alternative.generate_into(g, out);
+                }
+            }
+            InputGen::Seq(parts) => {
+                for part in parts {
+                    part.generate_into(g, out);
+                }
+            }
+            InputGen::Repeat {
+                item,
+                separator,
+                min,
+                max,
+            } => {
+                let count = min + below(g, max - min + 1);
+                for index in 0..count {
+                    if index > 0
+                        && let Some(separator) = separator
+                    {
+                        separator.generate_into(g, out);
+                    }
+                    item.generate_into(g, out);
+                }
+            }
+        }
+    }
+}
+
+/// Random printable ASCII text, including spaces, tabs and newlines
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AsciiText(pub String);
+
+impl Arbitrary for AsciiText {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let length = below(g, g.size() + 1);
+        let text = (0..length)
+            .map(|_| match below(g, 20) {
+                0 => '\n',
+                1 => '\t',
+                _ => (b' ' + below(g, 95) as u8) as char,
+            })
+            .collect();
+        AsciiText(text)
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        Box::new(
+            self.0
+                .shrink()
+                .filter(|text| text.is_ascii())
+                .map(AsciiText),
+        )
+    }
+}
+
+/// Random UTF-8 text, mostly ASCII with some multi-byte characters
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Utf8Text(pub String);
+
+impl Arbitrary for Utf8Text {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let length = below(g, g.size() + 1);
+        let text = (0..length)
+            .map(|_| {
+                if below(g, 4) == 0 {
+                    char::arbitrary(g)
+                } else {
+                    (b' ' + below(g, 95) as u8) as char
+                }
+            })
+            .collect();
+        Utf8Text(text)
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        Box::new(self.0.shrink().map(Utf8Text))
+    }
+}
+
+/// Apply random edits to `input`, each character is changed with probability `rate`
+///
+/// An edit deletes the character, replaces it with a random printable ASCII
+/// character or inserts one before it.
+pub fn with_noise(input: &str, rate: f64, g: &mut Gen) -> String {
+    let threshold = (rate.clamp(0.0, 1.0) * u32::MAX as f64) as u32;
+    let mut out = String::with_capacity(input.len());
+    for c in input.chars() {
+        if u32::arbitrary(g) >= threshold || threshold == 0 {
+            out.push(c);
+            continue;
+        }
+        let noise = (b' ' + below(g, 95) as u8) as char;
+        match below(g, 3) {
+            0 => {}
+            1 => out.push(noise),
+            _ => {
+                out.push(noise);
+                out.push(c);
+            }
+        }
+    }
+    out
+}
+
+/// Parse `input`, print the result and check that parsing the printed text gives
+/// the same value
+///
+/// `parse` parses a whole input, usually by building the grammar and calling
+/// [`check_parses`](crate::testing::check_parses). The output must not borrow from
+/// the input, since the printed text only lives for the duration of the check.
+///
+/// Example:
+/// ```
+/// use parsicomb::ascii::i64;
+/// use parsicomb::property::check_round_trip;
+/// use parsicomb::testing::check_parses;
+///
+/// let parse = |text: &str| check_parses(&i64(), text);
+/// assert!(check_round_trip(parse, |n| n.to_string(), "-17").is_ok());
+/// assert!(check_round_trip(parse, |n| n.to_string(), "+17").is_ok());
+/// ```
+pub fn check_round_trip<F, G, O>(parse: F, print: G, input: &str) -> Result<(), String>
+where
+    F: Fn(&str) -> Result<O, String>,
+    G: Fn(&O) -> String,
+    O: PartialEq + fmt::Debug,
+{
+    let value =
+        parse(input).map_err(|error| format!("input {:?} does not parse:\n{}", input, error))?;
+    let printed = print(&value);
+    let reparsed = parse(&printed).map_err(|error| {
+        format!(
+            "printed {:?} (from {:?}) does not parse:\n{}",
+            printed, input, error
+        )
+    })?;
+
+    if reparsed != value {
+        return Err(format!(
+            "round trip changed the value of {:?}\n  parsed: {:?}\n printed: {:?}\nreparsed: {:?}",
+            input, value, printed, reparsed
+        ));
+    }
+    Ok(())
+}
+
+/// Check round trips for `tests` inputs generated from `inputs`, panicking with the
+/// first failure
+#[track_caller]
+pub fn assert_round_trips<F, G, O>(parse: F, print: G, inputs: &InputGen, tests: usize)
+where
+    F: Fn(&str) -> Result<O, String>,
+    G: Fn(&O) -> String,
+    O: PartialEq + fmt::Debug,
+{
+    let mut g = Gen::new(16);
+    for _ in 0..tests {
+        let input = inputs.generate(&mut g);
+        if let Err(message) = check_round_trip(&parse, &print, &input) {
+            panic!("{}", message);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ByteCursor;
+    use crate::ascii::i64;
+    use crate::error::ErrorLeaf;
+    use crate::map::MapExt;
+    use crate::parser::Parser;
+    use crate::separated_list::separated_list;
+    use crate::testing::check_parses;
+    use crate::utf8::string::is_string;
+    use quickcheck::{QuickCheck, TestResult};
+
+    fn number() -> InputGen {
+        seq([
+            repeat(literal("-"), 0, 1),
+            char_range('1', '9'),
+            repeat(char_range('0', '9'), 0, 5),
+        ])
+    }
+
+    #[test]
+    fn test_generated_inputs_follow_description() {
+        let list = separated(number(), one_of([literal(","), literal(", ")]), 1, 6);
+        let mut g = Gen::from_size_and_seed(16, 7);
+
+        for _ in 0..100 {
+            let input = list.generate(&mut g);
+            let items: Vec<_> = input.split(',').map(str::trim).collect();
+            assert!((1..=6).contains(&items.len()), "{:?}", input);
+            for item in items {
+                assert!(item.parse::<i64>().is_ok(), "{:?}", input);
+            }
+        }
+    }
+
+    #[test]
+    fn test_round_trip_number_list() {
+        let parse = |text: &str| check_parses(&separated_list(i64(), is_string(",")), text);
+        let print = |items: &Vec<i64>| {
+            items
+                .iter()
+                .map(i64::to_string)
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        assert_round_trips(parse, print, &separated(number(), literal(","), 1, 8), 200);
+    }
+
+    #[test]
+    fn test_round_trip_reports_lossy_printer() {
+        let parse = |text: &str| check_parses(&i64(), text);
+        let error = check_round_trip(parse, |n| (n + 1).to_string(), "41").unwrap_err();
+        assert!(error.contains("round trip changed the value"));
+
+        let error = check_round_trip(parse, |n| n.to_string(), "x").unwrap_err();
+        assert!(error.contains("does not parse"));
+    }
+
+    #[test]
+    fn test_noise_rates() {
+        let mut g = Gen::from_size_and_seed(16, 3);
+        assert_eq!(with_noise("unchanged", 0.0, &mut g), "unchanged");
+        assert_ne!(
+            with_noise("abcdefghijklmnop", 1.0, &mut g),
+            "abcdefghijklmnop"
+        );
+    }
+
+    #[test]
+    fn test_arbitrary_text_never_panics_parser() {
+        fn prop(text: Utf8Text, ascii: AsciiText) -> TestResult {
+            let parser = i64().map(|n| n.to_string());
+            for input in [text.0.as_bytes(), ascii.0.as_bytes()] {
+                if let Err(error) = parser.parse(ByteCursor::new(input)) {
+                    let _ = error.loc();
+                }
+            }
+            TestResult::passed()
+        }
+        QuickCheck::new()
+            .tests(200)
+            .quickcheck(prop as fn(Utf8Text, AsciiText) -> TestResult);
+    }
+}
@@ -0,0 +1,168 @@
+use super::parser::Parser;
+use crate::ByteCursor;
+use crate::cursor::Cursor;
+use crate::describe::Description;
+use crate::error::{CodeLoc, ParsicombError};
+
+/// Parser combinator that returns the input consumed by the given parser
+///
+/// The output of the inner parser is discarded, only its extent is kept.
+pub struct Recognize<P> {
+    parser: P,
+}
+
+impl<P> Recognize<P> {
+    pub fn new(parser: P) -> Self {
+        Recognize { parser }
+    }
+}
+
+impl<'code, P> Parser<'code> for Recognize<P>
+where
+    P: Parser<'code>,
+    <P::Cursor as Cursor<'code>>::Element: 'code,
+{
+    type Cursor = P::Cursor;
+    type Output = &'code [<P::Cursor as Cursor<'code>>::Element];
+    type Error = P::Error;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (_, next_cursor) = self.parser.parse(cursor)?;
+        let consumed = &cursor.source()[cursor.position()..next_cursor.position()];
+        Ok((consumed, next_cursor))
+    }
+
+    fn describe(&self) -> Description {
+        self.parser.describe()
+    }
+}
+
+/// Parser combinator that returns the bytes consumed by the given parser as a `&str`
+///
+/// Fails if the consumed bytes are not valid UTF-8.
+pub struct RecognizeStr<P> {
+    parser: P,
+}
+
+impl<P> RecognizeStr<P> {
+    pub fn new(parser: P) -> Self {
+        RecognizeStr { parser }
+    }
+}
+
+impl<'code, P> Parser<'code> for RecognizeStr<P>
+where
+    P: Parser<'code, Cursor = ByteCursor<'code>>,
+    P::Error: 'code,
+{
+    type Cursor = ByteCursor<'code>;
+    type Output = &'code str;
+    type Error = ParsicombError<'code>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (consumed, next_cursor) = Recognize::new(&self.parser)
+            .parse(cursor)
+            .map_err(ParsicombError::wrap)?;
+
+        match std::str::from_utf8(consumed) {
+            Ok(text) => Ok((text, next_cursor)),
+            Err(e) => {
+                let (data, start) = cursor.inner();
+                Err(ParsicombError::SyntaxError {
+                    message: "invalid UTF-8 in recognized input".into(),
+                    loc: CodeLoc::new(data, start + e.valid_up_to()),
+                })
+            }
+        }
+    }
+
+    fn describe(&self) -> Description {
+        self.parser.describe()
+    }
+}
+
+/// Convenience function to create a Recognize parser
+pub fn recognize<'code, P>(parser: P) -> Recognize<P>
+where
+    P: Parser<'code>,
+{
+    Recognize::new(parser)
+}
+
+/// Extension trait to add .recognize() and .recognize_str() method support for parsers
+pub trait RecognizeExt<'code>: Parser<'code> + Sized {
+    fn recognize(self) -> Recognize<Self> {
+        Recognize::new(self)
+    }
+
+    fn recognize_str(self) -> RecognizeStr<Self>
+    where
+        Self: Parser<'code, Cursor = ByteCursor<'code>>,
+    {
+        RecognizeStr::new(self)
+    }
+}
+
+/// Implement RecognizeExt for all parsers
+impl<'code, P> RecognizeExt<'code> for P where P: Parser<'code> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::and::AndExt;
+    use crate::ascii::{digit, f64};
+    use crate::byte::{byte, is_byte};
+    use crate::error::ErrorLeaf;
+    use crate::many::many;
+    use crate::some::some;
+
+    #[test]
+    fn test_recognize_returns_consumed_slice() {
+        let data = b"3.50 rest";
+        let cursor = ByteCursor::new(data);
+
+        let (text, cursor) = f64().recognize().parse(cursor).unwrap();
+        assert_eq!(text, b"3.50");
+        assert_eq!(cursor.value().unwrap(), b' ');
+    }
+
+    #[test]
+    fn test_recognize_str() {
+        let data = b"007x";
+        let cursor = ByteCursor::new(data);
+
+        let (text, cursor) = some(digit()).recognize_str().parse(cursor).unwrap();
+        assert_eq!(text, "007");
+        assert_eq!(cursor.value().unwrap(), b'x');
+    }
+
+    #[test]
+    fn test_recognize_empty_match() {
+        let data = b"abc";
+        let cursor = ByteCursor::new(data);
+
+        let (text, cursor) = recognize(many(is_byte(b'x'))).parse(cursor).unwrap();
+        assert!(text.is_empty());
+        assert_eq!(cursor.position(), 0);
+    }
+
+    #[test]
+    fn test_recognize_propagates_error() {
+        let data = b"abc";
+        let cursor = ByteCursor::new(data);
+        assert!(digit().recognize().parse(cursor).is_err());
+    }
+
+    #[test]
+    fn test_recognize_str_invalid_utf8() {
+        let data = b"a\xffb";
+        let cursor = ByteCursor::new(data);
+
+        let error = byte()
+            .and(byte())
+            .recognize_str()
+            .parse(cursor)
+            .unwrap_err();
+        assert_eq!(error.loc().position(), 1);
+    }
+}
@@ -0,0 +1,64 @@
+use crate::atomic::Atomic;
+use crate::error::{ErrorLeaf, ErrorNode};
+use std::fmt;
+
+/// Error type shared by the repetition combinators recording which repetition failed
+///
+/// Returned by `count`, `some` and `take_until`. It only depends on the element type
+/// of the cursor, so it works the same over bytes, chars or any other `Atomic`.
+pub struct RepetitionError<'code, T: Atomic> {
+    index: usize,
+    inner: Box<dyn ErrorNode<'code, Element = T> + 'code>,
+}
+
+impl<'code, T: Atomic> RepetitionError<'code, T> {
+    pub fn new(index: usize, inner: impl ErrorNode<'code, Element = T> + 'code) -> Self {
+        RepetitionError {
+            index,
+            inner: Box::new(inner),
+        }
+    }
+
+    /// Index of the repetition that failed, starting at 0
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The error produced by the failing repetition
+    pub fn inner(&self) -> &(dyn ErrorNode<'code, Element = T> + 'code) {
+        self.inner.as_ref()
+    }
+}
+
+impl<'code, T: Atomic> fmt::Debug for RepetitionError<'code, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RepetitionError")
+            .field("index", &self.index)
+            .field("inner", &format!("{}", &*self.inner))
+            .finish()
+    }
+}
+
+impl<'code, T: Atomic> fmt::Display for RepetitionError<'code, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Repetition {} failed: {}", self.index, &*self.inner)
+    }
+}
+
+impl<'code, T: Atomic> std::error::Error for RepetitionError<'code, T> {}
+
+impl<'code, T: Atomic + 'code> ErrorNode<'code> for RepetitionError<'code, T> {
+    type Element = T;
+
+    fn likely_error(&self) -> &dyn ErrorLeaf<'code, Element = Self::Element> {
+        self.inner.likely_error()
+    }
+
+    fn furthest_leaves(&self) -> Vec<&dyn ErrorLeaf<'code, Element = Self::Element>> {
+        self.inner.furthest_leaves()
+    }
+
+    fn children(&self) -> Vec<&dyn ErrorNode<'code, Element = Self::Element>> {
+        vec![self.inner.as_ref()]
+    }
+}
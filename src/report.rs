@@ -0,0 +1,392 @@
+//! # Error Reports
+//!
+//! Renders parse errors as annotated source snippets. Where the `Display` impl of
+//! [`ParsicombError`](crate::ParsicombError) always uses the same fixed layout, a
+//! [`Report`] lets callers choose colors, how many lines of context to show, and
+//! how far the underline extends:
+//!
+//! ```text
+//! error: expected ']', found ';'
+//!  --> line 2, column 8
+//!   |
+//! 1 | let xs =
+//! 2 |   [1, 2; 3]
+//!   |        ^ here
+//!   = help: did you mean ','?
+//! ```
+
+use crate::atomic::Atomic;
+use crate::error::ErrorNode;
+use crate::position::Span;
+use std::borrow::Cow;
+use std::fmt;
+
+const RED_BOLD: &str = "\x1b[1;31m";
+const BLUE_BOLD: &str = "\x1b[1;34m";
+const BOLD: &str = "\x1b[1m";
+const RESET: &str = "\x1b[0m";
+
+/// Start and end (exclusive) element offsets of every line in the source
+pub(crate) fn line_ranges<T: Atomic>(source: &[T]) -> Vec<(usize, usize)> {
+    let mut lines = Vec::new();
+    let mut line_start = 0;
+
+    for (i, element) in source.iter().enumerate() {
+        if element.is_newline() {
+            lines.push((line_start, i));
+            line_start = i + 1;
+        }
+    }
+    lines.push((line_start, source.len()));
+
+    lines
+}
+
+/// Formatting options for a [`Report`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportConfig {
+    /// Emit ANSI color escape codes
+    pub color: bool,
+    /// Number of source lines shown before and after the error line
+    pub context_lines: usize,
+    /// Character used to underline the error range
+    pub underline: char,
+    /// Text printed after the underline
+    pub label: Option<Cow<'static, str>>,
+}
+
+impl Default for ReportConfig {
+    fn default() -> Self {
+        ReportConfig {
+            color: false,
+            context_lines: 2,
+            underline: '^',
+            label: None,
+        }
+    }
+}
+
+/// A renderable error report pointing at a range of source code
+///
+/// Implements `Display`, so it can be printed directly or turned into a string.
+#[derive(Debug, Clone)]
+pub struct Report<'code, T: Atomic = u8> {
+    message: String,
+    source: &'code [T],
+    start: usize,
+    end: usize,
+    hints: Vec<String>,
+    code: Option<String>,
+    file: Option<String>,
+    config: ReportConfig,
+}
+
+impl<'code, T: Atomic> Report<'code, T> {
+    /// Create a report for the likely error of an error tree
+    pub fn new<E>(error: &E) -> Self
+    where
+        E: ErrorNode<'code, Element = T> + ?Sized,
+    {
+        let leaf = error.likely_error();
+        let loc = leaf.loc();
+        let mut hints: Vec<String> = Vec::new();
+        for hint in error.hints() {
+            if !hints.iter().any(|other| *other == hint) {
+                hints.push(hint.into_owned());
+            }
+        }
+        Report {
+            message: leaf.message().into_owned(),
+            source: loc.source(),
+            start: loc.position(),
+            end: loc.position(),
+            hints,
+            code: error.code().map(Cow::into_owned),
+            file: None,
+            config: ReportConfig::default(),
+        }
+    }
+
+    /// Create a report with a custom message that underlines a span
+    pub fn from_span(message: impl Into<String>, span: Span<'code, T>) -> Self {
+        Report {
+            message: message.into(),
+            source: span.source,
+            start: span.start,
+            end: span.end,
+            hints: Vec::new(),
+            code: None,
+            file: None,
+            config: ReportConfig::default(),
+        }
+    }
+
+    /// Extend the underline from the error position up to `end` (exclusive)
+    pub fn underline_to(mut self, end: usize) -> Self {
+        self.end = end.max(self.start);
+        self
+    }
+
+    /// Replace all formatting options
+    pub fn with_config(mut self, config: ReportConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Enable or disable ANSI colors
+    pub fn color(mut self, color: bool) -> Self {
+        self.config.color = color;
+        self
+    }
+
+    /// Set the number of context lines shown around the error line
+    pub fn context_lines(mut self, context_lines: usize) -> Self {
+        self.config.context_lines = context_lines;
+        self
+    }
+
+    /// Set the text printed after the underline
+    pub fn label(mut self, label: impl Into<Cow<'static, str>>) -> Self {
+        self.config.label = Some(label.into());
+        self
+    }
+
+    /// Add a `help:` line below the source snippet
+    pub fn hint(mut self, hint: impl Into<String>) -> Self {
+        self.hints.push(hint.into());
+        self
+    }
+
+    /// Name the file in the location line, `--> name:line:column`
+    pub fn file(mut self, name: impl Into<String>) -> Self {
+        self.file = Some(name.into());
+        self
+    }
+
+    /// Show the report against `source` with the error at `start`
+    pub(crate) fn in_file(mut self, name: String, source: &'code [T], start: usize) -> Self {
+        let length = self.end - self.start;
+        self.source = source;
+        self.start = start;
+        self.end = start + length;
+        self.file(name)
+    }
+
+    fn width(elements: &[T]) -> usize {
+        elements.iter().map(|element| element.display_width()).sum()
+    }
+
+    fn paint(&self, f: &mut fmt::Formatter<'_>, style: &str, text: &str) -> fmt::Result {
+        if self.config.color {
+            write!(f, "{}{}{}", style, text, RESET)
+        } else {
+            write!(f, "{}", text)
+        }
+    }
+}
+
+impl<'code, T: Atomic> fmt::Display for Report<'code, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lines = line_ranges(self.source);
+        let start = self.start.min(self.source.len());
+        let error_line = lines
+            .iter()
+            .position(|&(_, line_end)| start <= line_end)
+            .unwrap_or(lines.len() - 1);
+        let (line_start, line_end) = lines[error_line];
+
+        let first = error_line.saturating_sub(self.config.context_lines);
+        let mut last = (error_line + self.config.context_lines).min(lines.len() - 1);
+        // A trailing newline leaves an empty final line that is only worth showing
+        // when the error is on it
+        if last > error_line && lines[last].0 == lines[last].1 {
+            last -= 1;
+        }
+        let gutter = (last + 1).to_string().len();
+
+        let offset = Self::width(&self.source[line_start..start]);
+        let underline_end = self.end.clamp(start, line_end);
+        let underline_width = Self::width(&self.source[start..underline_end]).max(1);
+
+        match &self.code {
+            Some(code) => self.paint(f, RED_BOLD, &format!("error[{}]", code))?,
+            None => self.paint(f, RED_BOLD, "error")?,
+        }
+        self.paint(f, BOLD, &format!(": {}", self.message))?;
+        writeln!(f)?;
+        self.paint(f, BLUE_BOLD, &format!("{:gutter$}-->", ""))?;
+        match &self.file {
+            Some(file) => writeln!(f, " {}:{}:{}", file, error_line + 1, offset + 1)?,
+            None => writeln!(f, " line {}, column {}", error_line + 1, offset + 1)?,
+        }
+        self.paint(f, BLUE_BOLD, &format!("{:gutter$} |", ""))?;
+        writeln!(f)?;
+
+        for (index, &(begin, end)) in lines.iter().enumerate().take(last + 1).skip(first) {
+            let text = T::format_slice(&self.source[begin..end]);
+            self.paint(f, BLUE_BOLD, &format!("{:>gutter$} |", index + 1))?;
+            writeln!(f, " {}", text.trim_end_matches('\r'))?;
+
+            if index == error_line {
+                self.paint(f, BLUE_BOLD, &format!("{:gutter$} |", ""))?;
+                write!(f, " {}", " ".repeat(offset))?;
+                let mut marker = self.config.underline.to_string().repeat(underline_width);
+                if let Some(label) = &self.config.label {
+                    marker.push(' ');
+                    marker.push_str(label);
+                }
+                self.paint(f, RED_BOLD, &marker)?;
+                writeln!(f)?;
+            }
+        }
+
+        for hint in &self.hints {
+            self.paint(f, BLUE_BOLD, &format!("{:gutter$} =", ""))?;
+            self.paint(f, BOLD, " help")?;
+            writeln!(f, ": {}", hint)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Convenience function to create a Report with default formatting
+pub fn report<'code, E>(error: &E) -> Report<'code, E::Element>
+where
+    E: ErrorNode<'code> + ?Sized,
+{
+    Report::new(error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::byte::is_byte;
+    use crate::error::{CodeLoc, ParsicombError};
+    use crate::{ByteCursor, Cursor, Parser};
+
+    fn syntax_error<'code>(
+        data: &'code [u8],
+        position: usize,
+        message: &'static str,
+    ) -> ParsicombError<'code> {
+        ParsicombError::SyntaxError {
+            message: message.into(),
+            loc: CodeLoc::new(data, position),
+        }
+    }
+
+    #[test]
+    fn test_report_plain() {
+        let data = b"let xs =\n  [1, 2; 3]\n";
+        let error = syntax_error(data, 16, "expected ']', found ';'");
+
+        let rendered = report(&error).to_string();
+        assert_eq!(
+            rendered,
+            "error: expected ']', found ';'\n\
+             \x20--> line 2, column 8\n\
+             \x20 |\n\
+             1 | let xs =\n\
+             2 |   [1, 2; 3]\n\
+             \x20 |        ^\n"
+        );
+    }
+
+    #[test]
+    fn test_report_underline_range_and_label() {
+        let data = b"x = fnord(1)";
+        let error = syntax_error(data, 4, "unknown function");
+
+        let rendered = Report::new(&error)
+            .underline_to(9)
+            .label("not defined")
+            .to_string();
+        assert!(rendered.contains("  |     ^^^^^ not defined\n"));
+    }
+
+    #[test]
+    fn test_report_help_lines() {
+        use crate::hint::HintExt;
+
+        let data = b"let x = 1\nlet y = 2";
+        let parser = is_byte(b';')
+            .with_hint("statements end with ';'")
+            .with_hint("statements end with ';'");
+        let error = parser.parse(ByteCursor::at(data, 9)).unwrap_err();
+
+        let rendered = report(&error).hint("see the language guide").to_string();
+        assert!(rendered.ends_with(
+            "  |          ^\n\
+             2 | let y = 2\n\
+             \x20 = help: statements end with ';'\n\
+             \x20 = help: see the language guide\n"
+        ));
+    }
+
+    #[test]
+    fn test_report_from_span() {
+        let data = b"a\nbb\nccc";
+        let rendered = Report::from_span("bad token", Span::new(data, 2, 4)).to_string();
+
+        assert!(rendered.starts_with("error: bad token\n"));
+        assert!(rendered.contains("2 | bb\n  | ^^\n"));
+        assert!(rendered.contains("3 | ccc\n"));
+    }
+
+    #[test]
+    fn test_report_context_lines() {
+        let data = b"one\ntwo\nthree\nfour\nfive";
+        let error = syntax_error(data, 8, "oops");
+
+        let rendered = report(&error).context_lines(0).to_string();
+        assert!(rendered.contains("3 | three\n"));
+        assert!(!rendered.contains("two"));
+        assert!(!rendered.contains("four"));
+
+        let rendered = report(&error).context_lines(1).to_string();
+        assert!(rendered.contains("2 | two\n"));
+        assert!(rendered.contains("4 | four\n"));
+        assert!(!rendered.contains("five"));
+    }
+
+    #[test]
+    fn test_report_color() {
+        let data = b"abc";
+        let error = syntax_error(data, 1, "oops");
+
+        let rendered = report(&error).color(true).to_string();
+        assert!(rendered.starts_with("\x1b[1;31merror\x1b[0m"));
+        assert!(rendered.contains("\x1b[1;31m^\x1b[0m"));
+        assert!(!report(&error).to_string().contains('\x1b'));
+    }
+
+    #[test]
+    fn test_report_at_end_of_input() {
+        let data = b"abc\n";
+        let cursor = ByteCursor::new(data).next().next().next().next();
+        let error = is_byte(b'x').parse(cursor).unwrap_err();
+
+        let rendered = report(&error).to_string();
+        assert!(rendered.contains("--> line 2, column 1"));
+        assert!(rendered.contains("2 | \n  | ^\n"));
+    }
+
+    #[test]
+    fn test_report_skips_trailing_empty_line() {
+        let data = b"abc\n";
+        let error = syntax_error(data, 0, "oops");
+
+        let rendered = report(&error).to_string();
+        assert!(!rendered.contains("2 |"));
+    }
+
+    #[test]
+    fn test_report_uses_message_of_wrapped_error() {
+        let data = b"abc";
+        let error = ParsicombError::wrap(syntax_error(data, 2, "inner message"));
+
+        let rendered = report(&error).to_string();
+        assert!(rendered.starts_with("error: inner message\n"));
+    }
+}
@@ -1,7 +1,10 @@
 use crate::atomic::Atomic;
 use crate::cursor::Cursor;
-use crate::error::{ErrorLeaf, ErrorNode};
+use crate::describe::Description;
+use crate::error::{ErrorLeaf, ErrorNode, ParsicombError};
 use crate::parser::Parser;
+use crate::position::{Span, Spanned};
+use crate::skip::skip_padding;
 use std::fmt;
 
 /// Error type for SeparatedList parser
@@ -42,6 +45,13 @@ where
             SeparatedListError::Separator(e) => e.likely_error(),
         }
     }
+
+    fn children(&self) -> Vec<&dyn ErrorNode<'code, Element = T>> {
+        match self {
+            SeparatedListError::Element(e) => vec![e],
+            SeparatedListError::Separator(e) => vec![e],
+        }
+    }
 }
 
 /// Parser combinator that matches a list of items separated by a parser
@@ -57,6 +67,7 @@ where
 /// - Requires at least one element
 /// - Trailing separators cause an error
 /// - Does not handle whitespace automatically
+/// - Separator errors end the list even if [committed](crate::cut::CutExt::cut)
 pub struct SeparatedList<P, PS> {
     parser: P,
     separator: PS,
@@ -104,6 +115,16 @@ where
 
         Ok((results, cursor))
     }
+
+    fn describe(&self) -> Description {
+        Description::sequence(
+            self.parser.describe(),
+            Description::repeat(
+                0,
+                Description::sequence(self.separator.describe(), self.parser.describe()),
+            ),
+        )
+    }
 }
 
 /// Creates a parser that matches a list of items separated by the given parser
@@ -119,6 +140,319 @@ where
     SeparatedList::new(parser, separator)
 }
 
+/// Parser combinator like [`SeparatedList`] that skips trivia around separators
+///
+/// Any number of `pad` matches, such as whitespace or comments, may appear between
+/// an item and the following separator and between a separator and the next item.
+/// Padding never fails and is not consumed before the first or after the last item,
+/// so errors point at the item or the end of the list, never into the trivia.
+///
+/// # Examples
+/// - `"1 , 2 // two\n, 3"` with separator `,` and whitespace or `//` comments as
+///   padding → `vec![1, 2, 3]`
+pub struct SeparatedListPadded<P, PS, PP> {
+    parser: P,
+    separator: PS,
+    pad: PP,
+}
+
+impl<P, PS, PP> SeparatedListPadded<P, PS, PP> {
+    pub fn new(parser: P, separator: PS, pad: PP) -> Self {
+        SeparatedListPadded {
+            parser,
+            separator,
+            pad,
+        }
+    }
+}
+
+impl<'code, P, PS, PP> Parser<'code> for SeparatedListPadded<P, PS, PP>
+where
+    P: Parser<'code>,
+    <P::Cursor as Cursor<'code>>::Element: Atomic + 'code,
+    P::Error: ErrorNode<'code, Element = <P::Cursor as Cursor<'code>>::Element>,
+    PS: Parser<'code, Cursor = P::Cursor>,
+    PP: Parser<'code, Cursor = P::Cursor>,
+{
+    type Cursor = P::Cursor;
+    type Output = Vec<P::Output>;
+    type Error = P::Error;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let mut results = Vec::new();
+
+        let (first_value, mut cursor) = self.parser.parse(cursor)?;
+        results.push(first_value);
+
+        loop {
+            // Trivia before a missing separator belongs to whatever follows the list
+            let padded = skip_padding(&self.pad, cursor).0;
+            let temp_cursor = match self.separator.parse(padded) {
+                Ok((_, new_cursor)) => skip_padding(&self.pad, new_cursor).0,
+                Err(_) => break,
+            };
+
+            let (value, next_cursor) = self.parser.parse(temp_cursor)?;
+            results.push(value);
+            cursor = next_cursor;
+        }
+
+        Ok((results, cursor))
+    }
+
+    fn describe(&self) -> Description {
+        let pad = || Description::repeat(0, self.pad.describe());
+        let separator = Description::Sequence(vec![pad(), self.separator.describe(), pad()]);
+        Description::sequence(
+            self.parser.describe(),
+            Description::repeat(0, Description::sequence(separator, self.parser.describe())),
+        )
+    }
+}
+
+/// Creates a parser for a separated list that skips `pad` matches around separators
+///
+/// The padding parser can match whitespace, comments, or any other trivia.
+pub fn separated_list_padded<'code, P, PS, PP>(
+    parser: P,
+    separator: PS,
+    pad: PP,
+) -> SeparatedListPadded<P, PS, PP>
+where
+    P: Parser<'code>,
+    PS: Parser<'code, Cursor = P::Cursor>,
+    PP: Parser<'code, Cursor = P::Cursor>,
+{
+    SeparatedListPadded::new(parser, separator, pad)
+}
+
+/// Items and separators matched by [`SeparatedListWith`], each with its span
+///
+/// Keeps everything a formatter or linter needs to reproduce the original list.
+/// Separators are stored in source order, `separators[i]` follows `items[i]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeparatedItems<'code, O, S, T: Atomic = u8> {
+    pub items: Vec<Spanned<'code, O, T>>,
+    pub separators: Vec<Spanned<'code, S, T>>,
+}
+
+impl<'code, O, S, T: Atomic> SeparatedItems<'code, O, S, T> {
+    /// Whether the list ended with a separator that no item followed
+    pub fn has_trailing_separator(&self) -> bool {
+        self.separators.len() == self.items.len()
+    }
+}
+
+/// Parser combinator like [`SeparatedList`] that also keeps separators and spans
+///
+/// Requires at least one element. Unlike `SeparatedList`, a trailing separator is
+/// accepted and recorded, see [`SeparatedItems::has_trailing_separator`], unless the
+/// item after it failed with a [committed](ErrorNode::is_committed) error.
+pub struct SeparatedListWith<P, PS> {
+    parser: P,
+    separator: PS,
+}
+
+impl<P, PS> SeparatedListWith<P, PS> {
+    pub fn new(parser: P, separator: PS) -> Self {
+        SeparatedListWith { parser, separator }
+    }
+}
+
+impl<'code, P, PS> Parser<'code> for SeparatedListWith<P, PS>
+where
+    P: Parser<'code>,
+    <P::Cursor as Cursor<'code>>::Element: Atomic + 'code,
+    PS: Parser<'code, Cursor = P::Cursor>,
+{
+    type Cursor = P::Cursor;
+    type Output =
+        SeparatedItems<'code, P::Output, PS::Output, <P::Cursor as Cursor<'code>>::Element>;
+    type Error = P::Error;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let source = cursor.source();
+        let span = |start: Self::Cursor, end: Self::Cursor| {
+            Span::new(source, start.position(), end.position())
+        };
+
+        let start = cursor;
+        let (first_value, mut cursor) = self.parser.parse(start)?;
+        let mut list = SeparatedItems {
+            items: vec![Spanned::new(first_value, span(start, cursor))],
+            separators: Vec::new(),
+        };
+
+        loop {
+            let (separator, after_separator) = match self.separator.parse(cursor) {
+                Ok(result) => result,
+                Err(_) => break,
+            };
+            list.separators
+                .push(Spanned::new(separator, span(cursor, after_separator)));
+            cursor = after_separator;
+
+            // A separator without an item after it is a trailing separator
+            let (value, next_cursor) = match self.parser.parse(cursor) {
+                Ok(result) => result,
+                Err(error) if error.is_committed() => return Err(error),
+                Err(_) => break,
+            };
+            list.items
+                .push(Spanned::new(value, span(cursor, next_cursor)));
+            cursor = next_cursor;
+        }
+
+        Ok((list, cursor))
+    }
+}
+
+/// Creates a parser that matches a separated list, keeping separators and spans
+pub fn separated_list_with<'code, P, PS>(parser: P, separator: PS) -> SeparatedListWith<P, PS>
+where
+    P: Parser<'code>,
+    PS: Parser<'code, Cursor = P::Cursor>,
+{
+    SeparatedListWith::new(parser, separator)
+}
+
+/// Elements and flattened errors produced by [`SeparatedListRecover`]
+pub type RecoveredList<'code, O, E, T = u8> = (Vec<Result<O, E>>, Vec<ParsicombError<'code, T>>);
+
+/// Parser combinator for separated lists that recovers from broken elements
+///
+/// When an element fails, its error is kept in place of the element and the input
+/// is skipped up to the next separator, where parsing continues. Skipping stops
+/// before `skip_to` (usually the closing delimiter) or at the end of input, which
+/// also ends the list. A missing separator between two elements is recovered the
+/// same way.
+///
+/// Returns one `Result` per element and every error flattened in source order, so
+/// an editor can show all of them at once. This parser never fails: an empty list
+/// is accepted when `skip_to` matches right away, and an empty list element is
+/// reported as an error.
+///
+/// # Examples
+/// - `"1,x,3"` → `[Ok(1), Err(..), Ok(3)]` with one error
+/// - `"1 2,3)"` → `[Ok(1), Ok(3)]` with an error for the missing separator
+pub struct SeparatedListRecover<P, PS, PK> {
+    parser: P,
+    separator: PS,
+    skip_to: PK,
+}
+
+impl<P, PS, PK> SeparatedListRecover<P, PS, PK> {
+    pub fn new(parser: P, separator: PS, skip_to: PK) -> Self {
+        SeparatedListRecover {
+            parser,
+            separator,
+            skip_to,
+        }
+    }
+}
+
+impl<'code, P, PS, PK> SeparatedListRecover<P, PS, PK>
+where
+    P: Parser<'code>,
+    PS: Parser<'code, Cursor = P::Cursor>,
+    PK: Parser<'code, Cursor = P::Cursor>,
+{
+    /// Whether the list ends at `cursor`
+    fn at_end(&self, cursor: P::Cursor) -> bool {
+        cursor.eos() || self.skip_to.parse(cursor).is_ok()
+    }
+
+    /// Skip to just after the next separator, `Err` at the end of the list if none follows
+    fn skip(&self, mut cursor: P::Cursor) -> Result<P::Cursor, P::Cursor> {
+        loop {
+            if self.at_end(cursor) {
+                return Err(cursor);
+            }
+            if let Ok((_, next_cursor)) = self.separator.parse(cursor) {
+                return Ok(next_cursor);
+            }
+            cursor = cursor.next();
+        }
+    }
+}
+
+impl<'code, P, PS, PK, T> Parser<'code> for SeparatedListRecover<P, PS, PK>
+where
+    T: Atomic + 'code,
+    P: Parser<'code>,
+    P::Cursor: Cursor<'code, Element = T>,
+    P::Error: ErrorNode<'code, Element = T>,
+    PS: Parser<'code, Cursor = P::Cursor>,
+    PS::Error: ErrorNode<'code, Element = T>,
+    PK: Parser<'code, Cursor = P::Cursor>,
+{
+    type Cursor = P::Cursor;
+    type Output = RecoveredList<'code, P::Output, P::Error, T>;
+    type Error = ParsicombError<'code, T>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let mut items = Vec::new();
+        let mut errors = Vec::new();
+        let mut cursor = cursor;
+
+        if self.skip_to.parse(cursor).is_ok() {
+            return Ok(((items, errors), cursor));
+        }
+
+        loop {
+            // A failed element leaves the cursor where the element started
+            let matched = match self.parser.parse(cursor) {
+                Ok((value, next_cursor)) => {
+                    items.push(Ok(value));
+                    cursor = next_cursor;
+                    true
+                }
+                Err(error) => {
+                    errors.push(ParsicombError::flatten(&error));
+                    items.push(Err(error));
+                    false
+                }
+            };
+
+            match self.separator.parse(cursor) {
+                Ok((_, next_cursor)) => cursor = next_cursor,
+                Err(_) if matched && self.at_end(cursor) => break,
+                Err(error) => {
+                    if matched {
+                        errors.push(ParsicombError::flatten(&error));
+                    }
+                    match self.skip(cursor) {
+                        Ok(next_cursor) => cursor = next_cursor,
+                        Err(end) => {
+                            cursor = end;
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(((items, errors), cursor))
+    }
+}
+
+/// Creates a separated list parser that recovers from broken elements
+///
+/// `skip_to` is only looked at, never consumed, so the closing delimiter is left
+/// for the enclosing parser.
+pub fn separated_list_recover<'code, P, PS, PK>(
+    parser: P,
+    separator: PS,
+    skip_to: PK,
+) -> SeparatedListRecover<P, PS, PK>
+where
+    P: Parser<'code>,
+    PS: Parser<'code, Cursor = P::Cursor>,
+    PK: Parser<'code, Cursor = P::Cursor>,
+{
+    SeparatedListRecover::new(parser, separator, skip_to)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -217,4 +551,148 @@ mod tests {
         assert_eq!(results, vec![1, 2, 3]);
         assert_eq!(cursor.value().unwrap(), b' ');
     }
+
+    #[test]
+    fn test_separated_list_with_spans_and_separators() {
+        let data = b"1, 22 ,3";
+        let cursor = ByteCursor::new(data);
+        let separator = is_string(", ").or(is_string(" ,"));
+        let parser = separated_list_with(i64(), separator);
+
+        let (list, cursor) = parser.parse(cursor).unwrap();
+        assert!(cursor.eos());
+        let items: Vec<_> = list.items.iter().map(|item| item.value).collect();
+        assert_eq!(items, vec![1, 22, 3]);
+        assert_eq!(list.items[1].span, Span::new(data, 3, 5));
+        assert_eq!(list.separators[0].value, ", ");
+        assert_eq!(list.separators[1].span.as_string(), " ,");
+        assert!(!list.has_trailing_separator());
+    }
+
+    #[test]
+    fn test_separated_list_with_trailing_separator() {
+        let data = b"1,2,]";
+        let cursor = ByteCursor::new(data);
+        let parser = separated_list_with(i64(), is_byte(b','));
+
+        let (list, cursor) = parser.parse(cursor).unwrap();
+        assert_eq!(list.items.len(), 2);
+        assert!(list.has_trailing_separator());
+        assert_eq!(list.separators[1].span, Span::new(data, 3, 4));
+        assert_eq!(cursor.value().unwrap(), b']');
+    }
+
+    #[test]
+    fn test_separated_list_with_requires_first_item() {
+        let data = b",1";
+        let cursor = ByteCursor::new(data);
+        assert!(
+            separated_list_with(i64(), is_byte(b','))
+                .parse(cursor)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_recover_skips_broken_elements() {
+        let data = b"1,x2,3)";
+        let parser = separated_list_recover(i64(), is_byte(b','), is_byte(b')'));
+
+        let ((items, errors), cursor) = parser.parse(ByteCursor::new(data)).unwrap();
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0].as_ref().ok(), Some(&1));
+        assert!(items[1].is_err());
+        assert_eq!(items[2].as_ref().ok(), Some(&3));
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].position(), 2);
+        assert_eq!(cursor.value().unwrap(), b')');
+    }
+
+    #[test]
+    fn test_recover_missing_separator_and_empty_elements() {
+        let data = b"1 2,,3,)";
+        let parser = separated_list_recover(i64(), is_byte(b','), is_byte(b')'));
+
+        let ((items, errors), cursor) = parser.parse(ByteCursor::new(data)).unwrap();
+        let values: Vec<_> = items.iter().map(|item| item.as_ref().ok()).collect();
+        assert_eq!(values, [Some(&1), None, Some(&3), None]);
+
+        // Missing separator after `1`, empty element before `,3`, empty element before `)`
+        let positions: Vec<_> = errors.iter().map(|error| error.position()).collect();
+        assert_eq!(positions, [1, 4, 7]);
+        assert_eq!(cursor.position(), 7);
+    }
+
+    #[test]
+    fn test_recover_empty_list_and_eof() {
+        let parser = separated_list_recover(i64(), is_byte(b','), is_byte(b')'));
+
+        let ((items, errors), cursor) = parser.parse(ByteCursor::new(b")")).unwrap();
+        assert!(items.is_empty() && errors.is_empty());
+        assert_eq!(cursor.position(), 0);
+
+        let ((items, errors), cursor) = parser.parse(ByteCursor::new(b"1,2")).unwrap();
+        assert_eq!(items.len(), 2);
+        assert!(errors.is_empty());
+        assert!(cursor.eos());
+
+        let ((items, errors), cursor) = parser.parse(ByteCursor::new(b"1,??")).unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(errors.len(), 1);
+        assert!(cursor.eos());
+    }
+
+    fn trivia<'code>() -> impl Parser<'code, Cursor = ByteCursor<'code>, Output = ()> {
+        use crate::map::MapExt;
+        use crate::utf8::{line_comment, unicode_whitespace};
+
+        unicode_whitespace()
+            .map(|_| ())
+            .or(line_comment("//").map(|_| ()))
+    }
+
+    #[test]
+    fn test_padded_skips_whitespace_and_comments() {
+        let data = b"1 , 2 // two\n, 3;";
+        let cursor = ByteCursor::new(data);
+        let parser = separated_list_padded(i64(), is_byte(b','), trivia());
+
+        let (values, cursor) = parser.parse(cursor).unwrap();
+        assert_eq!(values, vec![1, 2, 3]);
+        assert_eq!(cursor.value().unwrap(), b';');
+    }
+
+    #[test]
+    fn test_padded_leaves_trailing_trivia() {
+        let data = b"1,2  // done";
+        let cursor = ByteCursor::new(data);
+        let parser = separated_list_padded(i64(), is_byte(b','), trivia());
+
+        let (values, cursor) = parser.parse(cursor).unwrap();
+        assert_eq!(values, vec![1, 2]);
+        assert_eq!(cursor.position(), 3);
+    }
+
+    #[test]
+    fn test_padded_reports_item_after_trivia() {
+        let data = b"1, // note\n x";
+        let cursor = ByteCursor::new(data);
+        let parser = separated_list_padded(i64(), is_byte(b','), trivia());
+
+        let error = parser.parse(cursor).unwrap_err();
+        assert_eq!(error.likely_error().loc().position(), 12);
+    }
+
+    #[test]
+    fn test_padded_with_empty_matching_padding() {
+        use crate::ascii::take_whitespace;
+
+        let data = b"1 ,2, 3";
+        let cursor = ByteCursor::new(data);
+        let parser = separated_list_padded(i64(), is_byte(b','), take_whitespace());
+
+        let (values, cursor) = parser.parse(cursor).unwrap();
+        assert_eq!(values, vec![1, 2, 3]);
+        assert!(cursor.eos());
+    }
 }
@@ -1,7 +1,9 @@
 use crate::atomic::Atomic;
 use crate::cursor::Cursor;
+use crate::describe::Description;
 use crate::error::{ErrorLeaf, ErrorNode};
 use crate::parser::Parser;
+use crate::skip::skip_padding;
 use std::fmt;
 
 /// Error type for SeparatedPair parser that can wrap errors from all constituent parsers
@@ -50,6 +52,14 @@ where
             SeparatedPairError::RightParser(e2) => e2.likely_error(),
         }
     }
+
+    fn children(&self) -> Vec<&dyn ErrorNode<'code, Element = T>> {
+        match self {
+            SeparatedPairError::LeftParser(e1) => vec![e1],
+            SeparatedPairError::Separator(e) => vec![e],
+            SeparatedPairError::RightParser(e2) => vec![e2],
+        }
+    }
 }
 
 /// Parser that matches two values separated by a parser
@@ -57,8 +67,8 @@ where
 /// This combinator parses: `left + separator + right`
 /// and returns a tuple `(left_value, right_value)` with the separator discarded.
 ///
-/// Unlike the UTF-8 whitespace version, this does not handle whitespace automatically.
-/// For whitespace handling, use the specific version in `utf8::whitespace::separated_pair`.
+/// This does not handle whitespace automatically. To skip whitespace or comments
+/// around the separator, use [`separated_pair_padded`].
 ///
 /// # Examples
 /// - `"1.0,2.0"` with separator `,` → `(1.0, 2.0)`
@@ -112,6 +122,14 @@ where
 
         Ok(((left_val, right_val), cursor))
     }
+
+    fn describe(&self) -> Description {
+        Description::Sequence(vec![
+            self.left.describe(),
+            self.separator.describe(),
+            self.right.describe(),
+        ])
+    }
 }
 
 /// Creates a parser that matches two values separated by the given parser
@@ -137,6 +155,105 @@ where
     SeparatedPair::new(left, separator, right)
 }
 
+/// Parser that matches two values separated by a parser, with padding around the separator
+///
+/// Parses: `left + pad* + separator + pad* + right`, where `pad*` is zero or more
+/// matches of the padding parser whose outputs are discarded. Padding never fails,
+/// so errors have the same structure as [`SeparatedPair`]. A missing separator is
+/// reported where the left value ended, before any padding, while an error in the
+/// right value points past the padding at the value itself.
+pub struct SeparatedPairPadded<P1, PS, P2, PP> {
+    left: P1,
+    separator: PS,
+    right: P2,
+    pad: PP,
+}
+
+impl<P1, PS, P2, PP> SeparatedPairPadded<P1, PS, P2, PP> {
+    pub fn new(left: P1, separator: PS, right: P2, pad: PP) -> Self {
+        SeparatedPairPadded {
+            left,
+            separator,
+            right,
+            pad,
+        }
+    }
+}
+
+impl<'code, P1, PS, P2, PP> Parser<'code> for SeparatedPairPadded<P1, PS, P2, PP>
+where
+    P1: Parser<'code>,
+    <P1::Cursor as Cursor<'code>>::Element: Atomic + 'code,
+    P1::Error: ErrorNode<'code, Element = <P1::Cursor as Cursor<'code>>::Element>,
+    PS: Parser<'code, Cursor = P1::Cursor>,
+    PS::Error: ErrorNode<'code, Element = <P1::Cursor as Cursor<'code>>::Element>,
+    P2: Parser<'code, Cursor = P1::Cursor>,
+    P2::Error: ErrorNode<'code, Element = <P1::Cursor as Cursor<'code>>::Element>,
+    PP: Parser<'code, Cursor = P1::Cursor>,
+{
+    type Cursor = P1::Cursor;
+    type Output = (P1::Output, P2::Output);
+    type Error = SeparatedPairError<P1::Error, PS::Error, P2::Error>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (left_val, cursor) = self
+            .left
+            .parse(cursor)
+            .map_err(SeparatedPairError::LeftParser)?;
+        let padded = skip_padding(&self.pad, cursor).0;
+        let (_, cursor) = match self.separator.parse(padded) {
+            Ok(result) => result,
+            // Report a missing separator where the left value ended, not past the trivia
+            Err(_) if padded.position() != cursor.position() => self
+                .separator
+                .parse(cursor)
+                .map_err(SeparatedPairError::Separator)?,
+            Err(e) => return Err(SeparatedPairError::Separator(e)),
+        };
+        let cursor = skip_padding(&self.pad, cursor).0;
+        let (right_val, cursor) = self
+            .right
+            .parse(cursor)
+            .map_err(SeparatedPairError::RightParser)?;
+
+        Ok(((left_val, right_val), cursor))
+    }
+
+    fn describe(&self) -> Description {
+        let pad = || Description::repeat(0, self.pad.describe());
+        Description::Sequence(vec![
+            self.left.describe(),
+            pad(),
+            self.separator.describe(),
+            pad(),
+            self.right.describe(),
+        ])
+    }
+}
+
+/// Creates a parser that matches two values separated by the given parser,
+/// skipping any number of `pad` matches on either side of the separator
+///
+/// The padding parser can match whitespace, comments, or any other trivia.
+pub fn separated_pair_padded<'code, P1, PS, P2, PP>(
+    left: P1,
+    separator: PS,
+    right: P2,
+    pad: PP,
+) -> SeparatedPairPadded<P1, PS, P2, PP>
+where
+    P1: Parser<'code>,
+    PS: Parser<'code, Cursor = P1::Cursor>,
+    P2: Parser<'code, Cursor = P1::Cursor>,
+    PP: Parser<'code, Cursor = P1::Cursor>,
+    P1::Error: ErrorNode<'code, Element = <P1::Cursor as Cursor<'code>>::Element>,
+    PS::Error: ErrorNode<'code, Element = <P1::Cursor as Cursor<'code>>::Element>,
+    P2::Error: ErrorNode<'code, Element = <P1::Cursor as Cursor<'code>>::Element>,
+    <P1::Cursor as Cursor<'code>>::Element: Atomic + 'code,
+{
+    SeparatedPairPadded::new(left, separator, right, pad)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -243,4 +360,45 @@ mod tests {
         assert_eq!(left.as_ref(), "A");
         assert_eq!(right.as_ref(), "B");
     }
+
+    #[test]
+    fn test_padded_whitespace_around_separator() {
+        use crate::utf8::whitespace::unicode_whitespace;
+
+        let data = b"key \t= value;";
+        let cursor = ByteCursor::new(data);
+        let parser = separated_pair_padded(
+            is_string("key"),
+            is_byte(b'='),
+            is_string("value"),
+            unicode_whitespace(),
+        );
+
+        let ((left, right), cursor) = parser.parse(cursor).unwrap();
+        assert_eq!(left.as_ref(), "key");
+        assert_eq!(right.as_ref(), "value");
+        assert_eq!(cursor.value().unwrap(), b';');
+    }
+
+    #[test]
+    fn test_padded_reports_right_value_not_padding() {
+        let data = b"1.0 ,  x";
+        let cursor = ByteCursor::new(data);
+        let parser = separated_pair_padded(f64(), is_byte(b','), f64(), is_byte(b' '));
+
+        let error = parser.parse(cursor).unwrap_err();
+        assert!(matches!(error, SeparatedPairError::RightParser(_)));
+        assert_eq!(error.likely_error().loc().position(), 7);
+    }
+
+    #[test]
+    fn test_padded_reports_missing_separator() {
+        let data = b"1.0  2.0";
+        let cursor = ByteCursor::new(data);
+        let parser = separated_pair_padded(f64(), is_byte(b','), f64(), is_byte(b' '));
+
+        let error = parser.parse(cursor).unwrap_err();
+        assert!(matches!(error, SeparatedPairError::Separator(_)));
+        assert_eq!(error.likely_error().loc().position(), 3);
+    }
 }
@@ -0,0 +1,256 @@
+use super::parser::Parser;
+use crate::atomic::Atomic;
+use crate::cursor::Cursor;
+use crate::describe::Description;
+use crate::error::{ErrorLeaf, ErrorNode};
+use std::fmt;
+
+// # Seq Combinator - Flat Tuples for Sequences
+//
+// Chaining `.and()` produces nested outputs like `(((a, b), c), d)`. `seq` takes a
+// tuple of parsers and returns a flat tuple of their outputs instead. Rust has no
+// variadic generics, so the tuple impls are generated by a macro for 2 to 8 parsers.
+//
+// Like And, the error of the failing parser is boxed, so every arity shares the
+// single error type `SeqError<'code>` no matter what parsers it holds.
+
+/// Error type for Seq parser recording which parser in the sequence failed
+pub struct SeqError<'code, T: Atomic> {
+    index: usize,
+    inner: Box<dyn ErrorNode<'code, Element = T> + 'code>,
+}
+
+impl<'code, T: Atomic> SeqError<'code, T> {
+    fn new(index: usize, inner: impl ErrorNode<'code, Element = T> + 'code) -> Self {
+        SeqError {
+            index,
+            inner: Box::new(inner),
+        }
+    }
+
+    /// Position of the failing parser within the tuple, starting at 0
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The error produced by the failing parser
+    pub fn inner(&self) -> &(dyn ErrorNode<'code, Element = T> + 'code) {
+        self.inner.as_ref()
+    }
+}
+
+impl<'code, T: Atomic> fmt::Debug for SeqError<'code, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SeqError")
+            .field("index", &self.index)
+            .field("inner", &format!("{}", &*self.inner))
+            .finish()
+    }
+}
+
+impl<'code, T: Atomic> fmt::Display for SeqError<'code, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Parser {} in sequence failed: {}",
+            self.index, &*self.inner
+        )
+    }
+}
+
+impl<'code, T: Atomic> std::error::Error for SeqError<'code, T> {}
+
+impl<'code, T: Atomic + 'code> ErrorNode<'code> for SeqError<'code, T> {
+    type Element = T;
+
+    fn likely_error(&self) -> &dyn ErrorLeaf<'code, Element = Self::Element> {
+        self.inner.likely_error()
+    }
+
+    fn children(&self) -> Vec<&dyn ErrorNode<'code, Element = Self::Element>> {
+        vec![self.inner.as_ref()]
+    }
+}
+
+/// Tuples of parsers that can be run one after another by [`Seq`]
+pub trait SeqParsers<'code> {
+    type Element: Atomic + 'code;
+    type Cursor: Cursor<'code, Element = Self::Element>;
+    type Output;
+
+    /// Run every parser in order, threading the cursor through them
+    #[allow(clippy::type_complexity)]
+    fn parse_seq(
+        &self,
+        cursor: Self::Cursor,
+    ) -> Result<(Self::Output, Self::Cursor), SeqError<'code, Self::Element>>;
+
+    /// The sequence of the descriptions of the parsers
+    fn describe_seq(&self) -> Description;
+}
+
+macro_rules! impl_seq_parsers {
+    ($($parser:ident $index:tt),+) => {
+        impl<'code, C, $($parser),+> SeqParsers<'code> for ($($parser,)+)
+        where
+            C: Cursor<'code>,
+            C::Element: Atomic + 'code,
+            $($parser: Parser<'code, Cursor = C>, $parser::Error: 'code,)+
+        {
+            type Element = C::Element;
+            type Cursor = C;
+            type Output = ($($parser::Output,)+);
+
+            fn parse_seq(
+                &self,
+                mut cursor: Self::Cursor,
+            ) -> Result<(Self::Output, Self::Cursor), SeqError<'code, C::Element>> {
+                // Tuple fields are evaluated left to right, so the cursor is threaded in order
+                let output = ($(
+                    {
+                        let (value, next_cursor) = self
+                            .$index
+                            .parse(cursor)
+                            .map_err(|e| SeqError::new($index, e))?;
+                        cursor = next_cursor;
+                        value
+                    },
+                )+);
+                Ok((output, cursor))
+            }
+
+            fn describe_seq(&self) -> Description {
+                [$(self.$index.describe()),+]
+                    .into_iter()
+                    .reduce(Description::sequence)
+                    .unwrap_or(Description::Sequence(Vec::new()))
+            }
+        }
+    };
+}
+
+impl_seq_parsers!(P0 0, P1 1);
+impl_seq_parsers!(P0 0, P1 1, P2 2);
+impl_seq_parsers!(P0 0, P1 1, P2 2, P3 3);
+impl_seq_parsers!(P0 0, P1 1, P2 2, P3 3, P4 4);
+impl_seq_parsers!(P0 0, P1 1, P2 2, P3 3, P4 4, P5 5);
+impl_seq_parsers!(P0 0, P1 1, P2 2, P3 3, P4 4, P5 5, P6 6);
+impl_seq_parsers!(P0 0, P1 1, P2 2, P3 3, P4 4, P5 5, P6 6, P7 7);
+
+/// Parser combinator that runs a tuple of parsers in order and returns a flat tuple
+///
+/// Example:
+/// ```
+/// use parsicomb::ascii::{i64, u64};
+/// use parsicomb::byte::is_byte;
+/// use parsicomb::{ByteCursor, Parser, seq};
+///
+/// let data = b"123.456";
+/// let cursor = ByteCursor::new(data);
+/// let ((int_part, _, frac_part), _) = seq((i64(), is_byte(b'.'), u64()))
+///     .parse(cursor)
+///     .unwrap();
+/// assert_eq!(int_part, 123);
+/// assert_eq!(frac_part, 456);
+/// ```
+pub struct Seq<T> {
+    parsers: T,
+}
+
+impl<T> Seq<T> {
+    pub fn new(parsers: T) -> Self {
+        Seq { parsers }
+    }
+}
+
+impl<'code, T> Parser<'code> for Seq<T>
+where
+    T: SeqParsers<'code>,
+{
+    type Cursor = T::Cursor;
+    type Output = T::Output;
+    type Error = SeqError<'code, T::Element>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        self.parsers.parse_seq(cursor)
+    }
+
+    fn describe(&self) -> Description {
+        self.parsers.describe_seq()
+    }
+}
+
+/// Convenience function to create a Seq parser from a tuple of 2 to 8 parsers
+pub fn seq<'code, T>(parsers: T) -> Seq<T>
+where
+    T: SeqParsers<'code>,
+{
+    Seq::new(parsers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ByteCursor;
+    use crate::ascii::u64;
+    use crate::byte::is_byte;
+    use crate::utf8::string::is_string;
+
+    #[test]
+    fn test_seq_three() {
+        let data = b"10,20";
+        let cursor = ByteCursor::new(data);
+        let parser = seq((u64(), is_byte(b','), u64()));
+
+        let ((a, comma, b), cursor) = parser.parse(cursor).unwrap();
+        assert_eq!(a, 10);
+        assert_eq!(comma, b',');
+        assert_eq!(b, 20);
+        assert!(cursor.eos());
+    }
+
+    #[test]
+    fn test_seq_eight() {
+        let data = b"abcdefgh!";
+        let cursor = ByteCursor::new(data);
+        let parser = seq((
+            is_byte(b'a'),
+            is_byte(b'b'),
+            is_byte(b'c'),
+            is_byte(b'd'),
+            is_byte(b'e'),
+            is_byte(b'f'),
+            is_byte(b'g'),
+            is_string("h"),
+        ));
+
+        let ((a, _, _, _, _, _, g, h), cursor) = parser.parse(cursor).unwrap();
+        assert_eq!(a, b'a');
+        assert_eq!(g, b'g');
+        assert_eq!(h, "h");
+        assert_eq!(cursor.value().unwrap(), b'!');
+    }
+
+    #[test]
+    fn test_seq_reports_failing_index() {
+        let data = b"let x 1";
+        let cursor = ByteCursor::new(data);
+        let parser = seq((is_string("let"), is_string(" x"), is_string(" = ")));
+
+        let error = parser.parse(cursor).unwrap_err();
+        assert_eq!(error.index(), 2);
+        assert_eq!(error.likely_error().loc().position(), 6);
+        assert!(error.to_string().starts_with("Parser 2 in sequence failed"));
+    }
+
+    #[test]
+    fn test_seq_first_fails() {
+        let data = b"x";
+        let cursor = ByteCursor::new(data);
+        let parser = seq((is_byte(b'a'), is_byte(b'b')));
+
+        let error = parser.parse(cursor).unwrap_err();
+        assert_eq!(error.index(), 0);
+        assert_eq!(error.inner().likely_error().loc().position(), 0);
+    }
+}
@@ -0,0 +1,190 @@
+use super::parser::Parser;
+use crate::cursor::Cursor;
+use crate::describe::Description;
+use crate::error::ErrorNode;
+
+/// Parser combinator that matches zero or more occurrences of the given parser
+/// and discards their outputs
+///
+/// Behaves like `many` but never builds a `Vec`, which makes it the right tool
+/// for skipping whitespace, comments and other trivia. The repetition also ends
+/// when an item matches without consuming input, so items that can match empty
+/// don't loop forever.
+pub struct SkipMany<P> {
+    parser: P,
+}
+
+impl<P> SkipMany<P> {
+    pub fn new(parser: P) -> Self {
+        SkipMany { parser }
+    }
+}
+
+impl<'code, P> Parser<'code> for SkipMany<P>
+where
+    P: Parser<'code>,
+{
+    type Cursor = P::Cursor;
+    type Output = ();
+    type Error = P::Error;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        // Like many, errors end the repetition unless they are committed
+        match skip_padding(&self.parser, cursor) {
+            (_, Some(error)) => Err(error),
+            (cursor, None) => Ok(((), cursor)),
+        }
+    }
+
+    fn describe(&self) -> Description {
+        Description::repeat(0, self.parser.describe())
+    }
+}
+
+/// Convenience function to create a SkipMany parser
+pub fn skip_many<'code, P>(parser: P) -> SkipMany<P>
+where
+    P: Parser<'code>,
+{
+    SkipMany::new(parser)
+}
+
+/// Skip zero or more matches of `pad`, for `skip_many` and the padded variants of
+/// other combinators
+///
+/// Stops on the first failure and also on an empty match, so padding like
+/// `take_whitespace` that always succeeds cannot loop forever. Returns the cursor
+/// after the last match, together with the error that ended the loop if it was
+/// committed.
+pub(crate) fn skip_padding<'code, P>(
+    pad: &P,
+    mut cursor: P::Cursor,
+) -> (P::Cursor, Option<P::Error>)
+where
+    P: Parser<'code>,
+{
+    loop {
+        match pad.parse(cursor) {
+            Ok((_, next_cursor)) if next_cursor.position() != cursor.position() => {
+                cursor = next_cursor;
+            }
+            Ok(_) => return (cursor, None),
+            Err(error) if error.is_committed() => return (cursor, Some(error)),
+            Err(_) => return (cursor, None),
+        }
+    }
+}
+
+/// Parser combinator that runs a parser and discards its output
+pub struct Ignore<P> {
+    parser: P,
+}
+
+impl<P> Ignore<P> {
+    pub fn new(parser: P) -> Self {
+        Ignore { parser }
+    }
+}
+
+impl<'code, P> Parser<'code> for Ignore<P>
+where
+    P: Parser<'code>,
+{
+    type Cursor = P::Cursor;
+    type Output = ();
+    type Error = P::Error;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (_, cursor) = self.parser.parse(cursor)?;
+        Ok(((), cursor))
+    }
+
+    fn describe(&self) -> Description {
+        self.parser.describe()
+    }
+}
+
+/// Extension trait to add .ignore() method support for parsers
+pub trait IgnoreExt<'code>: Parser<'code> + Sized {
+    fn ignore(self) -> Ignore<Self> {
+        Ignore::new(self)
+    }
+}
+
+/// Implement IgnoreExt for all parsers
+impl<'code, P> IgnoreExt<'code> for P where P: Parser<'code> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ByteCursor;
+    use crate::Cursor;
+    use crate::and::AndExt;
+    use crate::byte::is_byte;
+    use crate::utf8::string::is_string;
+    use crate::utf8::whitespace::unicode_whitespace;
+
+    #[test]
+    fn test_skip_many_zero_matches() {
+        let data = b"xyz";
+        let cursor = ByteCursor::new(data);
+        let parser = skip_many(is_byte(b'a'));
+
+        let ((), cursor) = parser.parse(cursor).unwrap();
+        assert_eq!(cursor.position(), 0);
+    }
+
+    #[test]
+    fn test_skip_many_multiple_matches() {
+        let data = "  \u{3000}\tabc".as_bytes();
+        let cursor = ByteCursor::new(data);
+        let parser = skip_many(unicode_whitespace());
+
+        let ((), cursor) = parser.parse(cursor).unwrap();
+        assert_eq!(cursor.value().unwrap(), b'a');
+    }
+
+    #[test]
+    fn test_skip_many_to_end() {
+        let data = b"aaaa";
+        let cursor = ByteCursor::new(data);
+        let parser = skip_many(is_byte(b'a'));
+
+        let ((), cursor) = parser.parse(cursor).unwrap();
+        assert!(matches!(cursor, ByteCursor::EndOfFile { .. }));
+    }
+
+    #[test]
+    fn test_skip_many_stops_on_empty_match() {
+        use crate::ascii::take_whitespace;
+
+        let data = b"  x";
+        let cursor = ByteCursor::new(data);
+        let parser = skip_many(take_whitespace());
+
+        let ((), cursor) = parser.parse(cursor).unwrap();
+        assert_eq!(cursor.position(), 2);
+
+        let ((), cursor) = parser.parse(cursor).unwrap();
+        assert_eq!(cursor.position(), 2);
+    }
+
+    #[test]
+    fn test_ignore_discards_output() {
+        let data = b"let x";
+        let cursor = ByteCursor::new(data);
+        let parser = is_string("let").ignore().and(skip_many(is_byte(b' ')));
+
+        let (((), ()), cursor) = parser.parse(cursor).unwrap();
+        assert_eq!(cursor.value().unwrap(), b'x');
+    }
+
+    #[test]
+    fn test_ignore_propagates_error() {
+        let data = b"var x";
+        let cursor = ByteCursor::new(data);
+        let parser = is_string("let").ignore();
+
+        assert!(parser.parse(cursor).is_err());
+    }
+}
@@ -1,6 +1,15 @@
 use super::parser::Parser;
+use crate::atomic::Atomic;
+use crate::cursor::Cursor;
+use crate::describe::Description;
+use crate::error::ErrorNode;
+use crate::repetition::RepetitionError;
 
 /// Parser combinator that matches one or more occurrences of the given parser
+///
+/// After the first item, a failing item ends the repetition unless its error is
+/// [committed](crate::cut::CutExt::cut), in which case it is returned together with
+/// the index of the item.
 pub struct Some<P> {
     parser: P,
 }
@@ -14,34 +23,43 @@ impl<P> Some<P> {
 impl<'code, P> Parser<'code> for Some<P>
 where
     P: Parser<'code>,
+    P::Error: 'code,
+    <P::Cursor as Cursor<'code>>::Element: Atomic + 'code,
 {
     type Cursor = P::Cursor;
     type Output = Vec<P::Output>;
-    type Error = P::Error;
+    type Error = RepetitionError<'code, <P::Cursor as Cursor<'code>>::Element>;
 
     fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
         let mut results = Vec::new();
 
-        // First parse must succeed
-        let (first_value, mut cursor) = self.parser.parse(cursor)?;
+        // First parse must succeed, it is the only repetition that can fail
+        let (first_value, mut cursor) = self
+            .parser
+            .parse(cursor)
+            .map_err(|error| RepetitionError::new(0, error))?;
         results.push(first_value);
 
-        // Continue parsing zero or more times
+        // Continue parsing zero or more times, stopping on the first error unless it is committed
         loop {
             match self.parser.parse(cursor) {
                 Ok((value, next_cursor)) => {
                     results.push(value);
                     cursor = next_cursor;
                 }
-                Err(_) => {
-                    // Stop on first error after at least one match
-                    break;
+                Err(error) if error.is_committed() => {
+                    return Err(RepetitionError::new(results.len(), error));
                 }
+                Err(_) => break,
             }
         }
 
         Ok((results, cursor))
     }
+
+    fn describe(&self) -> Description {
+        Description::repeat(1, self.parser.describe())
+    }
 }
 
 /// Convenience function to create a Some parser
@@ -58,6 +76,7 @@ mod tests {
     use crate::ByteCursor;
     use crate::Cursor;
     use crate::byte::{ByteParser, is_byte};
+    use crate::error::ErrorNode;
 
     #[test]
     fn test_some_zero_matches_fails() {
@@ -122,4 +141,31 @@ mod tests {
         let result = parser.parse(cursor);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_some_reports_first_repetition() {
+        let data = b"xa";
+        let cursor = ByteCursor::new(data);
+        let parser = some(is_byte(b'a'));
+
+        let error = parser.parse(cursor).unwrap_err();
+        assert_eq!(error.index(), 0);
+        assert_eq!(error.likely_error().loc().position(), 0);
+    }
+
+    #[test]
+    fn test_some_returns_committed_errors() {
+        use crate::and::AndExt;
+        use crate::cut::CutExt;
+
+        let parser = some(is_byte(b'-').and(is_byte(b'1').cut()));
+
+        let (results, _) = parser.parse(ByteCursor::new(b"-1-1x")).unwrap();
+        assert_eq!(results.len(), 2);
+
+        let error = parser.parse(ByteCursor::new(b"-1-1-x")).unwrap_err();
+        assert!(error.is_committed());
+        assert_eq!(error.index(), 2);
+        assert_eq!(error.likely_error().loc().position(), 5);
+    }
 }
@@ -0,0 +1,510 @@
+//! # Source Maps - Multiple Files in One Session
+//!
+//! `CodeLoc` and `Span` point into a source slice but know nothing about where the
+//! slice came from. A [`SourceMap`] registers every file parsed in a session under
+//! its name and finds the file a location points into, so errors can be reported
+//! as `path/to/file.mao:12:5` without threading file ids through every parser.
+//!
+//! Files are recognized by address: any location inside a registered slice,
+//! including locations from parsers that were run on a sub-slice of a file,
+//! resolves to that file with the offset adjusted to the start of the file.
+//!
+//! Preprocessors that splice includes or expand macros parse a buffer that no user
+//! wrote. Registering that buffer with [`SourceMap::add_generated`] and a
+//! [`PositionRemapper`] maps its positions back to the files the text came from,
+//! and [`ExpansionMap`] records the usual copied and expanded regions.
+
+use crate::atomic::Atomic;
+use crate::error::{CodeLoc, ErrorNode};
+use crate::report::Report;
+use std::fmt;
+use std::rc::Rc;
+
+/// Identifier of a file registered in a [`SourceMap`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SourceId(u32);
+
+impl SourceId {
+    /// Position of the file in registration order, starting at 0
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// A location resolved to a file, displayed as `name:line:column`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileLoc<'m> {
+    pub id: SourceId,
+    pub name: &'m str,
+    /// Offset in elements from the start of the file
+    pub offset: usize,
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based column, counted in display widths like the error messages
+    pub column: usize,
+}
+
+impl fmt::Display for FileLoc<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.name, self.line, self.column)
+    }
+}
+
+/// Maps offsets in a generated source to the source the text came from
+///
+/// Implemented for closures taking the offset in the generated source.
+pub trait PositionRemapper {
+    /// The original source and offset of `offset`, or `None` to keep it as is
+    fn remap(&self, offset: usize) -> Option<(SourceId, usize)>;
+}
+
+impl<F> PositionRemapper for F
+where
+    F: Fn(usize) -> Option<(SourceId, usize)>,
+{
+    fn remap(&self, offset: usize) -> Option<(SourceId, usize)> {
+        self(offset)
+    }
+}
+
+/// One region of a generated source
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Segment {
+    /// Text copied verbatim from `source` starting at `start`
+    Copied { source: SourceId, start: usize },
+    /// Text produced by an expansion, reported at the expansion site
+    Expanded { source: SourceId, site: usize },
+}
+
+/// [`PositionRemapper`] built from the regions a preprocessor emitted in order
+///
+/// Example:
+/// ```
+/// use parsicomb::byte::is_byte;
+/// use parsicomb::source_map::{ExpansionMap, SourceMap};
+/// use parsicomb::{ByteCursor, Cursor, Parser};
+///
+/// let main = b"#include \"util\"\nmain;";
+/// let util = b"util?";
+/// let expanded = b"util?\nmain;";
+///
+/// let mut sources = SourceMap::new();
+/// let main_id = sources.add("main.mao", main);
+/// let util_id = sources.add("util.mao", util);
+/// let remapper = ExpansionMap::new()
+///     .copied(util_id, 0, 5)
+///     .copied(main_id, 15, 6);
+/// sources.add_generated("<expanded>", expanded, remapper);
+///
+/// let cursor = ByteCursor::new(expanded).next().next().next().next();
+/// let error = is_byte(b';').parse(cursor).unwrap_err();
+/// assert!(sources.describe(&error).starts_with("util.mao:1:5: "));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ExpansionMap {
+    /// Start offset in the generated source, length and origin of each region
+    segments: Vec<(usize, usize, Segment)>,
+    len: usize,
+}
+
+impl ExpansionMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append `len` elements copied from `source` starting at `start`
+    pub fn copied(mut self, source: SourceId, start: usize, len: usize) -> Self {
+        self.push(len, Segment::Copied { source, start });
+        self
+    }
+
+    /// Append `len` generated elements that are reported at `site` in `source`
+    pub fn expanded(mut self, source: SourceId, site: usize, len: usize) -> Self {
+        self.push(len, Segment::Expanded { source, site });
+        self
+    }
+
+    fn push(&mut self, len: usize, segment: Segment) {
+        self.segments.push((self.len, len, segment));
+        self.len += len;
+    }
+
+    /// Total length of the generated source described so far
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl PositionRemapper for ExpansionMap {
+    fn remap(&self, offset: usize) -> Option<(SourceId, usize)> {
+        // The end of the generated source maps to the end of the last region
+        let index = self
+            .segments
+            .partition_point(|&(start, _, _)| start <= offset)
+            .checked_sub(1)?;
+        let (start, len, segment) = self.segments[index];
+        let within = (offset - start).min(len);
+        Some(match segment {
+            Segment::Copied { source, start } => (source, start + within),
+            Segment::Expanded { source, site } => (source, site),
+        })
+    }
+}
+
+/// Registry of the named sources parsed in one session
+///
+/// Example:
+/// ```
+/// use parsicomb::byte::is_byte;
+/// use parsicomb::source_map::SourceMap;
+/// use parsicomb::{ByteCursor, Cursor, Parser};
+///
+/// let main = b"fn main() {}\n";
+/// let util = b"fn util() {}\nfn ;\n";
+///
+/// let mut sources = SourceMap::new();
+/// sources.add("src/main.mao", main);
+/// sources.add("src/util.mao", util);
+///
+/// let cursor = ByteCursor::new(util).next().next().next().next();
+/// let error = is_byte(b'x').parse(cursor).unwrap_err();
+/// assert!(sources.describe(&error).starts_with("src/util.mao:1:5: "));
+/// ```
+#[derive(Clone)]
+pub struct SourceMap<'code, T: Atomic = u8> {
+    files: Vec<SourceFile<'code, T>>,
+}
+
+/// A registered source and how to map it back to other sources
+#[derive(Clone)]
+struct SourceFile<'code, T: Atomic> {
+    name: String,
+    source: &'code [T],
+    remapper: Option<Rc<dyn PositionRemapper + 'code>>,
+}
+
+impl<'code, T: Atomic> fmt::Debug for SourceMap<'code, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.files.iter().map(|file| {
+                (
+                    &file.name,
+                    format!(
+                        "{} elements{}",
+                        file.source.len(),
+                        if file.remapper.is_some() {
+                            ", generated"
+                        } else {
+                            ""
+                        }
+                    ),
+                )
+            }))
+            .finish()
+    }
+}
+
+impl<'code, T: Atomic> Default for SourceMap<'code, T> {
+    fn default() -> Self {
+        SourceMap { files: Vec::new() }
+    }
+}
+
+impl<'code, T: Atomic> SourceMap<'code, T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a source under `name`, usually its path
+    pub fn add(&mut self, name: impl Into<String>, source: &'code [T]) -> SourceId {
+        self.push(name.into(), source, None)
+    }
+
+    /// Register a source produced by preprocessing other registered sources
+    ///
+    /// Locations in `source` are mapped through `remapper` before they are
+    /// reported, so errors point at the file the text originally came from.
+    pub fn add_generated(
+        &mut self,
+        name: impl Into<String>,
+        source: &'code [T],
+        remapper: impl PositionRemapper + 'code,
+    ) -> SourceId {
+        self.push(name.into(), source, Some(Rc::new(remapper)))
+    }
+
+    fn push(
+        &mut self,
+        name: String,
+        source: &'code [T],
+        remapper: Option<Rc<dyn PositionRemapper + 'code>>,
+    ) -> SourceId {
+        let id = SourceId(u32::try_from(self.files.len()).expect("more than u32::MAX sources"));
+        self.files.push(SourceFile {
+            name,
+            source,
+            remapper,
+        });
+        id
+    }
+
+    /// The name a source was registered under
+    pub fn name(&self, id: SourceId) -> Option<&str> {
+        self.files.get(id.index()).map(|file| file.name.as_str())
+    }
+
+    /// The contents of a registered source
+    pub fn source(&self, id: SourceId) -> Option<&'code [T]> {
+        self.files.get(id.index()).map(|file| file.source)
+    }
+
+    /// Number of registered sources
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// The file `loc` points into and the offset of `loc` from its start
+    ///
+    /// This is the source that was parsed, generated sources are not remapped.
+    pub fn find(&self, loc: CodeLoc<'code, T>) -> Option<(SourceId, usize)> {
+        let range = loc.source().as_ptr_range();
+        let size = std::mem::size_of::<T>().max(1);
+
+        self.files.iter().enumerate().find_map(|(index, file)| {
+            let bounds = file.source.as_ptr_range();
+            if range.start < bounds.start || range.end > bounds.end {
+                return None;
+            }
+            let delta = (range.start as usize - bounds.start as usize) / size;
+            Some((SourceId(index as u32), delta + loc.position()))
+        })
+    }
+
+    /// Follow the remappers of generated sources back to an original source
+    ///
+    /// Stops at the last source reached if a remapper has no answer, points
+    /// outside the map, or the remappers form a cycle.
+    pub fn remap(&self, id: SourceId, offset: usize) -> (SourceId, usize) {
+        let mut current = (id, offset);
+        for _ in 0..self.files.len() {
+            let Some(remapper) = self
+                .files
+                .get(current.0.index())
+                .and_then(|file| file.remapper.as_ref())
+            else {
+                break;
+            };
+            match remapper.remap(current.1) {
+                Some(next) if next.0.index() < self.files.len() => current = next,
+                _ => break,
+            }
+        }
+        current
+    }
+
+    /// The original file and offset of `loc`, after remapping generated sources
+    fn resolve(&self, loc: CodeLoc<'code, T>) -> Option<(SourceId, usize)> {
+        let (id, offset) = self.find(loc)?;
+        let (id, offset) = self.remap(id, offset);
+        let length = self.files[id.index()].source.len();
+        Some((id, offset.min(length)))
+    }
+
+    /// Resolve `loc` to a file name, line and column
+    pub fn locate(&self, loc: CodeLoc<'code, T>) -> Option<FileLoc<'_>> {
+        let (id, offset) = self.resolve(loc)?;
+        let file = &self.files[id.index()];
+        let position = CodeLoc::new(file.source, offset).readable_position();
+        Some(FileLoc {
+            id,
+            name: &file.name,
+            offset,
+            line: position.line,
+            column: position.byte_offset + 1,
+        })
+    }
+
+    /// One-line description of the likely error, `name:line:column: message`
+    ///
+    /// Errors outside every registered source are described as `line L, column C`.
+    pub fn describe<E>(&self, error: &E) -> String
+    where
+        E: ErrorNode<'code, Element = T> + ?Sized,
+    {
+        let leaf = error.likely_error();
+        let loc = leaf.loc();
+        match self.locate(loc) {
+            Some(file_loc) => format!("{}: {}", file_loc, leaf.message()),
+            None => {
+                let position = loc.readable_position();
+                format!(
+                    "line {}, column {}: {}",
+                    position.line,
+                    position.byte_offset + 1,
+                    leaf.message()
+                )
+            }
+        }
+    }
+
+    /// A [`Report`] for the likely error that names the file it is in
+    pub fn report<E>(&self, error: &E) -> Report<'code, T>
+    where
+        E: ErrorNode<'code, Element = T> + ?Sized,
+    {
+        let report = Report::new(error);
+        match self.resolve(error.likely_error().loc()) {
+            Some((id, offset)) => {
+                let file = &self.files[id.index()];
+                report.in_file(file.name.clone(), file.source, offset)
+            }
+            None => report,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::byte::is_byte;
+    use crate::tag::tag_slice;
+    use crate::{ByteCursor, CharSliceCursor, Cursor, Parser};
+
+    #[test]
+    fn test_locate_in_registered_files() {
+        let first = b"abc\n";
+        let second = b"one\ntwo\n";
+        let mut sources = SourceMap::new();
+        let a = sources.add("a.mao", first);
+        let b = sources.add("b.mao", second);
+        assert_eq!(sources.len(), 2);
+        assert_eq!(sources.name(b), Some("b.mao"));
+        assert_eq!(sources.source(a), Some(&first[..]));
+
+        let loc = sources.locate(CodeLoc::new(second, 5)).unwrap();
+        assert_eq!((loc.id, loc.offset), (b, 5));
+        assert_eq!(loc.to_string(), "b.mao:2:2");
+
+        let unknown = b"elsewhere";
+        assert_eq!(sources.locate(CodeLoc::new(unknown, 0)), None);
+    }
+
+    #[test]
+    fn test_sub_slice_rebased_to_file() {
+        let file = b"header\nbody x";
+        let mut sources = SourceMap::new();
+        sources.add("doc.txt", file);
+
+        // A parser run on the body alone still reports positions in the file
+        let body = &file[7..];
+        let cursor = ByteCursor::new(body).next().next().next().next().next();
+        let error = is_byte(b'y').parse(cursor).unwrap_err();
+
+        assert_eq!(
+            sources.find(CodeLoc::new(body, 5)).map(|(_, o)| o),
+            Some(12)
+        );
+        assert!(sources.describe(&error).starts_with("doc.txt:2:6: "));
+    }
+
+    #[test]
+    fn test_describe_unknown_source() {
+        let sources = SourceMap::new();
+        let error = is_byte(b'x').parse(ByteCursor::new(b"y")).unwrap_err();
+        assert!(sources.describe(&error).starts_with("line 1, column 1: "));
+    }
+
+    #[test]
+    fn test_report_names_file() {
+        let file = b"let a = 1\nlet b = ;\n";
+        let mut sources = SourceMap::new();
+        sources.add("src/lib.mao", file);
+
+        let body = &file[10..];
+        let cursor = ByteCursor::new(body);
+        let cursor = (0..8).fold(cursor, |cursor, _| cursor.next());
+        let error = is_byte(b'1').parse(cursor).unwrap_err();
+
+        let rendered = sources.report(&error).to_string();
+        assert!(rendered.contains(" --> src/lib.mao:2:9\n"));
+        assert!(rendered.contains("1 | let a = 1\n"));
+    }
+
+    #[test]
+    fn test_char_sources() {
+        let chars: Vec<char> = "ab\ncd".chars().collect();
+        let mut sources = SourceMap::new();
+        sources.add("chars", &chars);
+
+        let cursor = CharSliceCursor::new(&chars).next().next().next();
+        let error = tag_slice(&['x']).parse(cursor).unwrap_err();
+        assert!(sources.describe(&error).starts_with("chars:2:1: "));
+    }
+
+    #[test]
+    fn test_expansion_map_regions() {
+        let main = SourceId(0);
+        let defs = SourceId(1);
+        let map = ExpansionMap::new()
+            .copied(main, 0, 4)
+            .expanded(main, 4, 10)
+            .copied(defs, 20, 3);
+        assert_eq!(map.len(), 17);
+
+        assert_eq!(map.remap(2), Some((main, 2)));
+        assert_eq!(map.remap(9), Some((main, 4)));
+        assert_eq!(map.remap(15), Some((defs, 21)));
+        assert_eq!(map.remap(17), Some((defs, 23)));
+        assert_eq!(ExpansionMap::new().remap(0), None);
+    }
+
+    #[test]
+    fn test_generated_source_reports_original_file() {
+        let header = b"const N = 1;\n";
+        let main = b"#include header\nlet x = N N;\n";
+        let expanded = b"const N = 1;\nlet x = N N;\n";
+
+        let mut sources = SourceMap::new();
+        let main_id = sources.add("main.mao", main);
+        let header_id = sources.add("header.mao", header);
+        let remapper = ExpansionMap::new()
+            .copied(header_id, 0, 13)
+            .copied(main_id, 16, 13);
+        let expanded_id = sources.add_generated("<expanded>", expanded, remapper);
+
+        let cursor = (0..23).fold(ByteCursor::new(expanded), |cursor, _| cursor.next());
+        let error = is_byte(b';').parse(cursor).unwrap_err();
+
+        assert_eq!(
+            sources.find(error.likely_error().loc()),
+            Some((expanded_id, 23))
+        );
+        assert!(sources.describe(&error).starts_with("main.mao:2:11: "));
+        let rendered = sources.report(&error).to_string();
+        assert!(rendered.contains(" --> main.mao:2:11\n"));
+        assert!(rendered.contains("1 | #include header\n"));
+    }
+
+    #[test]
+    fn test_remap_closure_and_cycles() {
+        let text = b"abc";
+        let mut sources = SourceMap::new();
+        // Both sources claim to come from each other
+        let a = sources.add_generated("a", &text[..1], |offset| Some((SourceId(1), offset)));
+        let b = sources.add_generated("b", &text[1..], |offset| Some((SourceId(0), offset)));
+        assert_eq!(sources.remap(a, 0), (a, 0));
+        assert_eq!(sources.remap(b, 1), (b, 1));
+
+        // Remapping outside the map keeps the current position
+        let c = sources.add_generated("c", text, |offset| Some((SourceId(9), offset)));
+        assert_eq!(sources.remap(c, 2), (c, 2));
+    }
+}
@@ -0,0 +1,201 @@
+use crate::atomic::Atomic;
+use crate::cursor::Cursor;
+use crate::describe::Description;
+use crate::error::{CodeLoc, EofError, ParsicombError};
+use crate::parser::Parser;
+use std::marker::PhantomData;
+
+// # Space - Whitespace on Any Cursor
+//
+// `ascii::whitespace` and `utf8::unicode_whitespace` only read bytes. Token streams
+// that keep whitespace tokens, or decoded chars, need to skip trivia too. `space()`
+// and `skip_space()` work on any cursor whose elements say which of them are
+// whitespace through `Atomic::is_space`: ASCII whitespace for bytes, Unicode
+// whitespace for chars, and nothing unless overridden for other element types.
+// Integers never count as whitespace, wrap code points in a token type to skip them.
+//
+// They are plain trivia parsers, so they also work as the padding of the padded
+// combinators described in `utf8::whitespace`.
+
+/// Parser that matches a single whitespace element on any cursor
+pub struct Space<C> {
+    _phantom: PhantomData<C>,
+}
+
+impl<C> Space<C> {
+    pub fn new() -> Self {
+        Space {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<C> Default for Space<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'code, C, T> Parser<'code> for Space<C>
+where
+    C: Cursor<'code, Element = T>,
+    T: Atomic + 'code,
+{
+    type Cursor = C;
+    type Output = T;
+    type Error = ParsicombError<'code, T>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let loc = CodeLoc::new(cursor.source(), cursor.position());
+        match cursor.value() {
+            Ok(found) if found.is_space() => Ok((found, cursor.next())),
+            Ok(found) => Err(ParsicombError::SyntaxError {
+                message: format!("expected whitespace, found '{}'", T::format_slice(&[found]))
+                    .into(),
+                loc,
+            }),
+            Err(_) => Err(ParsicombError::CannotReadValueAtEof(
+                EofError::new(loc).expecting("whitespace"),
+            )),
+        }
+    }
+
+    fn describe(&self) -> Description {
+        Description::terminal("whitespace")
+    }
+}
+
+/// Parser that skips zero or more whitespace elements on any cursor, never failing
+pub struct SkipSpace<C> {
+    _phantom: PhantomData<C>,
+}
+
+impl<C> SkipSpace<C> {
+    pub fn new() -> Self {
+        SkipSpace {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<C> Default for SkipSpace<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'code, C, T> Parser<'code> for SkipSpace<C>
+where
+    C: Cursor<'code, Element = T>,
+    T: Atomic + 'code,
+{
+    type Cursor = C;
+    type Output = ();
+    type Error = ParsicombError<'code, T>;
+
+    fn parse(&self, mut cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        while let Ok(found) = cursor.value()
+            && found.is_space()
+        {
+            cursor = cursor.next();
+        }
+        Ok(((), cursor))
+    }
+
+    fn describe(&self) -> Description {
+        Description::repeat(0, Description::terminal("whitespace"))
+    }
+}
+
+/// Creates a parser for one whitespace element, as told by [`Atomic::is_space`]
+pub fn space<C>() -> Space<C> {
+    Space::new()
+}
+
+/// Creates a parser that skips any whitespace elements, as told by [`Atomic::is_space`]
+///
+/// Example:
+/// ```
+/// use parsicomb::and::AndExt;
+/// use parsicomb::space::skip_space;
+/// use parsicomb::tag::tag_slice;
+/// use parsicomb::{CharSliceCursor, Cursor, Parser};
+///
+/// let data: Vec<char> = "  x".chars().collect();
+/// let parser = skip_space().and(tag_slice(&['x']));
+/// let (_, cursor) = parser.parse(CharSliceCursor::new(&data)).unwrap();
+/// assert!(cursor.eos());
+/// ```
+pub fn skip_space<C>() -> SkipSpace<C> {
+    SkipSpace::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorLeaf;
+    use crate::{AtomicCursor, ByteCursor, CharSliceCursor};
+    use std::fmt;
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Token {
+        Ident,
+        Space,
+        Newline,
+    }
+
+    impl fmt::Display for Token {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{:?}", self)
+        }
+    }
+
+    impl Atomic for Token {
+        fn is_newline(&self) -> bool {
+            *self == Token::Newline
+        }
+
+        fn format_slice(slice: &[Self]) -> String {
+            slice
+                .iter()
+                .map(Token::to_string)
+                .collect::<Vec<_>>()
+                .join(" ")
+        }
+
+        fn is_space(&self) -> bool {
+            matches!(self, Token::Space | Token::Newline)
+        }
+    }
+
+    #[test]
+    fn test_skip_space_on_tokens() {
+        let tokens = [Token::Space, Token::Newline, Token::Ident, Token::Space];
+        let ((), cursor) = skip_space().parse(AtomicCursor::new(&tokens)).unwrap();
+        assert_eq!(cursor.position(), 2);
+        assert_eq!(cursor.value().unwrap(), Token::Ident);
+
+        let ((), cursor) = skip_space().parse(AtomicCursor::<Token>::new(&[])).unwrap();
+        assert!(cursor.eos());
+    }
+
+    #[test]
+    fn test_space_on_bytes_and_chars() {
+        let (found, cursor) = space().parse(ByteCursor::new(b"\tx")).unwrap();
+        assert_eq!((found, cursor.position()), (b'\t', 1));
+
+        let chars: Vec<char> = "\u{3000}x".chars().collect();
+        let ((), cursor) = skip_space().parse(CharSliceCursor::new(&chars)).unwrap();
+        assert_eq!(cursor.position(), 1);
+    }
+
+    #[test]
+    fn test_space_errors() {
+        // Integers may be machine words, so not even 32 counts as a space
+        let error = space().parse(AtomicCursor::new(&[32u32])).unwrap_err();
+        assert_eq!(error.message(), "expected whitespace, found '32'");
+
+        let error = space().parse(ByteCursor::new(b"")).unwrap_err();
+        assert_eq!(error.message(), "expected whitespace, reached end of file");
+    }
+}
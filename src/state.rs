@@ -0,0 +1,215 @@
+use super::parser::Parser;
+use std::cell::{Ref, RefCell, RefMut};
+use std::rc::Rc;
+
+// # User State
+//
+// `Parser::parse` only receives a cursor, so state such as symbol tables, interners
+// or feature flags is shared through a `State` handle captured by the parsers that
+// need it. The handle is a cheap `Rc` clone and lives as long as the grammar.
+//
+// Updates from `.with_state()` happen when its parser succeeds and are not undone
+// if an enclosing alternative fails later. Wrap such alternatives in
+// `.rollback_state()` to restore the state they started with on failure.
+
+/// Shared, mutable user state threaded through a parse
+pub struct State<S> {
+    inner: Rc<RefCell<S>>,
+}
+
+impl<S> State<S> {
+    pub fn new(state: S) -> Self {
+        State {
+            inner: Rc::new(RefCell::new(state)),
+        }
+    }
+
+    /// Borrow the state, e.g. to inspect it after parsing
+    pub fn borrow(&self) -> Ref<'_, S> {
+        self.inner.borrow()
+    }
+
+    /// Borrow the state mutably
+    pub fn borrow_mut(&self) -> RefMut<'_, S> {
+        self.inner.borrow_mut()
+    }
+
+    /// Replace the state, returning the previous value
+    pub fn replace(&self, state: S) -> S {
+        self.inner.replace(state)
+    }
+}
+
+impl<S: Default> State<S> {
+    /// Take the state out, leaving the default in its place
+    pub fn take(&self) -> S {
+        self.inner.take()
+    }
+}
+
+impl<S> Clone for State<S> {
+    fn clone(&self) -> Self {
+        State {
+            inner: Rc::clone(&self.inner),
+        }
+    }
+}
+
+impl<S: Default> Default for State<S> {
+    fn default() -> Self {
+        Self::new(S::default())
+    }
+}
+
+/// Parser combinator that maps the output with mutable access to the user state
+pub struct WithState<P, S, F> {
+    parser: P,
+    state: State<S>,
+    mapper: F,
+}
+
+impl<P, S, F> WithState<P, S, F> {
+    pub fn new(parser: P, state: State<S>, mapper: F) -> Self {
+        WithState {
+            parser,
+            state,
+            mapper,
+        }
+    }
+}
+
+impl<'code, P, S, F, U> Parser<'code> for WithState<P, S, F>
+where
+    P: Parser<'code>,
+    F: Fn(&mut S, P::Output) -> U,
+{
+    type Cursor = P::Cursor;
+    type Output = U;
+    type Error = P::Error;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (value, cursor) = self.parser.parse(cursor)?;
+        let mapped = (self.mapper)(&mut self.state.borrow_mut(), value);
+        Ok((mapped, cursor))
+    }
+}
+
+/// Parser combinator that restores the user state if the parser fails
+///
+/// The state is cloned before every attempt, keep it small or behind an `Rc`.
+pub struct RollbackState<P, S> {
+    parser: P,
+    state: State<S>,
+}
+
+impl<P, S> RollbackState<P, S> {
+    pub fn new(parser: P, state: State<S>) -> Self {
+        RollbackState { parser, state }
+    }
+}
+
+impl<'code, P, S> Parser<'code> for RollbackState<P, S>
+where
+    P: Parser<'code>,
+    S: Clone,
+{
+    type Cursor = P::Cursor;
+    type Output = P::Output;
+    type Error = P::Error;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let snapshot = self.state.borrow().clone();
+        self.parser.parse(cursor).inspect_err(|_| {
+            self.state.replace(snapshot);
+        })
+    }
+}
+
+/// Convenience function to create a WithState parser
+pub fn with_state<'code, P, S, F, U>(parser: P, state: &State<S>, mapper: F) -> WithState<P, S, F>
+where
+    P: Parser<'code>,
+    F: Fn(&mut S, P::Output) -> U,
+{
+    WithState::new(parser, state.clone(), mapper)
+}
+
+/// Extension trait to add user state methods to parsers
+pub trait StateExt<'code>: Parser<'code> + Sized {
+    /// Map the output with mutable access to `state`
+    fn with_state<S, F, U>(self, state: &State<S>, mapper: F) -> WithState<Self, S, F>
+    where
+        F: Fn(&mut S, Self::Output) -> U,
+    {
+        WithState::new(self, state.clone(), mapper)
+    }
+
+    /// Undo changes to `state` made while this parser ran if it fails
+    fn rollback_state<S: Clone>(self, state: &State<S>) -> RollbackState<Self, S> {
+        RollbackState::new(self, state.clone())
+    }
+}
+
+/// Implement StateExt for all parsers
+impl<'code, P> StateExt<'code> for P where P: Parser<'code> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ByteCursor;
+    use crate::and::AndExt;
+    use crate::byte::is_byte;
+    use crate::many::many;
+    use crate::map::MapExt;
+    use crate::or::OrExt;
+    use crate::utf8::identifier::identifier;
+    use crate::utf8::string::is_string;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_with_state_builds_symbol_table() {
+        let symbols: State<HashMap<String, usize>> = State::default();
+
+        let declaration = is_string("let ")
+            .and(identifier())
+            .and(is_byte(b';'))
+            .with_state(&symbols, |table, ((_, (name, _)), _)| {
+                let id = table.len();
+                *table.entry(name.to_string()).or_insert(id)
+            });
+
+        let data = b"let a;let b;let a;";
+        let (ids, _) = many(declaration).parse(ByteCursor::new(data)).unwrap();
+        assert_eq!(ids, [0, 1, 0]);
+        assert_eq!(symbols.borrow().len(), 2);
+    }
+
+    #[test]
+    fn test_rollback_state_on_failed_alternative() {
+        let seen = State::new(Vec::new());
+
+        // The first branch records the name, then fails on the missing ':'
+        let typed = identifier()
+            .with_state(&seen, |names: &mut Vec<String>, (name, _)| {
+                names.push(name.to_string());
+            })
+            .and(is_byte(b':'));
+        let untyped = identifier().and(is_byte(b';')).map(|_| ());
+
+        let parser = typed.map(|_| ()).or(untyped);
+        let _ = parser.parse(ByteCursor::new(b"x;")).unwrap();
+        assert_eq!(seen.take(), ["x"]);
+
+        let typed = identifier()
+            .with_state(&seen, |names: &mut Vec<String>, (name, _)| {
+                names.push(name.to_string());
+            })
+            .and(is_byte(b':'))
+            .rollback_state(&seen);
+        let untyped = identifier().and(is_byte(b';')).map(|_| ());
+
+        let parser = typed.map(|_| ()).or(untyped);
+        let _ = parser.parse(ByteCursor::new(b"x;")).unwrap();
+        assert!(seen.borrow().is_empty());
+    }
+}
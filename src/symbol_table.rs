@@ -0,0 +1,240 @@
+use crate::ByteCursor;
+use crate::Cursor;
+use crate::parser::Parser;
+use crate::utf8::keyword::expected_keywords;
+use crate::{CodeLoc, ParsicombError};
+
+/// Node of the symbol trie, children are sorted by byte
+#[derive(Debug, Clone, Default)]
+struct TrieNode {
+    children: Vec<(u8, usize)>,
+    value: Option<usize>,
+}
+
+/// Byte trie mapping symbols to indices, the matching core of [`SymbolTable`] and
+/// of the keyword parsers
+#[derive(Debug, Clone)]
+pub(crate) struct SymbolTrie {
+    nodes: Vec<TrieNode>,
+}
+
+impl SymbolTrie {
+    pub(crate) fn new() -> Self {
+        SymbolTrie {
+            nodes: vec![TrieNode::default()],
+        }
+    }
+
+    /// Add `symbol` with `index`, returning false if it was already present
+    ///
+    /// A symbol that is inserted twice keeps its first index.
+    pub(crate) fn insert(&mut self, symbol: &[u8], index: usize) -> bool {
+        let mut node = 0;
+        for &byte in symbol {
+            node = match self.nodes[node]
+                .children
+                .binary_search_by_key(&byte, |&(b, _)| b)
+            {
+                Ok(child) => self.nodes[node].children[child].1,
+                Err(child) => {
+                    let next = self.nodes.len();
+                    self.nodes.push(TrieNode::default());
+                    self.nodes[node].children.insert(child, (byte, next));
+                    next
+                }
+            };
+        }
+        if self.nodes[node].value.is_some() {
+            return false;
+        }
+        self.nodes[node].value = Some(index);
+        true
+    }
+
+    /// Index and length of every symbol that `input` starts with, shortest first
+    pub(crate) fn prefixes<'a>(
+        &'a self,
+        input: &'a [u8],
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        let mut node = 0;
+        let longer = input.iter().enumerate().map_while(move |(index, byte)| {
+            let children = &self.nodes[node].children;
+            let child = children.binary_search_by_key(byte, |&(b, _)| b).ok()?;
+            node = children[child].1;
+            Some(self.nodes[node].value.map(|value| (value, index + 1)))
+        });
+        let empty = self.nodes[0].value.map(|value| (value, 0));
+        empty.into_iter().chain(longer.flatten())
+    }
+
+    /// Index of `symbol` if it was inserted
+    pub(crate) fn get(&self, symbol: &[u8]) -> Option<usize> {
+        self.prefixes(symbol)
+            .last()
+            .filter(|&(_, length)| length == symbol.len())
+            .map(|(index, _)| index)
+    }
+}
+
+/// Parser that matches the longest symbol of a table and returns its value
+///
+/// The symbols are stored in a trie, so matching walks the input once instead of
+/// comparing every symbol, and `<<=` is preferred over `<<` and `<` regardless of
+/// table order. Unlike [`keywords`](crate::utf8::keywords) no word boundary is
+/// required, which suits operators and punctuation. If a symbol is listed twice the
+/// first entry wins.
+#[derive(Debug, Clone)]
+pub struct SymbolTable<V> {
+    trie: SymbolTrie,
+    values: Vec<V>,
+    symbols: Vec<String>,
+}
+
+impl<V: Clone> SymbolTable<V> {
+    pub fn new<S: AsRef<str>>(table: &[(S, V)]) -> Self {
+        let mut trie = SymbolTrie::new();
+        let mut values = Vec::new();
+        let mut symbols = Vec::new();
+
+        for (symbol, value) in table {
+            if trie.insert(symbol.as_ref().as_bytes(), values.len()) {
+                values.push(value.clone());
+                symbols.push(symbol.as_ref().to_string());
+            }
+        }
+
+        SymbolTable {
+            trie,
+            values,
+            symbols,
+        }
+    }
+
+    /// Value and length of the longest symbol that `input` starts with
+    pub fn longest_match(&self, input: &[u8]) -> Option<(&V, usize)> {
+        self.trie
+            .prefixes(input)
+            .last()
+            .map(|(value, length)| (&self.values[value], length))
+    }
+}
+
+impl<'code, V: Clone> Parser<'code> for SymbolTable<V> {
+    type Cursor = ByteCursor<'code>;
+    type Output = V;
+    type Error = ParsicombError<'code>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (data, start) = cursor.inner();
+
+        if let Some((value, length)) = self.longest_match(&data[start..]) {
+            return Ok((
+                value.clone(),
+                ByteCursor::scanned(data, start, start + length)?,
+            ));
+        }
+
+        let expected = match self.symbols.len() {
+            0 => "symbol".to_string(),
+            _ => expected_keywords(self.symbols.iter().map(String::as_str)),
+        };
+        Err(ParsicombError::SyntaxError {
+            message: format!("expected {}", expected).into(),
+            loc: CodeLoc::new(data, start),
+        })
+    }
+}
+
+/// Creates a parser matching the longest symbol of `table`, returning its value
+///
+/// Example:
+/// ```
+/// use parsicomb::Parser;
+/// use parsicomb::{ByteCursor, Cursor};
+/// use parsicomb::symbol_table::symbol_table;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// enum Tok { Lt, Shl, ShlAssign }
+///
+/// let parser = symbol_table(&[("<", Tok::Lt), ("<<", Tok::Shl), ("<<=", Tok::ShlAssign)]);
+/// let (tok, cursor) = parser.parse(ByteCursor::new(b"<<= 1")).unwrap();
+/// assert_eq!(tok, Tok::ShlAssign);
+/// assert_eq!(cursor.position(), 3);
+/// ```
+pub fn symbol_table<S: AsRef<str>, V: Clone>(table: &[(S, V)]) -> SymbolTable<V> {
+    SymbolTable::new(table)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Tok {
+        Lt,
+        Le,
+        Shl,
+        ShlAssign,
+        Arrow,
+        Minus,
+    }
+
+    fn table() -> SymbolTable<Tok> {
+        symbol_table(&[
+            ("<", Tok::Lt),
+            ("<=", Tok::Le),
+            ("<<", Tok::Shl),
+            ("<<=", Tok::ShlAssign),
+            ("->", Tok::Arrow),
+            ("-", Tok::Minus),
+        ])
+    }
+
+    #[test]
+    fn test_longest_match_wins() {
+        let parser = table();
+        let cases: [(&[u8], Tok, usize); 6] = [
+            (b"<<=x", Tok::ShlAssign, 3),
+            (b"<<x", Tok::Shl, 2),
+            (b"<=", Tok::Le, 2),
+            (b"< =", Tok::Lt, 1),
+            (b"->", Tok::Arrow, 2),
+            (b"-1", Tok::Minus, 1),
+        ];
+        for (input, expected, length) in cases {
+            let (tok, cursor) = parser.parse(ByteCursor::new(input)).unwrap();
+            assert_eq!((tok, cursor.position()), (expected, length));
+        }
+    }
+
+    #[test]
+    fn test_falls_back_to_shorter_prefix() {
+        // "<<" is a symbol and "<<<" is not, "<<<=" only has "<<" as a prefix symbol
+        let parser = symbol_table(&[("<<", 2), ("<<<=", 4)]);
+        let (value, cursor) = parser.parse(ByteCursor::new(b"<<<x")).unwrap();
+        assert_eq!((value, cursor.position()), (2, 2));
+        assert!(parser.parse(ByteCursor::new(b"<x")).is_err());
+    }
+
+    #[test]
+    fn test_duplicates_keep_first_and_multibyte_symbols() {
+        let parser = symbol_table(&[("→", 1), ("→", 2), ("=", 3)]);
+        let (value, cursor) = parser.parse(ByteCursor::new("→".as_bytes())).unwrap();
+        assert_eq!((value, cursor.position()), (1, 3));
+    }
+
+    #[test]
+    fn test_error_lists_symbols() {
+        let error = table().parse(ByteCursor::new(b"+")).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("expected '<', '<=', '<<', '<<=', '->' or '-'")
+        );
+        assert!(
+            symbol_table::<&str, ()>(&[])
+                .parse(ByteCursor::new(b""))
+                .is_err()
+        );
+    }
+}
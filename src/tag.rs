@@ -0,0 +1,130 @@
+use super::parser::Parser;
+use crate::atomic::Atomic;
+use crate::cursor::Cursor;
+use crate::error::{CodeLoc, ParsicombError};
+use std::marker::PhantomData;
+
+/// Parser that matches an exact sequence of elements on any cursor
+///
+/// The generic counterpart of `is_string`, for token streams, integer streams or
+/// chars. Returns the matched slice of the input.
+pub struct TagSliceParser<T: 'static, C> {
+    expected: &'static [T],
+    _phantom: PhantomData<C>,
+}
+
+impl<T: Atomic, C> TagSliceParser<T, C> {
+    pub fn new(expected: &'static [T]) -> Self {
+        TagSliceParser {
+            expected,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<'code, C, T> Parser<'code> for TagSliceParser<T, C>
+where
+    C: Cursor<'code, Element = T>,
+    T: Atomic + 'code,
+{
+    type Cursor = C;
+    type Output = &'code [T];
+    type Error = ParsicombError<'code, T>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let source = cursor.source();
+        let start = cursor.position();
+        let mut current = cursor;
+
+        for expected in self.expected {
+            let message = match current.value() {
+                Ok(found) if found == *expected => {
+                    current = current.next();
+                    continue;
+                }
+                Ok(found) => format!(
+                    "expected '{}', found '{}' while matching '{}'",
+                    T::format_slice(std::slice::from_ref(expected)),
+                    T::format_slice(&[found]),
+                    T::format_slice(self.expected)
+                ),
+                Err(_) => format!(
+                    "expected '{}', but reached end of input while matching '{}'",
+                    T::format_slice(std::slice::from_ref(expected)),
+                    T::format_slice(self.expected)
+                ),
+            };
+            return Err(ParsicombError::SyntaxError {
+                message: message.into(),
+                loc: CodeLoc::new(source, current.position()),
+            });
+        }
+
+        Ok((&source[start..current.position()], current))
+    }
+}
+
+/// Creates a parser that matches the exact sequence `expected`
+pub fn tag_slice<T: Atomic, C>(expected: &'static [T]) -> TagSliceParser<T, C> {
+    TagSliceParser::new(expected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorLeaf;
+    use crate::{AtomicCursor, ByteCursor, CharSliceCursor};
+
+    #[test]
+    fn test_tag_slice_u32() {
+        let data = [0xCAFEu32, 0xBABE, 7];
+        let cursor = AtomicCursor::new(&data);
+
+        let (matched, cursor) = tag_slice(&[0xCAFE, 0xBABE]).parse(cursor).unwrap();
+        assert_eq!(matched, [0xCAFE, 0xBABE]);
+        assert_eq!(cursor.value().unwrap(), 7);
+    }
+
+    #[test]
+    fn test_tag_slice_bytes_and_chars() {
+        let (matched, cursor) = tag_slice(b"let").parse(ByteCursor::new(b"let x")).unwrap();
+        assert_eq!(matched, b"let");
+        assert_eq!(cursor.position(), 3);
+
+        let chars: Vec<char> = "λx".chars().collect();
+        let (matched, cursor) = tag_slice(&['λ'])
+            .parse(CharSliceCursor::new(&chars))
+            .unwrap();
+        assert_eq!(matched, ['λ']);
+        assert_eq!(cursor.value().unwrap(), 'x');
+    }
+
+    #[test]
+    fn test_tag_slice_mismatch_and_eof() {
+        let data = [1u16, 2, 4];
+        let error = tag_slice(&[1u16, 2, 3])
+            .parse(AtomicCursor::new(&data))
+            .unwrap_err();
+        assert_eq!(error.loc().position(), 2);
+        assert_eq!(
+            error.message(),
+            "expected '3', found '4' while matching '1 2 3'"
+        );
+
+        let error = tag_slice(&[1u16, 2, 3])
+            .parse(AtomicCursor::new(&data[..2]))
+            .unwrap_err();
+        assert_eq!(error.loc().position(), 2);
+        assert!(error.message().contains("reached end of input"));
+    }
+
+    #[test]
+    fn test_tag_slice_empty_matches_nothing() {
+        let data = b"abc";
+        let (matched, cursor) = tag_slice::<u8, _>(&[])
+            .parse(ByteCursor::new(data))
+            .unwrap();
+        assert!(matched.is_empty());
+        assert_eq!(cursor.position(), 0);
+    }
+}
@@ -1,5 +1,7 @@
 use crate::Cursor;
+use crate::atomic::Atomic;
 use crate::parser::Parser;
+use crate::repetition::RepetitionError;
 
 /// Parser that repeatedly applies another parser until a predicate is satisfied
 pub struct TakeUntilParser<P, F> {
@@ -16,12 +18,13 @@ impl<P, F> TakeUntilParser<P, F> {
 impl<'code, P, F, T> Parser<'code> for TakeUntilParser<P, F>
 where
     P: Parser<'code, Output = T>,
-    P::Cursor: Cursor<'code>,
+    P::Error: 'code,
+    <P::Cursor as Cursor<'code>>::Element: Atomic + 'code,
     F: Fn(&T) -> bool,
 {
     type Cursor = P::Cursor;
     type Output = Vec<T>;
-    type Error = P::Error;
+    type Error = RepetitionError<'code, <P::Cursor as Cursor<'code>>::Element>;
 
     fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
         let mut result = Vec::new();
@@ -47,8 +50,8 @@ where
                     }
                 }
                 Err(error) => {
-                    // Parser failed - propagate the error
-                    return Err(error);
+                    // Parser failed - propagate the error with the index of the failing item
+                    return Err(RepetitionError::new(result.len(), error));
                 }
             }
         }
@@ -65,6 +68,7 @@ mod tests {
     use super::*;
     use crate::ByteCursor;
     use crate::byte::byte;
+    use crate::error::ErrorNode;
     use crate::utf8::char::char;
 
     #[test]
@@ -215,4 +219,16 @@ mod tests {
         let (next_char, _) = char().parse(remaining_cursor).unwrap();
         assert_eq!(next_char, '1');
     }
+
+    #[test]
+    fn test_take_until_reports_failing_item() {
+        // Invalid UTF-8 after two valid chars
+        let data = b"ab\xffc";
+        let cursor = ByteCursor::new(data);
+        let parser = take_until(char(), |c: &char| *c == 'c');
+
+        let error = parser.parse(cursor).unwrap_err();
+        assert_eq!(error.index(), 2);
+        assert_eq!(error.likely_error().loc().position(), 2);
+    }
 }
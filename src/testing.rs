@@ -0,0 +1,237 @@
+//! # Testing Grammars
+//!
+//! Helpers for the regression tests of downstream grammars.
+//! [`assert_parses!`](crate::assert_parses) checks that an input parses completely,
+//! optionally comparing the output, and
+//! [`assert_error_snapshot!`](crate::assert_error_snapshot) compares the rendered
+//! error for a bad input against a golden file:
+//!
+//! ```no_run
+//! use parsicomb::ascii::i64;
+//! use parsicomb::{assert_error_snapshot, assert_parses};
+//!
+//! assert_parses!(i64(), "-42", -42);
+//! assert_error_snapshot!(i64(), "4x2", "tests/snapshots/trailing_garbage.txt");
+//! ```
+//!
+//! Errors are rendered with [`Report`](crate::report::Report) without colors, so
+//! snapshots show positions as line and column rather than byte offsets, and
+//! trailing whitespace and `\r\n` line endings are normalized away. Missing or
+//! outdated snapshots are written by running the tests with `PARSICOMB_BLESS=1`.
+
+use crate::atomic::Atomic;
+use crate::cursors::AtomicCursor;
+use crate::parser::Parser;
+use crate::report::report;
+use std::fs;
+use std::path::Path;
+
+/// Environment variable that makes snapshot assertions write the actual output
+pub const BLESS_VAR: &str = "PARSICOMB_BLESS";
+
+/// Parse all of `input`, rendering any error as a snapshot-ready report
+pub fn check_parses<'code, P, T, I>(parser: &P, input: &'code I) -> Result<P::Output, String>
+where
+    P: Parser<'code, Cursor = AtomicCursor<'code, T>>,
+    P::Error: 'code,
+    T: Atomic + 'code,
+    I: AsRef<[T]> + ?Sized,
+{
+    parser
+        .parse_to_end(input.as_ref())
+        .map(|(output, _)| output)
+        .map_err(|error| normalize(&report(&error).to_string()))
+}
+
+/// The rendered error of parsing all of `input`, or a message if it parsed
+pub fn check_fails<'code, P, T, I>(parser: &P, input: &'code I) -> Result<String, String>
+where
+    P: Parser<'code, Cursor = AtomicCursor<'code, T>>,
+    P::Error: 'code,
+    T: Atomic + 'code,
+    I: AsRef<[T]> + ?Sized,
+{
+    match parser.parse_to_end(input.as_ref()) {
+        Ok(_) => Err(format!(
+            "expected an error, but input parsed: {}",
+            T::format_slice(input.as_ref())
+        )),
+        Err(error) => Ok(normalize(&report(&error).to_string())),
+    }
+}
+
+/// Normalize line endings and strip trailing whitespace from every line
+pub fn normalize(text: &str) -> String {
+    let mut normalized = String::with_capacity(text.len());
+    for line in text.lines() {
+        normalized.push_str(line.trim_end());
+        normalized.push('\n');
+    }
+    normalized
+}
+
+/// Compare `actual` with the golden file at `path`
+///
+/// With `PARSICOMB_BLESS` set the file is written instead, creating parent
+/// directories as needed.
+#[track_caller]
+pub fn assert_snapshot(path: impl AsRef<Path>, actual: &str) {
+    let bless = std::env::var_os(BLESS_VAR).is_some_and(|value| value != "0");
+    if let Err(message) = check_snapshot(path.as_ref(), actual, bless) {
+        panic!("{}", message);
+    }
+}
+
+fn check_snapshot(path: &Path, actual: &str, bless: bool) -> Result<(), String> {
+    let actual = normalize(actual);
+    if bless {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("{}: {}", parent.display(), e))?;
+        }
+        return fs::write(path, &actual).map_err(|e| format!("{}: {}", path.display(), e));
+    }
+
+    let expected = match fs::read_to_string(path) {
+        Ok(expected) => normalize(&expected),
+        Err(e) => {
+            return Err(format!(
+                "cannot read snapshot {}: {}\nrerun with {}=1 to create it",
+                path.display(),
+                e,
+                BLESS_VAR
+            ));
+        }
+    };
+    if expected != actual {
+        return Err(format!(
+            "snapshot {} does not match\n--- expected\n{}--- actual\n{}rerun with {}=1 to update it",
+            path.display(),
+            expected,
+            actual,
+            BLESS_VAR
+        ));
+    }
+    Ok(())
+}
+
+/// Assert that a parser consumes all of an input, evaluating to the output
+///
+/// With a third argument the output is also compared to the expected value.
+///
+/// ```
+/// use parsicomb::ascii::i64;
+/// use parsicomb::assert_parses;
+///
+/// assert_parses!(i64(), b"17", 17);
+/// let value = assert_parses!(i64(), "-3");
+/// assert_eq!(value, -3);
+/// ```
+#[macro_export]
+macro_rules! assert_parses {
+    ($parser:expr, $input:expr $(,)?) => {
+        match $crate::testing::check_parses(&$parser, $input) {
+            ::std::result::Result::Ok(output) => output,
+            ::std::result::Result::Err(error) => {
+                ::std::panic!("failed to parse {:?}:\n{}", $input, error)
+            }
+        }
+    };
+    ($parser:expr, $input:expr, $expected:expr $(,)?) => {{
+        let output = $crate::assert_parses!($parser, $input);
+        ::std::assert_eq!(output, $expected);
+    }};
+}
+
+/// Assert that a parser rejects an input, evaluating to the rendered error
+///
+/// ```
+/// use parsicomb::ascii::i64;
+/// use parsicomb::assert_fails;
+///
+/// let error = assert_fails!(i64(), "12a");
+/// assert!(error.starts_with("error: expected end of input, found 'a'\n"));
+/// ```
+#[macro_export]
+macro_rules! assert_fails {
+    ($parser:expr, $input:expr $(,)?) => {
+        match $crate::testing::check_fails(&$parser, $input) {
+            ::std::result::Result::Ok(error) => error,
+            ::std::result::Result::Err(message) => ::std::panic!("{}", message),
+        }
+    };
+}
+
+/// Assert that the rendered error for an input matches a golden file
+///
+/// The path is relative to the manifest directory of the crate running the test.
+#[macro_export]
+macro_rules! assert_error_snapshot {
+    ($parser:expr, $input:expr, $path:expr $(,)?) => {{
+        let error = $crate::assert_fails!($parser, $input);
+        let path = ::std::path::Path::new(::std::env!("CARGO_MANIFEST_DIR")).join($path);
+        $crate::testing::assert_snapshot(path, &error);
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::and::AndExt;
+    use crate::ascii::i64;
+    use crate::byte::is_byte;
+    use crate::tag::tag_slice;
+
+    #[test]
+    fn test_assert_parses() {
+        assert_parses!(i64(), b"42", 42);
+        assert_parses!(is_byte(b'a').and(is_byte(b'b')), "ab", (b'a', b'b'));
+
+        let chars: Vec<char> = "λx".chars().collect();
+        let matched = assert_parses!(tag_slice(&['λ', 'x']), &chars);
+        assert_eq!(matched, ['λ', 'x']);
+    }
+
+    #[test]
+    #[should_panic(expected = "expected end of input")]
+    fn test_assert_parses_rejects_leftover_input() {
+        assert_parses!(i64(), "42 ");
+    }
+
+    #[test]
+    fn test_assert_fails_renders_report() {
+        let error = assert_fails!(i64(), "1;\n2");
+        assert_eq!(
+            error,
+            "error: expected end of input, found ';'\n\
+             \x20--> line 1, column 2\n\
+             \x20 |\n\
+             1 | 1;\n\
+             \x20 |  ^\n\
+             2 | 2\n"
+        );
+
+        assert!(check_fails(&i64(), "7").is_err());
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("a  \r\nb\t\n\nc"), "a\nb\n\nc\n");
+    }
+
+    #[test]
+    fn test_snapshot_bless_and_compare() {
+        let dir = std::env::temp_dir().join(format!("parsicomb-snapshot-{}", std::process::id()));
+        let path = dir.join("nested").join("error.txt");
+
+        let error = assert_fails!(is_byte(b'x'), "y");
+        assert!(check_snapshot(&path, &error, false).is_err());
+        check_snapshot(&path, &error, true).unwrap();
+        check_snapshot(&path, &error.replace('\n', "\r\n"), false).unwrap();
+
+        let other = assert_fails!(is_byte(b'x'), "z");
+        let message = check_snapshot(&path, &other, false).unwrap_err();
+        assert!(message.contains("does not match"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,193 @@
+use crate::atomic::Atomic;
+use crate::cursor::Cursor;
+use crate::describe::Description;
+use crate::parser::Parser;
+use crate::position::Span;
+
+// # Tokens With Leading Trivia
+//
+// Grammars usually skip whitespace and comments between tokens and forget them.
+// Formatters and documentation tools need that trivia back, so `token` records
+// the span of every trivia match skipped before a token and attaches them to it.
+// Capturing allocates a `Vec` per token, so it is off by default and turned on
+// with `capture_trivia`; without it trivia is skipped exactly as by padding.
+
+/// A parsed token together with the trivia that preceded it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token<'code, K, T: Atomic = u8> {
+    /// The value produced by the token parser
+    pub kind: K,
+    /// Where the token itself is, excluding trivia
+    pub span: Span<'code, T>,
+    /// Spans of the trivia matches skipped before the token, in source order
+    ///
+    /// Always empty unless trivia capture is enabled.
+    pub leading_trivia: Vec<Span<'code, T>>,
+}
+
+/// Parser combinator that skips trivia and then parses a token
+///
+/// Example:
+/// ```
+/// use parsicomb::ascii::{take_ident, take_whitespace};
+/// use parsicomb::or::OrExt;
+/// use parsicomb::map::MapExt;
+/// use parsicomb::token::token;
+/// use parsicomb::utf8::comment::line_comment;
+/// use parsicomb::{ByteCursor, Parser};
+///
+/// // Comments go first, since `take_whitespace` also matches the empty string
+/// let trivia = line_comment("//").map(|_| ()).or(take_whitespace().map(|_| ()));
+/// let ident = token(take_ident(), trivia).capture_trivia();
+///
+/// let data = b"  // the answer\nanswer";
+/// let (token, _) = ident.parse(ByteCursor::new(data)).unwrap();
+/// assert_eq!(token.kind, "answer");
+/// let trivia: Vec<_> = token.leading_trivia.iter().map(|s| s.as_string()).collect();
+/// assert_eq!(trivia, ["  ", "// the answer", "\n"]);
+/// ```
+pub struct TokenParser<P, PT> {
+    parser: P,
+    trivia: PT,
+    capture: bool,
+}
+
+impl<P, PT> TokenParser<P, PT> {
+    pub fn new(parser: P, trivia: PT) -> Self {
+        TokenParser {
+            parser,
+            trivia,
+            capture: false,
+        }
+    }
+
+    /// Record the spans of skipped trivia in `Token::leading_trivia`
+    pub fn capture_trivia(self) -> Self {
+        self.with_trivia_capture(true)
+    }
+
+    /// Turn trivia capture on or off
+    pub fn with_trivia_capture(mut self, capture: bool) -> Self {
+        self.capture = capture;
+        self
+    }
+}
+
+impl<'code, P, PT> Parser<'code> for TokenParser<P, PT>
+where
+    P: Parser<'code>,
+    PT: Parser<'code, Cursor = P::Cursor>,
+    <P::Cursor as Cursor<'code>>::Element: Atomic + 'code,
+{
+    type Cursor = P::Cursor;
+    type Output = Token<'code, P::Output, <P::Cursor as Cursor<'code>>::Element>;
+    type Error = P::Error;
+
+    fn parse(&self, mut cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let mut leading_trivia = Vec::new();
+
+        // Stop on an empty match too, so trivia like `take_whitespace` cannot loop forever
+        while let Ok((_, next_cursor)) = self.trivia.parse(cursor) {
+            if next_cursor.position() == cursor.position() {
+                break;
+            }
+            if self.capture {
+                let (data, start) = cursor.inner();
+                leading_trivia.push(Span::new(data, start, next_cursor.position()));
+            }
+            cursor = next_cursor;
+        }
+
+        let (data, start) = cursor.inner();
+        let (kind, cursor) = self.parser.parse(cursor)?;
+        let token = Token {
+            kind,
+            span: Span::new(data, start, cursor.position()),
+            leading_trivia,
+        };
+        Ok((token, cursor))
+    }
+
+    fn describe(&self) -> Description {
+        Description::sequence(
+            Description::repeat(0, self.trivia.describe()),
+            self.parser.describe(),
+        )
+    }
+}
+
+/// Creates a parser for a token preceded by any number of `trivia` matches
+pub fn token<'code, P, PT>(parser: P, trivia: PT) -> TokenParser<P, PT>
+where
+    P: Parser<'code>,
+    PT: Parser<'code, Cursor = P::Cursor>,
+{
+    TokenParser::new(parser, trivia)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ByteCursor;
+    use crate::and::AndExt;
+    use crate::ascii::{i64, take_whitespace};
+    use crate::error::ErrorLeaf;
+    use crate::map::MapExt;
+    use crate::or::OrExt;
+    use crate::utf8::comment::block_comment;
+
+    #[test]
+    fn test_token_without_capture_skips_trivia() {
+        let data = b"   42";
+        let parser = token(i64(), take_whitespace());
+
+        let (token, cursor) = parser.parse(ByteCursor::new(data)).unwrap();
+        assert_eq!(token.kind, 42);
+        assert_eq!((token.span.start, token.span.end), (3, 5));
+        assert!(token.leading_trivia.is_empty());
+        assert!(cursor.eos());
+    }
+
+    #[test]
+    fn test_token_captures_comments_between_tokens() {
+        let data = b"1 /* one */ /* two */2";
+        let number = || token(i64(), trivia()).capture_trivia();
+        let parser = number().and(number());
+
+        let ((first, second), _) = parser.parse(ByteCursor::new(data)).unwrap();
+        assert!(first.leading_trivia.is_empty());
+        let comments: Vec<_> = second
+            .leading_trivia
+            .iter()
+            .map(|span| span.as_string())
+            .collect();
+        assert_eq!(comments, [" ", "/* one */", " ", "/* two */"]);
+        assert_eq!(second.span.start, 21);
+    }
+
+    #[test]
+    fn test_token_capture_can_be_switched_off() {
+        let data = b"  7";
+        let parser = token(i64(), take_whitespace())
+            .capture_trivia()
+            .with_trivia_capture(false);
+
+        let (token, _) = parser.parse(ByteCursor::new(data)).unwrap();
+        assert!(token.leading_trivia.is_empty());
+    }
+
+    #[test]
+    fn test_token_error_points_at_token() {
+        let data = b"  x";
+        let parser = token(i64(), take_whitespace());
+
+        let error = parser.parse(ByteCursor::new(data)).unwrap_err();
+        assert_eq!(error.loc().position(), 2);
+    }
+
+    fn trivia<'code>() -> impl Parser<'code, Cursor = ByteCursor<'code>> {
+        block_comment("/*", "*/")
+            .map(|_| ())
+            .or(take_whitespace().map(|_| ()))
+    }
+}
@@ -0,0 +1,540 @@
+use crate::atomic::Atomic;
+use crate::cursor::Cursor;
+use crate::describe::Description;
+use crate::error::ErrorNode;
+use crate::parser::Parser;
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::fmt;
+use std::ops::Range;
+
+// # Trace Combinator - Debugging Grammars
+//
+// `.traced("rule")` marks a parser as a named grammar rule. While tracing is enabled
+// on the current thread, every traced rule logs when it is entered, how much input it
+// consumed on success or why it failed, indented by how deeply rules are nested:
+//
+// ```text
+// > value at 0
+//   > number at 0
+//   < number failed at 0: expected digit, found '['
+//   > array at 0
+//   < array matched 0..7 "[1, 2]"
+// < value matched 0..7 "[1, 2]"
+// ```
+//
+// Tracing is off by default, in which case a traced parser only pays for one
+// thread-local flag check. Turn it on with `enable_tracing()` to print to stderr, or
+// run a closure under `capture_trace` to collect the lines instead.
+//
+// Wrapping the root parser in `record_trace` records the same information as a tree
+// of `RuleEvent`s, which can be pretty-printed or dumped as JSON.
+
+/// Longest consumed text shown in a trace line before it is cut short
+const MAX_TRACE_TEXT: usize = 32;
+
+enum TraceOutput {
+    Stderr,
+    Capture(Vec<String>),
+}
+
+struct TraceState {
+    output: Option<TraceOutput>,
+    depth: usize,
+    /// Children collected so far for every rule being recorded, innermost last
+    recording: Vec<Vec<RuleEvent>>,
+}
+
+thread_local! {
+    static TRACE: RefCell<TraceState> = const {
+        RefCell::new(TraceState {
+            output: None,
+            depth: 0,
+            recording: Vec::new(),
+        })
+    };
+}
+
+/// Print trace output of traced parsers on this thread to stderr
+pub fn enable_tracing() {
+    TRACE.with(|trace| trace.borrow_mut().output = Some(TraceOutput::Stderr));
+}
+
+/// Stop tracing parsers on this thread
+pub fn disable_tracing() {
+    TRACE.with(|trace| trace.borrow_mut().output = None);
+}
+
+/// Run `f` with tracing enabled and return its result together with the trace lines
+///
+/// The previous tracing state is restored afterwards.
+pub fn capture_trace<R>(f: impl FnOnce() -> R) -> (R, Vec<String>) {
+    let previous = TRACE.with(|trace| {
+        trace
+            .borrow_mut()
+            .output
+            .replace(TraceOutput::Capture(Vec::new()))
+    });
+    let result = f();
+    let captured = TRACE.with(|trace| std::mem::replace(&mut trace.borrow_mut().output, previous));
+
+    match captured {
+        Some(TraceOutput::Capture(lines)) => (result, lines),
+        _ => (result, Vec::new()),
+    }
+}
+
+fn is_tracing() -> bool {
+    TRACE.with(|trace| {
+        let trace = trace.borrow();
+        trace.output.is_some() || !trace.recording.is_empty()
+    })
+}
+
+/// Log entry into a rule and start collecting its children
+fn enter(name: &str, start: usize) {
+    TRACE.with(|trace| {
+        let mut trace = trace.borrow_mut();
+        let line = format!("{}> {} at {}", "  ".repeat(trace.depth), name, start);
+        write_line(&mut trace, line);
+        trace.depth += 1;
+        if !trace.recording.is_empty() {
+            trace.recording.push(Vec::new());
+        }
+    });
+}
+
+/// Log exit from a rule and attach its event to the enclosing rule
+fn exit(name: &str, span: Range<usize>, outcome: Outcome, consumed: impl FnOnce() -> String) {
+    TRACE.with(|trace| {
+        let mut trace = trace.borrow_mut();
+        trace.depth = trace.depth.saturating_sub(1);
+        if trace.output.is_some() {
+            let indent = "  ".repeat(trace.depth);
+            let line = match &outcome {
+                Outcome::Matched => format!(
+                    "{}< {} matched {}..{} {}",
+                    indent,
+                    name,
+                    span.start,
+                    span.end,
+                    consumed()
+                ),
+                Outcome::Failed { position, message } => {
+                    format!("{}< {} failed at {}: {}", indent, name, position, message)
+                }
+            };
+            write_line(&mut trace, line);
+        }
+
+        // The root frame belongs to record_trace, so a rule always has a parent frame
+        if trace.recording.len() >= 2 {
+            let children = trace.recording.pop().unwrap_or_default();
+            let event = RuleEvent {
+                name: name.to_string(),
+                span,
+                outcome,
+                children,
+            };
+            if let Some(parent) = trace.recording.last_mut() {
+                parent.push(event);
+            }
+        }
+    });
+}
+
+fn write_line(trace: &mut TraceState, line: String) {
+    match &mut trace.output {
+        Some(TraceOutput::Stderr) => eprintln!("{}", line),
+        Some(TraceOutput::Capture(lines)) => lines.push(line),
+        None => {}
+    }
+}
+
+fn describe<T: Atomic>(slice: &[T]) -> String {
+    let text = T::format_slice(slice);
+    if text.chars().count() > MAX_TRACE_TEXT {
+        let cut: String = text.chars().take(MAX_TRACE_TEXT).collect();
+        format!("{:?}...", cut)
+    } else {
+        format!("{:?}", text)
+    }
+}
+
+/// Parser combinator that logs entry and exit of a named rule while tracing is enabled
+pub struct Traced<P> {
+    parser: P,
+    name: Cow<'static, str>,
+}
+
+impl<P> Traced<P> {
+    pub fn new(parser: P, name: impl Into<Cow<'static, str>>) -> Self {
+        Traced {
+            parser,
+            name: name.into(),
+        }
+    }
+}
+
+impl<'code, P> Parser<'code> for Traced<P>
+where
+    P: Parser<'code>,
+    <P::Cursor as Cursor<'code>>::Element: Atomic + 'code,
+{
+    type Cursor = P::Cursor;
+    type Output = P::Output;
+    type Error = P::Error;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        if !is_tracing() {
+            return self.parser.parse(cursor);
+        }
+
+        let start = cursor.position();
+        enter(&self.name, start);
+
+        let result = self.parser.parse(cursor);
+        match &result {
+            Ok((_, next_cursor)) => {
+                let end = next_cursor.position();
+                exit(&self.name, start..end, Outcome::Matched, || {
+                    describe(&cursor.source()[start..end])
+                });
+            }
+            Err(error) => {
+                let leaf = error.likely_error();
+                let outcome = Outcome::Failed {
+                    position: leaf.loc().position(),
+                    message: leaf.message().into_owned(),
+                };
+                exit(&self.name, start..start, outcome, String::new);
+            }
+        }
+
+        result
+    }
+
+    fn describe(&self) -> Description {
+        Description::rule(self.name.clone(), self.parser.describe())
+    }
+}
+
+/// Convenience function to create a Traced parser
+pub fn traced<'code, P>(parser: P, name: impl Into<Cow<'static, str>>) -> Traced<P>
+where
+    P: Parser<'code>,
+{
+    Traced::new(parser, name)
+}
+
+/// Extension trait to add .traced() method support for parsers
+pub trait TracedExt<'code>: Parser<'code> + Sized {
+    fn traced(self, name: impl Into<Cow<'static, str>>) -> Traced<Self> {
+        Traced::new(self, name)
+    }
+}
+
+/// Implement TracedExt for all parsers
+impl<'code, P> TracedExt<'code> for P where P: Parser<'code> {}
+
+/// How a traced rule ended
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Matched,
+    Failed { position: usize, message: String },
+}
+
+/// A traced rule invocation recorded by [`record_trace`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleEvent {
+    /// Name given to `.traced()`
+    pub name: String,
+    /// Input consumed by the rule, empty when it failed
+    pub span: Range<usize>,
+    pub outcome: Outcome,
+    /// Traced rules invoked while this rule was running, in order
+    pub children: Vec<RuleEvent>,
+}
+
+impl RuleEvent {
+    /// Serialize the event and its children as a JSON object
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        self.write_json(&mut json);
+        json
+    }
+
+    fn write_json(&self, json: &mut String) {
+        json.push_str("{\"name\":");
+        write_json_string(json, &self.name);
+        json.push_str(&format!(
+            ",\"start\":{},\"end\":{},",
+            self.span.start, self.span.end
+        ));
+        match &self.outcome {
+            Outcome::Matched => json.push_str("\"outcome\":\"matched\""),
+            Outcome::Failed { position, message } => {
+                json.push_str(&format!(
+                    "\"outcome\":\"failed\",\"position\":{},\"message\":",
+                    position
+                ));
+                write_json_string(json, message);
+            }
+        }
+        json.push_str(",\"children\":[");
+        for (i, child) in self.children.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            child.write_json(json);
+        }
+        json.push_str("]}");
+    }
+
+    fn fmt_indented(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        let indent = "  ".repeat(depth);
+        match &self.outcome {
+            Outcome::Matched => writeln!(
+                f,
+                "{}{} matched {}..{}",
+                indent, self.name, self.span.start, self.span.end
+            )?,
+            Outcome::Failed { position, message } => writeln!(
+                f,
+                "{}{} failed at {}: {}",
+                indent, self.name, position, message
+            )?,
+        }
+        for child in &self.children {
+            child.fmt_indented(f, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for RuleEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_indented(f, 0)
+    }
+}
+
+fn write_json_string(json: &mut String, value: &str) {
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+/// Parser combinator that records the traced rules run by the wrapped parser
+///
+/// After each parse, [`RecordTrace::take_events`] returns the top-level traced
+/// rules as a tree of [`RuleEvent`]s, whether the parse succeeded or not.
+pub struct RecordTrace<P> {
+    parser: P,
+    events: RefCell<Vec<RuleEvent>>,
+}
+
+impl<P> RecordTrace<P> {
+    pub fn new(parser: P) -> Self {
+        RecordTrace {
+            parser,
+            events: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Take the events recorded by the most recent parse
+    pub fn take_events(&self) -> Vec<RuleEvent> {
+        std::mem::take(&mut self.events.borrow_mut())
+    }
+}
+
+impl<'code, P> Parser<'code> for RecordTrace<P>
+where
+    P: Parser<'code>,
+{
+    type Cursor = P::Cursor;
+    type Output = P::Output;
+    type Error = P::Error;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        TRACE.with(|trace| trace.borrow_mut().recording.push(Vec::new()));
+        let result = self.parser.parse(cursor);
+        let events = TRACE.with(|trace| trace.borrow_mut().recording.pop().unwrap_or_default());
+
+        *self.events.borrow_mut() = events;
+        result
+    }
+}
+
+/// Convenience function to create a RecordTrace parser
+pub fn record_trace<'code, P>(parser: P) -> RecordTrace<P>
+where
+    P: Parser<'code>,
+{
+    RecordTrace::new(parser)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ByteCursor;
+    use crate::and::AndExt;
+    use crate::byte::is_byte;
+    use crate::map::MapExt;
+    use crate::or::OrExt;
+    use crate::utf8::string::is_string;
+
+    #[test]
+    fn test_traced_is_silent_by_default() {
+        let data = b"a";
+        let cursor = ByteCursor::new(data);
+        let parser = is_byte(b'a').traced("a");
+
+        let (byte, _) = parser.parse(cursor).unwrap();
+        assert_eq!(byte, b'a');
+    }
+
+    #[test]
+    fn test_capture_nested_trace() {
+        let data = b"let x";
+        let cursor = ByteCursor::new(data);
+        let keyword = is_string("var")
+            .traced("var")
+            .or(is_string("let").traced("let"))
+            .traced("keyword");
+        let parser = keyword
+            .and(is_byte(b' '))
+            .and(is_byte(b'x').traced("name"))
+            .map(|_| ())
+            .traced("declaration");
+
+        let (result, lines) = capture_trace(|| parser.parse(cursor));
+        assert!(result.is_ok());
+        assert_eq!(
+            lines,
+            vec![
+                "> declaration at 0",
+                "  > keyword at 0",
+                "    > var at 0",
+                "    < var failed at 0: expected 'v', found 'l' while matching 'var'",
+                "    > let at 0",
+                "    < let matched 0..3 \"let\"",
+                "  < keyword matched 0..3 \"let\"",
+                "  > name at 4",
+                "  < name matched 4..5 \"x\"",
+                "< declaration matched 0..5 \"let x\"",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_capture_restores_previous_state() {
+        let data = b"a";
+        let cursor = ByteCursor::new(data);
+        let parser = is_byte(b'a').traced("a");
+
+        let (_, lines) = capture_trace(|| parser.parse(cursor));
+        assert_eq!(lines.len(), 2);
+        assert!(!is_tracing());
+    }
+
+    #[test]
+    fn test_trace_truncates_long_matches() {
+        let data = [b'a'; 40];
+        let cursor = ByteCursor::new(&data);
+        let parser = crate::many::many(is_byte(b'a')).traced("run");
+
+        let (_, lines) = capture_trace(|| parser.parse(cursor));
+        assert_eq!(
+            lines[1],
+            format!("< run matched 0..40 \"{}\"...", "a".repeat(32))
+        );
+    }
+
+    #[test]
+    fn test_record_trace_tree() {
+        let data = b"let x";
+        let cursor = ByteCursor::new(data);
+        let keyword = is_string("var")
+            .traced("var")
+            .or(is_string("let").traced("let"))
+            .traced("keyword");
+        let parser = record_trace(
+            keyword
+                .and(is_byte(b' '))
+                .and(is_byte(b'y').traced("name"))
+                .traced("declaration"),
+        );
+
+        assert!(parser.parse(cursor).is_err());
+        let events = parser.take_events();
+        assert_eq!(events.len(), 1);
+
+        let declaration = &events[0];
+        assert_eq!(declaration.name, "declaration");
+        assert!(matches!(
+            declaration.outcome,
+            Outcome::Failed { position: 4, .. }
+        ));
+        assert_eq!(declaration.children.len(), 2);
+
+        let keyword = &declaration.children[0];
+        assert_eq!(keyword.span, 0..3);
+        assert_eq!(keyword.children[0].name, "var");
+        assert_eq!(keyword.children[1].outcome, Outcome::Matched);
+
+        assert_eq!(
+            declaration.to_string(),
+            "declaration failed at 4: expected byte 0x79 ('y'), found 0x78 ('x')\n\
+             \x20 keyword matched 0..3\n\
+             \x20   var failed at 0: expected 'v', found 'l' while matching 'var'\n\
+             \x20   let matched 0..3\n\
+             \x20 name failed at 4: expected byte 0x79 ('y'), found 0x78 ('x')\n"
+        );
+        assert!(parser.take_events().is_empty());
+    }
+
+    #[test]
+    fn test_rule_event_json() {
+        let event = RuleEvent {
+            name: "str".to_string(),
+            span: 0..4,
+            outcome: Outcome::Matched,
+            children: vec![RuleEvent {
+                name: "quote".to_string(),
+                span: 0..0,
+                outcome: Outcome::Failed {
+                    position: 0,
+                    message: "expected '\"'\n".to_string(),
+                },
+                children: Vec::new(),
+            }],
+        };
+
+        assert_eq!(
+            event.to_json(),
+            r#"{"name":"str","start":0,"end":4,"outcome":"matched","children":[{"name":"quote","start":0,"end":0,"outcome":"failed","position":0,"message":"expected '\"'\n","children":[]}]}"#
+        );
+    }
+
+    #[test]
+    fn test_record_trace_does_not_print() {
+        let data = b"a";
+        let cursor = ByteCursor::new(data);
+        let parser = record_trace(is_byte(b'a').traced("a"));
+
+        let (_, lines) = capture_trace(|| parser.parse(cursor));
+        assert_eq!(lines.len(), 2);
+        assert_eq!(parser.take_events().len(), 1);
+        assert!(!is_tracing());
+    }
+}
@@ -29,7 +29,9 @@ impl<'code> Parser<'code> for CharParser {
         let byte_parser = ByteParser::new();
 
         // 1. Read the first byte
-        let (b1, mut current_cursor) = byte_parser.parse(cursor)?;
+        let (b1, mut current_cursor) = byte_parser
+            .parse(cursor)
+            .map_err(|error| error.expecting("a character"))?;
 
         // 2. Decode based on the first byte
         let codepoint = if b1 < 0x80 {
@@ -147,7 +149,9 @@ impl<'code> Parser<'code> for IsChar {
     type Error = ParsicombError<'code>;
 
     fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
-        let (ch, next_cursor) = char().parse(cursor)?;
+        let (ch, next_cursor) = char()
+            .parse(cursor)
+            .map_err(|error| error.expecting(format!("'{}'", self.0.escape_default())))?;
         if ch == self.0 {
             Ok((ch, next_cursor))
         } else {
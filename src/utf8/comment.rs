@@ -0,0 +1,222 @@
+use crate::ByteCursor;
+use crate::Cursor;
+use crate::parser::Parser;
+use crate::position::Span;
+use crate::{CodeLoc, ParsicombError};
+use std::borrow::Cow;
+
+/// Slice `data[start..end]` as UTF-8, reporting invalid bytes at their position
+fn comment_text(data: &[u8], start: usize, end: usize) -> Result<&str, ParsicombError<'_>> {
+    std::str::from_utf8(&data[start..end]).map_err(|e| ParsicombError::SyntaxError {
+        message: "invalid UTF-8 in comment".into(),
+        loc: CodeLoc::new(data, start + e.valid_up_to()),
+    })
+}
+
+/// Parser for comments that run from a prefix to the end of the line
+///
+/// Returns the comment body without the prefix together with the span of the whole
+/// comment. The line terminator is not consumed and a trailing `\r` is not part of
+/// the body.
+pub struct LineCommentParser {
+    prefix: Cow<'static, str>,
+}
+
+impl LineCommentParser {
+    pub fn new(prefix: impl Into<Cow<'static, str>>) -> Self {
+        LineCommentParser {
+            prefix: prefix.into(),
+        }
+    }
+}
+
+impl<'code> Parser<'code> for LineCommentParser {
+    type Cursor = ByteCursor<'code>;
+    type Output = (&'code str, Span<'code>);
+    type Error = ParsicombError<'code>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (data, start) = cursor.inner();
+        if !data[start..].starts_with(self.prefix.as_bytes()) {
+            return Err(ParsicombError::SyntaxError {
+                message: format!("expected line comment starting with '{}'", self.prefix).into(),
+                loc: CodeLoc::new(data, start),
+            });
+        }
+
+        let body_start = start + self.prefix.len();
+        let end = data[body_start..]
+            .iter()
+            .position(|&b| b == b'\n')
+            .map_or(data.len(), |newline| body_start + newline);
+        let body_end = if end > body_start && data[end - 1] == b'\r' {
+            end - 1
+        } else {
+            end
+        };
+
+        let body = comment_text(data, body_start, body_end)?;
+        Ok((
+            (body, Span::new(data, start, end)),
+            ByteCursor::scanned(data, start, end)?,
+        ))
+    }
+}
+
+/// Parser for comments delimited by an opening and a closing string
+///
+/// Returns the comment body without the delimiters together with the span of the
+/// whole comment. With nesting enabled, every opening delimiter inside the comment
+/// needs its own closing delimiter, as in Rust's `/* /* */ */`.
+pub struct BlockCommentParser {
+    open: Cow<'static, str>,
+    close: Cow<'static, str>,
+    nested: bool,
+}
+
+impl BlockCommentParser {
+    pub fn new(open: impl Into<Cow<'static, str>>, close: impl Into<Cow<'static, str>>) -> Self {
+        BlockCommentParser {
+            open: open.into(),
+            close: close.into(),
+            nested: false,
+        }
+    }
+
+    /// Allow block comments to nest
+    pub fn nested(mut self) -> Self {
+        self.nested = true;
+        self
+    }
+}
+
+impl<'code> Parser<'code> for BlockCommentParser {
+    type Cursor = ByteCursor<'code>;
+    type Output = (&'code str, Span<'code>);
+    type Error = ParsicombError<'code>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (data, start) = cursor.inner();
+        let open = self.open.as_bytes();
+        let close = self.close.as_bytes();
+
+        if !data[start..].starts_with(open) {
+            return Err(ParsicombError::SyntaxError {
+                message: format!("expected block comment starting with '{}'", self.open).into(),
+                loc: CodeLoc::new(data, start),
+            });
+        }
+
+        let body_start = start + open.len();
+        let mut position = body_start;
+        let mut depth = 1;
+
+        while position < data.len() {
+            let rest = &data[position..];
+            if rest.starts_with(close) {
+                depth -= 1;
+                if depth == 0 {
+                    let end = position + close.len();
+                    let body = comment_text(data, body_start, position)?;
+                    return Ok((
+                        (body, Span::new(data, start, end)),
+                        ByteCursor::scanned(data, start, end)?,
+                    ));
+                }
+                position += close.len();
+            } else if self.nested && rest.starts_with(open) {
+                depth += 1;
+                position += open.len();
+            } else {
+                position += 1;
+            }
+        }
+
+        // Point at the comment that was never closed rather than the end of input
+        Err(ParsicombError::SyntaxError {
+            message: format!(
+                "unterminated block comment, expected '{}' before end of input",
+                self.close
+            )
+            .into(),
+            loc: CodeLoc::new(data, start),
+        })
+    }
+}
+
+/// Creates a parser for comments from `prefix` to the end of the line
+pub fn line_comment(prefix: impl Into<Cow<'static, str>>) -> LineCommentParser {
+    LineCommentParser::new(prefix)
+}
+
+/// Creates a parser for comments between `open` and `close`, call `.nested()` to allow nesting
+pub fn block_comment(
+    open: impl Into<Cow<'static, str>>,
+    close: impl Into<Cow<'static, str>>,
+) -> BlockCommentParser {
+    BlockCommentParser::new(open, close)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_comment() {
+        let data = b"// hello world\r\nnext";
+        let cursor = ByteCursor::new(data);
+
+        let ((body, span), cursor) = line_comment("//").parse(cursor).unwrap();
+        assert_eq!(body, " hello world");
+        assert_eq!(span, Span::new(data, 0, 15));
+        assert_eq!(cursor.value().unwrap(), b'\n');
+    }
+
+    #[test]
+    fn test_line_comment_at_end_of_input() {
+        let data = "# grüße".as_bytes();
+        let ((body, _), cursor) = line_comment("#").parse(ByteCursor::new(data)).unwrap();
+        assert_eq!(body, " grüße");
+        assert!(cursor.eos());
+
+        assert!(line_comment("//").parse(ByteCursor::new(b"/ x")).is_err());
+    }
+
+    #[test]
+    fn test_block_comment() {
+        let data = b"/* a * b */ rest";
+        let cursor = ByteCursor::new(data);
+
+        let ((body, span), cursor) = block_comment("/*", "*/").parse(cursor).unwrap();
+        assert_eq!(body, " a * b ");
+        assert_eq!(span.as_string(), "/* a * b */");
+        assert_eq!(cursor.value().unwrap(), b' ');
+    }
+
+    #[test]
+    fn test_block_comment_nesting() {
+        let data = b"/* outer /* inner */ still outer */!";
+
+        let flat = block_comment("/*", "*/");
+        let ((body, _), _) = flat.parse(ByteCursor::new(data)).unwrap();
+        assert_eq!(body, " outer /* inner ");
+
+        let nested = block_comment("/*", "*/").nested();
+        let ((body, _), cursor) = nested.parse(ByteCursor::new(data)).unwrap();
+        assert_eq!(body, " outer /* inner */ still outer ");
+        assert_eq!(cursor.value().unwrap(), b'!');
+    }
+
+    #[test]
+    fn test_unterminated_block_comment() {
+        let data = b"x /* never /* closed */";
+        let cursor = ByteCursor::new(data).next().next();
+
+        let error = block_comment("/*", "*/")
+            .nested()
+            .parse(cursor)
+            .unwrap_err();
+        assert_eq!(error.position(), 2);
+        assert!(error.to_string().contains("unterminated block comment"));
+    }
+}
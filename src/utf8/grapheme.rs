@@ -0,0 +1,120 @@
+use crate::ByteCursor;
+use crate::Cursor;
+use crate::parser::Parser;
+use crate::{CodeLoc, ParsicombError};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Bytes decoded at first when looking for the end of a cluster, doubled as needed
+const INITIAL_WINDOW: usize = 32;
+
+/// Parser that consumes one extended grapheme cluster
+///
+/// Unlike `char()`, a cluster such as `👨‍💻` (three code points joined by zero
+/// width joiners) or `e` followed by a combining accent is returned as one element.
+pub struct GraphemeParser;
+
+impl<'code> Parser<'code> for GraphemeParser {
+    type Cursor = ByteCursor<'code>;
+    type Output = &'code str;
+    type Error = ParsicombError<'code>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (data, start) = cursor.inner();
+        let rest = &data[start..];
+        let error = |message: &'static str| ParsicombError::SyntaxError {
+            message: message.into(),
+            loc: CodeLoc::new(data, start),
+        };
+
+        if rest.is_empty() {
+            return Err(error("expected grapheme cluster, reached end of input"));
+        }
+
+        // Decode a growing window instead of the whole remaining input, a cluster
+        // is complete once another one starts after it inside the window
+        let mut window = INITIAL_WINDOW;
+        loop {
+            let end = window.min(rest.len());
+            // Invalid bytes end the text for good, a sequence cut by the window does not
+            let (text, decoded_all) = match std::str::from_utf8(&rest[..end]) {
+                Ok(text) => (text, end == rest.len()),
+                Err(e) => (
+                    std::str::from_utf8(&rest[..e.valid_up_to()]).unwrap_or_default(),
+                    e.error_len().is_some() || end == rest.len(),
+                ),
+            };
+
+            let Some(cluster) = text.graphemes(true).next() else {
+                return Err(error("invalid UTF-8"));
+            };
+            if cluster.len() < text.len() || decoded_all {
+                return Ok((
+                    cluster,
+                    ByteCursor::scanned(data, start, start + cluster.len())?,
+                ));
+            }
+            window *= 2;
+        }
+    }
+}
+
+/// Creates a parser that consumes one extended grapheme cluster
+pub fn grapheme() -> GraphemeParser {
+    GraphemeParser
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utf8::char::char;
+
+    #[test]
+    fn test_emoji_sequence_is_one_cluster() {
+        let data = "👨‍💻🔥".as_bytes();
+        let cursor = ByteCursor::new(data);
+
+        let (cluster, cursor) = grapheme().parse(cursor).unwrap();
+        assert_eq!(cluster, "👨‍💻");
+
+        let (cluster, cursor) = grapheme().parse(cursor).unwrap();
+        assert_eq!(cluster, "🔥");
+        assert!(cursor.eos());
+
+        // char() splits the same sequence at the joiner
+        let (first, _) = char().parse(ByteCursor::new(data)).unwrap();
+        assert_eq!(first, '👨');
+    }
+
+    #[test]
+    fn test_combining_marks_and_crlf() {
+        let data = "e\u{301}\u{302}x\r\ny".as_bytes();
+        let (cluster, cursor) = grapheme().parse(ByteCursor::new(data)).unwrap();
+        assert_eq!(cluster, "e\u{301}\u{302}");
+
+        let (_, cursor) = grapheme().parse(cursor).unwrap();
+        let (cluster, _) = grapheme().parse(cursor).unwrap();
+        assert_eq!(cluster, "\r\n");
+    }
+
+    #[test]
+    fn test_cluster_longer_than_window() {
+        let text = format!("a{}b", "\u{301}".repeat(40));
+        let (cluster, cursor) = grapheme().parse(ByteCursor::new(text.as_bytes())).unwrap();
+        assert_eq!(cluster.len(), text.len() - 1);
+        assert_eq!(cursor.value().unwrap(), b'b');
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = grapheme().parse(ByteCursor::new(b"")).unwrap_err();
+        assert!(error.to_string().contains("reached end of input"));
+
+        let error = grapheme().parse(ByteCursor::new(b"\xff")).unwrap_err();
+        assert!(error.to_string().contains("invalid UTF-8"));
+
+        // A cluster stops before invalid bytes
+        let (cluster, cursor) = grapheme().parse(ByteCursor::new(b"a\xff")).unwrap();
+        assert_eq!(cluster, "a");
+        assert_eq!(cursor.position(), 1);
+    }
+}
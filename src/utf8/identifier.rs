@@ -0,0 +1,200 @@
+use crate::ByteCursor;
+use crate::Cursor;
+use crate::parser::Parser;
+use crate::position::Span;
+use crate::utf8::char::char;
+use crate::{CodeLoc, ParsicombError};
+use unicode_ident::{is_xid_continue, is_xid_start};
+
+/// Configuration for which characters an identifier may contain beyond UAX #31
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IdentifierConfig {
+    /// Allow `_` as the first character (it is always allowed afterwards)
+    pub underscore_start: bool,
+    /// Allow `$` anywhere in the identifier, as in JavaScript
+    pub dollar: bool,
+}
+
+impl Default for IdentifierConfig {
+    fn default() -> Self {
+        IdentifierConfig {
+            underscore_start: true,
+            dollar: false,
+        }
+    }
+}
+
+impl IdentifierConfig {
+    fn is_start(&self, c: char) -> bool {
+        is_xid_start(c) || (self.underscore_start && c == '_') || (self.dollar && c == '$')
+    }
+
+    fn is_continue(&self, c: char) -> bool {
+        is_xid_continue(c) || (self.dollar && c == '$')
+    }
+}
+
+/// Parser for Unicode identifiers following UAX #31 (XID_Start XID_Continue*)
+///
+/// Returns the matched text as a slice of the input together with its span.
+/// ASCII characters are classified without UTF-8 decoding.
+pub struct IdentifierParser {
+    config: IdentifierConfig,
+}
+
+impl IdentifierParser {
+    pub fn new(config: IdentifierConfig) -> Self {
+        IdentifierParser { config }
+    }
+
+    /// Classify the character at the cursor, returning the cursor past it if accepted
+    fn accept<'code>(
+        cursor: ByteCursor<'code>,
+        accepts: impl Fn(char) -> bool,
+    ) -> Option<ByteCursor<'code>> {
+        match cursor.value() {
+            Ok(byte) if byte.is_ascii() => accepts(byte as char).then(|| cursor.next()),
+            Ok(_) => match char().parse(cursor) {
+                Ok((c, next)) if accepts(c) => Some(next),
+                _ => None,
+            },
+            Err(_) => None,
+        }
+    }
+}
+
+impl<'code> Parser<'code> for IdentifierParser {
+    type Cursor = ByteCursor<'code>;
+    type Output = (&'code str, Span<'code>);
+    type Error = ParsicombError<'code>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (data, start) = cursor.inner();
+
+        let mut current = match Self::accept(cursor, |c| self.config.is_start(c)) {
+            Some(next) => next,
+            None => {
+                return Err(ParsicombError::SyntaxError {
+                    message: "expected identifier".into(),
+                    loc: CodeLoc::new(data, start),
+                });
+            }
+        };
+
+        while let Some(next) = Self::accept(current, |c| self.config.is_continue(c)) {
+            current = next;
+        }
+
+        let end = current.position();
+        let text =
+            std::str::from_utf8(&data[start..end]).map_err(|_| ParsicombError::SyntaxError {
+                message: "invalid UTF-8 in identifier".into(),
+                loc: CodeLoc::new(data, start),
+            })?;
+
+        Ok(((text, Span::new(data, start, end)), current))
+    }
+}
+
+/// Creates an identifier parser that accepts `_` as a start character
+pub fn identifier() -> IdentifierParser {
+    IdentifierParser::new(IdentifierConfig::default())
+}
+
+/// Creates an identifier parser with the given configuration
+pub fn identifier_with(config: IdentifierConfig) -> IdentifierParser {
+    IdentifierParser::new(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ascii_identifier() {
+        let data = b"foo_bar1 = 2";
+        let cursor = ByteCursor::new(data);
+        let parser = identifier();
+
+        let ((name, span), cursor) = parser.parse(cursor).unwrap();
+        assert_eq!(name, "foo_bar1");
+        assert_eq!(span, Span::new(data, 0, 8));
+        assert_eq!(cursor.value().unwrap(), b' ');
+    }
+
+    #[test]
+    fn test_unicode_identifier() {
+        let data = "größe_変数 rest".as_bytes();
+        let cursor = ByteCursor::new(data);
+        let parser = identifier();
+
+        let ((name, span), _) = parser.parse(cursor).unwrap();
+        assert_eq!(name, "größe_変数");
+        assert_eq!(span.as_string(), "größe_変数");
+    }
+
+    #[test]
+    fn test_combining_mark_continues() {
+        // 'e' followed by COMBINING ACUTE ACCENT is XID_Continue but not XID_Start
+        let data = "e\u{0301}x".as_bytes();
+        let cursor = ByteCursor::new(data);
+        let ((name, _), _) = identifier().parse(cursor).unwrap();
+        assert_eq!(name, "e\u{0301}x");
+
+        let data = "\u{0301}x".as_bytes();
+        assert!(identifier().parse(ByteCursor::new(data)).is_err());
+    }
+
+    #[test]
+    fn test_digit_start_fails() {
+        let data = b"1abc";
+        let cursor = ByteCursor::new(data);
+
+        let result = identifier().parse(cursor);
+        assert!(result.is_err());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("expected identifier")
+        );
+    }
+
+    #[test]
+    fn test_underscore_start_configurable() {
+        let data = b"_private";
+        assert!(identifier().parse(ByteCursor::new(data)).is_ok());
+
+        let strict = identifier_with(IdentifierConfig {
+            underscore_start: false,
+            dollar: false,
+        });
+        assert!(strict.parse(ByteCursor::new(data)).is_err());
+    }
+
+    #[test]
+    fn test_dollar_configurable() {
+        let data = b"$el$ement";
+        assert!(identifier().parse(ByteCursor::new(data)).is_err());
+
+        let js = identifier_with(IdentifierConfig {
+            dollar: true,
+            ..IdentifierConfig::default()
+        });
+        let ((name, _), cursor) = js.parse(ByteCursor::new(data)).unwrap();
+        assert_eq!(name, "$el$ement");
+        assert!(cursor.eos());
+    }
+
+    #[test]
+    fn test_emoji_is_not_identifier() {
+        let data = "🦀".as_bytes();
+        assert!(identifier().parse(ByteCursor::new(data)).is_err());
+    }
+
+    #[test]
+    fn test_empty_input() {
+        let data = b"";
+        assert!(identifier().parse(ByteCursor::new(data)).is_err());
+    }
+}
@@ -0,0 +1,263 @@
+use crate::ByteCursor;
+use crate::Cursor;
+use crate::balanced::skip_string;
+use crate::parser::Parser;
+use crate::position::Span;
+use crate::{CodeLoc, ParsicombError};
+use std::borrow::Cow;
+
+/// One piece of an interpolated string
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StringPart<'code> {
+    /// Text between interpolations as written, escape sequences are not decoded
+    Literal(&'code str),
+    /// The source between the interpolation delimiters, to be parsed separately
+    Interpolation(Span<'code>),
+}
+
+/// Parser for a template literal such as `"Hello ${name}!"`
+///
+/// Splits the string into literal text and interpolations. The contents of an
+/// interpolation are not parsed, its span is returned so a sub-parser can be run on
+/// it with [`Span::cursor`], keeping positions relative to the whole input.
+///
+/// A backslash escapes the next character, so `\${` does not start an interpolation
+/// and `\"` does not end the string. Inside an interpolation, string literals are
+/// skipped, and when `close` is a single closing bracket, nested brackets are
+/// balanced, so `${ {"a": "}"} }` is one interpolation.
+pub struct InterpolatedString {
+    quote: char,
+    open: Cow<'static, str>,
+    close: Cow<'static, str>,
+}
+
+impl InterpolatedString {
+    pub fn new(open: impl Into<Cow<'static, str>>, close: impl Into<Cow<'static, str>>) -> Self {
+        InterpolatedString {
+            quote: '"',
+            open: open.into(),
+            close: close.into(),
+        }
+    }
+
+    /// Use `quote` to delimit the string instead of `"`
+    pub fn with_quote(mut self, quote: char) -> Self {
+        self.quote = quote;
+        self
+    }
+
+    /// Opening bracket that nests inside an interpolation ending in `close`
+    fn nesting_open(&self) -> Option<u8> {
+        match self.close.as_bytes() {
+            b"}" => Some(b'{'),
+            b")" => Some(b'('),
+            b"]" => Some(b'['),
+            _ => None,
+        }
+    }
+
+    /// End of the interpolation whose contents start at `start`
+    fn interpolation_end(&self, data: &[u8], start: usize) -> Option<usize> {
+        let close = self.close.as_bytes();
+        let nesting = self.nesting_open();
+        let mut depth = 0usize;
+        let mut position = start;
+
+        while position < data.len() {
+            let byte = data[position];
+            if depth == 0 && data[position..].starts_with(close) {
+                return Some(position);
+            }
+            if Some(byte) == nesting {
+                depth += 1;
+            } else if depth > 0 && byte == close[0] {
+                depth -= 1;
+            } else if byte == b'"' || byte == b'\'' {
+                position = skip_string(data, position, Some(b'\\'))?;
+                continue;
+            }
+            position += 1;
+        }
+        None
+    }
+}
+
+impl<'code> Parser<'code> for InterpolatedString {
+    type Cursor = ByteCursor<'code>;
+    type Output = Vec<StringPart<'code>>;
+    type Error = ParsicombError<'code>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (data, start) = cursor.inner();
+        let mut buffer = [0; 4];
+        let quote = self.quote.encode_utf8(&mut buffer).as_bytes();
+        if !data[start..].starts_with(quote) {
+            return Err(ParsicombError::SyntaxError {
+                message: format!("expected '{}'", self.quote).into(),
+                loc: CodeLoc::new(data, start),
+            });
+        }
+
+        let utf8 = |from: usize, to: usize| {
+            std::str::from_utf8(&data[from..to]).map_err(|e| ParsicombError::SyntaxError {
+                message: "invalid UTF-8 in string".into(),
+                loc: CodeLoc::new(data, from + e.valid_up_to()),
+            })
+        };
+
+        let open = self.open.as_bytes();
+        let mut parts = Vec::new();
+        let mut literal_start = start + quote.len();
+        let mut position = literal_start;
+
+        loop {
+            if position >= data.len() {
+                ByteCursor::charge(data, start, data.len())?;
+                return Err(ParsicombError::UnterminatedConstruct {
+                    terminator: self.quote.to_string().into(),
+                    span: Span::new(data, start, data.len()),
+                });
+            }
+
+            let rest = &data[position..];
+            if rest.starts_with(quote) {
+                break;
+            }
+            if rest[0] == b'\\' {
+                position = (position + 2).min(data.len());
+                continue;
+            }
+            if !open.is_empty() && rest.starts_with(open) {
+                if position > literal_start {
+                    parts.push(StringPart::Literal(utf8(literal_start, position)?));
+                }
+                let contents = position + open.len();
+                let Some(end) = self.interpolation_end(data, contents) else {
+                    ByteCursor::charge(data, start, data.len())?;
+                    return Err(ParsicombError::UnterminatedConstruct {
+                        terminator: self.close.clone(),
+                        span: Span::new(data, position, data.len()),
+                    });
+                };
+                parts.push(StringPart::Interpolation(Span::new(data, contents, end)));
+                position = end + self.close.len();
+                literal_start = position;
+                continue;
+            }
+            position += 1;
+        }
+
+        if position > literal_start {
+            parts.push(StringPart::Literal(utf8(literal_start, position)?));
+        }
+        let end = position + quote.len();
+        Ok((parts, ByteCursor::scanned(data, start, end)?))
+    }
+}
+
+/// Creates a parser for a `"`-quoted string with interpolations between `open` and `close`
+///
+/// Example:
+/// ```
+/// use parsicomb::Parser;
+/// use parsicomb::{ByteCursor, Cursor};
+/// use parsicomb::ascii::take_ident;
+/// use parsicomb::utf8::{StringPart, interpolated_string};
+///
+/// let data = br#""Hello ${name}!""#;
+/// let (parts, _) = interpolated_string("${", "}")
+///     .parse(ByteCursor::new(data))
+///     .unwrap();
+/// assert_eq!(parts.len(), 3);
+/// assert_eq!(parts[0], StringPart::Literal("Hello "));
+///
+/// // Interpolations are parsed in place, positions stay relative to the input
+/// let StringPart::Interpolation(span) = parts[1] else { panic!() };
+/// let (name, cursor) = take_ident().parse(span.cursor()).unwrap();
+/// assert_eq!(name, "name");
+/// assert_eq!(cursor.position(), span.end);
+/// ```
+pub fn interpolated_string(
+    open: impl Into<Cow<'static, str>>,
+    close: impl Into<Cow<'static, str>>,
+) -> InterpolatedString {
+    InterpolatedString::new(open, close)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorLeaf;
+
+    fn parts(data: &[u8]) -> Vec<StringPart<'_>> {
+        let (parts, cursor) = interpolated_string("${", "}")
+            .parse(ByteCursor::new(data))
+            .unwrap();
+        assert!(cursor.eos());
+        parts
+    }
+
+    fn text<'code>(part: &StringPart<'code>) -> String {
+        match part {
+            StringPart::Literal(text) => format!("L({})", text),
+            StringPart::Interpolation(span) => format!("I({})", span.as_string()),
+        }
+    }
+
+    #[test]
+    fn test_literal_only_and_empty() {
+        assert_eq!(parts(br#""plain""#), vec![StringPart::Literal("plain")]);
+        assert!(parts(br#""""#).is_empty());
+    }
+
+    #[test]
+    fn test_adjacent_interpolations() {
+        let parsed = parts(br#""${a}${b} and ${c}""#);
+        let texts: Vec<_> = parsed.iter().map(text).collect();
+        assert_eq!(texts, ["I(a)", "I(b)", "L( and )", "I(c)"]);
+    }
+
+    #[test]
+    fn test_nested_braces_and_strings() {
+        let parsed = parts(br#""x=${ {"k": "}"}["k"] }.""#);
+        let texts: Vec<_> = parsed.iter().map(text).collect();
+        assert_eq!(texts, ["L(x=)", r#"I( {"k": "}"}["k"] )"#, "L(.)"]);
+    }
+
+    #[test]
+    fn test_escapes_are_kept_raw() {
+        let parsed = parts(br#""cost: \${x} \"${y}\"""#);
+        let texts: Vec<_> = parsed.iter().map(text).collect();
+        assert_eq!(texts, [r#"L(cost: \${x} \")"#, "I(y)", r#"L(\")"#]);
+    }
+
+    #[test]
+    fn test_custom_quote_and_delimiters() {
+        let parser = interpolated_string("{{", "}}").with_quote('`');
+        let (parsed, cursor) = parser
+            .parse(ByteCursor::new(b"`Hi {{ user.name }}`;"))
+            .unwrap();
+        let texts: Vec<_> = parsed.iter().map(text).collect();
+        assert_eq!(texts, ["L(Hi )", "I( user.name )"]);
+        assert_eq!(cursor.value().unwrap(), b';');
+    }
+
+    #[test]
+    fn test_unterminated() {
+        let parser = interpolated_string("${", "}");
+
+        let error = parser.parse(ByteCursor::new(br#""abc"#)).unwrap_err();
+        assert!(matches!(
+            error,
+            ParsicombError::UnterminatedConstruct { .. }
+        ));
+        assert_eq!(error.message(), "expected '\"' before end of input");
+
+        let error = parser.parse(ByteCursor::new(br#""a ${b""#)).unwrap_err();
+        assert_eq!(error.message(), "expected '}' before end of input");
+        assert_eq!(error.loc().position(), 3);
+
+        let error = parser.parse(ByteCursor::new(b"abc")).unwrap_err();
+        assert_eq!(error.message(), "expected '\"'");
+    }
+}
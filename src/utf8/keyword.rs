@@ -0,0 +1,347 @@
+use crate::ByteCursor;
+use crate::Cursor;
+use crate::describe::Description;
+use crate::error::join_alternatives;
+use crate::parser::Parser;
+use crate::symbol_table::SymbolTrie;
+use crate::utf8::char::char;
+use crate::{CodeLoc, ParsicombError};
+use unicode_ident::is_xid_continue;
+
+/// Parser that matches one keyword of a table and returns its value
+///
+/// Keywords are tried in table order, so longer keywords should come first when
+/// one is a prefix of another. A keyword that ends in an identifier character
+/// only matches at a word boundary: `if` does not match the start of `iffy`.
+///
+/// This is the parser generated by `#[derive(Keywords)]` with the `derive` feature,
+/// which sorts the table longest first.
+pub struct KeywordsParser<V: 'static> {
+    table: &'static [(&'static str, V)],
+    /// Indices into `table`, so every keyword the input starts with is found in one walk
+    trie: SymbolTrie,
+}
+
+impl<V: Clone> KeywordsParser<V> {
+    pub fn new(table: &'static [(&'static str, V)]) -> Self {
+        let mut trie = SymbolTrie::new();
+        for (index, (keyword, _)) in table.iter().enumerate() {
+            trie.insert(keyword.as_bytes(), index);
+        }
+        KeywordsParser { table, trie }
+    }
+}
+
+/// Whether an identifier continues at `cursor`
+fn continues_word(cursor: ByteCursor<'_>) -> bool {
+    match cursor.value() {
+        Ok(byte) if byte.is_ascii() => byte.is_ascii_alphanumeric() || byte == b'_',
+        Ok(_) => matches!(char().parse(cursor), Ok((c, _)) if is_xid_continue(c)),
+        Err(_) => false,
+    }
+}
+
+impl<'code, V: Clone> Parser<'code> for KeywordsParser<V> {
+    type Cursor = ByteCursor<'code>;
+    type Output = V;
+    type Error = ParsicombError<'code>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (data, start) = cursor.inner();
+        let rest = &data[start..];
+
+        // The first keyword in table order wins among those the input starts with
+        let matched = self
+            .trie
+            .prefixes(rest)
+            .filter(|&(index, length)| {
+                let word_end = self.table[index]
+                    .0
+                    .chars()
+                    .last()
+                    .is_some_and(is_xid_continue);
+                !(word_end && continues_word(ByteCursor::at(data, start + length)))
+            })
+            .min_by_key(|&(index, _)| index);
+        if let Some((index, length)) = matched {
+            return Ok((
+                self.table[index].1.clone(),
+                ByteCursor::scanned(data, start, start + length)?,
+            ));
+        }
+
+        let expected = expected_keywords(self.table.iter().map(|(keyword, _)| *keyword));
+        Err(ParsicombError::SyntaxError {
+            message: format!("expected {}", expected).into(),
+            loc: CodeLoc::new(data, start),
+        })
+    }
+}
+
+/// Quote and join the keywords a parser expected, as in `'if', 'else' or 'while'`
+pub(crate) fn expected_keywords<'a>(keywords: impl Iterator<Item = &'a str>) -> String {
+    let quoted: Vec<String> = keywords.map(|keyword| format!("'{}'", keyword)).collect();
+    match quoted.len() {
+        0 => "keyword".to_string(),
+        _ => join_alternatives(quoted.iter().map(String::as_str)),
+    }
+}
+
+/// Creates a parser that matches any keyword of `table`, returning its value
+pub fn keywords<V: Clone>(table: &'static [(&'static str, V)]) -> KeywordsParser<V> {
+    KeywordsParser::new(table)
+}
+
+/// End of the identifier characters starting at `start`
+fn word_end(data: &[u8], start: usize) -> usize {
+    let mut end = start;
+    while let Some(&byte) = data.get(end) {
+        if byte.is_ascii() {
+            if !(byte.is_ascii_alphanumeric() || byte == b'_') {
+                break;
+            }
+            end += 1;
+            continue;
+        }
+        match char().parse(ByteCursor::at(data, end)) {
+            Ok((c, next)) if is_xid_continue(c) => end = next.position(),
+            _ => break,
+        }
+    }
+    end
+}
+
+/// Parser that matches any word of a set of identifier-like keywords
+///
+/// Unlike [`KeywordsParser`], which prefers keywords by table order, the word at the
+/// cursor is scanned once and looked up as a whole. Since a whole word is compared,
+/// `if` never matches the start of `iffy` and the order of the set doesn't matter.
+pub struct KeywordSet {
+    /// The keywords in the order they were given, for error messages
+    keywords: Vec<&'static str>,
+    /// Indices into `keywords` for looking up the scanned word
+    trie: SymbolTrie,
+}
+
+impl KeywordSet {
+    /// Panics if a keyword is empty or contains characters that can't be in an identifier
+    pub fn new(keywords: &[&'static str]) -> Self {
+        let mut unique = Vec::new();
+        let mut trie = SymbolTrie::new();
+        for &keyword in keywords {
+            assert!(
+                !keyword.is_empty() && word_end(keyword.as_bytes(), 0) == keyword.len(),
+                "'{}' is not an identifier-like keyword, use keywords() instead",
+                keyword
+            );
+            if trie.insert(keyword.as_bytes(), unique.len()) {
+                unique.push(keyword);
+            }
+        }
+        KeywordSet {
+            keywords: unique,
+            trie,
+        }
+    }
+}
+
+impl<'code> Parser<'code> for KeywordSet {
+    type Cursor = ByteCursor<'code>;
+    type Output = &'static str;
+    type Error = ParsicombError<'code>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (data, start) = cursor.inner();
+        let end = word_end(data, start);
+        let word = &data[start..end];
+
+        if let Some(index) = self.trie.get(word) {
+            return Ok((self.keywords[index], ByteCursor::scanned(data, start, end)?));
+        }
+
+        let expected = expected_keywords(self.keywords.iter().copied());
+        let found = match std::str::from_utf8(word) {
+            Ok(word) if !word.is_empty() => format!(", found '{}'", word),
+            _ => String::new(),
+        };
+        Err(ParsicombError::SyntaxError {
+            message: format!("expected {}{}", expected, found).into(),
+            loc: CodeLoc::new(data, start),
+        })
+    }
+
+    fn describe(&self) -> Description {
+        let mut alternatives = self
+            .keywords
+            .iter()
+            .map(|keyword| Description::terminal(format!("\"{}\"", keyword)));
+        match (alternatives.next(), alternatives.len()) {
+            (Some(first), 0) => first,
+            (first, _) => Description::Choice(first.into_iter().chain(alternatives).collect()),
+        }
+    }
+}
+
+/// Creates a parser that matches any keyword of `keywords` as a whole word
+///
+/// Panics if a keyword isn't made of identifier characters, use [`keywords`] for
+/// operators and other punctuation.
+///
+/// Example:
+/// ```
+/// use parsicomb::ByteCursor;
+/// use parsicomb::Parser;
+/// use parsicomb::utf8::keyword_set;
+///
+/// let keyword = keyword_set(&["if", "else", "while"]);
+/// let (word, _) = keyword.parse(ByteCursor::new(b"while x")).unwrap();
+/// assert_eq!(word, "while");
+/// assert!(keyword.parse(ByteCursor::new(b"iffy")).is_err());
+/// ```
+pub fn keyword_set(keywords: &[&'static str]) -> KeywordSet {
+    KeywordSet::new(keywords)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Op {
+        Arrow,
+        Minus,
+        In,
+        Instanceof,
+    }
+
+    const TABLE: &[(&str, Op)] = &[
+        ("instanceof", Op::Instanceof),
+        ("->", Op::Arrow),
+        ("in", Op::In),
+        ("-", Op::Minus),
+    ];
+
+    #[test]
+    fn test_keywords_match_longest_first() {
+        let parser = keywords(TABLE);
+
+        let (op, cursor) = parser.parse(ByteCursor::new(b"->x")).unwrap();
+        assert_eq!(op, Op::Arrow);
+        assert_eq!(cursor.position(), 2);
+
+        let (op, _) = parser.parse(ByteCursor::new(b"instanceof x")).unwrap();
+        assert_eq!(op, Op::Instanceof);
+
+        // Operators don't need a word boundary
+        let (op, _) = parser.parse(ByteCursor::new(b"-1")).unwrap();
+        assert_eq!(op, Op::Minus);
+    }
+
+    #[test]
+    fn test_keywords_respect_word_boundaries() {
+        let parser = keywords(TABLE);
+
+        let (op, cursor) = parser.parse(ByteCursor::new(b"in(")).unwrap();
+        assert_eq!(op, Op::In);
+        assert_eq!(cursor.position(), 2);
+
+        assert!(parser.parse(ByteCursor::new(b"inner")).is_err());
+        assert!(parser.parse(ByteCursor::new("iné".as_bytes())).is_err());
+        assert!(parser.parse(ByteCursor::new(b"in_")).is_err());
+    }
+
+    #[test]
+    fn test_keywords_prefer_table_order() {
+        const SHORT_FIRST: &[(&str, u8)] = &[("-", 1), ("->", 2)];
+        let (value, cursor) = keywords(SHORT_FIRST).parse(ByteCursor::new(b"->")).unwrap();
+        assert_eq!((value, cursor.position()), (1, 1));
+    }
+
+    #[test]
+    fn test_keywords_error_lists_table() {
+        let error = keywords(TABLE).parse(ByteCursor::new(b"x")).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("expected 'instanceof', '->', 'in' or '-'")
+        );
+    }
+
+    #[test]
+    fn test_keyword_set_matches_whole_words() {
+        let parser = keyword_set(&["while", "if", "else", "elif", "für"]);
+
+        for (input, expected, end) in [
+            ("if(x)", "if", 2),
+            ("elif x", "elif", 4),
+            ("else", "else", 4),
+            ("für x", "für", 4),
+        ] {
+            let (word, cursor) = parser.parse(ByteCursor::new(input.as_bytes())).unwrap();
+            assert_eq!((word, cursor.position()), (expected, end));
+        }
+
+        assert!(parser.parse(ByteCursor::new(b"iffy")).is_err());
+        assert!(parser.parse(ByteCursor::new(b"el")).is_err());
+        assert!(parser.parse(ByteCursor::new("füré".as_bytes())).is_err());
+    }
+
+    #[test]
+    fn test_keyword_set_errors() {
+        let parser = keyword_set(&["if", "else", "if"]);
+
+        let error = parser.parse(ByteCursor::new(b"iffy")).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("expected 'if' or 'else', found 'iffy'")
+        );
+        let error = parser.parse(ByteCursor::new(b"(")).unwrap_err();
+        assert!(error.to_string().contains("expected 'if' or 'else'"));
+        assert!(!error.to_string().contains("found"));
+
+        assert_eq!(
+            parser.describe().to_ebnf(),
+            "grammar ::= \"if\" | \"else\"\n"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "not an identifier-like keyword")]
+    fn test_keyword_set_rejects_operators() {
+        keyword_set(&["if", "->"]);
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn test_derive_keywords() {
+        use crate::Keywords;
+
+        #[derive(Debug, Clone, Copy, PartialEq, Keywords)]
+        enum Keyword {
+            If,
+            Else,
+            #[keyword = "elif"]
+            ElseIf,
+            While,
+        }
+
+        let parser = Keyword::keywords();
+        let (keyword, _) = parser.parse(ByteCursor::new(b"elif x")).unwrap();
+        assert_eq!(keyword, Keyword::ElseIf);
+        let (keyword, _) = parser.parse(ByteCursor::new(b"while")).unwrap();
+        assert_eq!(keyword, Keyword::While);
+        let (keyword, _) = parser.parse(ByteCursor::new(b"if(")).unwrap();
+        assert_eq!(keyword, Keyword::If);
+        assert!(parser.parse(ByteCursor::new(b"elsewhere")).is_err());
+
+        // Longest first, ties in declaration order
+        assert_eq!(
+            Keyword::KEYWORDS
+                .iter()
+                .map(|(k, _)| *k)
+                .collect::<Vec<_>>(),
+            ["while", "else", "elif", "if"]
+        );
+    }
+}
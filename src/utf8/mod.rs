@@ -1,13 +1,33 @@
 pub mod alphanumeric;
 pub mod char;
+pub mod comment;
 pub mod digit;
+#[cfg(feature = "unicode-segmentation")]
+pub mod grapheme;
+pub mod identifier;
+pub mod interpolated;
+pub mod keyword;
 pub mod letter;
+#[cfg(feature = "unicode-normalization")]
+pub mod normalize;
+pub mod quoted;
 pub mod string;
+pub mod take_until_string;
 pub mod whitespace;
 
 pub use alphanumeric::unicode_alphanumeric;
 pub use char::char;
+pub use comment::{block_comment, line_comment};
 pub use digit::unicode_digit;
+#[cfg(feature = "unicode-segmentation")]
+pub use grapheme::grapheme;
+pub use identifier::{IdentifierConfig, identifier, identifier_with};
+pub use interpolated::{InterpolatedString, StringPart, interpolated_string};
+pub use keyword::{KeywordSet, KeywordsParser, keyword_set, keywords};
 pub use letter::unicode_letter;
-pub use string::is_string;
+#[cfg(feature = "unicode-normalization")]
+pub use normalize::{NfcSource, nfc};
+pub use quoted::{EscapePolicy, quoted};
+pub use string::{CaseFolding, is_string, is_string_ci, is_string_ci_with};
+pub use take_until_string::take_until_string;
 pub use whitespace::unicode_whitespace;
@@ -0,0 +1,150 @@
+use crate::position::Span;
+use unicode_normalization::UnicodeNormalization;
+use unicode_normalization::char::{canonical_combining_class, compose};
+
+// # NFC Normalization
+//
+// The same text can be encoded in several ways, e.g. `é` as U+00E9 or as `e`
+// followed by U+0301. Parsers compare bytes, so `is_string("café")` only matches one
+// of them. Normalizing the source to NFC before parsing makes both spellings equal,
+// with expected strings written in NFC as well (which is how editors usually save).
+//
+// The source is normalized in segments that never compose with each other, a starter
+// and the marks that follow it. Every normalized byte maps back to the start of its
+// segment in the original, so reported positions point at the original text.
+
+/// A source normalized to NFC together with a map back to the original byte offsets
+#[derive(Debug, Clone)]
+pub struct NfcSource {
+    text: String,
+    offsets: Vec<usize>,
+}
+
+impl NfcSource {
+    /// The normalized text, parse this instead of the original
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// The normalized text as bytes, for use with `ByteCursor::new`
+    pub fn as_bytes(&self) -> &[u8] {
+        self.text.as_bytes()
+    }
+
+    /// Byte offset in the original source for a byte offset in the normalized text
+    ///
+    /// Offsets inside a composed character map to the start of its segment, offsets
+    /// past the end map to the end of the original.
+    pub fn original_position(&self, position: usize) -> usize {
+        self.offsets[position.min(self.text.len())]
+    }
+
+    /// Translate a span over the normalized text into a span over the original source
+    pub fn original_span<'code>(&self, original: &'code [u8], span: Span<'_>) -> Span<'code> {
+        Span::new(
+            original,
+            self.original_position(span.start),
+            self.original_position(span.end),
+        )
+    }
+}
+
+/// Normalize `source` to NFC, keeping a map from normalized to original positions
+pub fn nfc(source: &str) -> NfcSource {
+    let mut text = String::with_capacity(source.len());
+    let mut offsets = Vec::with_capacity(source.len() + 1);
+
+    let mut push_segment = |segment: &str, start: usize, text: &mut String| {
+        let before = text.len();
+        text.extend(segment.nfc());
+        offsets.extend(std::iter::repeat_n(start, text.len() - before));
+    };
+
+    let mut segment_start = 0;
+    let mut last = None;
+    for (index, c) in source.char_indices() {
+        // A new segment starts at a starter that does not compose with what came before
+        let starts_segment =
+            canonical_combining_class(c) == 0 && last.is_none_or(|last| compose(last, c).is_none());
+        if starts_segment && index > segment_start {
+            push_segment(&source[segment_start..index], segment_start, &mut text);
+            segment_start = index;
+            last = Some(c);
+        } else {
+            last = source[segment_start..index + c.len_utf8()].nfc().last();
+        }
+    }
+    push_segment(&source[segment_start..], segment_start, &mut text);
+    offsets.push(source.len());
+
+    NfcSource { text, offsets }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ByteCursor;
+    use crate::Parser;
+    use crate::error::ErrorLeaf;
+    use crate::position::PositionExt;
+    use crate::utf8::string::is_string;
+
+    #[test]
+    fn test_decomposed_source_matches() {
+        let source = "cafe\u{301} = 1";
+        assert!(
+            is_string("café")
+                .parse(ByteCursor::new(source.as_bytes()))
+                .is_err()
+        );
+
+        let normalized = nfc(source);
+        assert_eq!(normalized.as_str(), "café = 1");
+
+        let parser = is_string("café").map_with_span(|_, span| span);
+        let (span, _) = parser
+            .parse(ByteCursor::new(normalized.as_bytes()))
+            .unwrap();
+        let original = normalized.original_span(source.as_bytes(), span);
+        assert_eq!(original.as_string(), "cafe\u{301}");
+    }
+
+    #[test]
+    fn test_positions_map_to_original() {
+        let source = "a\u{301}b x";
+        let normalized = nfc(source);
+        assert_eq!(normalized.as_str(), "áb x");
+
+        // Both bytes of `á` map to the start of `a`
+        assert_eq!(normalized.original_position(0), 0);
+        assert_eq!(normalized.original_position(1), 0);
+        assert_eq!(normalized.original_position(2), 3);
+        assert_eq!(normalized.original_position(4), 5);
+        assert_eq!(normalized.original_position(100), source.len());
+
+        let data = normalized.as_bytes();
+        let error = is_string("áb y").parse(ByteCursor::new(data)).unwrap_err();
+        assert_eq!(normalized.original_position(error.loc().position()), 5);
+    }
+
+    #[test]
+    fn test_reorders_marks_and_composes_hangul() {
+        // Marks in non-canonical order are sorted before composing
+        let source = "q\u{307}\u{323}";
+        assert_eq!(nfc(source).as_str(), source.nfc().collect::<String>());
+
+        // Conjoining jamo compose across starters
+        let source = "\u{1100}\u{1161}\u{11A8}";
+        assert_eq!(nfc(source).as_str(), "\u{AC01}");
+    }
+
+    #[test]
+    fn test_already_normalized_is_unchanged() {
+        let source = "température 变量 🚀";
+        let normalized = nfc(source);
+        assert_eq!(normalized.as_str(), source);
+        for (position, _) in source.char_indices() {
+            assert_eq!(normalized.original_position(position), position);
+        }
+    }
+}
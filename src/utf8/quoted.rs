@@ -0,0 +1,318 @@
+use crate::ByteCursor;
+use crate::Cursor;
+use crate::parser::Parser;
+use crate::position::{Span, Spanned};
+use crate::{CodeLoc, ParsicombError};
+use std::borrow::Cow;
+
+/// How a quoted string lets its content contain the closing quote
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EscapePolicy {
+    /// No escapes, the content is taken verbatim up to the first closing quote
+    Raw,
+    /// The given character starts an escape sequence
+    ///
+    /// Recognised sequences are `n`, `r`, `t`, `0`, `u{XXXX}`, the escape
+    /// character itself and the first character of either quote.
+    Escape(char),
+    /// The closing quote written twice stands for one closing quote, as in SQL
+    Doubled,
+}
+
+/// Parser for a string delimited by custom quotes, decoding escapes
+///
+/// The output is the decoded content, borrowed from the input when it contains no
+/// escapes, together with the span of the whole literal including its quotes.
+/// Multi-character quotes such as `"""` give triple-quoted strings and
+/// `EscapePolicy::Raw` gives raw strings. Fails with
+/// `ParsicombError::UnterminatedConstruct` if the input ends before the closing quote.
+pub struct QuotedParser {
+    open: Cow<'static, str>,
+    close: Cow<'static, str>,
+    policy: EscapePolicy,
+}
+
+impl QuotedParser {
+    pub fn new(
+        open: impl Into<Cow<'static, str>>,
+        close: impl Into<Cow<'static, str>>,
+        policy: EscapePolicy,
+    ) -> Self {
+        QuotedParser {
+            open: open.into(),
+            close: close.into(),
+            policy,
+        }
+    }
+
+    fn error<'code>(
+        data: &'code [u8],
+        position: usize,
+        message: impl Into<Cow<'static, str>>,
+    ) -> ParsicombError<'code> {
+        ParsicombError::SyntaxError {
+            message: message.into(),
+            loc: CodeLoc::new(data, position),
+        }
+    }
+
+    /// Decode the escape sequence after the escape character at `position`,
+    /// returning the character and the length of the sequence after the escape character
+    fn escape<'code>(
+        &self,
+        data: &'code [u8],
+        position: usize,
+        escape: char,
+    ) -> Result<(char, usize), ParsicombError<'code>> {
+        let rest = &data[position..];
+        let Some(next) = std::str::from_utf8(&rest[..rest.len().min(4)])
+            .or_else(|e| std::str::from_utf8(&rest[..e.valid_up_to()]))
+            .ok()
+            .and_then(|text| text.chars().next())
+        else {
+            return Err(Self::error(data, position, "invalid escape sequence"));
+        };
+
+        let decoded = match next {
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            '0' => '\0',
+            'u' => return Self::unicode_escape(data, position),
+            c if c == escape || self.open.starts_with(c) || self.close.starts_with(c) => c,
+            _ => return Err(Self::error(data, position, "invalid escape sequence")),
+        };
+        Ok((decoded, next.len_utf8()))
+    }
+
+    /// Decode a `u{XXXX}` escape starting at `position`
+    fn unicode_escape(data: &[u8], position: usize) -> Result<(char, usize), ParsicombError<'_>> {
+        let invalid = || Self::error(data, position, "invalid unicode escape");
+        if data.get(position + 1) != Some(&b'{') {
+            return Err(invalid());
+        }
+        let digits_start = position + 2;
+        let digits_length = data[digits_start..]
+            .iter()
+            .take(7)
+            .position(|&b| b == b'}')
+            .ok_or_else(invalid)?;
+        let digits = &data[digits_start..digits_start + digits_length];
+        if digits.is_empty() || !digits.iter().all(u8::is_ascii_hexdigit) {
+            return Err(invalid());
+        }
+        // Hex digits are ASCII, so the slice is valid UTF-8
+        let value =
+            u32::from_str_radix(std::str::from_utf8(digits).unwrap(), 16).map_err(|_| invalid())?;
+        char::from_u32(value)
+            .map(|c| (c, digits_length + 3))
+            .ok_or_else(invalid)
+    }
+}
+
+impl<'code> Parser<'code> for QuotedParser {
+    type Cursor = ByteCursor<'code>;
+    type Output = Spanned<'code, Cow<'code, str>>;
+    type Error = ParsicombError<'code>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (data, start) = cursor.inner();
+        if !data[start..].starts_with(self.open.as_bytes()) {
+            return Err(Self::error(
+                data,
+                start,
+                format!("expected '{}'", self.open),
+            ));
+        }
+
+        let close = self.close.as_bytes();
+        let body_start = start + self.open.len();
+        let mut position = body_start;
+        let mut decoded: Option<String> = None;
+        // Start of the input not yet copied into `decoded`
+        let mut pending = body_start;
+
+        let utf8 = |from: usize, to: usize| {
+            std::str::from_utf8(&data[from..to])
+                .map_err(|e| Self::error(data, from + e.valid_up_to(), "invalid UTF-8 in string"))
+        };
+
+        loop {
+            if position >= data.len() {
+                return Err(ParsicombError::UnterminatedConstruct {
+                    terminator: self.close.clone(),
+                    span: Span::new(data, start, data.len()),
+                });
+            }
+
+            let rest = &data[position..];
+            if rest.starts_with(close) {
+                let doubled = self.policy == EscapePolicy::Doubled
+                    && !close.is_empty()
+                    && rest[close.len()..].starts_with(close);
+                if !doubled {
+                    break;
+                }
+                let text = decoded.get_or_insert_with(String::new);
+                text.push_str(utf8(pending, position)?);
+                text.push_str(&self.close);
+                position += 2 * close.len();
+                pending = position;
+                continue;
+            }
+
+            if let EscapePolicy::Escape(escape) = self.policy {
+                let mut buffer = [0; 4];
+                let marker = escape.encode_utf8(&mut buffer).as_bytes();
+                if rest.starts_with(marker) {
+                    let (c, length) = self.escape(data, position + marker.len(), escape)?;
+                    let text = decoded.get_or_insert_with(String::new);
+                    text.push_str(utf8(pending, position)?);
+                    text.push(c);
+                    position += marker.len() + length;
+                    pending = position;
+                    continue;
+                }
+            }
+
+            position += 1;
+        }
+
+        let rest = utf8(pending, position)?;
+        let content = match decoded {
+            Some(mut text) => {
+                text.push_str(rest);
+                Cow::Owned(text)
+            }
+            None => Cow::Borrowed(rest),
+        };
+
+        let end = position + close.len();
+        Ok((
+            Spanned::new(content, Span::new(data, start, end)),
+            ByteCursor::scanned(data, start, end)?,
+        ))
+    }
+}
+
+/// Creates a parser for a string between `open_quote` and `close_quote`
+///
+/// Example:
+/// ```
+/// use parsicomb::utf8::quoted::{EscapePolicy, quoted};
+/// use parsicomb::{ByteCursor, Parser};
+///
+/// let single = quoted("'", "'", EscapePolicy::Escape('\\'));
+/// let (literal, _) = single.parse(ByteCursor::new(br"'it\'s'")).unwrap();
+/// assert_eq!(literal.value, "it's");
+/// assert_eq!(literal.span.len(), 7);
+///
+/// let raw = quoted("r#\"", "\"#", EscapePolicy::Raw);
+/// let (literal, _) = raw.parse(ByteCursor::new(br##"r#"C:\dir\"#"##)).unwrap();
+/// assert_eq!(literal.value, r"C:\dir\");
+/// ```
+pub fn quoted(
+    open_quote: impl Into<Cow<'static, str>>,
+    close_quote: impl Into<Cow<'static, str>>,
+    escape_policy: EscapePolicy,
+) -> QuotedParser {
+    QuotedParser::new(open_quote, close_quote, escape_policy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorLeaf;
+
+    #[test]
+    fn test_borrows_content_without_escapes() {
+        let data = b"'hello' rest";
+        let parser = quoted("'", "'", EscapePolicy::Escape('\\'));
+
+        let (literal, cursor) = parser.parse(ByteCursor::new(data)).unwrap();
+        assert!(matches!(literal.value, Cow::Borrowed("hello")));
+        assert_eq!((literal.span.start, literal.span.end), (0, 7));
+        assert_eq!(cursor.position(), 7);
+    }
+
+    #[test]
+    fn test_decodes_escapes() {
+        let data = br#""a\"b\\c\n\u{1F600}""#;
+        let parser = quoted("\"", "\"", EscapePolicy::Escape('\\'));
+
+        let (literal, cursor) = parser.parse(ByteCursor::new(data)).unwrap();
+        assert_eq!(literal.value, "a\"b\\c\n\u{1F600}");
+        assert!(cursor.eos());
+    }
+
+    #[test]
+    fn test_custom_escape_character() {
+        let data = b"`a$`b$$`";
+        let parser = quoted("`", "`", EscapePolicy::Escape('$'));
+
+        let (literal, _) = parser.parse(ByteCursor::new(data)).unwrap();
+        assert_eq!(literal.value, "a`b$");
+    }
+
+    #[test]
+    fn test_invalid_escape() {
+        let data = br#""a\qb""#;
+        let parser = quoted("\"", "\"", EscapePolicy::Escape('\\'));
+
+        let error = parser.parse(ByteCursor::new(data)).unwrap_err();
+        assert_eq!(error.loc().position(), 3);
+        assert!(error.message().contains("invalid escape"));
+    }
+
+    #[test]
+    fn test_triple_quotes_span_lines() {
+        let data = b"\"\"\"line \"one\"\nline two\"\"\"";
+        let parser = quoted("\"\"\"", "\"\"\"", EscapePolicy::Escape('\\'));
+
+        let (literal, cursor) = parser.parse(ByteCursor::new(data)).unwrap();
+        assert_eq!(literal.value, "line \"one\"\nline two");
+        assert!(cursor.eos());
+    }
+
+    #[test]
+    fn test_raw_keeps_backslashes() {
+        let data = br#"r"a\nb" "#;
+        let parser = quoted("r\"", "\"", EscapePolicy::Raw);
+
+        let (literal, cursor) = parser.parse(ByteCursor::new(data)).unwrap();
+        assert_eq!(literal.value, r"a\nb");
+        assert_eq!(cursor.position(), 7);
+    }
+
+    #[test]
+    fn test_doubled_quotes() {
+        let data = b"'it''s' x";
+        let parser = quoted("'", "'", EscapePolicy::Doubled);
+
+        let (literal, cursor) = parser.parse(ByteCursor::new(data)).unwrap();
+        assert_eq!(literal.value, "it's");
+        assert_eq!(cursor.position(), 7);
+    }
+
+    #[test]
+    fn test_unterminated() {
+        let data = b"'abc";
+        let parser = quoted("'", "'", EscapePolicy::Escape('\\'));
+
+        match parser.parse(ByteCursor::new(data)).unwrap_err() {
+            ParsicombError::UnterminatedConstruct { terminator, span } => {
+                assert_eq!(terminator, "'");
+                assert_eq!((span.start, span.end), (0, 4));
+            }
+            other => panic!("unexpected error: {other}"),
+        }
+    }
+
+    #[test]
+    fn test_missing_open_quote() {
+        let parser = quoted("'", "'", EscapePolicy::Raw);
+
+        let error = parser.parse(ByteCursor::new(b"abc")).unwrap_err();
+        assert_eq!(error.loc().position(), 0);
+    }
+}
@@ -1,5 +1,6 @@
 use crate::ByteCursor;
 use crate::Cursor;
+use crate::describe::Description;
 use crate::parser::Parser;
 use crate::utf8::char::char;
 use crate::{CodeLoc, ParsicombError};
@@ -17,7 +18,10 @@ fn create_string_error<'code>(
     }
 }
 
-/// Parser that matches an exact UTF-8 string character by character
+/// Parser that matches an exact UTF-8 string
+///
+/// The bytes of the expected string are compared directly against the input.
+/// Characters are only decoded to describe a mismatch.
 pub struct IsStringParser {
     expected: Cow<'static, str>,
 }
@@ -28,6 +32,47 @@ impl IsStringParser {
             expected: expected.into(),
         }
     }
+
+    /// Build the error for input that matched the first `matched` bytes
+    fn mismatch<'code>(
+        &self,
+        data: &'code [u8],
+        start: usize,
+        matched: usize,
+    ) -> ParsicombError<'code> {
+        // Describe the mismatch in terms of the character containing the bad byte
+        let char_start = (0..=matched)
+            .rev()
+            .find(|&i| self.expected.is_char_boundary(i))
+            .unwrap_or(0);
+        let expected_char = self.expected[char_start..]
+            .chars()
+            .next()
+            .unwrap_or_default();
+
+        let message = if start + matched >= data.len() {
+            format!(
+                "expected '{}', but reached end of input while matching '{}'",
+                expected_char, self.expected
+            )
+        } else {
+            match char().parse(ByteCursor::at(data, start + char_start)) {
+                Ok((parsed_char, _)) => format!(
+                    "expected '{}', found '{}' while matching '{}'",
+                    expected_char, parsed_char, self.expected
+                ),
+                Err(_) => format!(
+                    "expected '{}', found invalid UTF-8 while matching '{}'",
+                    expected_char, self.expected
+                ),
+            }
+        };
+
+        ParsicombError::SyntaxError {
+            message: message.into(),
+            loc: CodeLoc::new(data, start + matched),
+        }
+    }
 }
 
 impl<'code> Parser<'code> for IsStringParser {
@@ -36,18 +81,135 @@ impl<'code> Parser<'code> for IsStringParser {
     type Error = ParsicombError<'code>;
 
     fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (data, start) = cursor.inner();
+        let expected = self.expected.as_bytes();
+
+        if data[start..].starts_with(expected) {
+            // Clone is cheap here - just copies the reference for &'static str
+            return Ok((
+                self.expected.clone(),
+                ByteCursor::scanned(data, start, start + expected.len())?,
+            ));
+        }
+
+        let matched = expected
+            .iter()
+            .zip(&data[start..])
+            .take_while(|(expected, found)| expected == found)
+            .count();
+        Err(self.mismatch(data, start, matched))
+    }
+
+    fn describe(&self) -> Description {
+        Description::terminal(format!("\"{}\"", self.expected.escape_default()))
+    }
+}
+
+/// Convenience function to create an IsStringParser
+pub fn is_string(expected: impl Into<Cow<'static, str>>) -> IsStringParser {
+    IsStringParser::new(expected)
+}
+
+/// How characters are compared by [`IsStringCiParser`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CaseFolding {
+    /// Only ASCII letters are compared case-insensitively
+    #[default]
+    Ascii,
+    /// Characters are compared by Unicode simple case folding, so final sigma `ς`
+    /// matches `σ` and `Σ`
+    Unicode,
+}
+
+impl CaseFolding {
+    fn matches(self, expected: char, parsed: char) -> bool {
+        match self {
+            CaseFolding::Ascii => expected.eq_ignore_ascii_case(&parsed),
+            CaseFolding::Unicode => {
+                expected == parsed || simple_fold(expected) == simple_fold(parsed)
+            }
+        }
+    }
+}
+
+/// Unicode simple case folding of a single character
+///
+/// Folding mostly agrees with lowercasing. The exceptions are the characters that
+/// fold to a different lowercase letter, and characters whose lowercase mapping is
+/// longer than one character, which simple folding leaves unchanged.
+fn simple_fold(c: char) -> char {
+    match c {
+        '\u{00B5}' => '\u{03BC}', // MICRO SIGN
+        '\u{017F}' => 's',        // LATIN SMALL LETTER LONG S
+        '\u{0345}' | '\u{1FBE}' => '\u{03B9}',
+        '\u{03C2}' => '\u{03C3}', // GREEK SMALL LETTER FINAL SIGMA
+        '\u{03D0}' => '\u{03B2}',
+        '\u{03D1}' => '\u{03B8}',
+        '\u{03D5}' => '\u{03C6}',
+        '\u{03D6}' => '\u{03C0}',
+        '\u{03F0}' => '\u{03BA}',
+        '\u{03F1}' => '\u{03C1}',
+        '\u{03F5}' => '\u{03B5}',
+        '\u{1C80}' => '\u{0432}',
+        '\u{1C81}' => '\u{0434}',
+        '\u{1C82}' => '\u{043E}',
+        '\u{1C83}' => '\u{0441}',
+        '\u{1C84}' | '\u{1C85}' => '\u{0442}',
+        '\u{1C86}' => '\u{044A}',
+        '\u{1C87}' => '\u{0463}',
+        '\u{1C88}' => '\u{A64B}',
+        '\u{1E9B}' => '\u{1E61}',
+        '\u{1FD3}' => '\u{0390}',
+        '\u{1FE3}' => '\u{03B0}',
+        '\u{FB05}' => '\u{FB06}',
+        _ => {
+            let mut lower = c.to_lowercase();
+            match (lower.next(), lower.next()) {
+                (Some(folded), None) => folded,
+                _ => c,
+            }
+        }
+    }
+}
+
+/// Parser that matches a UTF-8 string ignoring case
+///
+/// Returns the matched slice of the input, so `is_string_ci("select")` on
+/// `SeLeCt` yields `"SeLeCt"`. Matching is character by character, so foldings
+/// that change the length of a string (such as `ß` and `ss`) are not applied.
+pub struct IsStringCiParser {
+    expected: Cow<'static, str>,
+    folding: CaseFolding,
+}
+
+impl IsStringCiParser {
+    pub fn new(expected: impl Into<Cow<'static, str>>, folding: CaseFolding) -> Self {
+        Self {
+            expected: expected.into(),
+            folding,
+        }
+    }
+}
+
+impl<'code> Parser<'code> for IsStringCiParser {
+    type Cursor = ByteCursor<'code>;
+    type Output = &'code str;
+    type Error = ParsicombError<'code>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (data, start) = cursor.inner();
         let mut current_cursor = cursor;
 
         for expected_char in self.expected.chars() {
             match char().parse(current_cursor) {
                 Ok((parsed_char, new_cursor)) => {
-                    if parsed_char == expected_char {
+                    if self.folding.matches(expected_char, parsed_char) {
                         current_cursor = new_cursor;
                     } else {
                         return Err(create_string_error(
                             &current_cursor,
                             format!(
-                                "expected '{}', found '{}' while matching '{}'",
+                                "expected '{}', found '{}' while matching '{}' ignoring case",
                                 expected_char, parsed_char, self.expected
                             ),
                         ));
@@ -57,7 +219,7 @@ impl<'code> Parser<'code> for IsStringParser {
                     return Err(create_string_error(
                         &current_cursor,
                         format!(
-                            "expected '{}', but reached end of input while matching '{}'",
+                            "expected '{}', but reached end of input while matching '{}' ignoring case",
                             expected_char, self.expected
                         ),
                     ));
@@ -65,14 +227,26 @@ impl<'code> Parser<'code> for IsStringParser {
             }
         }
 
-        // Clone is cheap here - just copies the reference for &'static str
-        Ok((self.expected.clone(), current_cursor))
+        // Every character was decoded by char(), so the slice is valid UTF-8
+        let end = current_cursor.position();
+        let matched = std::str::from_utf8(&data[start..end])
+            .map_err(|_| create_string_error(&cursor, "invalid UTF-8 in input".to_string()))?;
+
+        Ok((matched, current_cursor))
     }
 }
 
-/// Convenience function to create an IsStringParser
-pub fn is_string(expected: impl Into<Cow<'static, str>>) -> IsStringParser {
-    IsStringParser::new(expected)
+/// Convenience function to create an ASCII case-insensitive IsStringCiParser
+pub fn is_string_ci(expected: impl Into<Cow<'static, str>>) -> IsStringCiParser {
+    IsStringCiParser::new(expected, CaseFolding::Ascii)
+}
+
+/// Convenience function to create an IsStringCiParser with the given case folding
+pub fn is_string_ci_with(
+    expected: impl Into<Cow<'static, str>>,
+    folding: CaseFolding,
+) -> IsStringCiParser {
+    IsStringCiParser::new(expected, folding)
 }
 
 #[cfg(test)]
@@ -285,4 +459,105 @@ mod tests {
         let (next_char, _) = char().parse(remaining_cursor).unwrap();
         assert_eq!(next_char, '🔥');
     }
+
+    #[test]
+    fn test_ci_returns_matched_slice() {
+        let data = b"SeLeCt * FROM t";
+        let cursor = ByteCursor::new(data);
+        let parser = is_string_ci("select");
+
+        let (result, cursor) = parser.parse(cursor).unwrap();
+        assert_eq!(result, "SeLeCt");
+        assert_eq!(cursor.position(), 6);
+    }
+
+    #[test]
+    fn test_ci_mismatch() {
+        let data = b"selext";
+        let cursor = ByteCursor::new(data);
+
+        let error = is_string_ci("select").parse(cursor).unwrap_err();
+        assert_eq!(error.position(), 4);
+        assert!(error.to_string().contains("expected 'c', found 'x'"));
+    }
+
+    #[test]
+    fn test_ci_insufficient_input() {
+        let data = b"SEL";
+        let cursor = ByteCursor::new(data);
+
+        let result = is_string_ci("select").parse(cursor);
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("reached end of input")
+        );
+    }
+
+    #[test]
+    fn test_ci_ascii_folding_is_exact_for_non_ascii() {
+        let data = "ÜBER".as_bytes();
+        let cursor = ByteCursor::new(data);
+
+        assert!(is_string_ci("über").parse(cursor).is_err());
+        assert!(is_string_ci("Über").parse(cursor).is_ok());
+    }
+
+    #[test]
+    fn test_ci_unicode_folding() {
+        let data = "ÜBER straße".as_bytes();
+        let cursor = ByteCursor::new(data);
+        let parser = is_string_ci_with("über", CaseFolding::Unicode);
+
+        let (result, _) = parser.parse(cursor).unwrap();
+        assert_eq!(result, "ÜBER");
+
+        let data = "ΣΟΦΟΣ".as_bytes();
+        let parser = is_string_ci_with("σοφοσ", CaseFolding::Unicode);
+        let (result, _) = parser.parse(ByteCursor::new(data)).unwrap();
+        assert_eq!(result, "ΣΟΦΟΣ");
+    }
+
+    #[test]
+    fn test_ci_unicode_folding_beyond_lowercase() {
+        // Final sigma only folds to σ, it is not the lowercase of Σ
+        let parser = is_string_ci_with("σοφος", CaseFolding::Unicode);
+        let (result, _) = parser.parse(ByteCursor::new("ΣΟΦΟΣ".as_bytes())).unwrap();
+        assert_eq!(result, "ΣΟΦΟΣ");
+        assert!(parser.parse(ByteCursor::new("σοφοσ".as_bytes())).is_ok());
+
+        let parser = is_string_ci_with("ſ µ", CaseFolding::Unicode);
+        assert!(parser.parse(ByteCursor::new("S Μ".as_bytes())).is_ok());
+
+        // İ lowercases to two characters, so simple folding leaves it alone
+        let parser = is_string_ci_with("i", CaseFolding::Unicode);
+        assert!(parser.parse(ByteCursor::new("İ".as_bytes())).is_err());
+    }
+
+    #[test]
+    fn test_mismatch_pinpoints_byte() {
+        // 'é' and 'è' share their first UTF-8 byte
+        let data = "caè".as_bytes();
+        let error = is_string("caé").parse(ByteCursor::new(data)).unwrap_err();
+        assert_eq!(error.position(), 3);
+        assert!(error.to_string().contains("expected 'é', found 'è'"));
+
+        let data = b"ca\xffz";
+        let error = is_string("cat").parse(ByteCursor::new(data)).unwrap_err();
+        assert_eq!(error.position(), 2);
+        assert!(error.to_string().contains("found invalid UTF-8"));
+    }
+
+    #[test]
+    fn test_match_at_end_of_input() {
+        let data = b"let";
+        let (_, cursor) = is_string("let").parse(ByteCursor::new(data)).unwrap();
+        assert!(cursor.eos());
+
+        let error = is_string("letter")
+            .parse(ByteCursor::new(data))
+            .unwrap_err();
+        assert_eq!(error.position(), 3);
+    }
 }
@@ -0,0 +1,110 @@
+use crate::ByteCursor;
+use crate::Cursor;
+use crate::parser::Parser;
+use crate::position::Span;
+use crate::{CodeLoc, ParsicombError};
+use std::borrow::Cow;
+
+/// Parser that consumes everything up to, but not including, a terminator
+///
+/// The terminator is found with `memchr::memmem`, so long bodies such as comments
+/// or raw strings are scanned in bulk and in linear time. Fails with
+/// `ParsicombError::UnterminatedConstruct` if the input ends first.
+pub struct TakeUntilStringParser {
+    terminator: Cow<'static, str>,
+}
+
+impl TakeUntilStringParser {
+    pub fn new(terminator: impl Into<Cow<'static, str>>) -> Self {
+        TakeUntilStringParser {
+            terminator: terminator.into(),
+        }
+    }
+
+    /// Offset of the first occurrence of the terminator in `haystack`
+    fn find(&self, haystack: &[u8]) -> Option<usize> {
+        memchr::memmem::find(haystack, self.terminator.as_bytes())
+    }
+}
+
+impl<'code> Parser<'code> for TakeUntilStringParser {
+    type Cursor = ByteCursor<'code>;
+    type Output = &'code str;
+    type Error = ParsicombError<'code>;
+
+    fn parse(&self, cursor: Self::Cursor) -> Result<(Self::Output, Self::Cursor), Self::Error> {
+        let (data, start) = cursor.inner();
+
+        let Some(length) = self.find(&data[start..]) else {
+            ByteCursor::charge(data, start, data.len())?;
+            return Err(ParsicombError::UnterminatedConstruct {
+                terminator: self.terminator.clone(),
+                span: Span::new(data, start, data.len()),
+            });
+        };
+
+        let end = start + length;
+        ByteCursor::charge(data, start, end)?;
+        let text =
+            std::str::from_utf8(&data[start..end]).map_err(|e| ParsicombError::SyntaxError {
+                message: "invalid UTF-8".into(),
+                loc: CodeLoc::new(data, start + e.valid_up_to()),
+            })?;
+
+        Ok((text, ByteCursor::at(data, end)))
+    }
+}
+
+/// Creates a parser that consumes input up to the next `terminator`
+pub fn take_until_string(terminator: impl Into<Cow<'static, str>>) -> TakeUntilStringParser {
+    TakeUntilStringParser::new(terminator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorLeaf;
+
+    #[test]
+    fn test_stops_before_terminator() {
+        let data = b"a * b / c */ rest";
+        let (body, cursor) = take_until_string("*/")
+            .parse(ByteCursor::new(data))
+            .unwrap();
+
+        assert_eq!(body, "a * b / c ");
+        assert_eq!(cursor.position(), 10);
+        assert_eq!(cursor.value().unwrap(), b'*');
+    }
+
+    #[test]
+    fn test_empty_body_and_unicode() {
+        let (body, cursor) = take_until_string("]]")
+            .parse(ByteCursor::new(b"]]"))
+            .unwrap();
+        assert_eq!(body, "");
+        assert_eq!(cursor.position(), 0);
+
+        let data = "høj → ende-->".as_bytes();
+        let (body, _) = take_until_string("-->")
+            .parse(ByteCursor::new(data))
+            .unwrap();
+        assert_eq!(body, "høj → ende");
+    }
+
+    #[test]
+    fn test_unterminated() {
+        let data = b"x /* never closed *";
+        let cursor = ByteCursor::at(data, 5);
+
+        let error = take_until_string("*/").parse(cursor).unwrap_err();
+        match &error {
+            ParsicombError::UnterminatedConstruct { terminator, span } => {
+                assert_eq!(terminator, "*/");
+                assert_eq!((span.start, span.end), (5, data.len()));
+            }
+            other => panic!("expected unterminated construct, got {:?}", other),
+        }
+        assert_eq!(error.message(), "expected '*/' before end of input");
+    }
+}
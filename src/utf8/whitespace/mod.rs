@@ -2,58 +2,40 @@
 //!
 //! This module provides basic Unicode whitespace parsing functionality.
 //!
-//! ## Why No Generic Whitespace Combinators?
+//! ## Whitespace Around Other Combinators
 //!
-//! You might expect to find generic whitespace-aware combinators here like
-//! `whitespace_between()`, `whitespace_separated_list()`, etc. We intentionally
-//! don't provide these because they create two significant problems:
+//! Whitespace and other trivia between tokens is handled by the padded variants
+//! of the structural combinators, which take the trivia parser as an argument:
 //!
-//! ### 1. Poor Error Location Reporting
+//! - `between_padded(open, pad, content, close)`
+//! - `separated_list_padded(item, separator, pad)`
+//! - `separated_pair_padded(left, separator, right, pad)`
 //!
-//! Generic whitespace combinators report errors at the wrong location:
+//! The padding can be `unicode_whitespace()`, `ascii::take_whitespace()`, a comment
+//! parser, or an `or` of several. On cursors other than bytes, `space::space()` and
+//! `space::skip_space()` match whatever the element type reports as whitespace
+//! through `Atomic::is_space`.
 //!
-//! ```text
-//! Input: "type hello    world"
-//!                      ^ Error points here after consuming whitespace
-//! ```
-//!
-//! But users expect errors to point to meaningful locations:
+//! Padding never fails and stops on an empty match, so it neither hides errors nor
+//! loops on parsers like `take_whitespace` that always succeed. Errors keep the
+//! structure of the unpadded combinator and point at meaningful locations:
 //!
 //! ```text
-//! Input: "type hello    world"  
-//!                  ^ Error should point here (after "hello")
+//! Input: "type hello    world"
+//!                  ^ a missing separator is reported after "hello",
+//!                    before the whitespace
 //! ```
 //!
-//! ### 2. Generic Error Messages
-//!
-//! Generic combinators can only provide generic error messages:
-//! - ❌ "Expected content after whitespace"
-//! - ❌ "Parser failed"
+//! Items, values and closing delimiters that fail are reported at the offending
+//! input after the trivia, and padding is never consumed after the last list item,
+//! so trailing trivia is left to whatever follows.
 //!
-//! But good parsers provide semantic, contextual error messages:
-//! - ✅ "Expected semicolon after statement"
-//! - ✅ "Missing closing bracket"
-//! - ✅ "Expected type annotation"
-//!
-//! ## Recommended Approach
-//!
-//! Instead of generic whitespace combinators, create semantic combinators
-//! that understand the parsing context:
-//!
-//! ```text
-//! ❌ Don't do this:
-//! whitespace_separated_pair(type_parser(), is_string(","), value_parser())
-//!
-//! ✅ Do this instead:
-//! fn parameter_declaration() -> impl Parser<...> {
-//!     // Handle whitespace and provide semantic errors
-//! }
-//! ```
+//! ## Semantic Error Messages
 //!
-//! This approach gives you:
-//! - Precise error locations (before whitespace consumption)
-//! - Meaningful error messages ("Expected parameter name", not "Parser failed")
-//! - Full control over whitespace handling for your specific syntax
+//! The combinators only know which part failed, not what it means in your grammar.
+//! For messages like "Expected semicolon after statement" or "Expected type
+//! annotation", attach them with `.map_err(..)` or `.with_hint(..)` on the part
+//! that failed.
 
 use crate::ByteCursor;
 use crate::ParsicombError;